@@ -25,6 +25,7 @@ fn expand_gemini_tool(input: &DeriveInput) -> syn::Result<TokenStream2> {
     let GeminiAttr {
         name: struct_name,
         description: struct_description,
+        behavior,
         ..
     } = struct_attrs;
     let function_name = struct_name.unwrap_or_else(|| name.to_string());
@@ -35,26 +36,14 @@ fn expand_gemini_tool(input: &DeriveInput) -> syn::Result<TokenStream2> {
         _ => return Err(syn::Error::new_spanned(input, "GeminiTool 仅支持结构体")),
     };
 
-    let (property_inserts, required_fields, ordering_fields) = collect_schema_fields(fields)?;
+    let schema_expr = build_object_schema_expr(fields, &quote!(None), "GeminiTool")?;
     let description_expr = build_description_expr(function_description);
+    let behavior_expr = build_behavior_expr(input, behavior.as_deref())?;
 
     Ok(quote! {
         impl #name {
             pub fn as_tool() -> ::rust_genai_types::tool::Tool {
-                let mut properties: ::std::collections::HashMap<String, Box<::rust_genai_types::tool::Schema>> =
-                    ::std::collections::HashMap::new();
-                #(#property_inserts)*
-
-                let required: Vec<String> = vec![#(#required_fields),*];
-                let ordering: Vec<String> = vec![#(#ordering_fields),*];
-
-                let schema = ::rust_genai_types::tool::Schema {
-                    ty: Some(::rust_genai_types::enums::Type::Object),
-                    properties: Some(properties),
-                    required: if required.is_empty() { None } else { Some(required) },
-                    property_ordering: if ordering.is_empty() { None } else { Some(ordering) },
-                    ..Default::default()
-                };
+                let schema = #schema_expr;
 
                 let declaration = ::rust_genai_types::tool::FunctionDeclaration {
                     name: #function_name.to_string(),
@@ -63,7 +52,7 @@ fn expand_gemini_tool(input: &DeriveInput) -> syn::Result<TokenStream2> {
                     parameters_json_schema: None,
                     response: None,
                     response_json_schema: None,
-                    behavior: None,
+                    behavior: #behavior_expr,
                 };
 
                 ::rust_genai_types::tool::Tool {
@@ -92,8 +81,71 @@ fn expand_gemini_tool(input: &DeriveInput) -> syn::Result<TokenStream2> {
     })
 }
 
+#[proc_macro_derive(GeminiSchema, attributes(gemini))]
+pub fn gemini_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_gemini_schema(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_gemini_schema(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let struct_attrs = parse_gemini_attrs(&input.attrs)?;
+    let description = struct_attrs
+        .description
+        .or_else(|| extract_doc_comment(&input.attrs));
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(syn::Error::new_spanned(input, "GeminiSchema 仅支持结构体")),
+    };
+
+    let description_expr = build_description_expr(description);
+    let schema_expr = build_object_schema_expr(fields, &description_expr, "GeminiSchema")?;
+
+    Ok(quote! {
+        impl #name {
+            pub fn response_schema() -> ::rust_genai_types::tool::Schema {
+                #schema_expr
+            }
+        }
+    })
+}
+
+fn build_object_schema_expr(
+    fields: &Fields,
+    description_expr: &TokenStream2,
+    derive_name: &str,
+) -> syn::Result<TokenStream2> {
+    let (property_inserts, required_fields, ordering_fields) =
+        collect_schema_fields(fields, derive_name)?;
+
+    Ok(quote! {
+        {
+            let mut properties: ::std::collections::HashMap<String, Box<::rust_genai_types::tool::Schema>> =
+                ::std::collections::HashMap::new();
+            #(#property_inserts)*
+
+            let required: Vec<String> = vec![#(#required_fields),*];
+            let ordering: Vec<String> = vec![#(#ordering_fields),*];
+
+            ::rust_genai_types::tool::Schema {
+                ty: Some(::rust_genai_types::enums::Type::Object),
+                description: #description_expr,
+                properties: Some(properties),
+                required: if required.is_empty() { None } else { Some(required) },
+                property_ordering: if ordering.is_empty() { None } else { Some(ordering) },
+                ..Default::default()
+            }
+        }
+    })
+}
+
 fn collect_schema_fields(
     fields: &Fields,
+    derive_name: &str,
 ) -> syn::Result<(Vec<TokenStream2>, Vec<TokenStream2>, Vec<TokenStream2>)> {
     let mut property_inserts = Vec::new();
     let mut required_fields = Vec::new();
@@ -102,10 +154,9 @@ fn collect_schema_fields(
     match fields {
         Fields::Named(named) => {
             for field in &named.named {
-                let field_ident = field
-                    .ident
-                    .as_ref()
-                    .ok_or_else(|| syn::Error::new_spanned(field, "GeminiTool 仅支持命名字段"))?;
+                let field_ident = field.ident.as_ref().ok_or_else(|| {
+                    syn::Error::new_spanned(field, format!("{derive_name} 仅支持命名字段"))
+                })?;
                 let field_attrs = parse_gemini_attrs(&field.attrs)?;
                 if field_attrs.skip {
                     continue;
@@ -138,7 +189,7 @@ fn collect_schema_fields(
         _ => {
             return Err(syn::Error::new_spanned(
                 fields,
-                "GeminiTool 仅支持具名字段结构体",
+                format!("{derive_name} 仅支持具名字段结构体"),
             ))
         }
     }
@@ -153,11 +204,26 @@ fn build_description_expr(function_description: Option<String>) -> TokenStream2
     )
 }
 
+fn build_behavior_expr(input: &DeriveInput, behavior: Option<&str>) -> syn::Result<TokenStream2> {
+    match behavior {
+        None => Ok(quote!(None)),
+        Some("blocking") => Ok(quote!(Some(::rust_genai_types::enums::Behavior::Blocking))),
+        Some("non_blocking") => Ok(quote!(Some(
+            ::rust_genai_types::enums::Behavior::NonBlocking
+        ))),
+        Some(other) => Err(syn::Error::new_spanned(
+            input,
+            format!("未知的 behavior: {other}（可选 blocking / non_blocking）"),
+        )),
+    }
+}
+
 #[derive(Default)]
 struct GeminiAttr {
     name: Option<String>,
     description: Option<String>,
     enum_values: Option<Vec<String>>,
+    behavior: Option<String>,
     required: bool,
     optional: bool,
     skip: bool,
@@ -194,6 +260,11 @@ fn parse_gemini_attrs(attrs: &[Attribute]) -> syn::Result<GeminiAttr> {
                 }
                 return Ok(());
             }
+            if meta.path.is_ident("behavior") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                output.behavior = Some(value.value());
+                return Ok(());
+            }
             if meta.path.is_ident("required") {
                 output.required = true;
                 return Ok(());
@@ -385,6 +456,7 @@ mod tests {
                 name = "tool_name",
                 description = "desc",
                 enum_values = "a, b",
+                behavior = "non_blocking",
                 required,
                 optional,
                 skip
@@ -397,6 +469,7 @@ mod tests {
             parsed.enum_values.as_ref().unwrap(),
             &vec!["a".to_string(), "b".to_string()]
         );
+        assert_eq!(parsed.behavior.as_deref(), Some("non_blocking"));
         assert!(parsed.required);
         assert!(parsed.optional);
         assert!(parsed.skip);
@@ -441,6 +514,25 @@ mod tests {
         assert!(err.to_string().contains("具名字段"));
     }
 
+    #[test]
+    fn expand_gemini_schema_rejects_non_structs() {
+        let input: DeriveInput = parse_quote!(
+            enum Bad {
+                A,
+            }
+        );
+        let err = expand_gemini_schema(&input).unwrap_err();
+        assert!(err.to_string().contains("GeminiSchema 仅支持结构体"));
+
+        let input: DeriveInput = parse_quote!(
+            struct Bad(String);
+        );
+        let err = expand_gemini_schema(&input).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("GeminiSchema 仅支持具名字段结构体"));
+    }
+
     #[test]
     fn schema_helpers_cover_variants() {
         let opt_vec: Type = parse_quote!(Option<Vec<String>>);
@@ -0,0 +1,95 @@
+use rust_genai_macros::GeminiSchema;
+use rust_genai_types::config::GenerationConfig;
+use rust_genai_types::enums::Type;
+use rust_genai_types::response::GenerateContentResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// A cooking recipe.
+#[derive(Debug, PartialEq, Serialize, Deserialize, GeminiSchema)]
+struct Recipe {
+    /// Recipe name
+    name: String,
+    servings: u32,
+    ingredients: Vec<String>,
+    #[gemini(enum_values = "easy,medium,hard")]
+    difficulty: Option<String>,
+    #[gemini(skip)]
+    #[serde(default)]
+    local_note: String,
+}
+
+#[test]
+fn test_gemini_schema_derive_builds_object_schema() {
+    let schema = Recipe::response_schema();
+    assert_eq!(schema.ty, Some(Type::Object));
+    assert_eq!(schema.description.as_deref(), Some("A cooking recipe."));
+
+    let properties = schema.properties.as_ref().expect("missing properties");
+    assert_eq!(properties.len(), 4);
+    assert!(!properties.contains_key("local_note"));
+    assert_eq!(
+        properties["name"].description.as_deref(),
+        Some("Recipe name")
+    );
+    assert_eq!(properties["servings"].ty, Some(Type::Integer));
+    let ingredients = &properties["ingredients"];
+    assert_eq!(ingredients.ty, Some(Type::Array));
+    assert_eq!(ingredients.items.as_ref().unwrap().ty, Some(Type::String));
+    assert_eq!(properties["difficulty"].nullable, Some(true));
+
+    assert_eq!(
+        schema.required.as_deref(),
+        Some(
+            &[
+                "name".to_string(),
+                "servings".to_string(),
+                "ingredients".to_string()
+            ][..]
+        )
+    );
+    assert_eq!(schema.property_ordering.as_ref().unwrap().len(), 4);
+}
+
+#[test]
+fn test_gemini_schema_response_round_trip() {
+    let config = GenerationConfig {
+        response_mime_type: Some("application/json".to_string()),
+        response_schema: Some(Recipe::response_schema()),
+        ..Default::default()
+    };
+    config.validate("gemini-2.5-flash").unwrap();
+    let body = serde_json::to_value(&config).unwrap();
+    assert_eq!(body["responseSchema"]["type"], json!("OBJECT"));
+    assert_eq!(
+        body["responseSchema"]["properties"]["ingredients"]["items"]["type"],
+        json!("STRING")
+    );
+
+    let response: GenerateContentResponse = serde_json::from_value(json!({
+        "candidates": [{
+            "content": {
+                "role": "model",
+                "parts": [
+                    {"text": "{\"name\": \"Pancakes\", \"servings\": 2, "},
+                    {"text": "\"ingredients\": [\"flour\", \"milk\"], \"difficulty\": \"easy\"}"}
+                ]
+            }
+        }]
+    }))
+    .unwrap();
+    let recipe: Recipe = response.parsed().unwrap();
+    assert_eq!(
+        recipe,
+        Recipe {
+            name: "Pancakes".to_string(),
+            servings: 2,
+            ingredients: vec!["flour".to_string(), "milk".to_string()],
+            difficulty: Some("easy".to_string()),
+            local_note: String::new(),
+        }
+    );
+
+    let empty: GenerateContentResponse = serde_json::from_value(json!({"candidates": []})).unwrap();
+    assert!(empty.parsed::<Recipe>().is_err());
+}
@@ -1,6 +1,6 @@
 use rust_genai_macros::GeminiTool;
 use rust_genai_types::content::FunctionCall;
-use rust_genai_types::enums::Type;
+use rust_genai_types::enums::{Behavior, Type};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -27,6 +27,12 @@ struct MixedTool {
     hidden: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, GeminiTool)]
+#[gemini(name = "send_report", behavior = "non_blocking")]
+struct SendReport {
+    recipient: String,
+}
+
 #[test]
 fn test_gemini_tool_macro_schema() {
     let tool = GetWeather::as_tool();
@@ -116,3 +122,13 @@ fn test_gemini_tool_from_call_errors() {
     };
     assert!(MixedTool::from_call(&call).is_err());
 }
+
+#[test]
+fn test_gemini_tool_behavior() {
+    let tool = SendReport::as_tool();
+    let declaration = &tool.function_declarations.unwrap()[0];
+    assert_eq!(declaration.behavior, Some(Behavior::NonBlocking));
+
+    let tool = GetWeather::as_tool();
+    assert_eq!(tool.function_declarations.unwrap()[0].behavior, None);
+}
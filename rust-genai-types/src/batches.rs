@@ -1,12 +1,15 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::content::Content;
 use crate::enums::JobState;
 use crate::http::{HttpOptions, HttpResponse};
 use crate::models::{EmbedContentConfig, GenerateContentConfig};
 use crate::response::GenerateContentResponse;
+use crate::timestamp::elapsed_between;
 use crate::webhooks::WebhookConfig;
 
 /// 内联请求。
@@ -144,6 +147,27 @@ pub struct BatchJob {
     pub dest: Option<BatchJobDestination>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completion_stats: Option<CompletionStats>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl BatchJob {
+    /// 任务运行时长：`start_time`（缺失时用 `create_time`）到 `end_time`，未结束时计到当前时间。
+    ///
+    /// 时间戳缺失或无法解析时返回 `None`。
+    #[must_use]
+    pub fn elapsed(&self) -> Option<Duration> {
+        let start = self.start_time.as_deref().or(self.create_time.as_deref());
+        elapsed_between(start, self.end_time.as_deref())
+    }
+
+    /// 排队时长：`create_time` 到 `start_time`，尚未开始时计到当前时间。
+    #[must_use]
+    pub fn queued_duration(&self) -> Option<Duration> {
+        elapsed_between(self.create_time.as_deref(), self.start_time.as_deref())
+    }
 }
 
 /// 创建批处理任务配置。
@@ -244,4 +268,8 @@ pub struct ListBatchJobsResponse {
     pub batch_jobs: Option<Vec<BatchJob>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_page_token: Option<String>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
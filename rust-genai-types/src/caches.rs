@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::content::Content;
 use crate::http::{HttpOptions, HttpResponse};
@@ -128,6 +131,10 @@ pub struct CachedContent {
     pub expire_time: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage_metadata: Option<CachedContentUsageMetadata>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 /// 列表响应。
@@ -141,4 +148,8 @@ pub struct ListCachedContentsResponse {
     pub cached_contents: Option<Vec<CachedContent>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_page_token: Option<String>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
@@ -65,6 +65,123 @@ pub struct GenerationConfig {
     pub routing_config: Option<GenerationConfigRoutingConfig>,
 }
 
+impl GenerationConfig {
+    /// 设置期望的输出模态（如 `[Modality::Text, Modality::Image]`）。
+    #[must_use]
+    pub fn with_response_modalities(mut self, modalities: impl Into<Vec<Modality>>) -> Self {
+        self.response_modalities = Some(modalities.into());
+        self
+    }
+
+    /// 设置思考 token 预算（写入 `thinking_config.thinking_budget`；`-1` 表示动态预算，`0` 关闭思考）。
+    #[must_use]
+    pub fn with_thinking_budget(mut self, budget: i32) -> Self {
+        self.thinking_config
+            .get_or_insert_with(ThinkingConfig::default)
+            .thinking_budget = Some(budget);
+        self
+    }
+
+    /// 设置是否在响应中返回思考摘要（写入 `thinking_config.include_thoughts`）。
+    #[must_use]
+    pub fn with_include_thoughts(mut self, include_thoughts: bool) -> Self {
+        self.thinking_config
+            .get_or_insert_with(ThinkingConfig::default)
+            .include_thoughts = Some(include_thoughts);
+        self
+    }
+
+    /// 设置语音合成配置（写入 `speech_config`）。
+    #[must_use]
+    pub fn with_speech_config(mut self, speech_config: SpeechConfig) -> Self {
+        self.speech_config = Some(speech_config);
+        self
+    }
+
+    /// 检查已知无效的参数组合与取值范围，返回描述性错误信息。
+    ///
+    /// `model` 可带 `models/` 等路径前缀；仅对已知不支持的模型拒绝模型相关参数。
+    ///
+    /// # Errors
+    /// 当配置包含已知会被 API 拒绝的组合时返回错误描述。
+    pub fn validate(&self, model: &str) -> Result<(), String> {
+        let model = model.rsplit('/').next().unwrap_or(model);
+        let mime_type = self.response_mime_type.as_deref();
+        let json_mode = mime_type == Some("application/json");
+
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                return Err(format!(
+                    "temperature must be between 0.0 and 2.0, got {temperature}"
+                ));
+            }
+        }
+        if let Some(top_p) = self.top_p {
+            if !(0.0..=1.0).contains(&top_p) {
+                return Err(format!("top_p must be between 0.0 and 1.0, got {top_p}"));
+            }
+        }
+        if let Some(top_k) = self.top_k {
+            if top_k <= 0.0 {
+                return Err(format!("top_k must be positive, got {top_k}"));
+            }
+            if model == "gemini-pro"
+                || model.starts_with("gemini-1.0-pro") && !model.contains("vision")
+            {
+                return Err(format!("top_k is not supported by model {model}"));
+            }
+        }
+        if let Some(max_output_tokens) = self.max_output_tokens {
+            if max_output_tokens <= 0 {
+                return Err(format!(
+                    "max_output_tokens must be positive, got {max_output_tokens}"
+                ));
+            }
+        }
+        if let Some(candidate_count) = self.candidate_count {
+            if candidate_count < 1 {
+                return Err(format!(
+                    "candidate_count must be at least 1, got {candidate_count}"
+                ));
+            }
+            if candidate_count > 1 && json_mode {
+                return Err(
+                    "candidate_count > 1 is not supported with response_mime_type \"application/json\""
+                        .into(),
+                );
+            }
+        }
+
+        if self.response_schema.is_some() && self.response_json_schema.is_some() {
+            return Err(
+                "response_schema and response_json_schema are mutually exclusive; set only one"
+                    .into(),
+            );
+        }
+        if self.response_schema.is_some() && !json_mode && mime_type != Some("text/x.enum") {
+            return Err(format!(
+                "response_schema requires response_mime_type \"application/json\" (or \"text/x.enum\" for enum schemas), got {}",
+                mime_type.map_or_else(|| "none".to_string(), |mime| format!("\"{mime}\""))
+            ));
+        }
+        if self.response_json_schema.is_some() && !json_mode {
+            return Err(format!(
+                "response_json_schema requires response_mime_type \"application/json\", got {}",
+                mime_type.map_or_else(|| "none".to_string(), |mime| format!("\"{mime}\""))
+            ));
+        }
+
+        if let Some(thinking) = &self.thinking_config {
+            if thinking.thinking_level.is_some() && thinking.thinking_budget.is_some() {
+                return Err(
+                    "thinking_level and thinking_budget cannot be set together; use one".into(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 /// 安全设置。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -76,6 +193,126 @@ pub struct SafetySetting {
     pub method: Option<HarmBlockMethod>,
 }
 
+/// `safety_settings` 构建器：按类别设置阈值，生成请求所需的 `Vec<SafetySetting>`。
+///
+/// 预设覆盖 [`SafetySettings::CATEGORIES`] 中的全部类别；对同一类别重复设置会覆盖之前的阈值。
+#[derive(Debug, Clone, Default)]
+pub struct SafetySettings {
+    settings: Vec<SafetySetting>,
+}
+
+impl SafetySettings {
+    /// 预设适用的内容类别（Gemini API 与 Vertex AI 均支持）。
+    pub const CATEGORIES: [HarmCategory; 5] = [
+        HarmCategory::HarmCategoryHarassment,
+        HarmCategory::HarmCategoryHateSpeech,
+        HarmCategory::HarmCategorySexuallyExplicit,
+        HarmCategory::HarmCategoryDangerousContent,
+        HarmCategory::HarmCategoryCivicIntegrity,
+    ];
+
+    /// 创建空构建器。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 所有类别使用同一阈值。
+    #[must_use]
+    pub fn all(threshold: HarmBlockThreshold) -> Self {
+        Self::CATEGORIES
+            .into_iter()
+            .fold(Self::new(), |settings, category| {
+                settings.category(category, threshold)
+            })
+    }
+
+    /// 所有类别均不拦截（`BLOCK_NONE`）。
+    #[must_use]
+    pub fn block_none() -> Self {
+        Self::all(HarmBlockThreshold::BlockNone)
+    }
+
+    /// 所有类别仅拦截高概率内容（`BLOCK_ONLY_HIGH`）。
+    #[must_use]
+    pub fn block_only_high() -> Self {
+        Self::all(HarmBlockThreshold::BlockOnlyHigh)
+    }
+
+    /// 所有类别拦截中等及以上概率内容（`BLOCK_MEDIUM_AND_ABOVE`）。
+    #[must_use]
+    pub fn block_medium_and_above() -> Self {
+        Self::all(HarmBlockThreshold::BlockMediumAndAbove)
+    }
+
+    /// 所有类别拦截低等及以上概率内容（`BLOCK_LOW_AND_ABOVE`）。
+    #[must_use]
+    pub fn block_low_and_above() -> Self {
+        Self::all(HarmBlockThreshold::BlockLowAndAbove)
+    }
+
+    /// 设置单个类别的阈值。
+    #[must_use]
+    pub fn category(mut self, category: HarmCategory, threshold: HarmBlockThreshold) -> Self {
+        if let Some(setting) = self
+            .settings
+            .iter_mut()
+            .find(|setting| setting.category == category)
+        {
+            setting.threshold = Some(threshold);
+        } else {
+            self.settings.push(SafetySetting {
+                category,
+                threshold: Some(threshold),
+                method: None,
+            });
+        }
+        self
+    }
+
+    /// 设置骚扰类阈值。
+    #[must_use]
+    pub fn harassment(self, threshold: HarmBlockThreshold) -> Self {
+        self.category(HarmCategory::HarmCategoryHarassment, threshold)
+    }
+
+    /// 设置仇恨言论类阈值。
+    #[must_use]
+    pub fn hate_speech(self, threshold: HarmBlockThreshold) -> Self {
+        self.category(HarmCategory::HarmCategoryHateSpeech, threshold)
+    }
+
+    /// 设置色情内容类阈值。
+    #[must_use]
+    pub fn sexually_explicit(self, threshold: HarmBlockThreshold) -> Self {
+        self.category(HarmCategory::HarmCategorySexuallyExplicit, threshold)
+    }
+
+    /// 设置危险内容类阈值。
+    #[must_use]
+    pub fn dangerous_content(self, threshold: HarmBlockThreshold) -> Self {
+        self.category(HarmCategory::HarmCategoryDangerousContent, threshold)
+    }
+
+    /// 设置公民诚信类阈值。
+    #[must_use]
+    pub fn civic_integrity(self, threshold: HarmBlockThreshold) -> Self {
+        self.category(HarmCategory::HarmCategoryCivicIntegrity, threshold)
+    }
+
+    /// 生成请求使用的安全设置列表。
+    #[must_use]
+    pub fn build(self) -> Vec<SafetySetting> {
+        self.settings
+    }
+}
+
+impl From<SafetySettings> for Vec<SafetySetting> {
+    fn from(settings: SafetySettings) -> Self {
+        settings.build()
+    }
+}
+
 /// Configuration for Model Armor integrations of prompt and responses.
 ///
 /// This data type is not supported in Gemini API.
@@ -118,6 +355,140 @@ pub struct SpeechConfig {
     pub extra: HashMap<String, Value>,
 }
 
+/// 已知的 Gemini TTS 预置音色名称。
+pub const PREBUILT_VOICE_NAMES: &[&str] = &[
+    "Zephyr",
+    "Puck",
+    "Charon",
+    "Kore",
+    "Fenrir",
+    "Leda",
+    "Orus",
+    "Aoede",
+    "Callirrhoe",
+    "Autonoe",
+    "Enceladus",
+    "Iapetus",
+    "Umbriel",
+    "Algieba",
+    "Despina",
+    "Erinome",
+    "Algenib",
+    "Rasalgethi",
+    "Laomedeia",
+    "Achernar",
+    "Alnilam",
+    "Schedar",
+    "Gacrux",
+    "Pulcherrima",
+    "Achird",
+    "Zubenelgenubi",
+    "Vindemiatrix",
+    "Sadachbia",
+    "Sadaltager",
+    "Sulafat",
+];
+
+/// 多说话人语音合成要求的说话人数（恰好两位）。
+pub const MAX_TTS_SPEAKERS: usize = 2;
+
+impl SpeechConfig {
+    /// 使用已知预置音色（大小写不敏感，见 [`PREBUILT_VOICE_NAMES`]）。
+    ///
+    /// # Errors
+    /// 音色名称未知时返回错误；新音色可改用 [`SpeechConfig::prebuilt_voice_unchecked`]。
+    pub fn prebuilt_voice(name: &str) -> Result<Self, String> {
+        Ok(Self::prebuilt_voice_unchecked(known_voice_name(name)?))
+    }
+
+    /// 使用预置音色且不校验名称（用于尚未收录的新音色）。
+    #[must_use]
+    pub fn prebuilt_voice_unchecked(name: impl Into<String>) -> Self {
+        Self {
+            voice_config: Some(VoiceConfig::prebuilt(name)),
+            ..Default::default()
+        }
+    }
+
+    /// 多说话人配置：`speakers` 为 `(说话人名称, 预置音色)` 列表，名称需与提示中的说话人一致。
+    ///
+    /// # Errors
+    /// 说话人数量不等于 [`MAX_TTS_SPEAKERS`]、说话人重名或音色名称未知时返回错误；
+    /// 新音色可改用 [`SpeechConfig::multi_speaker_unchecked`]，单一音色请用
+    /// [`SpeechConfig::prebuilt_voice`]。
+    pub fn multi_speaker<I, S, V>(speakers: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (S, V)>,
+        S: Into<String>,
+        V: AsRef<str>,
+    {
+        let speakers = speakers
+            .into_iter()
+            .map(|(speaker, voice)| Ok((speaker, known_voice_name(voice.as_ref())?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        Self::multi_speaker_unchecked(speakers)
+    }
+
+    /// 多说话人配置，且不校验音色名称（用于尚未收录的新音色）。
+    ///
+    /// # Errors
+    /// 说话人数量不等于 [`MAX_TTS_SPEAKERS`] 或说话人重名时返回错误；单一音色请用
+    /// [`SpeechConfig::prebuilt_voice_unchecked`]。
+    pub fn multi_speaker_unchecked<I, S, V>(speakers: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (S, V)>,
+        S: Into<String>,
+        V: Into<String>,
+    {
+        let mut configs: Vec<SpeakerVoiceConfig> = Vec::new();
+        for (speaker, voice) in speakers {
+            let speaker = speaker.into();
+            if configs
+                .iter()
+                .any(|config| config.speaker.as_deref() == Some(speaker.as_str()))
+            {
+                return Err(format!(
+                    "duplicate speaker {speaker} in multi-speaker config"
+                ));
+            }
+            configs.push(SpeakerVoiceConfig {
+                speaker: Some(speaker),
+                voice_config: Some(VoiceConfig::prebuilt(voice)),
+            });
+        }
+        if configs.len() != MAX_TTS_SPEAKERS {
+            return Err(format!(
+                "multi-speaker config requires exactly {MAX_TTS_SPEAKERS} speakers, got {}; \
+                 use prebuilt_voice for a single voice",
+                configs.len()
+            ));
+        }
+        Ok(Self {
+            multi_speaker_voice_config: Some(MultiSpeakerVoiceConfig {
+                speaker_voice_configs: Some(configs),
+            }),
+            ..Default::default()
+        })
+    }
+
+    /// 设置语言代码（如 `en-US`）。
+    #[must_use]
+    pub fn with_language_code(mut self, language_code: impl Into<String>) -> Self {
+        self.language_code = Some(language_code.into());
+        self
+    }
+}
+
+fn known_voice_name(name: &str) -> Result<&'static str, String> {
+    PREBUILT_VOICE_NAMES
+        .iter()
+        .copied()
+        .find(|known| known.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            format!("unknown prebuilt voice {name}; use prebuilt_voice_unchecked for new voices")
+        })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct VoiceConfig {
@@ -131,6 +502,20 @@ pub struct VoiceConfig {
     pub extra: HashMap<String, Value>,
 }
 
+impl VoiceConfig {
+    /// 使用预置音色（不校验名称）。
+    #[must_use]
+    pub fn prebuilt(name: impl Into<String>) -> Self {
+        Self {
+            prebuilt_voice_config: Some(PrebuiltVoiceConfig {
+                voice_name: Some(name.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ReplicatedVoiceConfig {
@@ -225,6 +610,59 @@ mod tests {
     use crate::enums::{MediaResolution, Modality, ThinkingLevel};
     use crate::tool::Schema;
 
+    #[test]
+    fn speech_config_builders_validate_voice_names() {
+        let config = SpeechConfig::prebuilt_voice("kore").unwrap();
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::json!({"voiceConfig": {"prebuiltVoiceConfig": {"voiceName": "Kore"}}})
+        );
+        assert!(SpeechConfig::prebuilt_voice("NotAVoice")
+            .unwrap_err()
+            .contains("prebuilt_voice_unchecked"));
+        let custom = SpeechConfig::prebuilt_voice_unchecked("NewVoice");
+        assert_eq!(
+            custom
+                .voice_config
+                .and_then(|voice| voice.prebuilt_voice_config)
+                .and_then(|prebuilt| prebuilt.voice_name)
+                .as_deref(),
+            Some("NewVoice")
+        );
+
+        let multi = SpeechConfig::multi_speaker([("Joe", "Kore"), ("Jane", "Puck")])
+            .unwrap()
+            .with_language_code("en-US");
+        assert_eq!(
+            serde_json::to_value(&multi).unwrap(),
+            serde_json::json!({
+                "languageCode": "en-US",
+                "multiSpeakerVoiceConfig": {"speakerVoiceConfigs": [
+                    {"speaker": "Joe", "voiceConfig": {"prebuiltVoiceConfig": {"voiceName": "Kore"}}},
+                    {"speaker": "Jane", "voiceConfig": {"prebuiltVoiceConfig": {"voiceName": "Puck"}}}
+                ]}
+            })
+        );
+        assert!(SpeechConfig::multi_speaker([("Joe", "Kore"), ("Joe", "Puck")]).is_err());
+        assert!(
+            SpeechConfig::multi_speaker([("A", "Kore"), ("B", "Puck"), ("C", "Leda")]).is_err()
+        );
+        assert!(SpeechConfig::multi_speaker(Vec::<(String, String)>::new()).is_err());
+
+        assert!(SpeechConfig::multi_speaker([("Joe", "Kore")]).is_err());
+        assert!(SpeechConfig::multi_speaker_unchecked([("Joe", "NewVoice")]).is_err());
+
+        assert!(SpeechConfig::multi_speaker([("Joe", "NewVoice"), ("Jane", "Puck")]).is_err());
+        let unchecked =
+            SpeechConfig::multi_speaker_unchecked([("Joe", "NewVoice"), ("Jane", "Puck")]).unwrap();
+        assert_eq!(
+            serde_json::to_value(&unchecked).unwrap()["multiSpeakerVoiceConfig"]
+                ["speakerVoiceConfigs"][0]["voiceConfig"]["prebuiltVoiceConfig"]["voiceName"],
+            "NewVoice"
+        );
+        assert!(SpeechConfig::multi_speaker_unchecked([("Joe", "A"), ("Joe", "B")]).is_err());
+    }
+
     #[test]
     fn generation_config_serializes_camel_case() {
         let config = GenerationConfig {
@@ -250,6 +688,51 @@ mod tests {
         assert!(value.get("thinkingConfig").is_some());
     }
 
+    #[test]
+    fn generation_config_validate_rejects_known_bad_combinations() {
+        let schema_without_mime = GenerationConfig {
+            response_schema: Some(Schema::string()),
+            ..Default::default()
+        };
+        let err = schema_without_mime
+            .validate("gemini-2.5-flash")
+            .unwrap_err();
+        assert!(err.contains("response_schema requires response_mime_type"));
+        assert!(err.contains("got none"));
+
+        let json_candidates = GenerationConfig {
+            response_mime_type: Some("application/json".into()),
+            candidate_count: Some(2),
+            ..Default::default()
+        };
+        assert!(json_candidates.validate("gemini-2.5-flash").is_err());
+
+        let top_k = GenerationConfig {
+            top_k: Some(40.0),
+            ..Default::default()
+        };
+        assert!(top_k.validate("models/gemini-1.0-pro-001").is_err());
+        assert!(top_k.validate("gemini-2.5-flash").is_ok());
+
+        let thinking = GenerationConfig {
+            thinking_config: Some(ThinkingConfig {
+                thinking_level: Some(ThinkingLevel::Low),
+                include_thoughts: None,
+                thinking_budget: Some(1024),
+            }),
+            ..Default::default()
+        };
+        assert!(thinking.validate("gemini-3-pro-preview").is_err());
+
+        let valid = GenerationConfig {
+            response_mime_type: Some("application/json".into()),
+            response_schema: Some(Schema::string()),
+            temperature: Some(1.0),
+            ..Default::default()
+        };
+        assert!(valid.validate("gemini-2.5-flash").is_ok());
+    }
+
     #[test]
     fn safety_setting_roundtrip() {
         let setting = SafetySetting {
@@ -261,4 +744,44 @@ mod tests {
         let decoded: SafetySetting = serde_json::from_str(&json).unwrap();
         assert_eq!(decoded.category, HarmCategory::HarmCategoryHarassment);
     }
+
+    #[test]
+    fn safety_settings_block_none_covers_all_categories() {
+        let settings = SafetySettings::block_none().build();
+        assert_eq!(settings.len(), SafetySettings::CATEGORIES.len());
+        for category in SafetySettings::CATEGORIES {
+            let setting = settings
+                .iter()
+                .find(|setting| setting.category == category)
+                .unwrap();
+            assert_eq!(setting.threshold, Some(HarmBlockThreshold::BlockNone));
+        }
+        let value = serde_json::to_value(&settings).unwrap();
+        assert_eq!(
+            value[0],
+            serde_json::json!({"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_NONE"})
+        );
+    }
+
+    #[test]
+    fn safety_settings_per_category_overrides() {
+        let settings: Vec<SafetySetting> = SafetySettings::block_only_high()
+            .dangerous_content(HarmBlockThreshold::BlockLowAndAbove)
+            .into();
+        assert_eq!(settings.len(), 5);
+        let dangerous = settings
+            .iter()
+            .find(|setting| setting.category == HarmCategory::HarmCategoryDangerousContent)
+            .unwrap();
+        assert_eq!(
+            dangerous.threshold,
+            Some(HarmBlockThreshold::BlockLowAndAbove)
+        );
+
+        let single = SafetySettings::new()
+            .harassment(HarmBlockThreshold::BlockMediumAndAbove)
+            .build();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].category, HarmCategory::HarmCategoryHarassment);
+    }
 }
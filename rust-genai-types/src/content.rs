@@ -1,10 +1,13 @@
 use crate::base64_serde;
 use crate::enums::{FunctionResponseScheduling, Language, Outcome, PartMediaResolutionLevel};
+use crate::text_encoding::TextEncoding;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 #[cfg(feature = "mcp")]
-use rmcp::model::CallToolResult;
+use rmcp::model::{CallToolResult, RawContent, ResourceContents};
+
+const PDF_MIME_TYPE: &str = "application/pdf";
 
 /// 对话内容。
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +52,38 @@ impl Content {
         self.parts.iter().find_map(|part| part.text_value())
     }
 
+    /// 构建要求模型通过代码执行工具运行修正后代码的用户消息（用于上一轮执行失败后的第二轮）。
+    pub fn corrected_code(code: impl AsRef<str>, language: Language) -> Self {
+        let fence = match language {
+            Language::Python => "python",
+            Language::LanguageUnspecified => "",
+        };
+        Self::user(format!(
+            "The previous code did not run successfully. Execute this corrected code with the \
+             code execution tool and report the result:\n```{fence}\n{}\n```",
+            code.as_ref().trim_end()
+        ))
+    }
+
+    /// 构建附带页码提示的 PDF 用户消息：`hint` 原样作为紧随文档之后的文本 Part。
+    ///
+    /// API 没有页码范围字段，这只是给模型的提示，由调用方决定措辞与语言
+    /// （如 `"Only use pages 3-5 of the PDF document above."`），不保证模型只读取这些页。
+    ///
+    /// # Errors
+    /// 当 `document` 不是 `application/pdf` 的内联数据或文件 Part 时返回错误描述。
+    pub fn pdf_with_page_hint(document: Part, hint: impl Into<String>) -> Result<Self, String> {
+        if !document.is_pdf() {
+            return Err(
+                "document part must be inline or file data with mime type `application/pdf`".into(),
+            );
+        }
+        Ok(Self::from_parts(
+            vec![document, Part::text(hint)],
+            Role::User,
+        ))
+    }
+
     fn from_text(text: impl Into<String>, role: Role) -> Self {
         Self {
             role: Some(role),
@@ -103,6 +138,32 @@ impl Part {
         }
     }
 
+    /// 按指定编码严格解码字节并创建文本 Part。
+    ///
+    /// # Errors
+    /// 当字节在该编码下无法解码时返回 `InvalidData` 错误。
+    pub fn text_from_bytes(bytes: &[u8], encoding: TextEncoding) -> std::io::Result<Self> {
+        encoding.decode(bytes).map(Self::text)
+    }
+
+    /// 读取本地文本文件创建文本 Part。
+    ///
+    /// `encoding` 为 `None` 时按 BOM 检测 UTF-8/UTF-16，无 BOM 则要求合法 UTF-8；
+    /// 其他编码（如 Latin-1）需显式指定。
+    ///
+    /// # Errors
+    /// 当文件读取失败或内容无法按编码解码时返回错误。
+    pub fn text_from_path(
+        path: impl AsRef<std::path::Path>,
+        encoding: Option<TextEncoding>,
+    ) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let encoding = encoding
+            .or_else(|| TextEncoding::from_bom(&bytes))
+            .unwrap_or(TextEncoding::Utf8);
+        Self::text_from_bytes(&bytes, encoding)
+    }
+
     /// 创建内联二进制数据 Part。
     pub fn inline_data(data: Vec<u8>, mime_type: impl Into<String>) -> Self {
         Self {
@@ -120,6 +181,26 @@ impl Part {
         }
     }
 
+    /// 创建内联 PDF 文档 Part（`application/pdf`）。
+    pub fn pdf(data: Vec<u8>) -> Self {
+        Self::inline_data(data, PDF_MIME_TYPE)
+    }
+
+    /// 创建 PDF 文件 URI Part（如 Files API 返回的 URI）。
+    pub fn pdf_file(file_uri: impl Into<String>) -> Self {
+        Self::file_data(file_uri, PDF_MIME_TYPE)
+    }
+
+    /// 是否为 `application/pdf` 的内联数据或文件 Part。
+    #[must_use]
+    pub fn is_pdf(&self) -> bool {
+        match &self.kind {
+            PartKind::InlineData { inline_data } => inline_data.mime_type == PDF_MIME_TYPE,
+            PartKind::FileData { file_data } => file_data.mime_type == PDF_MIME_TYPE,
+            _ => false,
+        }
+    }
+
     /// 创建文件 URI Part。
     pub fn file_data(file_uri: impl Into<String>, mime_type: impl Into<String>) -> Self {
         Self {
@@ -207,6 +288,22 @@ impl Part {
         self
     }
 
+    /// 获取 thought signature（原始字节）。
+    #[must_use]
+    pub fn thought_signature(&self) -> Option<&[u8]> {
+        self.thought_signature.as_deref()
+    }
+
+    /// 设置或清除 thought signature。
+    pub fn set_thought_signature(&mut self, signature: Option<Vec<u8>>) {
+        self.thought_signature = signature;
+    }
+
+    /// 取出并清除 thought signature。
+    pub const fn take_thought_signature(&mut self) -> Option<Vec<u8>> {
+        self.thought_signature.take()
+    }
+
     /// 设置媒体分辨率。
     #[must_use]
     pub const fn with_media_resolution(mut self, resolution: PartMediaResolution) -> Self {
@@ -238,6 +335,26 @@ impl Part {
             _ => None,
         }
     }
+
+    /// 获取可执行代码引用（仅当为 `ExecutableCode` Part）。
+    #[must_use]
+    pub const fn executable_code_ref(&self) -> Option<&ExecutableCode> {
+        match &self.kind {
+            PartKind::ExecutableCode { executable_code } => Some(executable_code),
+            _ => None,
+        }
+    }
+
+    /// 获取代码执行结果引用（仅当为 `CodeExecutionResult` Part）。
+    #[must_use]
+    pub const fn code_execution_result_ref(&self) -> Option<&CodeExecutionResult> {
+        match &self.kind {
+            PartKind::CodeExecutionResult {
+                code_execution_result,
+            } => Some(code_execution_result),
+            _ => None,
+        }
+    }
 }
 
 /// 内容部分的具体变体。
@@ -338,6 +455,112 @@ pub struct FunctionCall {
     pub will_continue: Option<bool>,
 }
 
+impl FunctionCall {
+    /// 将流式 `partial_args` 按 `json_path` 组装为完整参数（叠加在已有 `args` 之上）。
+    ///
+    /// 同一路径上连续的字符串片段（前一片段 `will_continue = true`）会被拼接。没有
+    /// `partial_args` 时返回 `args` 的副本。
+    #[must_use]
+    pub fn assembled_args(&self) -> Option<Value> {
+        let Some(partial_args) = self.partial_args.as_deref().filter(|args| !args.is_empty())
+        else {
+            return self.args.clone();
+        };
+        let mut root = match &self.args {
+            Some(Value::Object(map)) => Value::Object(map.clone()),
+            _ => Value::Object(serde_json::Map::new()),
+        };
+        let mut continuing: Option<&str> = None;
+        for arg in partial_args {
+            let path = arg.json_path.as_deref().unwrap_or("$");
+            let Some(slot) = json_path_slot(&mut root, path) else {
+                continuing = None;
+                continue;
+            };
+            match (&arg.string_value, slot) {
+                (Some(fragment), Value::String(existing)) if continuing == Some(path) => {
+                    existing.push_str(fragment);
+                }
+                (Some(fragment), slot) => *slot = Value::String(fragment.clone()),
+                (None, slot) => {
+                    *slot = if let Some(number) = arg.number_value {
+                        json_number(number)
+                    } else if let Some(flag) = arg.bool_value {
+                        Value::Bool(flag)
+                    } else {
+                        Value::Null
+                    };
+                }
+            }
+            continuing = (arg.will_continue == Some(true)).then_some(path);
+        }
+        Some(root)
+    }
+}
+
+/// 将流式参数中的数值转为 JSON：可精确表示为 `i64` / `u64` 的整数按整数输出，
+/// 以便通过 INTEGER 类型的 schema 校验与反序列化；其余按浮点数输出。
+fn json_number(number: f64) -> Value {
+    // 2^63 与 2^64 可被 f64 精确表示，作为开区间上界。
+    const I64_BOUND: f64 = 9_223_372_036_854_775_808.0;
+    const U64_BOUND: f64 = 18_446_744_073_709_551_616.0;
+    if number.fract() == 0.0 {
+        if (-I64_BOUND..I64_BOUND).contains(&number) {
+            return Value::from(number as i64);
+        }
+        if (0.0..U64_BOUND).contains(&number) {
+            return Value::from(number as u64);
+        }
+    }
+    serde_json::Number::from_f64(number).map_or(Value::Null, Value::Number)
+}
+
+/// 解析 `$.a.b[0]["c"]` 形式的 JSON path，返回（必要时创建的）目标位置。
+fn json_path_slot<'a>(root: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+    let mut current = root;
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            let key = &after_dot[..end];
+            rest = &after_dot[end..];
+            current = object_entry(current, key)?;
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = after_bracket.find(']')?;
+            let segment = &after_bracket[..end];
+            rest = &after_bracket[end + 1..];
+            if let Ok(index) = segment.parse::<usize>() {
+                if !current.is_array() {
+                    *current = Value::Array(Vec::new());
+                }
+                let items = current.as_array_mut()?;
+                if items.len() <= index {
+                    items.resize(index + 1, Value::Null);
+                }
+                current = &mut items[index];
+            } else {
+                let key = segment.trim_matches(|c| c == '\'' || c == '"');
+                current = object_entry(current, key)?;
+            }
+        } else {
+            return None;
+        }
+    }
+    Some(current)
+}
+
+fn object_entry<'a>(value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    if !value.is_object() {
+        *value = Value::Object(serde_json::Map::new());
+    }
+    Some(
+        value
+            .as_object_mut()?
+            .entry(key.to_string())
+            .or_insert(Value::Null),
+    )
+}
+
 /// 函数响应内容中的二进制数据。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -388,8 +611,41 @@ pub struct FunctionResponse {
 }
 
 impl FunctionResponse {
+    /// 构造中间工具结果（`willContinue = true`），表示同一调用后续还会发送响应。
+    ///
+    /// 适用于 Live API 中声明为 `NON_BLOCKING` 的长耗时函数，可用于上报进度或部分结果。
+    #[must_use]
+    pub fn interim(id: impl Into<String>, name: impl Into<String>, response: Value) -> Self {
+        Self {
+            will_continue: Some(true),
+            scheduling: None,
+            parts: None,
+            id: Some(id.into()),
+            name: Some(name.into()),
+            response: Some(response),
+        }
+    }
+
+    /// 构造最终工具结果（`willContinue = false`），结束同一调用的流式响应。
+    #[must_use]
+    pub fn final_response(id: impl Into<String>, name: impl Into<String>, response: Value) -> Self {
+        Self {
+            will_continue: Some(false),
+            ..Self::interim(id, name, response)
+        }
+    }
+
+    /// 设置模型处理该响应的时机（仅对 `NON_BLOCKING` 函数生效）。
+    #[must_use]
+    pub const fn with_scheduling(mut self, scheduling: FunctionResponseScheduling) -> Self {
+        self.scheduling = Some(scheduling);
+        self
+    }
+
     /// 从 MCP `CallToolResult` 构造 FunctionResponse（需要启用 `mcp` feature）。
     ///
+    /// 整个结果（含图片等内容的 base64）编码为 `response` JSON，适用于所有模型。
+    ///
     /// # Errors
     /// 当序列化 MCP 响应失败时返回错误。
     #[cfg(feature = "mcp")]
@@ -397,26 +653,95 @@ impl FunctionResponse {
         name: impl Into<String>,
         response: &CallToolResult,
     ) -> Result<Self, serde_json::Error> {
-        let value = serde_json::to_value(response)?;
-        let is_error = response.is_error.unwrap_or(false);
-        let response_value = if is_error {
-            let mut wrapper = serde_json::Map::new();
-            wrapper.insert("error".to_string(), value);
-            Value::Object(wrapper)
-        } else {
-            value
-        };
         Ok(Self {
             will_continue: None,
             scheduling: None,
             parts: None,
             id: None,
             name: Some(name.into()),
-            response: Some(response_value),
+            response: Some(mcp_result_to_value(response)?),
+        })
+    }
+
+    /// 从 MCP `CallToolResult` 构造多模态 FunctionResponse（需要启用 `mcp` feature）。
+    ///
+    /// 图片、音频与二进制资源内容会转为 `parts` 中的内联数据，其余内容保留在 `response` 中；
+    /// 无法解码的 base64 内容保留在 `response` 中。仅部分模型（如 Gemini 3）接受
+    /// `FunctionResponse.parts` 中的媒体。
+    ///
+    /// # Errors
+    /// 当序列化 MCP 响应失败时返回错误。
+    #[cfg(feature = "mcp")]
+    pub fn from_mcp_response_multimodal(
+        name: impl Into<String>,
+        response: &CallToolResult,
+    ) -> Result<Self, serde_json::Error> {
+        let mut remaining = response.clone();
+        let mut parts = Vec::new();
+        for content in std::mem::take(&mut remaining.content) {
+            match mcp_content_to_blob(&content.raw) {
+                Some(blob) => parts.push(FunctionResponsePart {
+                    inline_data: Some(blob),
+                    file_data: None,
+                }),
+                None => remaining.content.push(content),
+            }
+        }
+        Ok(Self {
+            parts: (!parts.is_empty()).then_some(parts),
+            ..Self::from_mcp_response(name, &remaining)?
         })
     }
 }
 
+/// 序列化 MCP 结果；`is_error` 时包装为 `{"error": ...}`。
+#[cfg(feature = "mcp")]
+fn mcp_result_to_value(response: &CallToolResult) -> Result<Value, serde_json::Error> {
+    let value = serde_json::to_value(response)?;
+    if response.is_error.unwrap_or(false) {
+        let mut wrapper = serde_json::Map::new();
+        wrapper.insert("error".to_string(), value);
+        Ok(Value::Object(wrapper))
+    } else {
+        Ok(value)
+    }
+}
+
+/// 将 MCP 媒体内容解码为内联数据；非媒体内容或 base64 无法解码时返回 `None`。
+#[cfg(feature = "mcp")]
+fn mcp_content_to_blob(content: &RawContent) -> Option<FunctionResponseBlob> {
+    use base64::Engine as _;
+
+    let (mime_type, data, display_name) = match content {
+        RawContent::Image(image) => (image.mime_type.clone(), &image.data, None),
+        RawContent::Audio(audio) => (audio.mime_type.clone(), &audio.data, None),
+        RawContent::Resource(resource) => match &resource.resource {
+            ResourceContents::BlobResourceContents {
+                uri,
+                mime_type,
+                blob,
+                ..
+            } => (
+                mime_type
+                    .clone()
+                    .unwrap_or_else(|| "application/octet-stream".to_string()),
+                blob,
+                Some(uri.clone()),
+            ),
+            ResourceContents::TextResourceContents { .. } => return None,
+        },
+        RawContent::Text(_) | RawContent::ResourceLink(_) => return None,
+    };
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(data.as_bytes())
+        .ok()?;
+    Some(FunctionResponseBlob {
+        mime_type,
+        data,
+        display_name,
+    })
+}
+
 /// 可执行代码。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -434,6 +759,14 @@ pub struct CodeExecutionResult {
     pub output: Option<String>,
 }
 
+impl CodeExecutionResult {
+    /// 执行是否成功（`OUTCOME_OK`）。
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.outcome == Outcome::OutcomeOk
+    }
+}
+
 /// 视频元数据。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -451,6 +784,34 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn pdf_with_page_hint_serializes_document_and_hint() {
+        let content = Content::pdf_with_page_hint(
+            Part::pdf(b"%PDF-1.7".to_vec()),
+            "Nur Seiten 3-5 verwenden.",
+        )
+        .unwrap();
+        let value = serde_json::to_value(&content).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "role": "user",
+                "parts": [
+                    {"inlineData": {"mimeType": "application/pdf", "data": "JVBERi0xLjc="}},
+                    {"text": "Nur Seiten 3-5 verwenden."}
+                ]
+            })
+        );
+
+        let file = Content::pdf_with_page_hint(Part::pdf_file("files/doc"), "page 2").unwrap();
+        assert_eq!(file.parts[1].text_value(), Some("page 2"));
+        assert_eq!(
+            Content::pdf_with_page_hint(Part::inline_data(vec![1], "image/png"), "page 1")
+                .unwrap_err(),
+            "document part must be inline or file data with mime type `application/pdf`"
+        );
+    }
+
     #[test]
     fn content_first_text_skips_non_text() {
         let content = Content::from_parts(
@@ -528,6 +889,23 @@ mod tests {
         assert_eq!(decoded.parts.len(), 1);
     }
 
+    #[test]
+    fn text_part_from_latin1_file() {
+        let path =
+            std::env::temp_dir().join(format!("rust-genai-latin1-{}.txt", std::process::id()));
+        std::fs::write(&path, b"Cr\xE8me br\xFBl\xE9e").unwrap();
+
+        let part = Part::text_from_path(&path, Some(TextEncoding::Latin1)).unwrap();
+        let err = Part::text_from_path(&path, None).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        match part.kind {
+            PartKind::Text { text } => assert_eq!(text, "Crème brûlée"),
+            _ => panic!("expected text part"),
+        }
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
     #[test]
     fn blob_base64_serialization() {
         let blob = Blob {
@@ -562,6 +940,53 @@ mod tests {
         assert!(json.contains("inlineData"));
     }
 
+    #[cfg(feature = "mcp")]
+    #[test]
+    fn function_response_from_mcp_defaults_to_json() {
+        let result = CallToolResult::success(vec![
+            rmcp::model::Content::text("chart rendered"),
+            rmcp::model::Content::image("AQID", "image/png"),
+        ]);
+        let response = FunctionResponse::from_mcp_response("render_chart", &result).unwrap();
+        assert!(response.parts.is_none());
+        let content = response.response.unwrap()["content"].clone();
+        assert_eq!(content.as_array().unwrap().len(), 2);
+        assert_eq!(content[1]["data"], "AQID");
+    }
+
+    #[cfg(feature = "mcp")]
+    #[test]
+    fn function_response_from_mcp_multimodal_skips_undecodable_media() {
+        let result = CallToolResult::success(vec![rmcp::model::Content::image(
+            "not base64!",
+            "image/png",
+        )]);
+        let response =
+            FunctionResponse::from_mcp_response_multimodal("render_chart", &result).unwrap();
+        assert!(response.parts.is_none());
+        let content = response.response.unwrap()["content"].clone();
+        assert_eq!(content[0]["data"], "not base64!");
+    }
+
+    #[cfg(feature = "mcp")]
+    #[test]
+    fn function_response_from_mcp_multimodal_keeps_image_parts() {
+        let result = CallToolResult::success(vec![
+            rmcp::model::Content::text("chart rendered"),
+            rmcp::model::Content::image("AQID", "image/png"),
+        ]);
+        let response =
+            FunctionResponse::from_mcp_response_multimodal("render_chart", &result).unwrap();
+        let parts = response.parts.unwrap();
+        assert_eq!(parts.len(), 1);
+        let blob = parts[0].inline_data.as_ref().unwrap();
+        assert_eq!(blob.mime_type, "image/png");
+        assert_eq!(blob.data, vec![1, 2, 3]);
+        let content = response.response.unwrap()["content"].clone();
+        assert_eq!(content.as_array().unwrap().len(), 1);
+        assert_eq!(content[0]["text"], "chart rendered");
+    }
+
     #[test]
     fn function_call_part_deserializes_from_camel_case() {
         let value = json!({
@@ -575,4 +1000,41 @@ mod tests {
         let call = part.function_call_ref().expect("missing function call");
         assert_eq!(call.name.as_deref(), Some("add_numbers"));
     }
+
+    #[test]
+    fn function_call_assembles_partial_args() {
+        let call: FunctionCall = serde_json::from_value(json!({
+            "name": "book",
+            "partialArgs": [
+                {"jsonPath": "$.city", "stringValue": "Bei", "willContinue": true},
+                {"jsonPath": "$.city", "stringValue": "jing"},
+                {"jsonPath": "$.guests", "numberValue": 2},
+                {"jsonPath": "$.budget", "numberValue": 99.5},
+                {"jsonPath": "$.options.late", "boolValue": true},
+                {"jsonPath": "$.rooms[1]['type']", "stringValue": "suite"},
+                {"jsonPath": "$.note", "nullValue": "NULL_VALUE"}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            call.assembled_args(),
+            Some(json!({
+                "city": "Beijing",
+                "guests": 2,
+                "budget": 99.5,
+                "options": {"late": true},
+                "rooms": [null, {"type": "suite"}],
+                "note": null
+            }))
+        );
+
+        let plain = FunctionCall {
+            id: None,
+            name: Some("noop".into()),
+            args: Some(json!({"a": 1})),
+            partial_args: None,
+            will_continue: None,
+        };
+        assert_eq!(plain.assembled_args(), Some(json!({"a": 1})));
+    }
 }
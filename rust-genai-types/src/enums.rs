@@ -314,8 +314,34 @@ pub enum HarmBlockThreshold {
     Off,
 }
 
+/// Implements `Display` / `FromStr` using the API enum strings (same as the serde form).
+macro_rules! impl_api_enum_string {
+    ($($ty:ty),+ $(,)?) => {$(
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match serde_json::to_value(self) {
+                    Ok(serde_json::Value::String(value)) => f.write_str(&value),
+                    _ => Err(std::fmt::Error),
+                }
+            }
+        }
+
+        impl std::str::FromStr for $ty {
+            type Err = serde_json::Error;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                serde_json::from_value(serde_json::Value::String(value.to_string()))
+            }
+        }
+    )+};
+}
+
+impl_api_enum_string!(HarmCategory, HarmBlockThreshold);
+
 /// Harm probability.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// 变体按风险从低到高排列，可直接比较大小（`Negligible < Low < Medium < High`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum HarmProbability {
     HarmProbabilityUnspecified,
@@ -352,6 +378,40 @@ pub enum JobState {
     JobStatePartiallySucceeded,
 }
 
+impl JobState {
+    /// Returns `true` once the job can no longer change state.
+    #[must_use]
+    pub const fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::JobStateSucceeded
+                | Self::JobStatePartiallySucceeded
+                | Self::JobStateFailed
+                | Self::JobStateCancelled
+                | Self::JobStateExpired
+        )
+    }
+
+    /// Short human-readable description for logs and dashboards.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::JobStateUnspecified => "state unspecified",
+            Self::JobStateQueued => "queued, waiting for resources",
+            Self::JobStatePending => "pending, preparing to run",
+            Self::JobStateRunning => "running",
+            Self::JobStateSucceeded => "succeeded",
+            Self::JobStateFailed => "failed",
+            Self::JobStateCancelling => "cancelling",
+            Self::JobStateCancelled => "cancelled",
+            Self::JobStatePaused => "paused",
+            Self::JobStateExpired => "expired before completion",
+            Self::JobStateUpdating => "updating",
+            Self::JobStatePartiallySucceeded => "partially succeeded",
+        }
+    }
+}
+
 /// Tuning method.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -420,6 +480,24 @@ pub enum FinishReason {
     ImageOther,
 }
 
+impl FinishReason {
+    /// Returns `true` when generation was stopped by a safety, recitation or policy filter.
+    #[must_use]
+    pub const fn is_blocked(self) -> bool {
+        matches!(
+            self,
+            Self::Safety
+                | Self::Recitation
+                | Self::Blocklist
+                | Self::ProhibitedContent
+                | Self::Spii
+                | Self::ImageSafety
+                | Self::ImageProhibitedContent
+                | Self::ImageRecitation
+        )
+    }
+}
+
 /// Thinking level for thinking models.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -579,16 +657,128 @@ pub enum PhishBlockThreshold {
     BlockOnlyExtremelyHigh,
 }
 
+/// Embedding task type.
+///
+/// Unknown values round-trip through [`TaskType::Other`] for forward compatibility.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TaskType {
+    TaskTypeUnspecified,
+    RetrievalQuery,
+    RetrievalDocument,
+    SemanticSimilarity,
+    Classification,
+    Clustering,
+    QuestionAnswering,
+    FactVerification,
+    CodeRetrievalQuery,
+    /// A task type not known to this SDK version, sent verbatim.
+    Other(String),
+}
+
+impl TaskType {
+    const KNOWN: [(Self, &'static str); 9] = [
+        (Self::TaskTypeUnspecified, "TASK_TYPE_UNSPECIFIED"),
+        (Self::RetrievalQuery, "RETRIEVAL_QUERY"),
+        (Self::RetrievalDocument, "RETRIEVAL_DOCUMENT"),
+        (Self::SemanticSimilarity, "SEMANTIC_SIMILARITY"),
+        (Self::Classification, "CLASSIFICATION"),
+        (Self::Clustering, "CLUSTERING"),
+        (Self::QuestionAnswering, "QUESTION_ANSWERING"),
+        (Self::FactVerification, "FACT_VERIFICATION"),
+        (Self::CodeRetrievalQuery, "CODE_RETRIEVAL_QUERY"),
+    ];
+
+    /// Wire string sent to the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Other(value) => value,
+            known => Self::KNOWN
+                .iter()
+                .find(|(variant, _)| variant == known)
+                .map_or("TASK_TYPE_UNSPECIFIED", |(_, name)| name),
+        }
+    }
+}
+
+impl From<&str> for TaskType {
+    fn from(value: &str) -> Self {
+        Self::KNOWN
+            .iter()
+            .find(|(_, name)| *name == value)
+            .map_or_else(
+                || Self::Other(value.to_string()),
+                |(variant, _)| variant.clone(),
+            )
+    }
+}
+
+impl From<String> for TaskType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl std::fmt::Display for TaskType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for TaskType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn job_state_terminal_and_description() {
+        assert!(JobState::JobStateExpired.is_terminal());
+        assert!(!JobState::JobStateCancelling.is_terminal());
+        assert_eq!(JobState::JobStateRunning.description(), "running");
+    }
+
     #[test]
     fn harm_category_serialization() {
         let value = serde_json::to_string(&HarmCategory::HarmCategoryDangerousContent).unwrap();
         assert_eq!(value, "\"HARM_CATEGORY_DANGEROUS_CONTENT\"");
     }
 
+    #[test]
+    fn harm_enums_round_trip_through_strings() {
+        let threshold: HarmBlockThreshold = "BLOCK_ONLY_HIGH".parse().unwrap();
+        assert_eq!(threshold, HarmBlockThreshold::BlockOnlyHigh);
+        assert_eq!(threshold.to_string(), "BLOCK_ONLY_HIGH");
+
+        let category: HarmCategory = "HARM_CATEGORY_HARASSMENT".parse().unwrap();
+        assert_eq!(category, HarmCategory::HarmCategoryHarassment);
+        assert_eq!(category.to_string(), "HARM_CATEGORY_HARASSMENT");
+        assert!("harassment".parse::<HarmCategory>().is_err());
+    }
+
+    #[test]
+    fn task_type_round_trips_known_and_unknown_values() {
+        assert_eq!(
+            serde_json::to_string(&TaskType::RetrievalDocument).unwrap(),
+            "\"RETRIEVAL_DOCUMENT\""
+        );
+        let parsed: TaskType = serde_json::from_str("\"CLUSTERING\"").unwrap();
+        assert_eq!(parsed, TaskType::Clustering);
+        let future = TaskType::from("NEW_TASK");
+        assert_eq!(future, TaskType::Other("NEW_TASK".into()));
+        assert_eq!(future.to_string(), "NEW_TASK");
+    }
+
     #[test]
     fn image_prompt_language_serialization() {
         let value = serde_json::to_string(&ImagePromptLanguage::Zh).unwrap();
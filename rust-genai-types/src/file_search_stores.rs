@@ -3,7 +3,7 @@ use serde_json::Value;
 
 use crate::documents::CustomMetadata;
 use crate::http::{HttpOptions, HttpResponse};
-use crate::operations::OperationError;
+use crate::operations::{OperationError, Status};
 
 /// Optional parameters for creating a file search store.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -196,6 +196,14 @@ pub struct ImportFileOperation {
     pub response: Option<ImportFileResponse>,
 }
 
+impl ImportFileOperation {
+    /// 操作失败时返回类型化的错误状态。
+    #[must_use]
+    pub fn error_status(&self) -> Option<Status> {
+        self.error.as_ref().map(OperationError::to_status)
+    }
+}
+
 /// Long-running operation for uploading a file to a `FileSearchStore`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -211,3 +219,11 @@ pub struct UploadToFileSearchStoreOperation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response: Option<UploadToFileSearchStoreResponse>,
 }
+
+impl UploadToFileSearchStoreOperation {
+    /// 操作失败时返回类型化的错误状态。
+    #[must_use]
+    pub fn error_status(&self) -> Option<Status> {
+        self.error.as_ref().map(OperationError::to_status)
+    }
+}
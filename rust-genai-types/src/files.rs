@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::enums::{FileSource, FileState};
 use crate::http::{HttpOptions, HttpResponse};
@@ -47,6 +50,10 @@ pub struct File {
     pub video_metadata: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<FileStatus>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 /// List files request configuration.
@@ -73,6 +80,10 @@ pub struct ListFilesResponse {
     pub next_page_token: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub files: Option<Vec<File>>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 /// Create file request configuration (resumable upload start).
@@ -15,6 +15,24 @@ pub struct HttpResponse {
     pub body: Option<String>,
 }
 
+impl HttpResponse {
+    /// 按名称（不区分大小写）读取响应头。
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .as_ref()?
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// 服务端请求 ID（`x-goog-request-id`），用于关联日志与支持工单。
+    #[must_use]
+    pub fn request_id(&self) -> Option<&str> {
+        self.header("x-goog-request-id")
+    }
+}
+
 /// HTTP retry options to be used in each of the requests.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -48,6 +66,9 @@ pub struct HttpRetryOptions {
 pub struct HttpOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_url: Option<String>,
+    /// Base URL for file upload `start` requests; defaults to `{base_url}upload/`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_base_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,9 +76,20 @@ pub struct HttpOptions {
     /// Timeout for the request in milliseconds.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
+    /// Idle timeout for streaming responses in milliseconds: the stream errors when no
+    /// SSE event arrives within this window (comment/heartbeat lines do not count).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_idle_timeout: Option<u64>,
     /// Extra parameters to add to the request body.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra_body: Option<serde_json::Value>,
+    /// Extra query parameters appended to the request URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_query: Option<Vec<(String, String)>>,
+    /// Client-supplied request ID sent on create calls (batches, tunings, caches) so a
+    /// retried create can be de-duplicated; a UUID is generated when omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
     /// HTTP retry options for the request.
     ///
     /// When omitted, the SDK default retry policy still applies.
@@ -19,6 +19,8 @@ pub mod logprobs;
 pub mod models;
 pub mod operations;
 pub mod response;
+pub mod text_encoding;
+pub mod timestamp;
 pub mod tokens;
 pub mod tool;
 pub mod tunings;
@@ -100,6 +100,17 @@ pub struct LiveMusicGenerationConfig {
     pub music_generation_mode: Option<MusicGenerationMode>,
 }
 
+impl WeightedPrompt {
+    /// 创建加权提示词。
+    #[must_use]
+    pub fn new(text: impl Into<String>, weight: f32) -> Self {
+        Self {
+            text: Some(text.into()),
+            weight: Some(weight),
+        }
+    }
+}
+
 /// Messages sent by the client in the `LiveMusicClientMessage` call.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -114,6 +125,37 @@ pub struct LiveMusicClientMessage {
     pub playback_control: Option<LiveMusicPlaybackControl>,
 }
 
+impl LiveMusicClientMessage {
+    /// 构建设置加权提示词的消息。
+    #[must_use]
+    pub fn from_weighted_prompts(prompts: Vec<WeightedPrompt>) -> Self {
+        Self {
+            client_content: Some(LiveMusicClientContent {
+                weighted_prompts: Some(prompts),
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// 构建设置音乐生成配置的消息。
+    #[must_use]
+    pub fn from_music_generation_config(config: LiveMusicGenerationConfig) -> Self {
+        Self {
+            music_generation_config: Some(config),
+            ..Default::default()
+        }
+    }
+
+    /// 构建播放控制消息。
+    #[must_use]
+    pub fn from_playback_control(control: LiveMusicPlaybackControl) -> Self {
+        Self {
+            playback_control: Some(control),
+            ..Default::default()
+        }
+    }
+}
+
 /// Sent in response to a `LiveMusicClientSetup` message from the client.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -184,6 +226,15 @@ impl LiveMusicServerMessage {
             .and_then(|content| content.audio_chunks.as_ref())
             .and_then(|chunks| chunks.first())
     }
+
+    /// 获取全部音频 chunk。
+    #[must_use]
+    pub fn audio_chunks(&self) -> &[AudioChunk] {
+        self.server_content
+            .as_ref()
+            .and_then(|content| content.audio_chunks.as_deref())
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +266,31 @@ mod tests {
         assert_eq!(first.mime_type.as_deref(), Some("audio/wav"));
     }
 
+    #[test]
+    fn weighted_prompts_message_shape() {
+        let message = LiveMusicClientMessage::from_weighted_prompts(vec![
+            WeightedPrompt::new("minimal techno", 1.0),
+            WeightedPrompt::new("piano", 0.5),
+        ]);
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            json!({
+                "clientContent": {
+                    "weightedPrompts": [
+                        {"text": "minimal techno", "weight": 1.0},
+                        {"text": "piano", "weight": 0.5}
+                    ]
+                }
+            })
+        );
+
+        let message = LiveMusicClientMessage::from_playback_control(LiveMusicPlaybackControl::Play);
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            json!({"playbackControl": "PLAY"})
+        );
+    }
+
     #[test]
     fn audio_chunk_base64_roundtrip() {
         let chunk = AudioChunk {
@@ -152,6 +152,32 @@ pub struct LiveServerMessage {
     pub voice_activity_detection_signal: Option<VoiceActivityDetectionSignal>,
 }
 
+impl LiveServerMessage {
+    /// Transcription of the user's input audio carried by this message, if any.
+    #[must_use]
+    pub fn input_transcription(&self) -> Option<&Transcription> {
+        self.server_content.as_ref()?.input_transcription.as_ref()
+    }
+
+    /// Transcription of the model's output audio carried by this message, if any.
+    #[must_use]
+    pub fn output_transcription(&self) -> Option<&Transcription> {
+        self.server_content.as_ref()?.output_transcription.as_ref()
+    }
+
+    /// Input audio transcription text (a caption fragment of what the user said).
+    #[must_use]
+    pub fn input_transcription_text(&self) -> Option<&str> {
+        self.input_transcription()?.text.as_deref()
+    }
+
+    /// Output audio transcription text (a caption fragment of what the model said).
+    #[must_use]
+    pub fn output_transcription_text(&self) -> Option<&str> {
+        self.output_transcription()?.text.as_deref()
+    }
+}
+
 /// Configures automatic detection of activity.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -375,6 +401,31 @@ mod tests {
     use crate::content::{Content, Part, Role};
     use crate::enums::{ActivityHandling, StartSensitivity, VadSignalType};
 
+    #[test]
+    fn live_server_message_transcription_accessors() {
+        let message: LiveServerMessage = serde_json::from_value(serde_json::json!({
+            "serverContent": {
+                "inputTranscription": {"text": "what's the weather"},
+                "outputTranscription": {"text": "It is sunny", "finished": true}
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            message.input_transcription_text(),
+            Some("what's the weather")
+        );
+        assert_eq!(message.output_transcription_text(), Some("It is sunny"));
+        assert_eq!(
+            message.output_transcription().and_then(|t| t.finished),
+            Some(true)
+        );
+
+        let empty: LiveServerMessage =
+            serde_json::from_value(serde_json::json!({"setupComplete": {}})).unwrap();
+        assert!(empty.input_transcription_text().is_none());
+        assert!(empty.output_transcription().is_none());
+    }
+
     #[test]
     fn live_server_message_roundtrip() {
         let message = LiveServerMessage {
@@ -9,6 +9,14 @@ pub struct LogprobCandidate {
     pub log_probability: f64,
 }
 
+impl LogprobCandidate {
+    /// 线性概率（`exp(log_probability)`）。
+    #[must_use]
+    pub fn probability(&self) -> f64 {
+        self.log_probability.exp()
+    }
+}
+
 /// Top candidates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -29,6 +37,17 @@ pub struct LogprobsResult {
     pub log_probability_sum: Option<f64>,
 }
 
+impl LogprobsResult {
+    /// 逐 token 的选中概率（按 `chosen_candidates` 顺序）。
+    #[must_use]
+    pub fn token_probabilities(&self) -> Vec<(&str, f64)> {
+        self.chosen_candidates
+            .iter()
+            .map(|candidate| (candidate.token.as_str(), candidate.probability()))
+            .collect()
+    }
+}
+
 // 兼容旧名称（避免外部依赖受影响）。
 pub type LogprobsResultCandidate = LogprobCandidate;
 pub type LogprobsResultTopCandidates = TopCandidates;
@@ -1,15 +1,16 @@
 use std::collections::HashMap;
+use std::ops::Range;
 
 use crate::base64_serde;
-use crate::config::{GenerationConfig, ModelArmorConfig, SafetySetting};
+use crate::config::{GenerationConfig, ModelArmorConfig, SafetySetting, SpeechConfig};
 use crate::content::Content;
 use crate::enums::{
-    ControlReferenceType, EditMode, ImagePromptLanguage, MaskReferenceMode, PersonGeneration,
-    ReferenceImageType, SafetyFilterLevel, SegmentMode, SubjectReferenceType,
-    VideoCompressionQuality, VideoGenerationMaskMode, VideoGenerationReferenceType,
+    ControlReferenceType, EditMode, ImagePromptLanguage, MaskReferenceMode, Modality,
+    PersonGeneration, ReferenceImageType, SafetyFilterLevel, SegmentMode, SubjectReferenceType,
+    TaskType, VideoCompressionQuality, VideoGenerationMaskMode, VideoGenerationReferenceType,
 };
 use crate::http::{HttpOptions, HttpResponse};
-use crate::operations::OperationError;
+use crate::operations::{OperationError, Status};
 use crate::tool::{Tool, ToolConfig};
 use crate::webhooks::WebhookConfig;
 use serde::{Deserialize, Serialize};
@@ -45,6 +46,110 @@ pub struct GenerateContentConfig {
     /// Note: Not supported in streaming methods.
     #[serde(skip_serializing, skip_deserializing)]
     pub should_return_http_response: Option<bool>,
+    /// Optional. If true, a blocked prompt or a safety/recitation finish is returned as
+    /// `Error::ContentBlocked` instead of an empty response (SDK only). Applies to every
+    /// automatic function calling turn; streaming methods yield the error in place of the
+    /// first blocked chunk and then end the stream.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub error_on_blocked_content: Option<bool>,
+    /// Optional. If true, `generate_json*` closes unterminated strings/brackets of JSON cut off
+    /// by `max_output_tokens` before parsing (SDK only, best effort).
+    #[serde(skip_serializing, skip_deserializing)]
+    pub repair_truncated_json: Option<bool>,
+    /// Optional. Number of times `generate_content_stream` re-sends the request after a
+    /// transport error before the stream finished, skipping first-candidate text that was
+    /// already emitted (SDK only, best effort: the API cannot resume a generation, so the
+    /// regenerated output may diverge from what was emitted).
+    #[serde(skip_serializing, skip_deserializing)]
+    pub stream_restart_attempts: Option<u32>,
+    /// Optional. Checks `contents` roles and function call/response pairing before sending:
+    /// `Validate` returns a descriptive error, `Normalize` fixes roles where possible (SDK only).
+    #[serde(skip_serializing, skip_deserializing)]
+    pub content_role_check: Option<ContentRoleCheck>,
+    /// Optional. HTTP request overrides (SDK only, not sent to API).
+    #[serde(skip_serializing, skip_deserializing)]
+    pub http_options: Option<HttpOptions>,
+}
+
+/// 发送前对 `contents` 角色序列的检查方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentRoleCheck {
+    /// 角色错误或函数调用/响应不配对时返回错误；函数响应可使用 `function` 或 `user` 角色。
+    Validate,
+    /// 将含函数调用的内容改为 `model`，角色为 `model` 或未设置的函数响应内容改为 `function`
+    /// （`user` 角色的函数响应保持不变）；配对错误仍返回错误。
+    Normalize,
+}
+
+impl GenerateContentConfig {
+    /// 设置纯文本系统指令。
+    #[must_use]
+    pub fn with_system_instruction(mut self, instruction: impl Into<String>) -> Self {
+        self.system_instruction = Some(Content::text(instruction));
+        self
+    }
+
+    /// 设置系统指令内容（可包含多个 part，如文本与内联文件）。
+    #[must_use]
+    pub fn with_system_instruction_content(mut self, instruction: Content) -> Self {
+        self.system_instruction = Some(instruction);
+        self
+    }
+
+    /// 设置安全设置（可传入 [`SafetySettings`](crate::config::SafetySettings) 构建器）。
+    #[must_use]
+    pub fn with_safety_settings(mut self, settings: impl Into<Vec<SafetySetting>>) -> Self {
+        self.safety_settings = Some(settings.into());
+        self
+    }
+
+    /// 设置期望的输出模态（写入 `generation_config.response_modalities`）。
+    #[must_use]
+    pub fn with_response_modalities(mut self, modalities: impl Into<Vec<Modality>>) -> Self {
+        self.generation_config = Some(
+            self.generation_config
+                .take()
+                .unwrap_or_default()
+                .with_response_modalities(modalities),
+        );
+        self
+    }
+
+    /// 设置思考 token 预算（写入 `generation_config.thinking_config.thinking_budget`）。
+    #[must_use]
+    pub fn with_thinking_budget(mut self, budget: i32) -> Self {
+        self.generation_config = Some(
+            self.generation_config
+                .take()
+                .unwrap_or_default()
+                .with_thinking_budget(budget),
+        );
+        self
+    }
+
+    /// 设置是否返回思考摘要（写入 `generation_config.thinking_config.include_thoughts`）。
+    #[must_use]
+    pub fn with_include_thoughts(mut self, include_thoughts: bool) -> Self {
+        self.generation_config = Some(
+            self.generation_config
+                .take()
+                .unwrap_or_default()
+                .with_include_thoughts(include_thoughts),
+        );
+        self
+    }
+
+    /// 设置语音合成配置（写入 `generation_config.speech_config`）。
+    #[must_use]
+    pub fn with_speech_config(mut self, speech_config: SpeechConfig) -> Self {
+        self.generation_config = Some(
+            self.generation_config
+                .take()
+                .unwrap_or_default()
+                .with_speech_config(speech_config),
+        );
+        self
+    }
 }
 
 /// `GenerateContent` 请求体。
@@ -85,6 +190,15 @@ pub struct AutomaticFunctionCallingConfig {
     /// 是否忽略自动函数调用历史。
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ignore_call_history: Option<bool>,
+    /// 达到 `maximum_remote_calls` 时返回 `Error::MaxToolCallsExceeded`，而非静默返回最后的响应。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_on_max_remote_calls: Option<bool>,
+    /// 流式 AFC 的事件缓冲区大小（默认 8，至少为 1）；缓冲区满时暂停生产，等待消费者读取。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_buffer_size: Option<u32>,
+    /// 整个 AFC 交互（所有模型轮次与工具执行）的总超时（毫秒），超时返回 `Error::Timeout`。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_timeout_ms: Option<u64>,
 }
 
 /// `CountTokens` 请求配置。
@@ -113,7 +227,7 @@ pub struct CountTokensRequest {
 }
 
 /// `CountTokens` 响应体。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CountTokensResponse {
     /// Optional. Used to retain the full HTTP response.
@@ -123,6 +237,18 @@ pub struct CountTokensResponse {
     pub total_tokens: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cached_content_token_count: Option<i32>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl CountTokensResponse {
+    /// 服务端请求 ID（`x-goog-request-id` 响应头）。
+    #[must_use]
+    pub fn request_id(&self) -> Option<&str> {
+        self.sdk_http_response.as_ref()?.request_id()
+    }
 }
 
 /// `ComputeTokens` 请求配置。
@@ -149,13 +275,44 @@ pub struct TokensInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub role: Option<String>,
     /// A list of token ids from the input.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    ///
+    /// Vertex AI 以 JSON 字符串返回 int64，反序列化时同时接受字符串与数字。
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_int64_list"
+    )]
     pub token_ids: Option<Vec<i64>>,
     /// A list of tokens from the input (base64-encoded strings).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tokens: Option<Vec<String>>,
 }
 
+fn deserialize_int64_list<'de, D>(deserializer: D) -> Result<Option<Vec<i64>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Int64 {
+        Number(i64),
+        String(String),
+    }
+
+    let values = Option::<Vec<Int64>>::deserialize(deserializer)?;
+    values
+        .map(|values| {
+            values
+                .into_iter()
+                .map(|value| match value {
+                    Int64::Number(value) => Ok(value),
+                    Int64::String(value) => value.parse().map_err(serde::de::Error::custom),
+                })
+                .collect()
+        })
+        .transpose()
+}
+
 /// Response for computing tokens.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -166,6 +323,97 @@ pub struct ComputeTokensResponse {
     /// Lists of tokens info from the input.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tokens_info: Option<Vec<TokensInfo>>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// `ComputeTokens` 返回的单个 token。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputedToken {
+    /// token 所属 Content 的角色。
+    pub role: Option<String>,
+    /// token id。
+    pub token_id: Option<i64>,
+    /// token 的原始字节（已从 base64 解码）。
+    pub bytes: Vec<u8>,
+}
+
+impl ComputedToken {
+    /// token 文本；字节不是合法 UTF-8（如多字节字符被拆分）时返回 `None`。
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+}
+
+impl TokensInfo {
+    /// 解码为逐 token 条目；token 与 id 按位置对应，无法 base64 解码的 token 按原始字符串处理。
+    #[must_use]
+    pub fn computed_tokens(&self) -> Vec<ComputedToken> {
+        use base64::Engine as _;
+
+        let tokens = self.tokens.as_deref().unwrap_or_default();
+        let ids = self.token_ids.as_deref().unwrap_or_default();
+        (0..tokens.len().max(ids.len()))
+            .map(|index| ComputedToken {
+                role: self.role.clone(),
+                token_id: ids.get(index).copied(),
+                bytes: tokens.get(index).map_or_else(Vec::new, |token| {
+                    base64::engine::general_purpose::STANDARD
+                        .decode(token)
+                        .unwrap_or_else(|_| token.clone().into_bytes())
+                }),
+            })
+            .collect()
+    }
+
+    /// 将 token 依次映射回 `input` 中的字符区间（按 `char` 计数）。
+    ///
+    /// SentencePiece 的 `▁` 视为空格；无法在剩余输入中定位的 token（如特殊 token 或被拆分的
+    /// 多字节字符）对应 `None`，不影响后续 token 的匹配。
+    #[must_use]
+    pub fn char_spans(&self, input: &str) -> Vec<Option<Range<usize>>> {
+        let mut byte_cursor = 0;
+        let mut char_cursor = 0;
+        self.computed_tokens()
+            .iter()
+            .map(|token| {
+                let text = token.text()?.replace('\u{2581}', " ");
+                if text.is_empty() {
+                    return None;
+                }
+                let rest = &input[byte_cursor..];
+                let trimmed = text.trim_start();
+                let (offset, matched) = rest.find(text.as_str()).map_or_else(
+                    || {
+                        (!trimmed.is_empty() && trimmed.len() != text.len())
+                            .then(|| rest.find(trimmed).map(|offset| (offset, trimmed)))
+                            .flatten()
+                    },
+                    |offset| Some((offset, text.as_str())),
+                )?;
+                let start = char_cursor + rest[..offset].chars().count();
+                let end = start + matched.chars().count();
+                byte_cursor += offset + matched.len();
+                char_cursor = end;
+                Some(start..end)
+            })
+            .collect()
+    }
+}
+
+impl ComputeTokensResponse {
+    /// 所有 `tokens_info` 条目解码后的 token 列表。
+    #[must_use]
+    pub fn computed_tokens(&self) -> Vec<ComputedToken> {
+        self.tokens_info
+            .iter()
+            .flatten()
+            .flat_map(TokensInfo::computed_tokens)
+            .collect()
+    }
 }
 
 /// `EmbedContent` 配置。
@@ -173,7 +421,7 @@ pub struct ComputeTokensResponse {
 #[serde(rename_all = "camelCase")]
 pub struct EmbedContentConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub task_type: Option<String>,
+    pub task_type: Option<TaskType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -190,7 +438,8 @@ pub struct EmbedContentConfig {
 pub struct ContentEmbeddingStatistics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub truncated: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Vertex `predict` 响应使用 `token_count`（snake_case）。
+    #[serde(skip_serializing_if = "Option::is_none", alias = "token_count")]
     pub token_count: Option<f32>,
 }
 
@@ -204,6 +453,20 @@ pub struct ContentEmbedding {
     pub statistics: Option<ContentEmbeddingStatistics>,
 }
 
+impl ContentEmbedding {
+    /// 输入是否因超出长度被截断（仅 Vertex AI 返回）。
+    #[must_use]
+    pub fn truncated(&self) -> Option<bool> {
+        self.statistics.as_ref()?.truncated
+    }
+
+    /// 输入的 token 数（仅 Vertex AI 返回）。
+    #[must_use]
+    pub fn token_count(&self) -> Option<f32> {
+        self.statistics.as_ref()?.token_count
+    }
+}
+
 /// `EmbedContent` 元数据。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -213,7 +476,7 @@ pub struct EmbedContentMetadata {
 }
 
 /// `EmbedContent` 响应。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct EmbedContentResponse {
     /// Optional. Used to retain the full HTTP response.
@@ -223,6 +486,27 @@ pub struct EmbedContentResponse {
     pub embeddings: Option<Vec<ContentEmbedding>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<EmbedContentMetadata>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl EmbedContentResponse {
+    /// 服务端请求 ID（`x-goog-request-id` 响应头）。
+    #[must_use]
+    pub fn request_id(&self) -> Option<&str> {
+        self.sdk_http_response.as_ref()?.request_id()
+    }
+
+    /// 是否有任一输入被截断（仅 Vertex AI 返回统计信息）。
+    #[must_use]
+    pub fn any_truncated(&self) -> bool {
+        self.embeddings
+            .iter()
+            .flatten()
+            .any(|embedding| embedding.truncated() == Some(true))
+    }
 }
 
 /// 模型信息。
@@ -239,14 +523,55 @@ pub struct Model {
     pub input_token_limit: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_token_limit: Option<i32>,
-    /// Current Gemini API responses often omit this field.
-    /// Prefer model-name allowlists or trial requests when selecting capabilities.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// 支持的生成方法（Gemini API 的 `supportedGenerationMethods`，如 `generateContent`）。
+    ///
+    /// Vertex AI 响应不包含该字段；此时请改用模型名白名单或试探请求判断能力。
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        alias = "supportedGenerationMethods"
+    )]
     pub supported_actions: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub labels: Option<HashMap<String, String>>,
 }
 
+impl Model {
+    /// 是否声明支持指定方法（如 `generateContent`、`embedContent`）。
+    ///
+    /// 未返回 `supported_actions` 时视为不支持。
+    #[must_use]
+    pub fn supports_method(&self, method: &str) -> bool {
+        self.supported_actions
+            .iter()
+            .flatten()
+            .any(|action| action == method)
+    }
+
+    /// 是否支持 `generateContent`。
+    #[must_use]
+    pub fn supports_generate_content(&self) -> bool {
+        self.supports_method("generateContent")
+    }
+
+    /// 是否支持 `embedContent` / `batchEmbedContents`。
+    #[must_use]
+    pub fn supports_embedding(&self) -> bool {
+        self.supports_method("embedContent") || self.supports_method("batchEmbedContents")
+    }
+
+    /// 是否支持 `countTokens`。
+    #[must_use]
+    pub fn supports_count_tokens(&self) -> bool {
+        self.supports_method("countTokens")
+    }
+
+    /// 是否支持 Live API（`bidiGenerateContent`）。
+    #[must_use]
+    pub fn supports_live(&self) -> bool {
+        self.supports_method("bidiGenerateContent")
+    }
+}
+
 /// `ListModels` 请求配置。
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -367,6 +692,53 @@ pub struct Image {
     pub mime_type: Option<String>,
 }
 
+impl Image {
+    /// 从内联字节创建图像。
+    #[must_use]
+    pub fn from_bytes(image_bytes: Vec<u8>, mime_type: impl Into<String>) -> Self {
+        Self {
+            gcs_uri: None,
+            image_bytes: Some(image_bytes),
+            mime_type: Some(mime_type.into()),
+        }
+    }
+
+    /// 从 GCS URI 创建图像（仅 Vertex AI 支持）。
+    #[must_use]
+    pub fn from_gcs(uri: impl Into<String>) -> Self {
+        Self {
+            gcs_uri: Some(uri.into()),
+            image_bytes: None,
+            mime_type: None,
+        }
+    }
+
+    /// 读取本地文件创建图像，MIME 类型按扩展名推断。
+    ///
+    /// # Errors
+    /// 当文件读取失败时返回错误。
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let mime_type = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| match ext.to_ascii_lowercase().as_str() {
+                "png" => Some("image/png"),
+                "jpg" | "jpeg" => Some("image/jpeg"),
+                "webp" => Some("image/webp"),
+                "gif" => Some("image/gif"),
+                "heic" => Some("image/heic"),
+                "heif" => Some("image/heif"),
+                _ => None,
+            });
+        Ok(Self {
+            gcs_uri: None,
+            image_bytes: Some(std::fs::read(path)?),
+            mime_type: mime_type.map(str::to_string),
+        })
+    }
+}
+
 /// Safety attributes for generated media.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -393,6 +765,44 @@ pub struct GeneratedImage {
     pub enhanced_prompt: Option<String>,
 }
 
+impl GeneratedImage {
+    /// 按 MIME 类型推断的文件扩展名（缺省为 `png`，未知类型为 `bin`）。
+    #[must_use]
+    pub fn file_extension(&self) -> &'static str {
+        match self
+            .image
+            .as_ref()
+            .and_then(|image| image.mime_type.as_deref())
+        {
+            None | Some("image/png") => "png",
+            Some("image/jpeg") => "jpg",
+            Some("image/webp") => "webp",
+            Some("image/gif") => "gif",
+            Some("image/heic") => "heic",
+            Some("image/heif") => "heif",
+            Some(_) => "bin",
+        }
+    }
+
+    /// 将内联图像字节写入 `path`。
+    ///
+    /// # Errors
+    /// 当图像没有内联字节（如仅返回 GCS URI）或写入失败时返回错误。
+    pub fn write_to(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let bytes = self
+            .image
+            .as_ref()
+            .and_then(|image| image.image_bytes.as_deref())
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "generated image has no inline bytes",
+                )
+            })?;
+        std::fs::write(path, bytes)
+    }
+}
+
 /// 图像生成响应。
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -406,6 +816,37 @@ pub struct GenerateImagesResponse {
     pub positive_prompt_safety_attributes: Option<SafetyAttributes>,
 }
 
+impl GenerateImagesResponse {
+    /// 将所有内联图像保存到 `dir`，文件名为 `{prefix}_{index}.{ext}`，返回写入的路径。
+    ///
+    /// 没有内联字节的结果（如仅返回 GCS URI 或被过滤）会被跳过，`index` 仍按原始位置计数。
+    ///
+    /// # Errors
+    /// 当目录创建或文件写入失败时返回错误。
+    pub fn save_all(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+        prefix: &str,
+    ) -> std::io::Result<Vec<std::path::PathBuf>> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let mut paths = Vec::new();
+        for (index, generated) in self.generated_images.iter().enumerate() {
+            let has_bytes = generated
+                .image
+                .as_ref()
+                .is_some_and(|image| image.image_bytes.is_some());
+            if !has_bytes {
+                continue;
+            }
+            let path = dir.join(format!("{prefix}_{index}.{}", generated.file_extension()));
+            generated.write_to(&path)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
 /// Mask reference config for image editing.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -817,3 +1258,109 @@ pub struct GenerateVideosOperation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response: Option<GenerateVideosResponse>,
 }
+
+impl GenerateVideosOperation {
+    /// 操作失败时返回类型化的错误状态。
+    #[must_use]
+    pub fn error_status(&self) -> Option<Status> {
+        self.error.as_ref().map(OperationError::to_status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn response_types_capture_unknown_fields_in_extra() {
+        let count: CountTokensResponse = serde_json::from_value(serde_json::json!({
+            "totalTokens": 3,
+            "promptTokensDetails": [{"modality": "TEXT", "tokenCount": 3}]
+        }))
+        .unwrap();
+        assert_eq!(count.total_tokens, Some(3));
+        assert!(count.extra.contains_key("promptTokensDetails"));
+        let round_trip = serde_json::to_value(&count).unwrap();
+        assert_eq!(round_trip["promptTokensDetails"][0]["tokenCount"], 3);
+
+        let embed: EmbedContentResponse = serde_json::from_value(serde_json::json!({
+            "embeddings": [{"values": [0.5]}],
+            "usageMetadata": {"promptTokenCount": 1}
+        }))
+        .unwrap();
+        assert!(embed.extra.contains_key("usageMetadata"));
+
+        let file: crate::files::File =
+            serde_json::from_value(serde_json::json!({"name": "files/a", "newField": true}))
+                .unwrap();
+        assert_eq!(file.extra.get("newField"), Some(&Value::Bool(true)));
+
+        let cache: crate::caches::CachedContent =
+            serde_json::from_value(serde_json::json!({"name": "cachedContents/a", "newField": 1}))
+                .unwrap();
+        assert!(cache.extra.contains_key("newField"));
+
+        let batch: crate::batches::BatchJob =
+            serde_json::from_value(serde_json::json!({"name": "batches/a", "newField": 1}))
+                .unwrap();
+        assert!(batch.extra.contains_key("newField"));
+
+        let tuning: crate::tunings::TuningJob =
+            serde_json::from_value(serde_json::json!({"name": "tuningJobs/a", "newField": 1}))
+                .unwrap();
+        assert!(tuning.extra.contains_key("newField"));
+        assert!(CountTokensResponse::default().extra.is_empty());
+    }
+
+    #[test]
+    fn compute_tokens_response_decodes_tokens_and_spans() {
+        let response: ComputeTokensResponse = serde_json::from_value(serde_json::json!({
+            "tokensInfo": [{
+                "role": "user",
+                "tokenIds": ["1596", "2134", "1"],
+                "tokens": ["4paBSGVs", "bG8gd29ybGQ=", "PGVvcz4="]
+            }]
+        }))
+        .unwrap();
+        let tokens = response.computed_tokens();
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0].token_id, Some(1596));
+        assert_eq!(tokens[0].role.as_deref(), Some("user"));
+        assert_eq!(tokens[0].text(), Some("\u{2581}Hel"));
+        assert_eq!(tokens[1].text(), Some("lo world"));
+
+        let info = &response.tokens_info.as_ref().unwrap()[0];
+        assert_eq!(
+            info.char_spans("Hello world"),
+            vec![Some(0..3), Some(3..11), None]
+        );
+    }
+
+    #[test]
+    fn save_all_writes_inline_images_and_skips_gcs_results() {
+        let response: GenerateImagesResponse = serde_json::from_value(json!({
+            "generatedImages": [
+                {"image": {"bytesBase64Encoded": "iVBORw0KGgo=", "mimeType": "image/png"}},
+                {"image": {"gcsUri": "gs://bucket/out.png"}},
+                {"image": {"bytesBase64Encoded": "/9j/4A==", "mimeType": "image/jpeg"}}
+            ]
+        }))
+        .unwrap();
+        let dir = std::env::temp_dir().join(format!("rust-genai-images-{}", std::process::id()));
+
+        let paths = response.save_all(&dir, "cat").unwrap();
+        let written: Vec<_> = paths
+            .iter()
+            .map(|path| std::fs::read(path).unwrap())
+            .collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(paths, vec![dir.join("cat_0.png"), dir.join("cat_2.jpg")]);
+        assert_eq!(written[0], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(written[1], [0xFF, 0xD8, 0xFF, 0xE0]);
+        assert!(response.generated_images[1]
+            .write_to(dir.join("x.png"))
+            .is_err());
+    }
+}
@@ -15,6 +15,36 @@ pub struct OperationError {
     pub details: Option<Value>,
 }
 
+impl OperationError {
+    /// 转为类型化的 `google.rpc.Status`（缺失字段取默认值，单个 `details` 对象包装为数组）。
+    #[must_use]
+    pub fn to_status(&self) -> Status {
+        let details = match &self.details {
+            Some(Value::Array(details)) => details.clone(),
+            Some(Value::Null) | None => Vec::new(),
+            Some(detail) => vec![detail.clone()],
+        };
+        Status {
+            code: self.code.unwrap_or_default(),
+            message: self.message.clone().unwrap_or_default(),
+            details,
+        }
+    }
+}
+
+/// `google.rpc.Status`：失败操作携带的错误状态。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    /// `google.rpc.Code` 数值。
+    #[serde(default)]
+    pub code: i32,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub details: Vec<Value>,
+}
+
 /// Long-running operation.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +61,14 @@ pub struct Operation {
     pub metadata: Option<Value>,
 }
 
+impl Operation {
+    /// 操作失败时返回类型化的错误状态。
+    #[must_use]
+    pub fn error_status(&self) -> Option<Status> {
+        self.error.as_ref().map(OperationError::to_status)
+    }
+}
+
 /// Get operation config.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
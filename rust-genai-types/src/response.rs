@@ -1,16 +1,23 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
-use crate::content::{Content, FunctionCall};
+use crate::content::{
+    Blob, CodeExecutionResult, Content, ExecutableCode, FunctionCall, Part, PartKind,
+};
 use crate::enums::{
     BlockedReason, FinishReason, HarmBlockThreshold, HarmCategory, HarmProbability, HarmSeverity,
     MediaModality, TrafficType, UrlRetrievalStatus,
 };
-use crate::grounding::{CitationMetadata, GroundingMetadata};
+use crate::grounding::{
+    Citation, CitationMetadata, GroundingChunk, GroundingMetadata, SearchEntryPoint,
+};
 use crate::http::HttpResponse;
-use crate::logprobs::LogprobsResult;
+use crate::logprobs::{LogprobsResult, TopCandidates};
 
 /// 生成内容响应。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct GenerateContentResponse {
     /// Optional. Used to retain the full HTTP response.
@@ -30,10 +37,23 @@ pub struct GenerateContentResponse {
     pub model_version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_id: Option<String>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 impl GenerateContentResponse {
-    /// 提取第一个候选的文本。
+    /// 服务端请求 ID（`x-goog-request-id` 响应头）。
+    #[must_use]
+    pub fn request_id(&self) -> Option<&str> {
+        self.sdk_http_response.as_ref()?.request_id()
+    }
+
+    /// 提取第一个候选的第一段文本。
+    ///
+    /// 多段文本（例如文本 + 函数调用 + 文本）只返回第一段；需要完整文本时使用
+    /// [`GenerateContentResponse::all_text`]。
     #[must_use]
     pub fn text(&self) -> Option<String> {
         self.candidates
@@ -43,6 +63,229 @@ impl GenerateContentResponse {
             .map(ToString::to_string)
     }
 
+    /// 拼接第一个候选的全部文本段（跳过 thought 与非文本段）。
+    ///
+    /// 没有任何文本段时返回 `None`。
+    #[must_use]
+    pub fn all_text(&self) -> Option<String> {
+        self.candidates.first()?.all_text()
+    }
+
+    /// 将第一个候选的完整文本（见 [`GenerateContentResponse::all_text`]）按 JSON 解析为 `T`。
+    ///
+    /// 通常与 `response_schema`（例如 `#[derive(GeminiSchema)]` 生成的 schema）配合使用。
+    ///
+    /// # Errors
+    /// 当响应没有文本或 JSON 无法反序列化为 `T` 时返回错误。
+    pub fn parsed<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        let text = self
+            .all_text()
+            .ok_or_else(|| serde::de::Error::custom("response has no text to parse"))?;
+        serde_json::from_str(&text)
+    }
+
+    /// 将本轮结果并入调用方维护的会话历史（`history` 即本轮请求的 `contents`）。
+    ///
+    /// 存在 `automatic_function_calling_history` 时，追加其中超出 `history` 的部分（即自动函数调用的
+    /// 函数调用/响应往返），再追加第一个候选的回复内容，使下一轮请求包含完整的工具交换。
+    pub fn extend_history(&self, history: &mut Vec<Content>) {
+        if let Some(afc_history) = &self.automatic_function_calling_history {
+            if let Some(tool_exchange) = afc_history.get(history.len()..) {
+                history.extend_from_slice(tool_exchange);
+            }
+        }
+        if let Some(content) = self
+            .candidates
+            .first()
+            .and_then(|candidate| candidate.content.as_ref())
+        {
+            history.push(content.clone());
+        }
+    }
+
+    /// 按索引获取候选（对应请求中的 `candidate_count`）。
+    #[must_use]
+    pub fn candidate(&self, index: usize) -> Option<&Candidate> {
+        self.candidates.get(index)
+    }
+
+    /// 每个候选的完整文本，顺序与 `candidates` 一致；没有文本的候选为空字符串。
+    #[must_use]
+    pub fn candidates_text(&self) -> Vec<String> {
+        self.candidates
+            .iter()
+            .map(|candidate| candidate.all_text().unwrap_or_default())
+            .collect()
+    }
+
+    /// 提取第一个候选中的内联音频（`audio/*`），返回 `(数据, MIME 类型)`。
+    #[must_use]
+    pub fn audio_parts(&self) -> Vec<(&[u8], &str)> {
+        self.candidates
+            .first()
+            .and_then(|candidate| candidate.content.as_ref())
+            .map(|content| {
+                content
+                    .parts
+                    .iter()
+                    .filter_map(|part| match &part.kind {
+                        PartKind::InlineData { inline_data }
+                            if inline_data.mime_type.starts_with("audio/") =>
+                        {
+                            Some((inline_data.data.as_slice(), inline_data.mime_type.as_str()))
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 按模态拆分第一个候选的输出（文本、图像、音频），跳过 thought 段。
+    ///
+    /// 适用于设置了 `response_modalities`（如 `[TEXT, IMAGE]`）的请求。
+    #[must_use]
+    pub fn modality_parts(&self) -> ModalityParts<'_> {
+        let mut split = ModalityParts::default();
+        let Some(content) = self
+            .candidates
+            .first()
+            .and_then(|candidate| candidate.content.as_ref())
+        else {
+            return split;
+        };
+        for part in content
+            .parts
+            .iter()
+            .filter(|part| part.thought != Some(true))
+        {
+            match &part.kind {
+                PartKind::Text { text } => split.text.push(text.as_str()),
+                PartKind::InlineData { inline_data }
+                    if inline_data.mime_type.starts_with("image/") =>
+                {
+                    split.images.push(inline_data);
+                }
+                PartKind::InlineData { inline_data }
+                    if inline_data.mime_type.starts_with("audio/") =>
+                {
+                    split.audio.push(inline_data);
+                }
+                _ => {}
+            }
+        }
+        split
+    }
+
+    /// 第一个候选的结束原因。
+    #[must_use]
+    pub fn finish_reason(&self) -> Option<FinishReason> {
+        self.candidates
+            .first()
+            .and_then(|candidate| candidate.finish_reason)
+    }
+
+    /// Prompt 被拦截的原因（来自 `prompt_feedback`）。
+    #[must_use]
+    pub fn blocked_reason(&self) -> Option<BlockedReason> {
+        self.prompt_feedback
+            .as_ref()
+            .and_then(|feedback| feedback.block_reason)
+    }
+
+    /// Prompt 被拦截时服务端给出的说明（来自 `prompt_feedback`）。
+    #[must_use]
+    pub fn block_reason_message(&self) -> Option<&str> {
+        self.prompt_feedback
+            .as_ref()
+            .and_then(|feedback| feedback.block_reason_message.as_deref())
+    }
+
+    /// 第一个候选的 grounding 来源（缺失时为空）。
+    #[must_use]
+    pub fn grounding_chunks(&self) -> &[GroundingChunk] {
+        self.candidates
+            .first()
+            .map_or(&[], Candidate::grounding_chunks)
+    }
+
+    /// 第一个候选触发的 Web 搜索查询（缺失时为空）。
+    #[must_use]
+    pub fn web_search_queries(&self) -> &[String] {
+        self.candidates
+            .first()
+            .map_or(&[], Candidate::web_search_queries)
+    }
+
+    /// 第一个候选的引用（缺失时为空）。
+    #[must_use]
+    pub fn citations(&self) -> &[Citation] {
+        self.candidates.first().map_or(&[], Candidate::citations)
+    }
+
+    /// 第一个候选的搜索入口。
+    #[must_use]
+    pub fn search_entry_point(&self) -> Option<&SearchEntryPoint> {
+        self.candidates
+            .first()
+            .and_then(Candidate::search_entry_point)
+    }
+
+    /// 第一个候选中代码执行工具生成的代码（按出现顺序）。
+    #[must_use]
+    pub fn executable_code(&self) -> Vec<&ExecutableCode> {
+        self.first_candidate_parts()
+            .filter_map(Part::executable_code_ref)
+            .collect()
+    }
+
+    /// 第一个候选中的代码执行结果（按出现顺序）。
+    #[must_use]
+    pub fn code_execution_results(&self) -> Vec<&CodeExecutionResult> {
+        self.first_candidate_parts()
+            .filter_map(Part::code_execution_result_ref)
+            .collect()
+    }
+
+    /// 拼接第一个候选中全部代码执行结果的输出（stdout）；没有输出时返回 `None`。
+    #[must_use]
+    pub fn code_execution_output(&self) -> Option<String> {
+        let mut outputs = self
+            .code_execution_results()
+            .into_iter()
+            .filter_map(|result| result.output.as_deref())
+            .peekable();
+        outputs.peek()?;
+        Some(outputs.collect())
+    }
+
+    /// 代码执行产生的文件（如图表图像），即包含代码执行的第一个候选中的内联数据。
+    ///
+    /// 没有代码执行 Part 时返回空。
+    #[must_use]
+    pub fn code_execution_files(&self) -> Vec<&Blob> {
+        let has_code_execution = self.first_candidate_parts().any(|part| {
+            part.executable_code_ref().is_some() || part.code_execution_result_ref().is_some()
+        });
+        if !has_code_execution {
+            return Vec::new();
+        }
+        self.first_candidate_parts()
+            .filter_map(|part| match &part.kind {
+                PartKind::InlineData { inline_data } => Some(inline_data),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn first_candidate_parts(&self) -> impl Iterator<Item = &Part> {
+        self.candidates
+            .first()
+            .and_then(|candidate| candidate.content.as_ref())
+            .into_iter()
+            .flat_map(|content| content.parts.iter())
+    }
+
     /// 提取所有函数调用。
     #[must_use]
     pub fn function_calls(&self) -> Vec<&FunctionCall> {
@@ -61,7 +304,7 @@ impl GenerateContentResponse {
 }
 
 /// 响应候选。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Candidate {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -86,10 +329,140 @@ pub struct Candidate {
     pub safety_ratings: Vec<SafetyRating>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url_context_metadata: Option<UrlContextMetadata>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl Candidate {
+    /// 该候选的第一段文本。
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        self.content.as_ref()?.first_text()
+    }
+
+    /// 拼接该候选的全部文本段（跳过 thought 与非文本段）。
+    ///
+    /// 没有任何文本段时返回 `None`。
+    #[must_use]
+    pub fn all_text(&self) -> Option<String> {
+        let content = self.content.as_ref()?;
+        let mut texts = content
+            .parts
+            .iter()
+            .filter(|part| part.thought != Some(true))
+            .filter_map(|part| part.text_value())
+            .peekable();
+        texts.peek()?;
+        Some(texts.collect())
+    }
+
+    /// 安全评级中概率最高的一项（多项并列时取第一项）；没有评级时返回 `None`。
+    #[must_use]
+    pub fn max_safety_probability(&self) -> Option<&SafetyRating> {
+        self.safety_ratings.iter().reduce(|max, rating| {
+            if rating.probability > max.probability {
+                rating
+            } else {
+                max
+            }
+        })
+    }
+
+    /// 是否有任一类别的概率达到或高于 `threshold`。
+    #[must_use]
+    pub fn exceeds(&self, threshold: HarmProbability) -> bool {
+        self.safety_ratings
+            .iter()
+            .any(|rating| rating.probability >= threshold)
+    }
+
+    /// 该候选中的函数调用。
+    #[must_use]
+    pub fn function_calls(&self) -> Vec<&FunctionCall> {
+        self.content
+            .as_ref()
+            .map(|content| {
+                content
+                    .parts
+                    .iter()
+                    .filter_map(|part| part.function_call_ref())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Grounding 来源（缺失 `grounding_metadata` 时为空）。
+    #[must_use]
+    pub fn grounding_chunks(&self) -> &[GroundingChunk] {
+        self.grounding_metadata
+            .as_ref()
+            .map_or(&[], |metadata| metadata.grounding_chunks.as_slice())
+    }
+
+    /// Web 搜索查询（缺失 `grounding_metadata` 时为空）。
+    #[must_use]
+    pub fn web_search_queries(&self) -> &[String] {
+        self.grounding_metadata
+            .as_ref()
+            .map_or(&[], |metadata| metadata.web_search_queries.as_slice())
+    }
+
+    /// 引用（缺失 `citation_metadata` 时为空）。
+    #[must_use]
+    pub fn citations(&self) -> &[Citation] {
+        self.citation_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.citations.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// 每个解码步骤的 top 候选 logprobs（缺失 `logprobs_result` 时为空）。
+    #[must_use]
+    pub fn top_logprobs(&self) -> &[TopCandidates] {
+        self.logprobs_result
+            .as_ref()
+            .map_or(&[], |result| result.top_candidates.as_slice())
+    }
+
+    /// 平均 logprob（整段输出的置信度）。
+    #[must_use]
+    pub const fn avg_logprob(&self) -> Option<f64> {
+        self.avg_logprobs
+    }
+
+    /// 逐 token 的选中概率（缺失 `logprobs_result` 时为空）。
+    #[must_use]
+    pub fn token_probabilities(&self) -> Vec<(&str, f64)> {
+        self.logprobs_result
+            .as_ref()
+            .map(LogprobsResult::token_probabilities)
+            .unwrap_or_default()
+    }
+
+    /// 搜索入口（Google Search 建议渲染内容）。
+    #[must_use]
+    pub fn search_entry_point(&self) -> Option<&SearchEntryPoint> {
+        self.grounding_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.search_entry_point.as_ref())
+    }
+}
+
+/// [`GenerateContentResponse::modality_parts`] 的结果：按模态分组的输出段。
+#[derive(Debug, Clone, Default)]
+pub struct ModalityParts<'a> {
+    /// 文本段，按出现顺序。
+    pub text: Vec<&'a str>,
+    /// 内联图像（`image/*`）。
+    pub images: Vec<&'a Blob>,
+    /// 内联音频（`audio/*`）。
+    pub audio: Vec<&'a Blob>,
 }
 
 /// Prompt 反馈。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct PromptFeedback {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -98,6 +471,10 @@ pub struct PromptFeedback {
     pub block_reason_message: Option<String>,
     #[serde(default)]
     pub safety_ratings: Vec<SafetyRating>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 /// 安全评级。
@@ -157,7 +534,7 @@ pub struct UsageMetadata {
 }
 
 /// `GenerateContentResponse` 使用的 usage metadata（包含 candidates 统计）。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct GenerateContentResponseUsageMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -182,6 +559,10 @@ pub struct GenerateContentResponseUsageMetadata {
     pub total_token_count: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub traffic_type: Option<TrafficType>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 /// URL metadata.
@@ -236,6 +617,7 @@ mod tests {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
                 Candidate {
                     content: Some(call_content),
@@ -249,6 +631,7 @@ mod tests {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
             ],
             create_time: None,
@@ -257,6 +640,7 @@ mod tests {
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         };
 
         assert_eq!(response.text(), Some("hello".to_string()));
@@ -264,4 +648,245 @@ mod tests {
         assert_eq!(calls.len(), 1);
         assert_eq!(calls[0].name.as_deref(), Some("lookup"));
     }
+
+    #[test]
+    fn response_modality_parts_split_text_image_audio() {
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{"content": {"role": "model", "parts": [
+                {"text": "planning", "thought": true},
+                {"text": "Here you go"},
+                {"inlineData": {"mimeType": "image/png", "data": "AQI="}},
+                {"inlineData": {"mimeType": "audio/pcm", "data": "Aw=="}}
+            ]}}]
+        }))
+        .unwrap();
+        let parts = response.modality_parts();
+        assert_eq!(parts.text, vec!["Here you go"]);
+        assert_eq!(parts.images.len(), 1);
+        assert_eq!(parts.images[0].data, vec![1, 2]);
+        assert_eq!(parts.audio.len(), 1);
+        assert_eq!(parts.audio[0].mime_type, "audio/pcm");
+    }
+
+    #[test]
+    fn candidate_safety_probability_helpers() {
+        let candidate: Candidate = serde_json::from_value(json!({
+            "safetyRatings": [
+                {"category": "HARM_CATEGORY_HARASSMENT", "probability": "LOW"},
+                {"category": "HARM_CATEGORY_DANGEROUS_CONTENT", "probability": "MEDIUM"},
+                {"category": "HARM_CATEGORY_HATE_SPEECH", "probability": "NEGLIGIBLE"}
+            ]
+        }))
+        .unwrap();
+        let max = candidate.max_safety_probability().unwrap();
+        assert_eq!(max.category, HarmCategory::HarmCategoryDangerousContent);
+        assert_eq!(max.probability, HarmProbability::Medium);
+        assert!(candidate.exceeds(HarmProbability::Medium));
+        assert!(!candidate.exceeds(HarmProbability::High));
+
+        let empty: Candidate = serde_json::from_value(json!({})).unwrap();
+        assert!(empty.max_safety_probability().is_none());
+        assert!(!empty.exceeds(HarmProbability::Negligible));
+    }
+
+    #[test]
+    fn response_finish_and_blocked_reason() {
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{"finishReason": "SAFETY"}],
+            "promptFeedback": {"blockReason": "PROHIBITED_CONTENT"}
+        }))
+        .unwrap();
+        assert_eq!(response.finish_reason(), Some(FinishReason::Safety));
+        assert_eq!(
+            response.blocked_reason(),
+            Some(BlockedReason::ProhibitedContent)
+        );
+        assert!(FinishReason::Recitation.is_blocked());
+        assert!(!FinishReason::Stop.is_blocked());
+
+        let empty: GenerateContentResponse = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(empty.finish_reason(), None);
+        assert_eq!(empty.blocked_reason(), None);
+    }
+
+    #[test]
+    fn response_all_text_concatenates_text_parts() {
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{"content": {"role": "model", "parts": [
+                {"text": "thinking...", "thought": true},
+                {"text": "Hello, "},
+                {"functionCall": {"name": "lookup", "args": {}}},
+                {"text": "world"}
+            ]}}]
+        }))
+        .unwrap();
+        assert_eq!(response.text(), Some("thinking...".to_string()));
+        assert_eq!(response.all_text(), Some("Hello, world".to_string()));
+
+        let no_text: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{"content": {"role": "model", "parts": [
+                {"functionCall": {"name": "lookup", "args": {}}}
+            ]}}]
+        }))
+        .unwrap();
+        assert_eq!(no_text.all_text(), None);
+    }
+
+    #[test]
+    fn response_candidates_text_per_candidate() {
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [
+                {"index": 0, "finishReason": "STOP", "content": {"role": "model", "parts": [
+                    {"text": "Sample A"}
+                ]}},
+                {"index": 1, "finishReason": "MAX_TOKENS", "content": {"role": "model", "parts": [
+                    {"text": "Sample "},
+                    {"functionCall": {"name": "lookup", "args": {}}},
+                    {"text": "B"}
+                ]}}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(response.candidates_text(), vec!["Sample A", "Sample B"]);
+
+        let second = response.candidate(1).unwrap();
+        assert_eq!(second.text(), Some("Sample "));
+        assert_eq!(second.finish_reason, Some(FinishReason::MaxTokens));
+        assert_eq!(second.function_calls().len(), 1);
+        assert!(response.candidate(0).unwrap().function_calls().is_empty());
+        assert!(response.candidate(2).is_none());
+    }
+
+    #[test]
+    fn response_grounding_helpers() {
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "Rust 1.0 shipped in 2015."}]},
+                "groundingMetadata": {
+                    "webSearchQueries": ["rust 1.0 release date"],
+                    "groundingChunks": [
+                        {"web": {"uri": "https://blog.rust-lang.org/", "title": "Rust Blog"}},
+                        {"web": {"uri": "https://www.rust-lang.org/", "title": "Rust"}}
+                    ],
+                    "searchEntryPoint": {"renderedContent": "<div></div>"}
+                },
+                "citationMetadata": {
+                    "citations": [{"uri": "https://blog.rust-lang.org/", "startIndex": 0, "endIndex": 8}]
+                }
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(response.web_search_queries(), ["rust 1.0 release date"]);
+        let uris: Vec<_> = response
+            .grounding_chunks()
+            .iter()
+            .filter_map(GroundingChunk::uri)
+            .collect();
+        assert_eq!(
+            uris,
+            vec!["https://blog.rust-lang.org/", "https://www.rust-lang.org/"]
+        );
+        assert_eq!(response.citations().len(), 1);
+        assert!(response.search_entry_point().is_some());
+
+        let plain: GenerateContentResponse =
+            serde_json::from_value(json!({"candidates": [{}]})).unwrap();
+        assert!(plain.grounding_chunks().is_empty());
+        assert!(plain.web_search_queries().is_empty());
+        assert!(plain.citations().is_empty());
+        assert!(plain.search_entry_point().is_none());
+        let empty: GenerateContentResponse = serde_json::from_value(json!({})).unwrap();
+        assert!(empty.citations().is_empty());
+    }
+
+    #[test]
+    fn candidate_logprobs_helpers() {
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "avgLogprobs": -0.35,
+                "logprobsResult": {
+                    "topCandidates": [
+                        {"candidates": [
+                            {"token": "Yes", "tokenId": 1, "logProbability": -0.1},
+                            {"token": "No", "tokenId": 2, "logProbability": -2.4}
+                        ]},
+                        {"candidates": [
+                            {"token": ".", "tokenId": 3, "logProbability": -0.6}
+                        ]}
+                    ],
+                    "chosenCandidates": [
+                        {"token": "Yes", "tokenId": 1, "logProbability": -0.1},
+                        {"token": ".", "tokenId": 3, "logProbability": -0.6}
+                    ]
+                }
+            }]
+        }))
+        .unwrap();
+        let candidate = &response.candidates[0];
+
+        assert_eq!(candidate.avg_logprob(), Some(-0.35));
+        assert_eq!(candidate.top_logprobs().len(), 2);
+        let top_sum: f64 = candidate.top_logprobs()[0]
+            .candidates
+            .iter()
+            .map(crate::logprobs::LogprobCandidate::probability)
+            .sum();
+        assert!(top_sum > 0.9 && top_sum <= 1.0);
+
+        let probs = candidate.token_probabilities();
+        assert_eq!(probs.len(), 2);
+        assert_eq!(probs[0].0, "Yes");
+        assert!((probs[0].1 - (-0.1f64).exp()).abs() < 1e-12);
+        assert!(probs.iter().all(|(_, p)| *p > 0.0 && *p <= 1.0));
+
+        let plain: GenerateContentResponse =
+            serde_json::from_value(json!({"candidates": [{}]})).unwrap();
+        assert!(plain.candidates[0].top_logprobs().is_empty());
+        assert!(plain.candidates[0].token_probabilities().is_empty());
+    }
+
+    #[test]
+    fn code_execution_accessors_extract_code_output_and_files() {
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "Let me compute that."},
+                    {"executableCode": {"language": "PYTHON", "code": "print(sum(range(10)))"}},
+                    {"codeExecutionResult": {"outcome": "OUTCOME_OK", "output": "45\n"}},
+                    {"inlineData": {"mimeType": "image/png", "data": "iVBORw=="}},
+                    {"text": "The sum is 45."}
+                ]}
+            }]
+        }))
+        .unwrap();
+
+        let code = response.executable_code();
+        assert_eq!(code.len(), 1);
+        assert_eq!(code[0].code, "print(sum(range(10)))");
+        assert_eq!(code[0].language, crate::enums::Language::Python);
+        let results = response.code_execution_results();
+        assert!(results[0].is_ok());
+        assert_eq!(response.code_execution_output().as_deref(), Some("45\n"));
+        let files = response.code_execution_files();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].mime_type, "image/png");
+
+        let plain: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{"content": {"role": "model", "parts": [
+                {"inlineData": {"mimeType": "image/png", "data": "iVBORw=="}}
+            ]}}]
+        }))
+        .unwrap();
+        assert!(plain.executable_code().is_empty());
+        assert!(plain.code_execution_output().is_none());
+        assert!(plain.code_execution_files().is_empty());
+
+        let retry = Content::corrected_code("print(1)\n", crate::enums::Language::Python);
+        assert_eq!(retry.role, Some(Role::User));
+        assert!(retry
+            .first_text()
+            .unwrap()
+            .ends_with("```python\nprint(1)\n```"));
+    }
 }
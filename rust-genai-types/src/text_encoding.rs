@@ -0,0 +1,153 @@
+//! Strict decoding of text file bytes for text parts.
+
+use std::io;
+
+/// 文本文件编码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextEncoding {
+    /// UTF-8（可带 BOM）。
+    Utf8,
+    /// UTF-16 little-endian（可带 BOM）。
+    Utf16Le,
+    /// UTF-16 big-endian（可带 BOM）。
+    Utf16Be,
+    /// ISO-8859-1（Latin-1），每个字节对应同值码点。
+    Latin1,
+    /// Windows-1252；0x80-0x9F 中未定义的字节视为错误。
+    Windows1252,
+}
+
+/// Windows-1252 在 0x80-0x9F 区间与 Latin-1 不同的映射（`None` 表示未定义）。
+const WINDOWS_1252_HIGH: [Option<char>; 32] = [
+    Some('\u{20AC}'),
+    None,
+    Some('\u{201A}'),
+    Some('\u{0192}'),
+    Some('\u{201E}'),
+    Some('\u{2026}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{02C6}'),
+    Some('\u{2030}'),
+    Some('\u{0160}'),
+    Some('\u{2039}'),
+    Some('\u{0152}'),
+    None,
+    Some('\u{017D}'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('\u{2022}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{02DC}'),
+    Some('\u{2122}'),
+    Some('\u{0161}'),
+    Some('\u{203A}'),
+    Some('\u{0153}'),
+    None,
+    Some('\u{017E}'),
+    Some('\u{0178}'),
+];
+
+impl TextEncoding {
+    /// 根据 BOM 检测编码；无 BOM 时返回 `None`。
+    #[must_use]
+    pub fn from_bom(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            Some(Self::Utf8)
+        } else if bytes.starts_with(&[0xFF, 0xFE]) {
+            Some(Self::Utf16Le)
+        } else if bytes.starts_with(&[0xFE, 0xFF]) {
+            Some(Self::Utf16Be)
+        } else {
+            None
+        }
+    }
+
+    /// 将字节严格解码为字符串（去除匹配的 BOM）。
+    ///
+    /// # Errors
+    /// 当字节在该编码下无法解码时返回 `InvalidData` 错误，不做有损替换。
+    pub fn decode(self, bytes: &[u8]) -> io::Result<String> {
+        match self {
+            Self::Utf8 => {
+                let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+                String::from_utf8(bytes.to_vec()).map_err(|err| {
+                    invalid_data(format!(
+                        "invalid UTF-8 at byte {}",
+                        err.utf8_error().valid_up_to()
+                    ))
+                })
+            }
+            Self::Utf16Le => decode_utf16(bytes, [0xFF, 0xFE], u16::from_le_bytes),
+            Self::Utf16Be => decode_utf16(bytes, [0xFE, 0xFF], u16::from_be_bytes),
+            Self::Latin1 => Ok(bytes.iter().map(|&byte| char::from(byte)).collect()),
+            Self::Windows1252 => bytes
+                .iter()
+                .enumerate()
+                .map(|(index, &byte)| match byte {
+                    0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)].ok_or_else(|| {
+                        invalid_data(format!(
+                            "byte 0x{byte:02X} at {index} is undefined in Windows-1252"
+                        ))
+                    }),
+                    _ => Ok(char::from(byte)),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], bom: [u8; 2], to_unit: fn([u8; 2]) -> u16) -> io::Result<String> {
+    let bytes = bytes.strip_prefix(&bom).unwrap_or(bytes);
+    if !bytes.len().is_multiple_of(2) {
+        return Err(invalid_data("UTF-16 input has an odd number of bytes"));
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| to_unit([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(|_| invalid_data("invalid UTF-16 surrogate sequence"))
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_latin1_and_windows_1252() {
+        let bytes = b"caf\xE9 \xFCber";
+        assert_eq!(TextEncoding::Latin1.decode(bytes).unwrap(), "café über");
+        assert_eq!(
+            TextEncoding::Windows1252
+                .decode(b"\x93quoted\x94 \x80")
+                .unwrap(),
+            "\u{201C}quoted\u{201D} €"
+        );
+        assert!(TextEncoding::Windows1252.decode(b"\x81").is_err());
+    }
+
+    #[test]
+    fn decodes_utf16_with_bom_detection() {
+        let bytes = [0xFF, 0xFE, b'h', 0, b'i', 0];
+        let encoding = TextEncoding::from_bom(&bytes).unwrap();
+        assert_eq!(encoding, TextEncoding::Utf16Le);
+        assert_eq!(encoding.decode(&bytes).unwrap(), "hi");
+        assert!(TextEncoding::Utf16Be.decode(&[0x00]).is_err());
+    }
+
+    #[test]
+    fn utf8_rejects_invalid_bytes() {
+        let err = TextEncoding::Utf8.decode(b"ok\xE9").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(TextEncoding::Utf8.decode(b"\xEF\xBB\xBFhi").unwrap(), "hi");
+    }
+}
@@ -0,0 +1,150 @@
+//! Minimal RFC3339 timestamp parsing for job `create_time` / `start_time` / `end_time` fields.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 解析 RFC3339 时间戳（如 `2024-05-01T12:00:00.123456Z` 或带 `+08:00` 偏移）。
+///
+/// 无法解析或早于 Unix 纪元时返回 `None`。
+#[must_use]
+pub fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    // 下文按字节下标切片，非 ASCII 输入可能切在字符中间导致 panic。
+    if !value.is_ascii() {
+        return None;
+    }
+    let bytes = value.as_bytes();
+    if bytes.len() < 20 || !matches!(bytes[10], b'T' | b't' | b' ') {
+        return None;
+    }
+    if bytes[4] != b'-' || bytes[7] != b'-' || bytes[13] != b':' || bytes[16] != b':' {
+        return None;
+    }
+    let year = digits(&value[0..4])?;
+    let month = digits(&value[5..7])?;
+    let day = digits(&value[8..10])?;
+    let hour = digits(&value[11..13])?;
+    let minute = digits(&value[14..16])?;
+    let second = digits(&value[17..19])?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+        return None;
+    }
+    if second > 60 {
+        return None;
+    }
+
+    let mut rest = &value[19..];
+    let mut nanos = 0u32;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return None;
+        }
+        for (index, digit) in fraction[..len].bytes().take(9).enumerate() {
+            nanos += u32::from(digit - b'0') * 10u32.pow(8 - u32::try_from(index).ok()?);
+        }
+        rest = &fraction[len..];
+    }
+
+    let offset_seconds: i64 = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let (sign, offset) = match rest.as_bytes().first()? {
+                b'+' => (1, &rest[1..]),
+                b'-' => (-1, &rest[1..]),
+                _ => return None,
+            };
+            if offset.len() != 5 || offset.as_bytes()[2] != b':' {
+                return None;
+            }
+            let hours = digits(&offset[0..2])?;
+            let minutes = digits(&offset[3..5])?;
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86_400 + hour * 3600 + minute * 60 + second - offset_seconds;
+    let seconds = u64::try_from(seconds).ok()?;
+    Some(UNIX_EPOCH + Duration::new(seconds, nanos))
+}
+
+/// 计算两个 RFC3339 时间戳之间的时长；`end` 缺失时以当前时间为终点。
+///
+/// 任一时间戳无法解析或 `end` 早于 `start` 时返回 `None`。
+#[must_use]
+pub fn elapsed_between(start: Option<&str>, end: Option<&str>) -> Option<Duration> {
+    let start = parse_rfc3339(start?)?;
+    let end = match end {
+        Some(end) => parse_rfc3339(end)?,
+        None => SystemTime::now(),
+    };
+    end.duration_since(start).ok()
+}
+
+fn digits(value: &str) -> Option<i64> {
+    if value.bytes().all(|byte| byte.is_ascii_digit()) {
+        value.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// 公历日期到 Unix 纪元天数（Howard Hinnant 的 `days_from_civil` 算法）。
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_utc_fraction_and_offsets() {
+        let time = parse_rfc3339("2024-03-01T00:00:01.5Z").unwrap();
+        assert_eq!(
+            time.duration_since(UNIX_EPOCH).unwrap(),
+            Duration::new(1_709_251_201, 500_000_000)
+        );
+        assert_eq!(parse_rfc3339("2024-03-01T08:00:01.5+08:00"), Some(time));
+        assert!(parse_rfc3339("2024-13-01T00:00:00Z").is_none());
+        assert!(parse_rfc3339("2024-03-01 00:00:00").is_none());
+    }
+
+    #[test]
+    fn rejects_non_ascii_without_panicking() {
+        assert!(parse_rfc3339("2024-01-01T00:00:0€").is_none());
+        assert!(parse_rfc3339("2024-01-01T00:00:00.€Z").is_none());
+        assert!(parse_rfc3339("2024-01-01T00:00:00+0€:00").is_none());
+        assert!(parse_rfc3339("２０２４-01-01T00:00:00Z").is_none());
+    }
+
+    #[test]
+    fn elapsed_between_returns_duration() {
+        let elapsed = elapsed_between(
+            Some("2024-05-01T12:00:00Z"),
+            Some("2024-05-01T13:30:15.250Z"),
+        );
+        assert_eq!(elapsed, Some(Duration::from_millis(5_415_250)));
+        assert!(
+            elapsed_between(Some("2024-05-01T13:00:00Z"), Some("2024-05-01T12:00:00Z")).is_none()
+        );
+        assert!(elapsed_between(None, Some("2024-05-01T12:00:00Z")).is_none());
+    }
+
+    #[test]
+    fn batch_job_elapsed_uses_start_and_end_times() {
+        let job = crate::batches::BatchJob {
+            create_time: Some("2024-05-01T11:59:00Z".into()),
+            start_time: Some("2024-05-01T12:00:00Z".into()),
+            end_time: Some("2024-05-01T12:10:00.5Z".into()),
+            ..Default::default()
+        };
+        assert_eq!(job.elapsed(), Some(Duration::from_millis(600_500)));
+        assert_eq!(job.queued_duration(), Some(Duration::from_secs(60)));
+    }
+}
@@ -33,6 +33,77 @@ pub struct Tool {
     pub url_context: Option<UrlContext>,
 }
 
+impl Tool {
+    /// 从 OpenAI 风格的函数/工具定义构建仅含一个函数声明的 Tool。
+    ///
+    /// 同时接受 `{"type": "function", "function": {...}}` 与裸 `{name, description, parameters}`。
+    ///
+    /// # Errors
+    /// 当缺少 `name` 或 `parameters` 不是合法的 JSON Schema 对象时返回错误。
+    pub fn from_openai_function(value: Value) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            function_declarations: Some(vec![FunctionDeclaration::from_openai_function(value)?]),
+            ..Self::default()
+        })
+    }
+}
+
+impl FunctionDeclaration {
+    /// 从 OpenAI 风格的函数定义构建函数声明（参见 [`Tool::from_openai_function`]）。
+    ///
+    /// # Errors
+    /// 当缺少 `name` 或 `parameters` 不是合法的 JSON Schema 对象时返回错误。
+    pub fn from_openai_function(mut value: Value) -> Result<Self, serde_json::Error> {
+        if value.get("type").and_then(Value::as_str) == Some("function") {
+            if let Some(function) = value.get_mut("function") {
+                value = function.take();
+            }
+        }
+        let name = value
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                serde::de::Error::custom("OpenAI function definition is missing `name`")
+            })?
+            .to_string();
+        let description = value
+            .get("description")
+            .and_then(Value::as_str)
+            .map(ToString::to_string);
+        let parameters = value
+            .get("parameters")
+            .map(Schema::from_json_schema)
+            .transpose()?;
+        Ok(Self {
+            name,
+            description,
+            parameters,
+            parameters_json_schema: None,
+            response: None,
+            response_json_schema: None,
+            behavior: None,
+        })
+    }
+
+    /// 按声明的响应 schema（`response` 或 `response_json_schema`）校验函数响应负载。
+    ///
+    /// 未声明响应 schema 时视为通过。
+    ///
+    /// # Errors
+    /// 当负载与 schema 不匹配或 `response_json_schema` 无法解析时返回错误描述。
+    pub fn validate_response(&self, response: &Value) -> Result<(), String> {
+        if let Some(schema) = &self.response {
+            return schema.validate(response);
+        }
+        if let Some(json_schema) = &self.response_json_schema {
+            let schema = Schema::from_json_schema(json_schema)
+                .map_err(|err| format!("invalid response_json_schema: {err}"))?;
+            return schema.validate(response);
+        }
+        Ok(())
+    }
+}
+
 /// 函数声明。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -415,6 +486,56 @@ pub struct FunctionCallingConfig {
     pub stream_function_call_arguments: Option<bool>,
 }
 
+impl ToolConfig {
+    /// 由模型自行决定是否调用函数（`AUTO`）。
+    #[must_use]
+    pub fn auto() -> Self {
+        Self::with_mode(FunctionCallingMode::Auto, None)
+    }
+
+    /// 强制模型调用任一函数（`ANY`）。
+    #[must_use]
+    pub fn any() -> Self {
+        Self::with_mode(FunctionCallingMode::Any, None)
+    }
+
+    /// 禁止函数调用（`NONE`）。
+    #[must_use]
+    pub fn none() -> Self {
+        Self::with_mode(FunctionCallingMode::None, None)
+    }
+
+    /// 强制模型从给定函数中选择调用（`ANY` + `allowed_function_names`）。
+    #[must_use]
+    pub fn any_of<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::with_mode(
+            FunctionCallingMode::Any,
+            Some(names.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// 强制模型调用指定函数。
+    #[must_use]
+    pub fn force_function(name: impl Into<String>) -> Self {
+        Self::any_of([name])
+    }
+
+    fn with_mode(mode: FunctionCallingMode, allowed: Option<Vec<String>>) -> Self {
+        Self {
+            function_calling_config: Some(FunctionCallingConfig {
+                allowed_function_names: allowed,
+                mode: Some(mode),
+                stream_function_call_arguments: None,
+            }),
+            retrieval_config: None,
+        }
+    }
+}
+
 /// 经纬度位置。
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -552,6 +673,213 @@ impl Schema {
             ..Default::default()
         }
     }
+
+    /// 设置对象字段的输出顺序（`propertyOrdering`），可只列出部分字段。
+    ///
+    /// # Errors
+    /// 当列出的字段名不存在于 `properties` 或重复出现时返回错误描述。
+    pub fn with_ordering(mut self, names: &[&str]) -> Result<Self, String> {
+        let properties = self.properties.as_ref();
+        for (index, name) in names.iter().enumerate() {
+            if !properties.is_some_and(|properties| properties.contains_key(*name)) {
+                return Err(format!("propertyOrdering: unknown property `{name}`"));
+            }
+            if names[..index].contains(name) {
+                return Err(format!("propertyOrdering: duplicate property `{name}`"));
+            }
+        }
+        self.property_ordering = Some(names.iter().map(|name| (*name).to_string()).collect());
+        Ok(self)
+    }
+
+    /// 从 JSON Schema 转换（支持 `type`、`properties`、`required`、`enum`、`items`、`anyOf`
+    /// 及常见约束字段；`"type": ["string", "null"]` 映射为 `nullable`，其余关键字忽略）。
+    ///
+    /// # Errors
+    /// 当 Schema 不是对象或 `type` 无法识别时返回错误。
+    pub fn from_json_schema(value: &Value) -> Result<Self, serde_json::Error> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| serde::de::Error::custom("JSON Schema must be an object"))?;
+        let mut schema = Self::default();
+
+        match object.get("type") {
+            Some(Value::String(name)) => schema.ty = Some(json_schema_type(name)?),
+            Some(Value::Array(names)) => {
+                for name in names.iter().filter_map(Value::as_str) {
+                    if name == "null" {
+                        schema.nullable = Some(true);
+                    } else {
+                        schema.ty = Some(json_schema_type(name)?);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let string = |key: &str| {
+            object
+                .get(key)
+                .and_then(Value::as_str)
+                .map(ToString::to_string)
+        };
+        let int = |key: &str| object.get(key).and_then(Value::as_i64);
+        let float = |key: &str| object.get(key).and_then(Value::as_f64);
+        schema.description = string("description");
+        schema.title = string("title");
+        schema.format = string("format");
+        schema.pattern = string("pattern");
+        schema.minimum = float("minimum");
+        schema.maximum = float("maximum");
+        schema.min_length = int("minLength");
+        schema.max_length = int("maxLength");
+        schema.min_items = int("minItems");
+        schema.max_items = int("maxItems");
+        schema.min_properties = int("minProperties");
+        schema.max_properties = int("maxProperties");
+        schema.default = object.get("default").cloned();
+        if schema.nullable.is_none() {
+            schema.nullable = object.get("nullable").and_then(Value::as_bool);
+        }
+
+        if let Some(values) = object.get("enum").and_then(Value::as_array) {
+            schema.enum_values = Some(
+                values
+                    .iter()
+                    .filter(|value| !value.is_null())
+                    .map(|value| match value {
+                        Value::String(text) => text.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect(),
+            );
+            schema.ty.get_or_insert(Type::String);
+        }
+        if let Some(properties) = object.get("properties").and_then(Value::as_object) {
+            let mut converted = HashMap::new();
+            for (name, property) in properties {
+                converted.insert(name.clone(), Box::new(Self::from_json_schema(property)?));
+            }
+            schema.properties = Some(converted);
+        }
+        if let Some(required) = object.get("required").and_then(Value::as_array) {
+            schema.required = Some(
+                required
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(ToString::to_string)
+                    .collect(),
+            );
+        }
+        if let Some(items) = object.get("items") {
+            schema.items = Some(Box::new(Self::from_json_schema(items)?));
+        }
+        if let Some(any_of) = object
+            .get("anyOf")
+            .or_else(|| object.get("oneOf"))
+            .and_then(Value::as_array)
+        {
+            schema.any_of = Some(
+                any_of
+                    .iter()
+                    .map(Self::from_json_schema)
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        Ok(schema)
+    }
+
+    /// 按 Schema 校验 JSON 值（类型、必填字段、枚举、`any_of`，递归检查属性与数组元素）。
+    ///
+    /// # Errors
+    /// 返回第一处不匹配的描述，形如 `$.city: missing required field`。
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        self.validate_at(value, "$")
+    }
+
+    fn validate_at(&self, value: &Value, path: &str) -> Result<(), String> {
+        if value.is_null() && self.nullable == Some(true) {
+            return Ok(());
+        }
+        if let Some(any_of) = &self.any_of {
+            if !any_of.is_empty()
+                && !any_of
+                    .iter()
+                    .any(|schema| schema.validate_at(value, path).is_ok())
+            {
+                return Err(format!("{path}: does not match any of the allowed schemas"));
+            }
+        }
+        if let Some(ty) = self.ty {
+            let matches = match ty {
+                Type::String => value.is_string(),
+                Type::Number => value.is_number(),
+                Type::Integer => value.is_i64() || value.is_u64(),
+                Type::Boolean => value.is_boolean(),
+                Type::Array => value.is_array(),
+                Type::Object => value.is_object(),
+                Type::Null => value.is_null(),
+                Type::TypeUnspecified => true,
+            };
+            if !matches {
+                return Err(format!(
+                    "{path}: expected {ty:?}, got {}",
+                    json_type_name(value)
+                ));
+            }
+        }
+        if let (Some(allowed), Some(text)) = (&self.enum_values, value.as_str()) {
+            if !allowed.iter().any(|item| item == text) {
+                return Err(format!("{path}: {text:?} is not one of {allowed:?}"));
+            }
+        }
+        if let Some(object) = value.as_object() {
+            for name in self.required.iter().flatten() {
+                if !object.contains_key(name) {
+                    return Err(format!("{path}.{name}: missing required field"));
+                }
+            }
+            if let Some(properties) = &self.properties {
+                for (name, field) in object {
+                    if let Some(schema) = properties.get(name) {
+                        schema.validate_at(field, &format!("{path}.{name}"))?;
+                    }
+                }
+            }
+        }
+        if let (Some(items), Some(array)) = (&self.items, value.as_array()) {
+            for (index, item) in array.iter().enumerate() {
+                items.validate_at(item, &format!("{path}[{index}]"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn json_schema_type(name: &str) -> Result<Type, serde_json::Error> {
+    match name {
+        "string" => Ok(Type::String),
+        "number" => Ok(Type::Number),
+        "integer" => Ok(Type::Integer),
+        "boolean" => Ok(Type::Boolean),
+        "array" => Ok(Type::Array),
+        "object" => Ok(Type::Object),
+        "null" => Ok(Type::Null),
+        other => Err(serde::de::Error::custom(format!(
+            "unsupported JSON Schema type: {other}"
+        ))),
+    }
+}
+
+const fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
 }
 
 pub struct SchemaBuilder {
@@ -618,6 +946,24 @@ impl SchemaBuilder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn tool_config_mode_helpers() {
+        let value = serde_json::to_value(ToolConfig::force_function("get_weather")).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "functionCallingConfig": {
+                    "mode": "ANY",
+                    "allowedFunctionNames": ["get_weather"]
+                }
+            })
+        );
+        let mode = |config: ToolConfig| config.function_calling_config.unwrap().mode;
+        assert_eq!(mode(ToolConfig::auto()), Some(FunctionCallingMode::Auto));
+        assert_eq!(mode(ToolConfig::none()), Some(FunctionCallingMode::None));
+        assert_eq!(mode(ToolConfig::any()), Some(FunctionCallingMode::Any));
+    }
+
     #[test]
     fn schema_builder_object() {
         let schema = Schema::object()
@@ -628,6 +974,109 @@ mod tests {
         assert!(schema.properties.unwrap().contains_key("name"));
     }
 
+    #[test]
+    fn schema_with_ordering_serializes_property_ordering() {
+        let schema = Schema::object()
+            .property("summary", Schema::string())
+            .property("title", Schema::string())
+            .property("score", Schema::number())
+            .build()
+            .with_ordering(&["title", "summary"])
+            .unwrap();
+        let value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            value["propertyOrdering"],
+            serde_json::json!(["title", "summary"])
+        );
+
+        let err = schema.clone().with_ordering(&["missing"]).unwrap_err();
+        assert_eq!(err, "propertyOrdering: unknown property `missing`");
+        assert!(schema.with_ordering(&["title", "title"]).is_err());
+    }
+
+    #[test]
+    fn schema_validate_reports_first_violation() {
+        let schema = Schema::object()
+            .property("city", Schema::string())
+            .property("days", Schema::integer())
+            .property("tags", Schema::array().items(Schema::string()).build())
+            .required("city")
+            .build();
+        assert!(schema
+            .validate(&serde_json::json!({"city": "Paris", "days": 3, "tags": ["a"]}))
+            .is_ok());
+        assert_eq!(
+            schema
+                .validate(&serde_json::json!({"days": 3}))
+                .unwrap_err(),
+            "$.city: missing required field"
+        );
+        assert_eq!(
+            schema
+                .validate(&serde_json::json!({"city": "Paris", "days": "3"}))
+                .unwrap_err(),
+            "$.days: expected Integer, got string"
+        );
+        assert_eq!(
+            schema
+                .validate(&serde_json::json!({"city": "Paris", "tags": [1]}))
+                .unwrap_err(),
+            "$.tags[0]: expected String, got number"
+        );
+    }
+
+    #[test]
+    fn tool_from_openai_function() {
+        let tool = Tool::from_openai_function(serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": "get_weather",
+                "description": "Get the current weather",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "location": {"type": "string", "description": "City name"},
+                        "unit": {"type": ["string", "null"], "enum": ["celsius", "fahrenheit"]},
+                        "days": {"type": "integer", "minimum": 1},
+                        "tags": {"type": "array", "items": {"type": "string"}}
+                    },
+                    "required": ["location"],
+                    "additionalProperties": false
+                }
+            }
+        }))
+        .unwrap();
+
+        let value = serde_json::to_value(&tool).unwrap();
+        let declaration = &value["functionDeclarations"][0];
+        assert_eq!(declaration["name"], "get_weather");
+        assert_eq!(declaration["description"], "Get the current weather");
+        let parameters = &declaration["parameters"];
+        assert_eq!(parameters["type"], "OBJECT");
+        assert_eq!(parameters["required"], serde_json::json!(["location"]));
+        assert!(parameters.get("additionalProperties").is_none());
+        let properties = &parameters["properties"];
+        assert_eq!(
+            properties["location"],
+            serde_json::json!({"type": "STRING", "description": "City name"})
+        );
+        assert_eq!(
+            properties["unit"],
+            serde_json::json!({
+                "type": "STRING",
+                "nullable": true,
+                "enum": ["celsius", "fahrenheit"]
+            })
+        );
+        assert_eq!(
+            properties["days"],
+            serde_json::json!({"type": "INTEGER", "minimum": 1.0})
+        );
+        assert_eq!(properties["tags"]["items"]["type"], "STRING");
+
+        assert!(Tool::from_openai_function(serde_json::json!({"description": "x"})).is_err());
+    }
+
     #[test]
     fn schema_builder_array_and_enum() {
         let schema = Schema::array()
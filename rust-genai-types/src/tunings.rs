@@ -1,10 +1,12 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::enums::{AdapterSize, JobState, TuningMethod, TuningMode};
 use crate::http::{HttpOptions, HttpResponse};
+use crate::timestamp::elapsed_between;
 
 /// Tuning job state alias.
 pub type TuningJobState = JobState;
@@ -15,7 +17,7 @@ pub type TuningJobState = JobState;
 pub struct TuningExample {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "text_input")]
     pub text_input: Option<String>,
 }
 
@@ -159,6 +161,23 @@ pub struct CancelTuningJobResponse {
     pub sdk_http_response: Option<HttpResponse>,
 }
 
+/// Optional parameters for deleting a tuned model (`tunings.delete`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTunedModelConfig {
+    #[serde(skip_serializing, skip_deserializing)]
+    pub http_options: Option<HttpOptions>,
+}
+
+/// Empty response for deleting a tuned model (`tunings.delete`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTunedModelResponse {
+    /// Optional. Used to retain the full HTTP response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdk_http_response: Option<HttpResponse>,
+}
+
 /// `TunedModel` checkpoint.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -267,6 +286,27 @@ pub struct TuningJob {
     pub tuned_model_display_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub veo_tuning_spec: Option<Value>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl TuningJob {
+    /// 任务运行时长：`start_time`（缺失时用 `create_time`）到 `end_time`，未结束时计到当前时间。
+    ///
+    /// 时间戳缺失或无法解析时返回 `None`。
+    #[must_use]
+    pub fn elapsed(&self) -> Option<Duration> {
+        let start = self.start_time.as_deref().or(self.create_time.as_deref());
+        elapsed_between(start, self.end_time.as_deref())
+    }
+
+    /// 排队时长：`create_time` 到 `start_time`，尚未开始时计到当前时间。
+    #[must_use]
+    pub fn queued_duration(&self) -> Option<Duration> {
+        elapsed_between(self.create_time.as_deref(), self.start_time.as_deref())
+    }
 }
 
 /// Response for list tuning jobs.
@@ -280,6 +320,10 @@ pub struct ListTuningJobsResponse {
     pub tuning_jobs: Option<Vec<TuningJob>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_page_token: Option<String>,
+    /// Forward-compatible extension fields.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 /// Tuning operation (Gemini Developer API).
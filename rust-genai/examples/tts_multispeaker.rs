@@ -1,3 +1,4 @@
+use rust_genai::audio::{save_wav, PcmFormat};
 use rust_genai::types::config::{
     GenerationConfig, MultiSpeakerVoiceConfig, PrebuiltVoiceConfig, SpeakerVoiceConfig,
     SpeechConfig, VoiceConfig,
@@ -6,8 +7,6 @@ use rust_genai::types::content::{Content, PartKind};
 use rust_genai::types::enums::Modality;
 use rust_genai::types::models::GenerateContentConfig;
 use rust_genai::Client;
-use std::fs::File;
-use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 fn example_files_dir() -> PathBuf {
@@ -20,42 +19,6 @@ fn example_files_dir() -> PathBuf {
         .join("output")
 }
 
-fn parse_sample_rate(mime_type: &str) -> u32 {
-    const DEFAULT_RATE: u32 = 24_000;
-    mime_type
-        .split(';')
-        .find_map(|part| part.trim().strip_prefix("rate="))
-        .and_then(|rate| rate.parse().ok())
-        .unwrap_or(DEFAULT_RATE)
-}
-
-fn write_wav(path: &Path, pcm_data: &[u8], sample_rate: u32) -> std::io::Result<()> {
-    let mut file = File::create(path)?;
-    let channels: u16 = 1;
-    let bits_per_sample: u16 = 16;
-    let byte_rate = sample_rate * u32::from(channels) * u32::from(bits_per_sample) / 8;
-    let block_align = channels * bits_per_sample / 8;
-    let data_len = u32::try_from(pcm_data.len())
-        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "pcm data too large"))?;
-    let chunk_size = 36 + data_len;
-
-    file.write_all(b"RIFF")?;
-    file.write_all(&chunk_size.to_le_bytes())?;
-    file.write_all(b"WAVE")?;
-    file.write_all(b"fmt ")?;
-    file.write_all(&16u32.to_le_bytes())?;
-    file.write_all(&1u16.to_le_bytes())?;
-    file.write_all(&channels.to_le_bytes())?;
-    file.write_all(&sample_rate.to_le_bytes())?;
-    file.write_all(&byte_rate.to_le_bytes())?;
-    file.write_all(&block_align.to_le_bytes())?;
-    file.write_all(&bits_per_sample.to_le_bytes())?;
-    file.write_all(b"data")?;
-    file.write_all(&data_len.to_le_bytes())?;
-    file.write_all(pcm_data)?;
-    Ok(())
-}
-
 #[tokio::main]
 async fn main() -> rust_genai::Result<()> {
     let client = Client::from_env()?;
@@ -126,8 +89,8 @@ async fn main() -> rust_genai::Result<()> {
                         if inline_data.mime_type.contains("wav") {
                             std::fs::write(&filename, &inline_data.data)?;
                         } else {
-                            let rate = parse_sample_rate(&inline_data.mime_type);
-                            write_wav(&filename, &inline_data.data, rate)?;
+                            let format = PcmFormat::from_mime_type(&inline_data.mime_type);
+                            save_wav(&filename, &inline_data.data, format)?;
                         }
                         println!(
                             "saved {} (可用 GENAI_EXAMPLE_FILES_DIR 覆盖输出目录)",
@@ -3,18 +3,26 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::hash::BuildHasher;
+use std::sync::Arc;
+use std::time::Duration;
 
 use futures_util::future::BoxFuture;
-use rust_genai_types::content::{FunctionCall, FunctionResponse, Part};
+use rust_genai_types::content::{FunctionCall, FunctionResponse, Part, PartKind};
+use rust_genai_types::enums::Behavior;
 use rust_genai_types::models::GenerateContentConfig;
-use rust_genai_types::tool::{FunctionDeclaration, Tool};
-use serde_json::Value;
+use rust_genai_types::tool::{CodeExecution, FunctionDeclaration, GoogleSearch, Tool, UrlContext};
+use serde_json::{json, Value};
 
 use crate::error::{Error, Result};
+use crate::model_capabilities::check_tool_combination;
+use crate::rt::JoinHandle;
 
 /// 默认最大远程调用次数。
 pub const DEFAULT_MAX_REMOTE_CALLS: usize = 10;
 
+/// 流式 AFC 默认事件缓冲区大小。
+pub const DEFAULT_STREAM_BUFFER_SIZE: usize = 8;
+
 /// 可调用工具接口。
 pub trait CallableTool: Send {
     fn tool(&mut self) -> BoxFuture<'_, Result<Tool>>;
@@ -25,11 +33,26 @@ pub trait CallableTool: Send {
 pub type ToolHandler =
     Box<dyn Fn(Value) -> BoxFuture<'static, Result<Value>> + Send + Sync + 'static>;
 
+/// 后台（`NON_BLOCKING`）handler 失败时传给钩子的上下文。
+#[derive(Debug)]
+pub struct BackgroundToolFailure<'a> {
+    /// 函数名。
+    pub name: &'a str,
+    /// 触发调用的函数调用 ID。
+    pub call_id: Option<&'a str>,
+    /// handler 返回的错误，或任务 panic 时的 `JoinError`。
+    pub error: &'a (dyn std::error::Error + Send + Sync),
+}
+
+/// 后台 handler 失败钩子。
+pub type BackgroundToolHook = Arc<dyn Fn(&BackgroundToolFailure<'_>) + Send + Sync>;
+
 /// 以函数声明 + handler 组合的可调用工具。
 #[derive(Default)]
 pub struct InlineCallableTool {
     tool: Tool,
     handlers: HashMap<String, ToolHandler>,
+    background_error_hook: Option<BackgroundToolHook>,
 }
 
 impl InlineCallableTool {
@@ -42,6 +65,7 @@ impl InlineCallableTool {
                 ..Tool::default()
             },
             handlers: HashMap::new(),
+            background_error_hook: None,
         }
     }
 
@@ -71,6 +95,47 @@ impl InlineCallableTool {
         self.register_handler(name, handler);
         self
     }
+
+    /// 注册后台（`NON_BLOCKING`）handler 失败时的钩子。
+    ///
+    /// 后台 handler 的结果不会回传给模型；返回错误或 panic 时调用该钩子，
+    /// 并在启用 `tracing` 时记录警告。
+    #[must_use]
+    pub fn on_background_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&BackgroundToolFailure<'_>) + Send + Sync + 'static,
+    {
+        self.background_error_hook = Some(Arc::new(hook));
+        self
+    }
+}
+
+impl InlineCallableTool {
+    fn declaration(&self, name: &str) -> Option<&FunctionDeclaration> {
+        self.tool
+            .function_declarations
+            .iter()
+            .flatten()
+            .find(|declaration| declaration.name == name)
+    }
+
+    fn is_non_blocking(&self, name: &str) -> bool {
+        self.declaration(name)
+            .is_some_and(|declaration| declaration.behavior == Some(Behavior::NonBlocking))
+    }
+
+    fn validate_args(&self, name: &str, args: &Value) -> std::result::Result<(), String> {
+        let schema = self
+            .declaration(name)
+            .and_then(|declaration| declaration.parameters.as_ref());
+        let Some(schema) = schema else {
+            return Ok(());
+        };
+        if args.is_null() {
+            return schema.validate(&Value::Object(serde_json::Map::new()));
+        }
+        schema.validate(args)
+    }
 }
 
 impl CallableTool for InlineCallableTool {
@@ -89,7 +154,25 @@ impl CallableTool for InlineCallableTool {
                     continue;
                 };
                 let args = call.args.clone().unwrap_or(Value::Null);
-                let response_value = handler(args).await?;
+                let response_value = match self.validate_args(name, &args) {
+                    // `NON_BLOCKING` 声明：handler 在后台运行，不阻塞下一轮模型请求；
+                    // 其结果不会回传给模型，失败交给 `on_background_error` 钩子。
+                    Ok(()) if self.is_non_blocking(name) => {
+                        let task = crate::rt::spawn(handler(args));
+                        crate::rt::spawn(report_background_failure(
+                            task,
+                            name.clone(),
+                            call.id.clone(),
+                            self.background_error_hook.clone(),
+                        ));
+                        json!({ "status": "scheduled" })
+                    }
+                    Ok(()) => handler(args).await?,
+                    // 参数不符合声明时把错误回传给模型，让其修正后重新调用。
+                    Err(message) => json!({
+                        "error": format!("Invalid arguments for {name}: {message}")
+                    }),
+                };
                 let function_response = FunctionResponse {
                     will_continue: None,
                     scheduling: None,
@@ -105,6 +188,29 @@ impl CallableTool for InlineCallableTool {
     }
 }
 
+/// 等待后台 handler 结束，将其错误或 panic 交给钩子，并在启用 `tracing` 时记录警告。
+async fn report_background_failure(
+    task: JoinHandle<Result<Value>>,
+    name: String,
+    call_id: Option<String>,
+    hook: Option<BackgroundToolHook>,
+) {
+    let error: Box<dyn std::error::Error + Send + Sync> = match task.await {
+        Ok(Ok(_)) => return,
+        Ok(Err(error)) => Box::new(error),
+        Err(error) => Box::new(error),
+    };
+    #[cfg(feature = "tracing")]
+    tracing::warn!(function = %name, error = %error, "non-blocking tool handler failed");
+    if let Some(hook) = hook {
+        hook(&BackgroundToolFailure {
+            name: &name,
+            call_id: call_id.as_deref(),
+            error: error.as_ref(),
+        });
+    }
+}
+
 /// 解析 callable tools，返回声明列表与函数映射。
 ///
 /// # Errors
@@ -166,6 +272,94 @@ pub async fn call_callable_tools<S: BuildHasher + Sync>(
     Ok(parts)
 }
 
+/// 组合可调用工具与内置/声明工具的工具集。
+///
+/// 通过 [`ToolSet::validate`] 在发送前检查工具组合是否被模型支持，
+/// 再用 [`ToolSet::into_parts`] 拆分为配置与 callable tools，交给
+/// `generate_content_with_callable_tools` 使用。
+#[derive(Default)]
+pub struct ToolSet {
+    tools: Vec<Tool>,
+    callable_tools: Vec<Box<dyn CallableTool>>,
+}
+
+impl ToolSet {
+    /// 创建空工具集。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加声明工具（内置工具或 function declarations）。
+    #[must_use]
+    pub fn with_tool(mut self, tool: Tool) -> Self {
+        self.tools.push(tool);
+        self
+    }
+
+    /// 添加 Google 搜索工具。
+    #[must_use]
+    pub fn with_google_search(self) -> Self {
+        self.with_tool(Tool {
+            google_search: Some(GoogleSearch::default()),
+            ..Tool::default()
+        })
+    }
+
+    /// 添加代码执行工具。
+    #[must_use]
+    pub fn with_code_execution(self) -> Self {
+        self.with_tool(Tool {
+            code_execution: Some(CodeExecution::default()),
+            ..Tool::default()
+        })
+    }
+
+    /// 添加 URL 上下文工具。
+    #[must_use]
+    pub fn with_url_context(self) -> Self {
+        self.with_tool(Tool {
+            url_context: Some(UrlContext::default()),
+            ..Tool::default()
+        })
+    }
+
+    /// 添加可调用工具（例如 `GeminiTool` 派生的工具）。
+    #[must_use]
+    pub fn with_callable_tool(mut self, tool: impl CallableTool + 'static) -> Self {
+        self.callable_tools.push(Box::new(tool));
+        self
+    }
+
+    /// 检查工具组合是否被模型支持（按模型名启发式判断，需显式调用）。
+    ///
+    /// 请求发送前只会检查必然无效的冲突；是否允许混用函数调用与内置工具仅在此处校验。
+    ///
+    /// # Errors
+    /// 当组合不被支持时返回错误（例如旧模型上混用函数调用与 Google 搜索）。
+    pub fn validate(&self, model: &str) -> Result<()> {
+        let has_functions = !self.callable_tools.is_empty()
+            || self.tools.iter().any(|tool| {
+                tool.function_declarations
+                    .as_ref()
+                    .is_some_and(|declarations| !declarations.is_empty())
+            });
+        check_tool_combination(model, &self.tools, has_functions)
+    }
+
+    /// 将声明工具追加到 `config.tools`，并返回 callable tools。
+    #[must_use]
+    pub fn into_parts(
+        self,
+        mut config: GenerateContentConfig,
+    ) -> (GenerateContentConfig, Vec<Box<dyn CallableTool>>) {
+        if !self.tools.is_empty() {
+            config.tools.get_or_insert_with(Vec::new).extend(self.tools);
+        }
+        (config, self.callable_tools)
+    }
+}
+
 /// callable tools 解析结果。
 pub struct CallableToolInfo<S = std::collections::hash_map::RandomState> {
     pub tools: Vec<Tool>,
@@ -209,6 +403,60 @@ pub fn max_remote_calls(config: &GenerateContentConfig) -> usize {
         .unwrap_or(DEFAULT_MAX_REMOTE_CALLS)
 }
 
+/// 达到最大远程调用次数时是否返回错误。
+#[must_use]
+pub fn should_error_on_max_remote_calls(config: &GenerateContentConfig) -> bool {
+    config
+        .automatic_function_calling
+        .as_ref()
+        .and_then(|cfg| cfg.error_on_max_remote_calls)
+        .unwrap_or(false)
+}
+
+/// 流式 AFC 的事件缓冲区大小（至少为 1）。
+#[must_use]
+pub fn stream_buffer_size(config: &GenerateContentConfig) -> usize {
+    config
+        .automatic_function_calling
+        .as_ref()
+        .and_then(|cfg| cfg.stream_buffer_size)
+        .and_then(|value| usize::try_from(value).ok())
+        .unwrap_or(DEFAULT_STREAM_BUFFER_SIZE)
+        .max(1)
+}
+
+/// 整个 AFC 交互的总超时（`total_timeout_ms`）。
+#[must_use]
+pub fn total_timeout(config: &GenerateContentConfig) -> Option<Duration> {
+    config
+        .automatic_function_calling
+        .as_ref()
+        .and_then(|cfg| cfg.total_timeout_ms)
+        .map(Duration::from_millis)
+}
+
+pub(crate) fn total_timeout_error(timeout: Duration) -> Error {
+    Error::Timeout {
+        message: format!(
+            "Automatic function calling exceeded total timeout of {} ms",
+            timeout.as_millis()
+        ),
+    }
+}
+
+/// 在总超时内运行 AFC 循环；未设置超时时直接运行。
+pub(crate) async fn with_total_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout {
+        Some(timeout) => crate::rt::timeout(timeout, future)
+            .await
+            .unwrap_or_else(|_| Err(total_timeout_error(timeout))),
+        None => future.await,
+    }
+}
+
 /// 是否应附加 AFC 历史。
 #[must_use]
 pub fn should_append_history(config: &GenerateContentConfig) -> bool {
@@ -243,6 +491,40 @@ pub fn validate_afc_tools<S: BuildHasher>(
     Ok(())
 }
 
+/// 按 `tools` 中声明的响应 schema 校验 `parts` 里的函数响应（可在手动回传工具结果前调用）。
+///
+/// 找不到对应声明或声明未定义响应 schema 的函数响应会被跳过。
+///
+/// # Errors
+/// 当函数响应与声明的 schema 不匹配时返回错误。
+pub fn validate_function_responses(tools: &[Tool], parts: &[Part]) -> Result<()> {
+    for part in parts {
+        let PartKind::FunctionResponse { function_response } = &part.kind else {
+            continue;
+        };
+        let Some(name) = function_response.name.as_deref() else {
+            continue;
+        };
+        let declaration = tools
+            .iter()
+            .filter_map(|tool| tool.function_declarations.as_ref())
+            .flatten()
+            .find(|declaration| declaration.name == name);
+        let Some(declaration) = declaration else {
+            continue;
+        };
+        let response = function_response.response.as_ref().unwrap_or(&Value::Null);
+        declaration
+            .validate_response(response)
+            .map_err(|message| Error::InvalidConfig {
+                message: format!(
+                    "Function response for {name} does not match its declared schema: {message}"
+                ),
+            })?;
+    }
+    Ok(())
+}
+
 /// 校验 AFC 与其他配置的冲突。
 ///
 /// # Errors
@@ -316,6 +598,37 @@ mod tests {
         assert!(matches!(err, Error::InvalidConfig { .. }));
     }
 
+    #[test]
+    fn test_tool_set_validates_and_splits_parts() {
+        let tool_set = ToolSet::new().with_google_search().with_callable_tool(
+            InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+                name: "lookup".to_string(),
+                description: None,
+                parameters: None,
+                parameters_json_schema: None,
+                response: None,
+                response_json_schema: None,
+                behavior: None,
+            }]),
+        );
+        let err = tool_set.validate("gemini-2.5-flash").unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::InvalidConfig { message }
+                if message.contains("does not support combining function declarations with built-in tools (google_search)")
+        ));
+        assert!(tool_set.validate("gemini-3-flash-preview").is_ok());
+
+        let (config, callable_tools) = tool_set.into_parts(GenerateContentConfig::default());
+        assert_eq!(callable_tools.len(), 1);
+        let tools = config.tools.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert!(tools[0].google_search.is_some());
+
+        let builtin_only = ToolSet::new().with_google_search().with_code_execution();
+        assert!(builtin_only.validate("gemini-2.5-flash").is_ok());
+    }
+
     #[tokio::test]
     async fn test_inline_callable_tool_roundtrip() {
         let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
@@ -350,6 +663,120 @@ mod tests {
         assert_eq!(parts.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_non_blocking_tool_is_not_awaited() {
+        let gate = std::sync::Arc::new(tokio::sync::Notify::new());
+        let (done_tx, mut done_rx) = tokio::sync::mpsc::unbounded_channel();
+        let handler_gate = gate.clone();
+        let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+            name: "send_report".to_string(),
+            description: None,
+            parameters: None,
+            parameters_json_schema: None,
+            response: None,
+            response_json_schema: None,
+            behavior: Some(Behavior::NonBlocking),
+        }])
+        .with_handler("send_report", move |value| {
+            let gate = handler_gate.clone();
+            let done_tx = done_tx.clone();
+            async move {
+                gate.notified().await;
+                done_tx.send(value).unwrap();
+                Ok(json!({ "sent": true }))
+            }
+        });
+
+        let mut tools: Vec<Box<dyn CallableTool>> = vec![Box::new(tool)];
+        let info = resolve_callable_tools(&mut tools).await.unwrap();
+        let calls = vec![FunctionCall {
+            id: Some("call-1".into()),
+            name: Some("send_report".into()),
+            args: Some(json!({"to": "ops"})),
+            partial_args: None,
+            will_continue: None,
+        }];
+        let parts = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            call_callable_tools(&mut tools, &info.function_map, &calls),
+        )
+        .await
+        .expect("non-blocking tool must not be awaited")
+        .unwrap();
+
+        let PartKind::FunctionResponse { function_response } = &parts[0].kind else {
+            panic!("expected function response");
+        };
+        assert_eq!(
+            function_response.response,
+            Some(json!({ "status": "scheduled" }))
+        );
+        assert!(done_rx.try_recv().is_err());
+
+        gate.notify_one();
+        assert_eq!(done_rx.recv().await, Some(json!({"to": "ops"})));
+    }
+
+    #[tokio::test]
+    async fn test_non_blocking_tool_failures_reach_hook() {
+        let (failed_tx, mut failed_rx) = tokio::sync::mpsc::unbounded_channel();
+        let declaration = |name: &str| FunctionDeclaration {
+            name: name.to_string(),
+            description: None,
+            parameters: None,
+            parameters_json_schema: None,
+            response: None,
+            response_json_schema: None,
+            behavior: Some(Behavior::NonBlocking),
+        };
+        let tool = InlineCallableTool::from_declarations(vec![
+            declaration("fails"),
+            declaration("panics"),
+        ])
+        .with_handler("fails", |_| async {
+            Err(Error::InvalidConfig {
+                message: "smtp down".into(),
+            })
+        })
+        .with_handler("panics", |_| async { panic!("handler bug") })
+        .on_background_error(move |failure| {
+            failed_tx
+                .send((
+                    failure.name.to_string(),
+                    failure.call_id.map(ToString::to_string),
+                    failure.error.to_string(),
+                ))
+                .unwrap();
+        });
+
+        let mut tools: Vec<Box<dyn CallableTool>> = vec![Box::new(tool)];
+        let info = resolve_callable_tools(&mut tools).await.unwrap();
+        let call = |id: &str, name: &str| FunctionCall {
+            id: Some(id.into()),
+            name: Some(name.into()),
+            args: None,
+            partial_args: None,
+            will_continue: None,
+        };
+        let calls = vec![call("call-1", "fails"), call("call-2", "panics")];
+        let parts = call_callable_tools(&mut tools, &info.function_map, &calls)
+            .await
+            .unwrap();
+        assert_eq!(parts.len(), 2);
+
+        let mut failures = [
+            failed_rx.recv().await.unwrap(),
+            failed_rx.recv().await.unwrap(),
+        ];
+        failures.sort();
+        assert_eq!(failures[0].0, "fails");
+        assert_eq!(failures[0].1.as_deref(), Some("call-1"));
+        assert!(failures[0].2.contains("smtp down"));
+        assert_eq!(failures[1].0, "panics");
+        assert_eq!(failures[1].1.as_deref(), Some("call-2"));
+        assert!(failures[1].2.contains("panic"));
+    }
+
     #[tokio::test]
     async fn test_call_callable_tools_rejects_missing_name() {
         let mut tools: Vec<Box<dyn CallableTool>> = Vec::new();
@@ -380,4 +807,42 @@ mod tests {
             .unwrap_err();
         assert!(matches!(err, Error::InvalidConfig { .. }));
     }
+
+    #[test]
+    fn test_validate_function_responses_rejects_mismatched_type() {
+        let tool = Tool {
+            function_declarations: Some(vec![FunctionDeclaration {
+                name: "get_weather".to_string(),
+                description: None,
+                parameters: None,
+                parameters_json_schema: None,
+                response: None,
+                response_json_schema: Some(json!({
+                    "type": "object",
+                    "properties": {"temp": {"type": "number"}},
+                    "required": ["temp"]
+                })),
+                behavior: None,
+            }]),
+            ..Default::default()
+        };
+        let response = |value| {
+            Part::function_response(FunctionResponse {
+                will_continue: None,
+                scheduling: None,
+                parts: None,
+                id: None,
+                name: Some("get_weather".to_string()),
+                response: Some(value),
+            })
+        };
+
+        let tools = [tool];
+        validate_function_responses(&tools, &[response(json!({"temp": 21.5}))]).unwrap();
+        let err =
+            validate_function_responses(&tools, &[response(json!({"temp": "hot"}))]).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidConfig { ref message } if message.contains("does not match"))
+        );
+    }
 }
@@ -0,0 +1,164 @@
+//! Helpers for audio returned by TTS / native-audio models.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+/// PCM 参数（Gemini TTS 默认 24kHz、单声道、16-bit）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u16,
+}
+
+impl Default for PcmFormat {
+    fn default() -> Self {
+        Self {
+            sample_rate: 24_000,
+            channels: 1,
+            bits_per_sample: 16,
+        }
+    }
+}
+
+impl PcmFormat {
+    /// 从 MIME 类型（如 `audio/L16;codec=pcm;rate=24000`）解析采样率，其余参数使用默认值。
+    #[must_use]
+    pub fn from_mime_type(mime_type: &str) -> Self {
+        let mut format = Self::default();
+        if let Some(rate) = mime_type
+            .split(';')
+            .find_map(|part| part.trim().strip_prefix("rate="))
+            .and_then(|rate| rate.parse().ok())
+        {
+            format.sample_rate = rate;
+        }
+        format
+    }
+}
+
+/// 将 PCM 数据写成 WAV（RIFF 头 + 原始数据）。
+///
+/// # Errors
+/// 当 PCM 数据超过 WAV 长度上限、格式参数导致头字段溢出或写入失败时返回错误。
+pub fn write_wav(mut writer: impl Write, pcm_data: &[u8], format: PcmFormat) -> io::Result<()> {
+    let PcmFormat {
+        sample_rate,
+        channels,
+        bits_per_sample,
+    } = format;
+    let block_align = channels
+        .checked_mul(bits_per_sample)
+        .map(|bits| bits / 8)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "wav block align overflows"))?;
+    let byte_rate = sample_rate
+        .checked_mul(u32::from(block_align))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "wav byte rate overflows"))?;
+    let data_len = u32::try_from(pcm_data.len())
+        .ok()
+        .filter(|len| *len <= u32::MAX - 36)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "pcm data too large"))?;
+
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_len).to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    writer.write_all(&1u16.to_le_bytes())?;
+    writer.write_all(&channels.to_le_bytes())?;
+    writer.write_all(&sample_rate.to_le_bytes())?;
+    writer.write_all(&byte_rate.to_le_bytes())?;
+    writer.write_all(&block_align.to_le_bytes())?;
+    writer.write_all(&bits_per_sample.to_le_bytes())?;
+    writer.write_all(b"data")?;
+    writer.write_all(&data_len.to_le_bytes())?;
+    writer.write_all(pcm_data)?;
+    Ok(())
+}
+
+/// 将 PCM 数据保存为 WAV 文件。
+///
+/// # Errors
+/// 当创建文件或写入失败时返回错误。
+pub fn save_wav(path: impl AsRef<Path>, pcm_data: &[u8], format: PcmFormat) -> io::Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut writer = io::BufWriter::new(file);
+    write_wav(&mut writer, pcm_data, format)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_wav_emits_riff_header() {
+        let pcm = [0u8, 1, 2, 3];
+        let mut out = Vec::new();
+        write_wav(&mut out, &pcm, PcmFormat::default()).unwrap();
+
+        assert_eq!(out.len(), 44 + pcm.len());
+        assert_eq!(&out[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(out[4..8].try_into().unwrap()), 40);
+        assert_eq!(&out[8..16], b"WAVEfmt ");
+        assert_eq!(u16::from_le_bytes(out[22..24].try_into().unwrap()), 1);
+        assert_eq!(u32::from_le_bytes(out[24..28].try_into().unwrap()), 24_000);
+        assert_eq!(u32::from_le_bytes(out[28..32].try_into().unwrap()), 48_000);
+        assert_eq!(&out[36..40], b"data");
+        assert_eq!(u32::from_le_bytes(out[40..44].try_into().unwrap()), 4);
+        assert_eq!(&out[44..], &pcm);
+    }
+
+    #[test]
+    fn write_wav_rejects_overflowing_format() {
+        let formats = [
+            PcmFormat {
+                sample_rate: 24_000,
+                channels: u16::MAX,
+                bits_per_sample: 16,
+            },
+            PcmFormat {
+                sample_rate: u32::MAX,
+                channels: 2,
+                bits_per_sample: 16,
+            },
+        ];
+        for format in formats {
+            let err = write_wav(Vec::new(), &[0, 1], format).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn audio_parts_round_trip_to_wav() {
+        use rust_genai_types::response::GenerateContentResponse;
+        use serde_json::json;
+
+        let response: GenerateContentResponse = serde_json::from_value(json!({
+            "candidates": [{"content": {"role": "model", "parts": [
+                {"text": "transcript"},
+                {"inlineData": {"mimeType": "audio/pcm;rate=16000", "data": "AAEAAQ=="}}
+            ]}}]
+        }))
+        .unwrap();
+        let parts = response.audio_parts();
+        assert_eq!(parts.len(), 1);
+        let (data, mime_type) = parts[0];
+        assert_eq!(data, &[0, 1, 0, 1]);
+
+        let mut out = Vec::new();
+        write_wav(&mut out, data, PcmFormat::from_mime_type(mime_type)).unwrap();
+        assert_eq!(&out[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(out[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(&out[44..], data);
+    }
+
+    #[test]
+    fn pcm_format_parses_rate_from_mime() {
+        assert_eq!(
+            PcmFormat::from_mime_type("audio/L16;codec=pcm;rate=16000").sample_rate,
+            16_000
+        );
+        assert_eq!(PcmFormat::from_mime_type("audio/pcm"), PcmFormat::default());
+    }
+}
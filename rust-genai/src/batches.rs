@@ -1,5 +1,7 @@
 //! Batches API surface.
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -10,11 +12,18 @@ use rust_genai_types::batches::{
     ListBatchJobsConfig, ListBatchJobsResponse,
 };
 use rust_genai_types::enums::JobState;
+#[cfg(not(target_arch = "wasm32"))]
+use rust_genai_types::files::UploadFileConfig;
 use serde_json::{json, Map, Value};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::AsyncBufReadExt;
 
-use crate::client::{Backend, ClientInner};
+use crate::client::{apply_request_id, Backend, ClientInner};
 use crate::error::{Error, Result};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::files::Files;
 use crate::http_response::sdk_http_response_from_headers;
+use crate::poll::{job_failed_error, PollConfig};
 
 #[derive(Clone)]
 pub struct Batches {
@@ -46,6 +55,7 @@ impl Batches {
 
         let url = build_batch_create_url(&self.inner, &model, http_options.as_ref())?;
         let mut request = self.inner.http.post(url).json(&body);
+        request = apply_request_id(request, http_options.as_ref());
         request = apply_http_options(request, http_options.as_ref())?;
 
         let response = self
@@ -53,12 +63,51 @@ impl Batches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let value = response.json::<Value>().await?;
         parse_batch_job_response(&self.inner, &value)
     }
 
+    /// 上传 JSONL 请求文件（可续传上传）并以该文件创建批处理任务（仅 Gemini API）。
+    ///
+    /// 上传前会逐行校验：每个非空行必须是包含 `request` 对象的 JSON 对象。
+    ///
+    /// # Errors
+    /// 当后端不支持、文件无效、某行不是合法请求、上传失败或创建请求失败时返回错误。
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn create_from_file(
+        &self,
+        model: impl Into<String>,
+        jsonl_path: impl AsRef<Path>,
+        config: CreateBatchJobConfig,
+    ) -> Result<BatchJob> {
+        if self.inner.config.backend == Backend::VertexAi {
+            return Err(Error::InvalidConfig {
+                message: "create_from_file is only supported in Gemini API".into(),
+            });
+        }
+        let path = jsonl_path.as_ref();
+        validate_jsonl_requests(path).await?;
+
+        let upload_config = UploadFileConfig {
+            mime_type: Some(JSONL_MIME_TYPE.to_string()),
+            ..Default::default()
+        };
+        let file = Files::new(self.inner.clone())
+            .upload_from_path_with_config(path, upload_config)
+            .await?;
+        let file_name = file.name.ok_or_else(|| Error::Parse {
+            message: "Uploaded batch file is missing name".into(),
+        })?;
+
+        let src = BatchJobSource {
+            file_name: Some(file_name),
+            ..Default::default()
+        };
+        self.create(model, src, config).await
+    }
+
     /// 获取批处理任务。
     ///
     /// # Errors
@@ -88,12 +137,40 @@ impl Batches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let value = response.json::<Value>().await?;
         parse_batch_job_response(&self.inner, &value)
     }
 
+    /// 轮询直到批处理任务进入终止状态，并返回成功（或部分成功）的任务。
+    ///
+    /// # Errors
+    /// 当请求失败、响应解析失败、任务失败/取消/过期或超时返回错误。
+    pub async fn wait(
+        &self,
+        name: impl AsRef<str>,
+        config: impl Into<PollConfig>,
+    ) -> Result<BatchJob> {
+        let config = config.into();
+        let mut poller = config.start();
+        loop {
+            let job = self.get(name.as_ref()).await?;
+            match job.state {
+                Some(
+                    state @ (JobState::JobStateFailed
+                    | JobState::JobStateCancelled
+                    | JobState::JobStateExpired),
+                ) => return Err(batch_job_error(state, &job)),
+                Some(state) if state.is_terminal() => return Ok(job),
+                _ => {}
+            }
+            poller
+                .wait("Timed out waiting for batch job to finish")
+                .await?;
+        }
+    }
+
     /// 删除批处理任务。
     ///
     /// # Errors
@@ -123,7 +200,7 @@ impl Batches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let text = response.text().await.unwrap_or_default();
@@ -165,7 +242,7 @@ impl Batches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(())
     }
@@ -197,7 +274,7 @@ impl Batches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let value = response.json::<Value>().await?;
@@ -239,6 +316,63 @@ impl Batches {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+const JSONL_MIME_TYPE: &str = "application/jsonl";
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn validate_jsonl_requests(path: &Path) -> Result<()> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut lines = tokio::io::BufReader::new(file).lines();
+    let mut line_number = 0usize;
+    let mut request_count = 0usize;
+    while let Some(line) = lines.next_line().await? {
+        line_number += 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        validate_jsonl_request_line(line).map_err(|reason| Error::InvalidConfig {
+            message: format!("{} line {line_number}: {reason}", path.display()),
+        })?;
+        request_count += 1;
+    }
+    if request_count == 0 {
+        return Err(Error::InvalidConfig {
+            message: format!("{} contains no batch requests", path.display()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn validate_jsonl_request_line(line: &str) -> std::result::Result<(), String> {
+    let value: Value = serde_json::from_str(line).map_err(|err| err.to_string())?;
+    let Some(object) = value.as_object() else {
+        return Err("expected a JSON object".into());
+    };
+    match object.get("request") {
+        Some(Value::Object(_)) => Ok(()),
+        Some(_) => Err("`request` must be a JSON object".into()),
+        None => Err("missing `request` field".into()),
+    }
+}
+
+fn batch_job_error(state: JobState, job: &BatchJob) -> Error {
+    let error = job.error.as_ref();
+    job_failed_error(
+        "Batch job",
+        state,
+        error.and_then(|error| error.code),
+        error.and_then(|error| error.message.as_deref()),
+        error
+            .and_then(|error| error.details.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(Value::String)
+            .collect(),
+    )
+}
+
 fn normalize_batch_model(inner: &ClientInner, model: &str) -> String {
     match inner.config.backend {
         Backend::GeminiApi => {
@@ -828,6 +962,9 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
@@ -853,6 +990,15 @@ mod tests {
         assert_eq!(name, "batches/abc-123");
     }
 
+    #[test]
+    fn test_validate_jsonl_request_line() {
+        assert!(validate_jsonl_request_line(r#"{"key":"a","request":{"contents":[]}}"#).is_ok());
+        assert!(validate_jsonl_request_line("not json").is_err());
+        assert!(validate_jsonl_request_line("[1]").is_err());
+        assert!(validate_jsonl_request_line(r#"{"key":"a"}"#).is_err());
+        assert!(validate_jsonl_request_line(r#"{"request":"text"}"#).is_err());
+    }
+
     #[test]
     fn test_normalize_batch_model_and_job_vertex() {
         let inner = test_client_inner(Backend::VertexAi);
@@ -0,0 +1,273 @@
+//! Blocking (synchronous) wrapper around the async client (requires the `blocking` feature).
+//!
+//! Each [`Client`] owns a single-threaded Tokio runtime and drives the async API on it,
+//! so callers do not need a runtime of their own. Do not call these methods from inside
+//! an async context: blocking on a runtime from within another runtime panics.
+//!
+//! ```rust,no_run
+//! use rust_genai::blocking::Client;
+//! use rust_genai::types::content::Content;
+//!
+//! let client = Client::new("api-key")?;
+//! let response = client
+//!     .models()
+//!     .generate_content("gemini-2.5-flash", vec![Content::text("Hello")])?;
+//! println!("{:?}", response.text());
+//! # Ok::<(), rust_genai::Error>(())
+//! ```
+
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures_util::{Stream, StreamExt};
+use rust_genai_types::content::Content;
+use rust_genai_types::files::{File, GetFileConfig, UploadFileConfig};
+use rust_genai_types::models::{
+    CountTokensConfig, CountTokensResponse, EmbedContentConfig, EmbedContentResponse,
+    GenerateContentConfig,
+};
+use rust_genai_types::response::GenerateContentResponse;
+use tokio::runtime::Runtime;
+
+use crate::error::Result;
+use crate::models::ModelName;
+
+/// 同步客户端。
+#[derive(Clone)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// 创建新客户端（Gemini API）。
+    ///
+    /// # Errors
+    /// 当配置无效、构建客户端或运行时失败时返回错误。
+    pub fn new(api_key: impl Into<String>) -> Result<Self> {
+        Self::from_async(crate::Client::new(api_key)?)
+    }
+
+    /// 从环境变量创建客户端。
+    ///
+    /// # Errors
+    /// 当环境变量缺失、构建客户端或运行时失败时返回错误。
+    pub fn from_env() -> Result<Self> {
+        Self::from_async(crate::Client::from_env()?)
+    }
+
+    /// 包装已有的异步客户端（例如通过 [`crate::ClientBuilder`] 构建）。
+    ///
+    /// # Errors
+    /// 当构建运行时失败时返回错误。
+    pub fn from_async(client: crate::Client) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// 访问 Models API。
+    #[must_use]
+    pub fn models(&self) -> Models {
+        Models {
+            inner: self.inner.models(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// 访问 Files API。
+    #[must_use]
+    pub fn files(&self) -> Files {
+        Files {
+            inner: self.inner.files(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+/// 同步 Models API。
+#[derive(Clone)]
+pub struct Models {
+    inner: crate::models::Models,
+    runtime: Arc<Runtime>,
+}
+
+impl Models {
+    /// 生成内容（默认配置）。
+    ///
+    /// # Errors
+    /// 当请求失败、配置校验失败或响应解析失败时返回错误。
+    pub fn generate_content(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+    ) -> Result<GenerateContentResponse> {
+        self.runtime
+            .block_on(self.inner.generate_content(model, contents))
+    }
+
+    /// 生成内容（自定义配置）。
+    ///
+    /// # Errors
+    /// 当请求失败、配置校验失败或响应解析失败时返回错误。
+    pub fn generate_content_with_config(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        config: GenerateContentConfig,
+    ) -> Result<GenerateContentResponse> {
+        self.runtime.block_on(
+            self.inner
+                .generate_content_with_config(model, contents, config),
+        )
+    }
+
+    /// 生成内容（流式），以迭代器形式返回。
+    ///
+    /// # Errors
+    /// 当请求失败或配置校验失败时返回错误。
+    pub fn generate_content_stream(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        config: GenerateContentConfig,
+    ) -> Result<BlockingStream<GenerateContentResponse>> {
+        let stream = self
+            .runtime
+            .block_on(self.inner.generate_content_stream(model, contents, config))?;
+        Ok(BlockingStream {
+            stream,
+            runtime: self.runtime.clone(),
+        })
+    }
+
+    /// 计数 tokens（默认配置）。
+    ///
+    /// # Errors
+    /// 当请求失败或响应解析失败时返回错误。
+    pub fn count_tokens(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+    ) -> Result<CountTokensResponse> {
+        self.runtime
+            .block_on(self.inner.count_tokens(model, contents))
+    }
+
+    /// 计数 tokens（自定义配置）。
+    ///
+    /// # Errors
+    /// 当请求失败、配置不合法或响应解析失败时返回错误。
+    pub fn count_tokens_with_config(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        config: CountTokensConfig,
+    ) -> Result<CountTokensResponse> {
+        self.runtime
+            .block_on(self.inner.count_tokens_with_config(model, contents, config))
+    }
+
+    /// 生成嵌入向量（默认配置）。
+    ///
+    /// # Errors
+    /// 当请求失败或响应解析失败时返回错误。
+    pub fn embed_content(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+    ) -> Result<EmbedContentResponse> {
+        self.runtime
+            .block_on(self.inner.embed_content(model, contents))
+    }
+
+    /// 生成嵌入向量（自定义配置）。
+    ///
+    /// # Errors
+    /// 当请求失败、配置不合法或响应解析失败时返回错误。
+    pub fn embed_content_with_config(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        config: EmbedContentConfig,
+    ) -> Result<EmbedContentResponse> {
+        self.runtime.block_on(
+            self.inner
+                .embed_content_with_config(model, contents, config),
+        )
+    }
+}
+
+/// 同步 Files API。
+#[derive(Clone)]
+pub struct Files {
+    inner: crate::files::Files,
+    runtime: Arc<Runtime>,
+}
+
+impl Files {
+    /// 上传文件（直接上传字节数据）。
+    ///
+    /// # Errors
+    /// 当配置无效、请求失败或响应解析失败时返回错误。
+    pub fn upload(&self, data: Vec<u8>, mime_type: impl Into<String>) -> Result<File> {
+        self.runtime.block_on(self.inner.upload(data, mime_type))
+    }
+
+    /// 上传文件（自定义配置）。
+    ///
+    /// # Errors
+    /// 当配置无效、请求失败或响应解析失败时返回错误。
+    pub fn upload_with_config(&self, data: Vec<u8>, config: UploadFileConfig) -> Result<File> {
+        self.runtime
+            .block_on(self.inner.upload_with_config(data, config))
+    }
+
+    /// 从文件路径上传。
+    ///
+    /// # Errors
+    /// 当文件无效、请求失败或响应解析失败时返回错误。
+    pub fn upload_from_path(&self, path: impl AsRef<Path>) -> Result<File> {
+        self.runtime.block_on(self.inner.upload_from_path(path))
+    }
+
+    /// 获取文件元数据。
+    ///
+    /// # Errors
+    /// 当请求失败或响应解析失败时返回错误。
+    pub fn get(&self, name_or_uri: impl AsRef<str>) -> Result<File> {
+        self.runtime.block_on(self.inner.get(name_or_uri))
+    }
+
+    /// 获取文件元数据（自定义配置）。
+    ///
+    /// # Errors
+    /// 当请求失败或响应解析失败时返回错误。
+    pub fn get_with_config(
+        &self,
+        name_or_uri: impl AsRef<str>,
+        config: GetFileConfig,
+    ) -> Result<File> {
+        self.runtime
+            .block_on(self.inner.get_with_config(name_or_uri, config))
+    }
+}
+
+/// 同步流式响应迭代器。
+pub struct BlockingStream<T> {
+    stream: Pin<Box<dyn Stream<Item = Result<T>> + Send>>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T> Iterator for BlockingStream<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
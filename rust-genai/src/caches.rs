@@ -9,11 +9,15 @@ use rust_genai_types::caches::{
     DeleteCachedContentResponse, GetCachedContentConfig, ListCachedContentsConfig,
     ListCachedContentsResponse, UpdateCachedContentConfig,
 };
+use rust_genai_types::content::{Content, PartKind};
+use rust_genai_types::timestamp::parse_rfc3339;
 use serde_json::{json, Map, Value};
 
-use crate::client::{Backend, ClientInner};
+use crate::client::{apply_request_id, Backend, ClientInner};
 use crate::error::{Error, Result};
 use crate::http_response::sdk_http_response_from_headers;
+use crate::model_capabilities::min_cache_tokens;
+use crate::tokenizer::{SimpleTokenEstimator, TokenEstimator};
 
 #[derive(Clone)]
 pub struct Caches {
@@ -52,6 +56,7 @@ impl Caches {
 
         let url = build_cached_contents_url(&self.inner, http_options.as_ref())?;
         let mut request = self.inner.http.post(url).json(&body);
+        request = apply_request_id(request, http_options.as_ref());
         request = apply_http_options(request, http_options.as_ref())?;
 
         let response = self
@@ -59,11 +64,74 @@ impl Caches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<CachedContent>().await?)
     }
 
+    /// 由内容直接创建缓存，并在请求前估算 token 数。
+    ///
+    /// # Errors
+    /// 当估算的 token 数低于模型的最小缓存要求、请求失败或响应解析失败时返回错误。
+    pub async fn create_from_contents(
+        &self,
+        model: impl Into<String>,
+        contents: Vec<Content>,
+        ttl: Duration,
+    ) -> Result<CachedContent> {
+        let model = model.into();
+        check_cacheable_tokens(&model, &contents, &SimpleTokenEstimator)?;
+        let config = CreateCachedContentConfig {
+            ttl: Some(format!("{}s", ttl.as_secs())),
+            contents: Some(contents),
+            ..Default::default()
+        };
+        self.create(model, config).await
+    }
+
+    /// 按 `display_name` 幂等地获取或创建缓存。
+    ///
+    /// 先列出全部缓存，若存在同名、同模型且未过期的缓存则直接返回；否则以
+    /// `contents` 与 `ttl` 创建新缓存（与 [`Caches::create_from_contents`] 相同的 token 检查）。
+    ///
+    /// # Errors
+    /// 当列出或创建请求失败、内容不足最小缓存 token 数或响应解析失败时返回错误。
+    pub async fn get_or_create(
+        &self,
+        display_name: impl Into<String>,
+        model: impl Into<String>,
+        contents: Vec<Content>,
+        ttl: Duration,
+    ) -> Result<CachedContent> {
+        let display_name = display_name.into();
+        let model = model.into();
+        let normalized_model = normalize_cache_model(&self.inner, &model)?;
+        let existing = self.all().await?.into_iter().find(|cache| {
+            cache.display_name.as_deref() == Some(display_name.as_str())
+                && cache
+                    .model
+                    .as_deref()
+                    .is_none_or(|cache_model| cache_model == normalized_model)
+                && cache
+                    .expire_time
+                    .as_deref()
+                    .and_then(parse_rfc3339)
+                    .is_none_or(|expire_time| !crate::rt::duration_until(expire_time).is_zero())
+        });
+        if let Some(cache) = existing {
+            return Ok(cache);
+        }
+
+        check_cacheable_tokens(&model, &contents, &SimpleTokenEstimator)?;
+        let config = CreateCachedContentConfig {
+            ttl: Some(format!("{}s", ttl.as_secs())),
+            display_name: Some(display_name),
+            contents: Some(contents),
+            ..Default::default()
+        };
+        self.create(model, config).await
+    }
+
     /// 获取缓存。
     ///
     /// # Errors
@@ -93,7 +161,7 @@ impl Caches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<CachedContent>().await?)
     }
@@ -122,7 +190,7 @@ impl Caches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<CachedContent>().await?)
     }
@@ -156,7 +224,7 @@ impl Caches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let text = response.text().await.unwrap_or_default();
@@ -197,7 +265,7 @@ impl Caches {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let mut result = response.json::<ListCachedContentsResponse>().await?;
@@ -404,6 +472,34 @@ fn handle_kms_key(inner: &ClientInner, body: &mut Map<String, Value>) -> Result<
     Ok(())
 }
 
+/// 估算内容 token 数并校验是否达到模型的最小缓存要求，返回估算值。
+///
+/// 含 `file_data` 的内容无法在本地估算，直接放行交由服务端校验。
+///
+/// # Errors
+/// 当估算值低于 [`min_cache_tokens`] 时返回错误。
+pub fn check_cacheable_tokens(
+    model: &str,
+    contents: &[Content],
+    estimator: &impl TokenEstimator,
+) -> Result<usize> {
+    let estimated = estimator.estimate_tokens(contents);
+    let has_file_data = contents
+        .iter()
+        .flat_map(|content| &content.parts)
+        .any(|part| matches!(part.kind, PartKind::FileData { .. }));
+    let minimum = min_cache_tokens(model);
+    if !has_file_data && estimated < minimum {
+        return Err(Error::InvalidConfig {
+            message: format!(
+                "Cached content for {model} needs at least {minimum} tokens, \
+                 but the contents are estimated at {estimated}"
+            ),
+        });
+    }
+    Ok(estimated)
+}
+
 fn apply_http_options(
     mut request: reqwest::RequestBuilder,
     http_options: Option<&rust_genai_types::http::HttpOptions>,
@@ -424,6 +520,9 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
@@ -459,6 +558,50 @@ mod tests {
     use wiremock::matchers::{method, path, query_param, query_param_is_missing};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    #[tokio::test]
+    async fn test_create_from_contents_rejects_short_content_before_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let caches = Caches::new(Arc::new(test_client_inner_with_base(
+            Backend::GeminiApi,
+            &server.uri(),
+            "v1beta",
+        )));
+
+        let err = caches
+            .create_from_contents(
+                "gemini-2.5-flash",
+                vec![Content::text("too short to cache")],
+                Duration::from_secs(300),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidConfig { ref message } if message.contains("at least 1024"))
+        );
+    }
+
+    #[test]
+    fn test_check_cacheable_tokens() {
+        let long = vec![Content::text("a".repeat(4096 * 4))];
+        assert_eq!(
+            check_cacheable_tokens("gemini-2.5-pro", &long, &SimpleTokenEstimator).unwrap(),
+            4096
+        );
+        let file = vec![Content::from_parts(
+            vec![rust_genai_types::content::Part::file_data(
+                "gs://bucket/video.mp4",
+                "video/mp4",
+            )],
+            rust_genai_types::content::Role::User,
+        )];
+        check_cacheable_tokens("gemini-2.5-pro", &file, &SimpleTokenEstimator).unwrap();
+    }
+
     #[test]
     fn test_normalize_cached_content_name_gemini() {
         let inner = test_client_inner(Backend::GeminiApi);
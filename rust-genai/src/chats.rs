@@ -133,7 +133,7 @@ impl ChatSession {
         let history_ref = self.history.clone();
         let (tx, rx) = tokio::sync::mpsc::channel(8);
 
-        tokio::spawn(async move {
+        crate::rt::spawn(async move {
             let mut stream = stream;
             let mut last_content: Option<Content> = None;
 
@@ -203,17 +203,7 @@ impl ChatSession {
             )
             .await?;
 
-        if let Some(afc_history) = response.automatic_function_calling_history.clone() {
-            let mut history = self.history.write().await;
-            *history = afc_history;
-        }
-
-        if let Some(candidate) = response.candidates.first() {
-            if let Some(content) = &candidate.content {
-                let mut history = self.history.write().await;
-                history.push(content.clone());
-            }
-        }
+        response.extend_history(&mut *self.history.write().await);
 
         Ok(response)
     }
@@ -249,7 +239,7 @@ impl ChatSession {
         let history_ref = self.history.clone();
         let (tx, rx) = tokio::sync::mpsc::channel(8);
 
-        tokio::spawn(async move {
+        crate::rt::spawn(async move {
             let mut stream = stream;
             let mut last_content: Option<Content> = None;
             let mut last_afc_history: Option<Vec<Content>> = None;
@@ -3,19 +3,35 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, LazyLock};
-#[cfg(feature = "tracing")]
-use std::time::Instant;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
-use reqwest::{Client as HttpClient, Proxy};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, USER_AGENT};
+use reqwest::Client as HttpClient;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+#[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::OnceCell;
+#[cfg(feature = "tracing")]
+use web_time::Instant;
+use web_time::{SystemTime, UNIX_EPOCH};
 
+#[cfg(not(target_arch = "wasm32"))]
 use crate::auth::OAuthTokenProvider;
 use crate::error::{Error, Result};
+use crate::token_cache::TokenCountCache;
+#[cfg(target_arch = "wasm32")]
+use crate::transport::FetchTransport;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::transport::ReqwestTransport;
+use crate::transport::{
+    DefaultHeadersTransport, RateLimitedTransport, RequestSigner, Response, Transport,
+    TransportRequest,
+};
+#[cfg(not(target_arch = "wasm32"))]
 use google_cloud_auth::credentials::{
     Builder as AuthBuilder, CacheableResource, Credentials as GoogleCredentials,
 };
+#[cfg(not(target_arch = "wasm32"))]
 use http::Extensions;
 use rust_genai_types::http::HttpRetryOptions;
 
@@ -37,8 +53,31 @@ pub(crate) struct ClientInner {
     pub config: ClientConfig,
     pub api_client: ApiClient,
     pub(crate) auth_provider: Option<AuthProvider>,
+    pub(crate) transport: Arc<dyn Transport>,
+    pub(crate) token_count_cache: Option<TokenCountCache>,
+    pub(crate) request_signer: Option<Arc<dyn RequestSigner>>,
+    pub(crate) response_parse_hook: Option<ResponseParseHook>,
+    pub(crate) raw_body_in_parse_errors: bool,
+    pub(crate) app_name: Option<String>,
+    pub(crate) validate_media_inputs: bool,
+    pub(crate) validate_input_length: bool,
+    pub(crate) validate_model_capabilities: bool,
+}
+
+/// 响应反序列化失败时传给钩子的上下文。
+#[derive(Debug)]
+pub struct ResponseParseFailure<'a> {
+    /// 产生该响应的 API 方法（如 `generateContent`）。
+    pub method: &'a str,
+    /// 原始响应体（JSON 文本；流式响应为出错的单个 SSE 事件数据）。
+    pub raw_body: &'a str,
+    /// 反序列化错误。
+    pub error: &'a serde_json::Error,
 }
 
+/// 响应反序列化失败钩子。
+pub type ResponseParseHook = Arc<dyn Fn(&ResponseParseFailure<'_>) + Send + Sync>;
+
 /// 客户端配置。
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
@@ -54,6 +93,25 @@ pub struct ClientConfig {
     pub credentials: Credentials,
     /// OAuth scopes（服务账号/ADC 使用）。
     pub auth_scopes: Vec<String>,
+    /// 默认 labels（仅 Vertex AI），与请求级 labels 合并。
+    pub default_labels: HashMap<String, String>,
+}
+
+impl ClientConfig {
+    /// 合并默认 labels 与请求级 labels，键冲突时请求级优先。
+    ///
+    /// Gemini API 不支持 labels，因此该后端不会注入默认 labels。
+    pub(crate) fn merge_labels(
+        &self,
+        labels: Option<HashMap<String, String>>,
+    ) -> Option<HashMap<String, String>> {
+        if self.backend != Backend::VertexAi || self.default_labels.is_empty() {
+            return labels;
+        }
+        let mut merged = self.default_labels.clone();
+        merged.extend(labels.unwrap_or_default());
+        Some(merged)
+    }
 }
 
 /// 后端选择。
@@ -83,6 +141,27 @@ pub struct VertexConfig {
     pub project: String,
     pub location: String,
     pub credentials: Option<VertexCredentials>,
+    /// 自定义 API host（如私有端点 `my-psc.example.com`）；为空时按 `location` 推导。
+    pub api_host: Option<String>,
+}
+
+impl VertexConfig {
+    /// 根据 `api_host` 或 `location` 推导 base URL：`global`（或未设置区域）使用
+    /// `aiplatform.googleapis.com`，其余区域使用 `{location}-aiplatform.googleapis.com`。
+    #[must_use]
+    pub fn base_url(&self) -> String {
+        if let Some(host) = self.api_host.as_deref().filter(|host| !host.is_empty()) {
+            return if host.contains("://") {
+                normalize_base_url(host)
+            } else {
+                format!("https://{}/", host.trim_end_matches('/'))
+            };
+        }
+        match self.location.as_str() {
+            "" | "global" => "https://aiplatform.googleapis.com/".to_string(),
+            location => format!("https://{location}-aiplatform.googleapis.com/"),
+        }
+    }
 }
 
 /// Vertex AI 认证占位。
@@ -98,6 +177,8 @@ pub struct HttpOptions {
     pub proxy: Option<String>,
     pub headers: HashMap<String, String>,
     pub base_url: Option<String>,
+    /// 文件上传基础 URL（默认 `{base_url}upload/`），用于会改写路径的代理。
+    pub upload_base_url: Option<String>,
     pub api_version: Option<String>,
     pub retry_options: Option<HttpRetryOptions>,
 }
@@ -130,14 +211,28 @@ impl Client {
         };
 
         let mut builder = if use_vertex {
-            let mut builder = Self::builder().backend(Backend::VertexAi);
-            if let Some(project) = vertex_project {
-                builder = builder.vertex_project(project);
-            }
-            if let Some(location) = vertex_location {
-                builder = builder.vertex_location(location);
-            }
-            builder
+            let (project, location) = match (vertex_project, vertex_location) {
+                (Some(project), Some(location)) => (project, location),
+                (project, location) => {
+                    let missing = [
+                        ("GOOGLE_CLOUD_PROJECT", project.is_none()),
+                        ("GOOGLE_CLOUD_LOCATION", location.is_none()),
+                    ]
+                    .into_iter()
+                    .filter_map(|(name, missing)| missing.then_some(name))
+                    .collect::<Vec<_>>()
+                    .join(" and ");
+                    return Err(Error::InvalidConfig {
+                        message: format!(
+                            "GOOGLE_GENAI_USE_VERTEXAI selects Vertex AI but {missing} not set"
+                        ),
+                    });
+                }
+            };
+            Self::builder()
+                .backend(Backend::VertexAi)
+                .vertex_project(project)
+                .vertex_location(location)
         } else {
             let api_key = api_key.ok_or_else(|| Error::InvalidConfig {
                 message: "GEMINI_API_KEY or GOOGLE_API_KEY not found".into(),
@@ -196,6 +291,17 @@ impl Client {
             .build()
     }
 
+    /// 预热客户端：发送一次轻量请求（列出 1 个模型）以建立 TLS 连接，
+    /// 并预先获取 OAuth/ADC 访问令牌，降低首个业务请求的延迟。
+    ///
+    /// 服务端返回的错误状态码会被忽略，只要连接与鉴权成功即视为预热完成。
+    ///
+    /// # Errors
+    /// 当鉴权令牌获取失败或网络请求失败时返回错误。
+    pub async fn warmup(&self) -> Result<()> {
+        self.models().warmup().await
+    }
+
     /// 创建 Builder。
     #[must_use]
     pub fn builder() -> ClientBuilder {
@@ -233,12 +339,14 @@ impl Client {
     }
 
     /// 访问 Live API。
+    #[cfg(not(target_arch = "wasm32"))]
     #[must_use]
     pub fn live(&self) -> crate::live::Live {
         crate::live::Live::new(self.inner.clone())
     }
 
     /// 访问 Live Music API。
+    #[cfg(not(target_arch = "wasm32"))]
     #[must_use]
     pub fn live_music(&self) -> crate::live_music::LiveMusic {
         crate::live_music::LiveMusic::new(self.inner.clone())
@@ -309,8 +417,20 @@ pub struct ClientBuilder {
     backend: Option<Backend>,
     vertex_project: Option<String>,
     vertex_location: Option<String>,
+    vertex_api_host: Option<String>,
     http_options: HttpOptions,
     auth_scopes: Option<Vec<String>>,
+    default_labels: HashMap<String, String>,
+    transport: Option<Arc<dyn Transport>>,
+    token_count_cache: Option<usize>,
+    rate_limit: Option<u32>,
+    request_signer: Option<Arc<dyn RequestSigner>>,
+    response_parse_hook: Option<ResponseParseHook>,
+    raw_body_in_parse_errors: bool,
+    app_name: Option<String>,
+    validate_media_inputs: bool,
+    validate_input_length: bool,
+    validate_model_capabilities: bool,
 }
 
 impl ClientBuilder {
@@ -349,6 +469,13 @@ impl ClientBuilder {
         self
     }
 
+    /// 设置 Vertex AI API host（默认按区域推导，`global` 使用 `aiplatform.googleapis.com`）。
+    #[must_use]
+    pub fn vertex_api_host(mut self, host: impl Into<String>) -> Self {
+        self.vertex_api_host = Some(host.into());
+        self
+    }
+
     /// 设置请求超时（秒）。
     #[must_use]
     pub const fn timeout(mut self, secs: u64) -> Self {
@@ -377,6 +504,13 @@ impl ClientBuilder {
         self
     }
 
+    /// 设置文件上传基础 URL（替代默认的 `{base_url}upload/`）。
+    #[must_use]
+    pub fn upload_base_url(mut self, upload_base_url: impl Into<String>) -> Self {
+        self.http_options.upload_base_url = Some(upload_base_url.into());
+        self
+    }
+
     /// 设置 API 版本。
     #[must_use]
     pub fn api_version(mut self, api_version: impl Into<String>) -> Self {
@@ -398,6 +532,122 @@ impl ClientBuilder {
         self
     }
 
+    /// 设置默认 labels（仅 Vertex AI），会与每次 `generate_content` 请求的 labels 合并，
+    /// 键冲突时请求级 labels 优先。
+    #[must_use]
+    pub fn default_labels<I, K, V>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.default_labels.extend(
+            labels
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
+    /// 设置自定义 HTTP 传输层（默认使用 reqwest）。
+    #[must_use]
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// 启用 `count_tokens` 结果的内存 LRU 缓存，最多保留 `capacity` 条。
+    ///
+    /// 以完整请求 URL（含模型）与序列化后的请求体（contents 与配置）作为键，逐字节比较命中。
+    #[must_use]
+    pub const fn token_count_cache(mut self, capacity: usize) -> Self {
+        self.token_count_cache = Some(capacity);
+        self
+    }
+
+    /// 限制所有请求（含重试）的速率：每分钟最多 `requests_per_minute` 个，超出的请求在
+    /// 传输层排队等待而不是触发 429。
+    #[must_use]
+    pub const fn rate_limit(mut self, requests_per_minute: u32) -> Self {
+        self.rate_limit = Some(requests_per_minute);
+        self
+    }
+
+    /// 设置请求签名器（如网关要求的 HMAC 签名），每次发送前以方法、URL 与请求体调用，
+    /// 返回的 headers 会加入请求。与 API Key / OAuth 鉴权相互独立。
+    #[must_use]
+    pub fn request_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.request_signer = Some(signer);
+        self
+    }
+
+    /// 注册响应反序列化失败钩子，以原始响应体与错误调用，便于记录上游的未知格式。
+    ///
+    /// 未注册时仍可通过环境变量 `RUST_GENAI_DEBUG_RESPONSE` 将原始响应打印到 stderr。
+    #[must_use]
+    pub fn on_response_parse_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ResponseParseFailure<'_>) + Send + Sync + 'static,
+    {
+        self.response_parse_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// 响应反序列化失败时返回附带原始响应体片段的 `Error::Parse`，便于 CI 日志与测试断言定位
+    /// 新增字段等问题。环境变量 `RUST_GENAI_DEBUG_RESPONSE` 只打印到 stderr，不会启用该选项。
+    #[must_use]
+    pub const fn raw_body_in_parse_errors(mut self, enabled: bool) -> Self {
+        self.raw_body_in_parse_errors = enabled;
+        self
+    }
+
+    /// 在发送 Imagen/Veo 请求前校验内联图像/视频的字节数与图像尺寸（默认关闭）。
+    ///
+    /// 限制见 [`crate::models::MAX_INLINE_IMAGE_BYTES`] 等常量，超限时返回 `Error::InvalidConfig`。
+    #[must_use]
+    pub const fn validate_media_inputs(mut self, enabled: bool) -> Self {
+        self.validate_media_inputs = enabled;
+        self
+    }
+
+    /// 在发送 `generate_content` / `generate_content_stream` 请求前执行
+    /// [`Models::check_input_length`](crate::models::Models::check_input_length) 预检（默认关闭）。
+    ///
+    /// 每次生成前会多发一次 `countTokens` 请求（可配合 [`Self::token_count_cache`] 复用），
+    /// 超过模型输入上限时返回 `Error::InputTooLong` 且不发送生成请求。
+    #[must_use]
+    pub const fn validate_input_length(mut self, enabled: bool) -> Self {
+        self.validate_input_length = enabled;
+        self
+    }
+
+    /// 在发送 `generate_content` 请求前按模型名校验图像/音频输出模态与 `seed` / `logprobs`
+    /// 等特性是否受支持（默认关闭）。
+    ///
+    /// 能力表基于模型名推断，可能落后于服务端；关闭时这些字段原样交给 API 判断。
+    #[must_use]
+    pub const fn validate_model_capabilities(mut self, enabled: bool) -> Self {
+        self.validate_model_capabilities = enabled;
+        self
+    }
+
+    /// 设置应用标识（如 `my-app/1.2.0`），追加到 `User-Agent` 与 `x-goog-api-client` 的 SDK 标识之后。
+    #[must_use]
+    pub fn app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = Some(app_name.into());
+        self
+    }
+
+    /// 使用进程内 handler 代替真实 HTTP 请求（需要 `testing` feature）。
+    #[cfg(feature = "testing")]
+    #[must_use]
+    pub fn mock_handler<F>(self, handler: F) -> Self
+    where
+        F: Fn(crate::testing::MockRequest) -> crate::testing::MockResponse + Send + Sync + 'static,
+    {
+        self.transport(crate::testing::MockTransport::new(handler))
+    }
+
     /// 构建客户端。
     ///
     /// # Errors
@@ -409,8 +659,20 @@ impl ClientBuilder {
             backend,
             vertex_project,
             vertex_location,
+            vertex_api_host,
             http_options,
             auth_scopes,
+            default_labels,
+            transport,
+            token_count_cache,
+            rate_limit,
+            request_signer,
+            response_parse_hook,
+            raw_body_in_parse_errors,
+            app_name,
+            validate_media_inputs,
+            validate_input_length,
+            validate_model_capabilities,
         } = self;
 
         let backend = Self::resolve_backend(
@@ -424,15 +686,33 @@ impl ClientBuilder {
             vertex_location.as_deref(),
         )?;
         let credentials = Self::resolve_credentials(backend, api_key.as_deref(), credentials)?;
-        let headers = Self::build_headers(&http_options, backend, &credentials)?;
-        let http = Self::build_http_client(&http_options, headers)?;
+        let app_name = app_name.filter(|name| !name.trim().is_empty());
+        let headers =
+            Self::build_headers(&http_options, backend, &credentials, app_name.as_deref())?;
+        let http = Self::build_http_client(&http_options, headers.clone())?;
+        let transport: Arc<dyn Transport> = match transport {
+            Some(transport) => Arc::new(DefaultHeadersTransport::new(transport, headers)),
+            None => Self::default_transport(&http_options, &http, headers),
+        };
+        let transport: Arc<dyn Transport> = match rate_limit {
+            Some(0) => {
+                return Err(Error::InvalidConfig {
+                    message: "rate_limit requests_per_minute must be greater than 0".into(),
+                })
+            }
+            Some(requests_per_minute) => {
+                Arc::new(RateLimitedTransport::new(transport, requests_per_minute))
+            }
+            None => transport,
+        };
 
         let auth_scopes = auth_scopes.unwrap_or_else(|| default_auth_scopes(backend));
         let api_key = match &credentials {
             Credentials::ApiKey(key) => Some(key.clone()),
             _ => None,
         };
-        let vertex_config = Self::build_vertex_config(backend, vertex_project, vertex_location)?;
+        let vertex_config =
+            Self::build_vertex_config(backend, vertex_project, vertex_location, vertex_api_host)?;
         let config = ClientConfig {
             api_key,
             backend,
@@ -440,6 +720,7 @@ impl ClientBuilder {
             http_options,
             credentials: credentials.clone(),
             auth_scopes,
+            default_labels,
         };
 
         let auth_provider = build_auth_provider(&credentials)?;
@@ -451,6 +732,15 @@ impl ClientBuilder {
                 config,
                 api_client,
                 auth_provider,
+                transport,
+                token_count_cache: token_count_cache.map(TokenCountCache::new),
+                request_signer,
+                response_parse_hook,
+                raw_body_in_parse_errors,
+                app_name,
+                validate_media_inputs,
+                validate_input_length,
+                validate_model_capabilities,
             }),
         })
     }
@@ -524,6 +814,7 @@ impl ClientBuilder {
         http_options: &HttpOptions,
         backend: Backend,
         credentials: &Credentials,
+        app_name: Option<&str>,
     ) -> Result<HeaderMap> {
         let mut headers = HeaderMap::new();
         for (key, value) in &http_options.headers {
@@ -537,6 +828,16 @@ impl ClientBuilder {
             headers.insert(name, value);
         }
 
+        if !headers.contains_key(USER_AGENT) {
+            let user_agent =
+                HeaderValue::from_str(&sdk_client_identifier(app_name)).map_err(|_| {
+                    Error::InvalidConfig {
+                        message: format!("Invalid app_name: {}", app_name.unwrap_or_default()),
+                    }
+                })?;
+            headers.insert(USER_AGENT, user_agent);
+        }
+
         if backend == Backend::GeminiApi {
             let api_key = match credentials {
                 Credentials::ApiKey(key) => key.as_str(),
@@ -556,6 +857,30 @@ impl ClientBuilder {
         Ok(headers)
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default_transport(
+        _http_options: &HttpOptions,
+        http: &HttpClient,
+        _headers: HeaderMap,
+    ) -> Arc<dyn Transport> {
+        Arc::new(ReqwestTransport::new(http.clone()))
+    }
+
+    /// The browser transport does not see reqwest's client defaults, so they are re-applied.
+    #[cfg(target_arch = "wasm32")]
+    fn default_transport(
+        http_options: &HttpOptions,
+        _http: &HttpClient,
+        headers: HeaderMap,
+    ) -> Arc<dyn Transport> {
+        let mut fetch = FetchTransport::new();
+        if let Some(timeout) = http_options.timeout {
+            fetch = fetch.with_timeout(Duration::from_secs(timeout));
+        }
+        Arc::new(DefaultHeadersTransport::new(Arc::new(fetch), headers))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     fn build_http_client(http_options: &HttpOptions, headers: HeaderMap) -> Result<HttpClient> {
         let mut http_builder = HttpClient::builder();
         if let Some(timeout) = http_options.timeout {
@@ -576,10 +901,26 @@ impl ClientBuilder {
         Ok(http_builder.build()?)
     }
 
+    /// Under `wasm32` the client only builds requests; timeouts are applied by the transport.
+    #[cfg(target_arch = "wasm32")]
+    fn build_http_client(http_options: &HttpOptions, headers: HeaderMap) -> Result<HttpClient> {
+        if http_options.proxy.is_some() {
+            return Err(Error::InvalidConfig {
+                message: "Proxies are not supported on wasm32".into(),
+            });
+        }
+        let mut http_builder = HttpClient::builder();
+        if !headers.is_empty() {
+            http_builder = http_builder.default_headers(headers);
+        }
+        Ok(http_builder.build()?)
+    }
+
     fn build_vertex_config(
         backend: Backend,
         vertex_project: Option<String>,
         vertex_location: Option<String>,
+        api_host: Option<String>,
     ) -> Result<Option<VertexConfig>> {
         if backend != Backend::VertexAi {
             return Ok(None);
@@ -594,10 +935,12 @@ impl ClientBuilder {
             project,
             location,
             credentials: None,
+            api_host,
         }))
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn build_auth_provider(credentials: &Credentials) -> Result<Option<AuthProvider>> {
     match credentials {
         Credentials::ApiKey(_) => Ok(None),
@@ -613,12 +956,33 @@ fn build_auth_provider(credentials: &Credentials) -> Result<Option<AuthProvider>
     }
 }
 
+#[cfg(target_arch = "wasm32")]
+fn build_auth_provider(credentials: &Credentials) -> Result<Option<AuthProvider>> {
+    match credentials {
+        Credentials::ApiKey(_) => Ok(None),
+        Credentials::OAuth { .. } | Credentials::ApplicationDefault => Err(Error::InvalidConfig {
+            message: "Only API key credentials are supported on wasm32".into(),
+        }),
+    }
+}
+
 #[derive(Clone)]
 pub(crate) enum AuthProvider {
+    #[cfg(not(target_arch = "wasm32"))]
     OAuth(Arc<OAuthTokenProvider>),
+    #[cfg(not(target_arch = "wasm32"))]
     ApplicationDefault(Arc<OnceCell<Arc<GoogleCredentials>>>),
 }
 
+#[cfg(target_arch = "wasm32")]
+impl AuthProvider {
+    #[allow(clippy::unused_async)]
+    async fn headers(&self, _scopes: &[&str]) -> Result<HeaderMap> {
+        match *self {}
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 impl AuthProvider {
     async fn headers(&self, scopes: &[&str]) -> Result<HeaderMap> {
         match self {
@@ -679,6 +1043,66 @@ static DEFAULT_HTTP_RETRY_OPTIONS: LazyLock<HttpRetryOptions> =
         http_status_codes: Some(DEFAULT_RETRY_HTTP_STATUS_CODES.to_vec()),
     });
 
+/// 创建类请求（batches、tunings、caches）携带的请求 ID 头。
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 附加请求 ID：优先使用 `HttpOptions.request_id`，否则生成 UUID v4。
+///
+/// 重试时复用同一请求模板，因此每次尝试携带相同的 ID。
+pub(crate) fn apply_request_id(
+    request: reqwest::RequestBuilder,
+    http_options: Option<&rust_genai_types::http::HttpOptions>,
+) -> reqwest::RequestBuilder {
+    let request_id = http_options
+        .and_then(|options| options.request_id.clone())
+        .unwrap_or_else(generate_request_id);
+    request.header(REQUEST_ID_HEADER, request_id)
+}
+
+fn generate_request_id() -> String {
+    use std::hash::{BuildHasher, Hasher};
+
+    // Randomly keyed std hashers stand in for an RNG dependency.
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let mut bytes = [0u8; 16];
+    for chunk in bytes.chunks_mut(8) {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+const MAX_RAW_BODY_IN_ERROR: usize = 4096;
+
+fn debug_response_enabled() -> bool {
+    std::env::var_os("RUST_GENAI_DEBUG_RESPONSE").is_some()
+}
+
+fn truncate_raw_body(raw_body: &str) -> String {
+    if raw_body.len() <= MAX_RAW_BODY_IN_ERROR {
+        return raw_body.to_string();
+    }
+    let mut end = MAX_RAW_BODY_IN_ERROR;
+    while !raw_body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... ({} bytes total)", &raw_body[..end], raw_body.len())
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct RetryMetadata {
     pub attempts: u32,
@@ -686,11 +1110,53 @@ pub(crate) struct RetryMetadata {
 }
 
 impl ClientInner {
+    /// 解析失败时是否需要保留原始响应体（已注册钩子、启用了原始响应体附带或设置了调试环境变量）。
+    pub(crate) fn keeps_raw_response(&self) -> bool {
+        self.response_parse_hook.is_some()
+            || self.raw_body_in_parse_errors
+            || debug_response_enabled()
+    }
+
+    /// 处理响应反序列化失败：调用已注册的钩子，并在启用 `tracing` 时记录警告。
+    ///
+    /// 设置 `RUST_GENAI_DEBUG_RESPONSE` 时将原始响应体打印到 stderr（不影响返回的错误类型）。
+    /// 启用原始响应体附带时返回包含响应体片段的 `Error::Parse`，否则返回原始反序列化错误。
+    pub(crate) fn response_parse_error(
+        &self,
+        method: &str,
+        raw_body: Option<&str>,
+        error: serde_json::Error,
+    ) -> Error {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(method, error = %error, "failed to deserialize response");
+        if let Some(raw_body) = raw_body.filter(|_| debug_response_enabled()) {
+            eprintln!(
+                "rust-genai: failed to parse {method} response: {error}; raw body: {raw_body}"
+            );
+        }
+        if let (Some(hook), Some(raw_body)) = (&self.response_parse_hook, raw_body) {
+            hook(&ResponseParseFailure {
+                method,
+                raw_body,
+                error: &error,
+            });
+        }
+        match raw_body {
+            Some(raw_body) if self.raw_body_in_parse_errors => Error::Parse {
+                message: format!(
+                    "failed to parse {method} response: {error}; raw body: {}",
+                    truncate_raw_body(raw_body)
+                ),
+            },
+            _ => error.into(),
+        }
+    }
+
     /// 发送请求并自动注入鉴权头。
     ///
     /// # Errors
     /// 当请求构建、鉴权头获取或网络请求失败时返回错误。
-    pub async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    pub async fn send(&self, request: reqwest::RequestBuilder) -> Result<Response> {
         self.send_with_http_options(request, None).await
     }
 
@@ -702,7 +1168,7 @@ impl ClientInner {
         &self,
         request: reqwest::RequestBuilder,
         request_http_options: Option<&rust_genai_types::http::HttpOptions>,
-    ) -> Result<reqwest::Response> {
+    ) -> Result<Response> {
         let retry_options = request_http_options
             .and_then(|options| options.retry_options.as_ref())
             .or(self.config.http_options.retry_options.as_ref())
@@ -713,16 +1179,21 @@ impl ClientInner {
             .await
     }
 
-    async fn execute_once(&self, mut request: reqwest::Request) -> Result<reqwest::Response> {
+    async fn execute_once(&self, mut request: reqwest::Request) -> Result<Response> {
         self.prepare_request(&mut request).await?;
-        Ok(self.http.execute(request).await?)
+        let url = request.url().clone();
+        let response = self
+            .transport
+            .execute(TransportRequest::from_reqwest(request))
+            .await?;
+        response.into_response(url)
     }
 
     async fn execute_with_retry(
         &self,
         request_template: reqwest::Request,
         retry_options: &HttpRetryOptions,
-    ) -> Result<reqwest::Response> {
+    ) -> Result<Response> {
         #[cfg(feature = "tracing")]
         let trace_request = TraceRequestInfo::new(self.config.backend, &request_template);
 
@@ -817,7 +1288,7 @@ impl ClientInner {
             // Drop the response before retrying to release the connection back to the pool.
             drop(response);
             if delay > 0.0 {
-                tokio::time::sleep(Duration::from_secs_f64(delay)).await;
+                crate::rt::sleep(Duration::from_secs_f64(delay)).await;
             }
         }
 
@@ -839,10 +1310,17 @@ impl ClientInner {
             }
         }
         if self.config.backend == Backend::GeminiApi {
-            append_sdk_usage_header(request.headers_mut())?;
+            append_sdk_usage_header(request.headers_mut(), self.app_name.as_deref())?;
         }
         #[cfg(feature = "mcp")]
         crate::mcp::append_mcp_usage_header(request.headers_mut())?;
+        if let Some(signer) = &self.request_signer {
+            let body = request.body().and_then(reqwest::Body::as_bytes);
+            let headers = signer.sign(request.method(), request.url(), body)?;
+            for (name, value) in &headers {
+                request.headers_mut().insert(name.clone(), value.clone());
+            }
+        }
         Ok(())
     }
 
@@ -857,7 +1335,15 @@ impl ClientInner {
     }
 }
 
-fn append_sdk_usage_header(headers: &mut HeaderMap) -> Result<()> {
+/// SDK 标识（`User-Agent` / `x-goog-api-client`），可附加应用标识。
+fn sdk_client_identifier(app_name: Option<&str>) -> String {
+    match app_name {
+        Some(app_name) => format!("{SDK_USAGE_HEADER_VALUE} {}", app_name.trim()),
+        None => SDK_USAGE_HEADER_VALUE.to_string(),
+    }
+}
+
+fn append_sdk_usage_header(headers: &mut HeaderMap, app_name: Option<&str>) -> Result<()> {
     let header_name = HeaderName::from_static(X_GOOG_API_CLIENT_HEADER);
     let existing_values = headers
         .get_all(&header_name)
@@ -877,12 +1363,13 @@ fn append_sdk_usage_header(headers: &mut HeaderMap) -> Result<()> {
         .filter(|value| !value.is_empty())
         .collect::<Vec<_>>()
         .join(" ");
-    let combined = if existing.contains(SDK_USAGE_HEADER_VALUE) {
+    let identifier = sdk_client_identifier(app_name);
+    let combined = if existing.contains(&identifier) {
         existing
     } else if existing.is_empty() {
-        SDK_USAGE_HEADER_VALUE.to_string()
+        identifier
     } else {
-        format!("{SDK_USAGE_HEADER_VALUE} {existing}")
+        format!("{identifier} {existing}")
     };
     let value = HeaderValue::from_str(&combined).map_err(|_| Error::InvalidConfig {
         message: "Invalid x-goog-api-client header value".into(),
@@ -909,7 +1396,7 @@ fn env_flag(name: &str) -> Option<bool> {
     }
 }
 
-fn attach_retry_metadata(response: &mut reqwest::Response, attempts: u32, retryable: bool) {
+fn attach_retry_metadata(response: &mut Response, attempts: u32, retryable: bool) {
     response.extensions_mut().insert(RetryMetadata {
         attempts,
         retryable,
@@ -917,7 +1404,7 @@ fn attach_retry_metadata(response: &mut reqwest::Response, attempts: u32, retrya
 }
 
 fn attach_retry_metadata_for_codes(
-    response: &mut reqwest::Response,
+    response: &mut Response,
     attempts: u32,
     retryable_codes: &[u16],
 ) {
@@ -936,12 +1423,9 @@ fn retry_after_delay_secs(headers: &HeaderMap) -> Option<f64> {
         .ok()
         .map(|delay| delay.max(0.0))
         .or_else(|| {
-            httpdate::parse_http_date(retry_after).ok().map(|deadline| {
-                deadline
-                    .duration_since(SystemTime::now())
-                    .unwrap_or_default()
-                    .as_secs_f64()
-            })
+            httpdate::parse_http_date(retry_after)
+                .ok()
+                .map(|deadline| crate::rt::duration_until(deadline).as_secs_f64())
         })
 }
 
@@ -1069,11 +1553,7 @@ impl TraceAttemptInfo {
 }
 
 #[cfg(feature = "tracing")]
-fn emit_request_trace(
-    request: &TraceRequestInfo,
-    attempt: TraceAttemptInfo,
-    response: &reqwest::Response,
-) {
+fn emit_request_trace(request: &TraceRequestInfo, attempt: TraceAttemptInfo, response: &Response) {
     tracing::debug!(
         target: "rust_genai::http",
         backend = request.backend,
@@ -1092,6 +1572,7 @@ fn emit_request_trace(
 
 pub(crate) struct ApiClient {
     pub base_url: String,
+    pub upload_base_url: String,
     pub api_version: String,
 }
 
@@ -1100,17 +1581,10 @@ impl ApiClient {
     pub fn new(config: &ClientConfig) -> Self {
         let base_url = config.http_options.base_url.as_deref().map_or_else(
             || match config.backend {
-                Backend::VertexAi => {
-                    let location = config
-                        .vertex_config
-                        .as_ref()
-                        .map_or("", |cfg| cfg.location.as_str());
-                    if location.is_empty() {
-                        "https://aiplatform.googleapis.com/".to_string()
-                    } else {
-                        format!("https://{location}-aiplatform.googleapis.com/")
-                    }
-                }
+                Backend::VertexAi => config.vertex_config.as_ref().map_or_else(
+                    || "https://aiplatform.googleapis.com/".to_string(),
+                    VertexConfig::base_url,
+                ),
                 Backend::GeminiApi => "https://generativelanguage.googleapis.com/".to_string(),
             },
             normalize_base_url,
@@ -1126,11 +1600,33 @@ impl ApiClient {
                     Backend::GeminiApi => "v1beta".to_string(),
                 });
 
+        let upload_base_url = config
+            .http_options
+            .upload_base_url
+            .as_deref()
+            .map_or_else(|| format!("{base_url}upload/"), normalize_base_url);
+
         Self {
             base_url,
+            upload_base_url,
             api_version,
         }
     }
+
+    /// 文件上传基础 URL：请求级 `upload_base_url` > 请求级 `base_url` + `upload/` > 客户端配置。
+    pub fn upload_base(
+        &self,
+        http_options: Option<&rust_genai_types::http::HttpOptions>,
+    ) -> String {
+        if let Some(upload_base_url) = http_options.and_then(|opts| opts.upload_base_url.as_deref())
+        {
+            return normalize_base_url(upload_base_url);
+        }
+        if let Some(base_url) = http_options.and_then(|opts| opts.base_url.as_deref()) {
+            return format!("{base_url}upload/");
+        }
+        self.upload_base_url.clone()
+    }
 }
 
 fn normalize_base_url(base_url: &str) -> String {
@@ -1179,6 +1675,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vertex_host_follows_location() {
+        let regional = Client::new_vertex("my-project", "us-central1").unwrap();
+        assert_eq!(
+            regional.inner.api_client.base_url,
+            "https://us-central1-aiplatform.googleapis.com/"
+        );
+
+        let global = Client::new_vertex("my-project", "global").unwrap();
+        assert_eq!(
+            global.inner.api_client.base_url,
+            "https://aiplatform.googleapis.com/"
+        );
+
+        let custom = Client::builder()
+            .vertex_project("my-project")
+            .vertex_location("us-central1")
+            .vertex_api_host("my-psc.example.com")
+            .build()
+            .unwrap();
+        assert_eq!(
+            custom.inner.api_client.base_url,
+            "https://my-psc.example.com/"
+        );
+    }
+
     #[test]
     fn test_base_url_normalization() {
         let client = Client::builder()
@@ -1413,8 +1935,25 @@ mod tests {
                 ("GOOGLE_API_KEY", None),
             ],
             || {
-                let result = Client::from_env();
-                assert!(matches!(result, Err(Error::InvalidConfig { .. })));
+                let err = Client::from_env().err().unwrap();
+                assert!(matches!(err, Error::InvalidConfig { .. }));
+                assert!(err.to_string().contains("GOOGLE_CLOUD_LOCATION not set"));
+                assert!(!err.to_string().contains("GOOGLE_CLOUD_PROJECT"));
+            },
+        );
+        with_env(
+            &[
+                ("GOOGLE_GENAI_USE_VERTEXAI", Some("1")),
+                ("GOOGLE_CLOUD_PROJECT", None),
+                ("GOOGLE_CLOUD_LOCATION", None),
+                ("GEMINI_API_KEY", Some("env-key")),
+                ("GOOGLE_API_KEY", None),
+            ],
+            || {
+                let err = Client::from_env().err().unwrap();
+                assert!(err
+                    .to_string()
+                    .contains("GOOGLE_CLOUD_PROJECT and GOOGLE_CLOUD_LOCATION not set"));
             },
         );
     }
@@ -1567,6 +2106,77 @@ mod tests {
         assert!(retry_metadata.retryable);
     }
 
+    #[tokio::test]
+    async fn test_warmup_prefetches_oauth_token_once() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "access-1",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1beta/models"))
+            .and(wiremock::matchers::query_param("pageSize", "1"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Bearer access-1",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [{"name": "models/gemini-2.5-flash"}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1beta/models"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Bearer access-1",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": []
+            })))
+            .mount(&server)
+            .await;
+
+        let dir = tempdir().unwrap();
+        let secret_path = dir.path().join("client_secret.json");
+        let token_path = dir.path().join("token.json");
+        let token_uri = format!("{}/token", server.uri());
+        std::fs::write(
+            &secret_path,
+            serde_json::json!({
+                "installed": {
+                    "client_id": "client-a",
+                    "client_secret": "secret-a",
+                    "token_uri": token_uri
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        std::fs::write(
+            &token_path,
+            serde_json::json!({"refresh_token": "refresh-1"}).to_string(),
+        )
+        .unwrap();
+
+        let client = Client::builder()
+            .credentials(Credentials::OAuth {
+                client_secret_path: secret_path,
+                token_cache_path: Some(token_path),
+            })
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        client.warmup().await.unwrap();
+        client.models().list().await.unwrap();
+    }
+
     #[test]
     fn test_with_oauth_missing_client_secret_errors() {
         let dir = tempdir().unwrap();
@@ -1598,6 +2208,31 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_builder_default_labels_merge_with_call_labels() {
+        let client = Client::builder()
+            .vertex_project("proj")
+            .vertex_location("loc")
+            .default_labels([("team", "ml"), ("env", "prod")])
+            .build()
+            .unwrap();
+        let merged = client
+            .inner
+            .config
+            .merge_labels(Some([("env".to_string(), "dev".to_string())].into()))
+            .unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["team"], "ml");
+        assert_eq!(merged["env"], "dev");
+
+        let gemini = Client::builder()
+            .api_key("test-key")
+            .default_labels([("team", "ml")])
+            .build()
+            .unwrap();
+        assert!(gemini.inner.config.merge_labels(None).is_none());
+    }
+
     #[test]
     fn test_valid_proxy_is_accepted() {
         let client = Client::builder()
@@ -1699,7 +2334,7 @@ mod tests {
     #[test]
     fn test_append_sdk_usage_header() {
         let mut headers = HeaderMap::new();
-        append_sdk_usage_header(&mut headers).unwrap();
+        append_sdk_usage_header(&mut headers, None).unwrap();
         assert_eq!(
             headers
                 .get(X_GOOG_API_CLIENT_HEADER)
@@ -1715,8 +2350,8 @@ mod tests {
             HeaderName::from_static(X_GOOG_API_CLIENT_HEADER),
             HeaderValue::from_static("custom-client/1.0.0"),
         );
-        append_sdk_usage_header(&mut headers).unwrap();
-        append_sdk_usage_header(&mut headers).unwrap();
+        append_sdk_usage_header(&mut headers, None).unwrap();
+        append_sdk_usage_header(&mut headers, None).unwrap();
         assert_eq!(
             headers
                 .get(X_GOOG_API_CLIENT_HEADER)
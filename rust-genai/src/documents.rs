@@ -52,7 +52,7 @@ impl Documents {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<Document>().await?)
     }
@@ -88,7 +88,7 @@ impl Documents {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(())
     }
@@ -124,7 +124,7 @@ impl Documents {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let mut result = response.json::<ListDocumentsResponse>().await?;
@@ -276,6 +276,9 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
@@ -3,7 +3,7 @@
 use std::collections::{HashMap, VecDeque};
 use std::hash::{Hash, Hasher};
 use std::sync::{LazyLock, Mutex};
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
 
 use http::StatusCode;
 use serde_json::Value;
@@ -13,6 +13,10 @@ use thiserror::Error;
 use rmcp::service::ServiceError;
 
 use crate::client::RetryMetadata;
+use crate::transport::Response;
+use rust_genai_types::enums::{BlockedReason, FinishReason};
+use rust_genai_types::operations::Status;
+use rust_genai_types::response::GenerateContentResponse;
 
 const API_ERROR_METADATA_CAPACITY: usize = 4096;
 const API_ERROR_METADATA_MAX_TOTAL_BYTES: usize = 512 * 1024;
@@ -155,9 +159,28 @@ pub enum Error {
     #[error("Auth error: {message}")]
     Auth { message: String },
 
+    #[error("Automatic function calling stopped after {limit} remote calls")]
+    MaxToolCallsExceeded { limit: usize },
+
+    #[error("Input too long: {tokens} tokens exceeds the model limit of {limit}")]
+    InputTooLong { tokens: usize, limit: usize },
+
+    #[error("Output truncated: {message}")]
+    OutputTruncated { message: String },
+
+    #[error("Content blocked: {reason:?}")]
+    ContentBlocked { reason: ContentBlockReason },
+
+    #[error("Operation failed (code {}): {}", status.code, status.message)]
+    OperationFailed { status: Status },
+
     #[error("Channel closed")]
     ChannelClosed,
 
+    #[error("Cancelled")]
+    Cancelled,
+
+    #[cfg(not(target_arch = "wasm32"))]
     #[error("WebSocket error: {source}")]
     WebSocket {
         #[from]
@@ -170,6 +193,33 @@ pub enum Error {
         #[from]
         source: ServiceError,
     },
+
+    #[cfg(feature = "mcp")]
+    #[error("MCP reconnect failed after {attempts} attempts: {message}")]
+    McpReconnectFailed { attempts: u32, message: String },
+}
+
+/// Why a response was rejected by `error_on_blocked_content`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentBlockReason {
+    /// The prompt itself was blocked (`prompt_feedback.block_reason`).
+    Prompt(BlockedReason),
+    /// Generation stopped on a safety/recitation filter (`candidates[0].finish_reason`).
+    Finish(FinishReason),
+}
+
+impl ContentBlockReason {
+    /// Detects a blocked prompt or a filtered finish in `response`.
+    #[must_use]
+    pub fn from_response(response: &GenerateContentResponse) -> Option<Self> {
+        if let Some(reason) = response.blocked_reason() {
+            return Some(Self::Prompt(reason));
+        }
+        response
+            .finish_reason()
+            .filter(|reason| reason.is_blocked())
+            .map(Self::Finish)
+    }
 }
 
 impl Error {
@@ -191,7 +241,7 @@ impl Error {
     }
 
     pub(crate) async fn api_error_from_response(
-        response: reqwest::Response,
+        response: Response,
         retryable_override: Option<bool>,
     ) -> Self {
         let status = response.status().as_u16();
@@ -219,6 +269,59 @@ impl Error {
         Self::ApiError { status, message }
     }
 
+    /// 将流式响应中的 Google 错误信封（`{"error": {...}}`）转换为 API 错误。
+    ///
+    /// HTTP 状态已是 200，因此状态码取自 `error.code`（缺失时按 500 处理）。
+    pub(crate) fn api_error_from_stream_event(data: &str) -> Option<Self> {
+        let value = serde_json::from_str::<Value>(data).ok()?;
+        let error = value.get("error")?.as_object()?;
+        let status = error
+            .get("code")
+            .and_then(Value::as_u64)
+            .and_then(|code| u16::try_from(code).ok())
+            .filter(|code| StatusCode::from_u16(*code).is_ok())
+            .unwrap_or(500);
+        let (message, code, details) = parse_google_error(data, status);
+        set_api_metadata(
+            status,
+            &message,
+            ApiErrorMetadata {
+                code,
+                details,
+                body: Some(data.to_string()),
+                retryable: Some(default_retryable_status(status)),
+                ..Default::default()
+            },
+        );
+        Some(Self::ApiError { status, message })
+    }
+
+    /// 以请求路径（如 `/v1beta/models/gemini-2.5-flash`）作为上下文构建 API 错误，
+    /// 便于从日志定位失败的端点与模型。
+    pub(crate) async fn api_error_with_endpoint(response: Response) -> Self {
+        let endpoint = response.url().path().to_string();
+        Self::api_error_from_response(response, None)
+            .await
+            .with_context(endpoint)
+    }
+
+    /// 为 API 错误附加上下文（如端点或模型名），前缀到错误消息并保留状态码与元数据；
+    /// 其他错误原样返回。
+    #[must_use]
+    pub fn with_context(self, context: impl std::fmt::Display) -> Self {
+        match self {
+            Self::ApiError { status, message } => {
+                let metadata = api_metadata(status, &message);
+                let message = format!("{context}: {message}");
+                if let Some(metadata) = metadata {
+                    set_api_metadata(status, &message, metadata);
+                }
+                Self::ApiError { status, message }
+            }
+            other => other,
+        }
+    }
+
     fn api_metadata(&self) -> Option<ApiErrorMetadata> {
         match self {
             Self::ApiError { status, message } => api_metadata(*status, message),
@@ -373,12 +476,9 @@ fn retry_after_secs(headers: &reqwest::header::HeaderMap) -> Option<u64> {
         .trim();
 
     retry_after.parse::<u64>().ok().or_else(|| {
-        httpdate::parse_http_date(retry_after).ok().map(|deadline| {
-            deadline
-                .duration_since(SystemTime::now())
-                .unwrap_or_default()
-                .as_secs()
-        })
+        httpdate::parse_http_date(retry_after)
+            .ok()
+            .map(|deadline| crate::rt::duration_until(deadline).as_secs())
     })
 }
 
@@ -445,6 +545,23 @@ mod tests {
         assert_eq!(details, Some(json!([{"kind": "quota"}])));
     }
 
+    #[test]
+    fn api_error_from_stream_event_reads_envelope() {
+        let data = json!({
+            "error": {"code": 429, "message": "quota exceeded", "status": "RESOURCE_EXHAUSTED"}
+        })
+        .to_string();
+        let err = Error::api_error_from_stream_event(&data).unwrap();
+        assert_eq!(err.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(err.code().as_deref(), Some("RESOURCE_EXHAUSTED"));
+        assert!(err.is_rate_limited());
+
+        let err = Error::api_error_from_stream_event(r#"{"error": {"message": "boom"}}"#).unwrap();
+        assert_eq!(err.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(Error::api_error_from_stream_event(r#"{"candidates": []}"#).is_none());
+        assert!(Error::api_error_from_stream_event(r#"{"error": "text"}"#).is_none());
+    }
+
     #[test]
     fn parse_google_error_falls_back_to_body() {
         let body = "plain-text failure";
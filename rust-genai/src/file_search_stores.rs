@@ -1,5 +1,6 @@
 //! `FileSearchStores` API surface.
 
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
@@ -58,7 +59,7 @@ impl FileSearchStores {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<FileSearchStore>().await?)
     }
@@ -93,7 +94,7 @@ impl FileSearchStores {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<FileSearchStore>().await?)
     }
@@ -129,7 +130,7 @@ impl FileSearchStores {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(())
     }
@@ -163,7 +164,7 @@ impl FileSearchStores {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let mut result = response.json::<ListFileSearchStoresResponse>().await?;
@@ -245,6 +246,7 @@ impl FileSearchStores {
     ///
     /// # Errors
     /// 当文件无效、请求失败或响应解析失败时返回错误。
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn upload_to_file_search_store_from_path(
         &self,
         file_search_store_name: impl AsRef<str>,
@@ -369,7 +371,7 @@ impl FileSearchStores {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<ImportFileOperation>().await?)
     }
@@ -411,7 +413,7 @@ impl FileSearchStores {
             .send_with_http_options(request, http_options)
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
@@ -447,6 +449,7 @@ impl FileSearchStores {
         .await
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     async fn upload_reader(
         &self,
         upload_url: &str,
@@ -495,7 +498,7 @@ impl FileSearchStores {
             .send_with_http_options(request, http_options)
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let status = response
             .headers()
@@ -605,13 +608,11 @@ fn build_file_search_store_upload_url(
     store_name: &str,
     http_options: Option<&rust_genai_types::http::HttpOptions>,
 ) -> String {
-    let base = http_options
-        .and_then(|opts| opts.base_url.as_deref())
-        .unwrap_or(&inner.api_client.base_url);
+    let base = inner.api_client.upload_base(http_options);
     let version = http_options
         .and_then(|opts| opts.api_version.as_deref())
         .unwrap_or(&inner.api_client.api_version);
-    format!("{base}upload/{version}/{store_name}:uploadToFileSearchStore")
+    format!("{base}{version}/{store_name}:uploadToFileSearchStore")
 }
 
 fn add_list_query_params(url: &str, config: &ListFileSearchStoresConfig) -> Result<String> {
@@ -663,6 +664,9 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
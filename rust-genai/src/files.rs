@@ -1,10 +1,20 @@
 //! Files API surface.
 
+use std::collections::HashMap;
+use std::fmt::Write as _;
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
-use std::sync::Arc;
-use std::time::{Duration, Instant};
-
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RANGE};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::AsyncReadExt;
 
 use crate::client::Credentials;
 use crate::client::{Backend, ClientInner};
@@ -12,6 +22,7 @@ use crate::error::{Error, Result};
 use crate::http_response::{
     sdk_http_response_from_headers, sdk_http_response_from_headers_and_body,
 };
+use crate::poll::PollConfig;
 use crate::upload;
 #[cfg(test)]
 use crate::upload::CHUNK_SIZE;
@@ -139,6 +150,7 @@ impl Files {
     ///
     /// # Errors
     /// 当文件无效、请求失败或响应解析失败时返回错误。
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn upload_from_path(&self, path: impl AsRef<Path>) -> Result<File> {
         self.upload_from_path_with_config(path, UploadFileConfig::default())
             .await
@@ -148,6 +160,7 @@ impl Files {
     ///
     /// # Errors
     /// 当文件无效、请求失败或响应解析失败时返回错误。
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn upload_from_path_with_config(
         &self,
         path: impl AsRef<Path>,
@@ -193,6 +206,50 @@ impl Files {
         .await
     }
 
+    /// 上传文件字节；若 `cache` 中已有相同内容（SHA-256）的文件则直接返回，跳过上传。
+    ///
+    /// 缓存不会校验文件是否已过期（Gemini API 文件默认保留 48 小时），由调用方负责淘汰。
+    ///
+    /// # Errors
+    /// 当配置无效、请求失败或响应解析失败时返回错误。
+    pub async fn upload_with_cache(
+        &self,
+        data: Vec<u8>,
+        config: UploadFileConfig,
+        cache: &dyn UploadCache,
+    ) -> Result<File> {
+        let hash = sha256_hex(&data);
+        if let Some(file) = cache.get(&hash) {
+            return Ok(file);
+        }
+        let file = self.upload_with_config(data, config).await?;
+        cache.insert(hash, file.clone());
+        Ok(file)
+    }
+
+    /// 从文件路径上传；若 `cache` 中已有相同内容（SHA-256）的文件则直接返回，跳过上传。
+    ///
+    /// 哈希以流式方式计算，不会将整个文件读入内存。
+    ///
+    /// # Errors
+    /// 当文件无效、请求失败或响应解析失败时返回错误。
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_from_path_with_cache(
+        &self,
+        path: impl AsRef<Path>,
+        config: UploadFileConfig,
+        cache: &dyn UploadCache,
+    ) -> Result<File> {
+        let path = path.as_ref();
+        let hash = sha256_file(path).await?;
+        if let Some(file) = cache.get(&hash) {
+            return Ok(file);
+        }
+        let file = self.upload_from_path_with_config(path, config).await?;
+        cache.insert(hash, file.clone());
+        Ok(file)
+    }
+
     /// 下载 GENERATED 文件（返回字节内容）。
     ///
     /// Gemini API 当前只允许下载服务端生成的文件；上传得到的 `source=UPLOADED`
@@ -226,12 +283,73 @@ impl Files {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let bytes = response.bytes().await?;
         Ok(bytes.to_vec())
     }
 
+    /// 以字节流形式下载 GENERATED 文件，适合直接转发到其他管道而无需先写入内存或文件。
+    ///
+    /// # Errors
+    /// 当请求失败时返回错误；流中的条目在读取响应体失败时返回错误。
+    pub async fn download_stream(
+        &self,
+        name_or_uri: impl AsRef<str>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>> {
+        self.download_stream_from(name_or_uri, 0).await
+    }
+
+    /// 从 `start_offset` 字节处开始以字节流形式下载 GENERATED 文件（HTTP Range 请求）。
+    ///
+    /// 若服务端忽略 Range 返回完整内容，会在客户端跳过前 `start_offset` 个字节。
+    ///
+    /// # Errors
+    /// 当请求失败时返回错误；流中的条目在读取响应体失败时返回错误。
+    pub async fn download_stream_from(
+        &self,
+        name_or_uri: impl AsRef<str>,
+        start_offset: u64,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>> {
+        ensure_gemini_backend(&self.inner)?;
+
+        let file_name = normalize_file_name(name_or_uri.as_ref())?;
+        let url = build_file_download_url(&self.inner, &file_name, None);
+        let mut request = self.inner.http.get(url);
+        if start_offset > 0 {
+            request = request.header(RANGE, format!("bytes={start_offset}-"));
+        }
+        let response = self.inner.send(request).await?;
+        if !response.status().is_success() {
+            return Err(Error::api_error_with_endpoint(response).await);
+        }
+
+        let mut skip = if response.status() == StatusCode::PARTIAL_CONTENT {
+            0
+        } else {
+            start_offset
+        };
+        let stream = response.bytes_stream().filter_map(move |chunk| {
+            let item = match chunk {
+                Ok(mut chunk) => {
+                    let len = u64::try_from(chunk.len()).unwrap_or(u64::MAX);
+                    if skip >= len {
+                        skip -= len;
+                        None
+                    } else {
+                        // skip < len <= usize::MAX，转换不会截断。
+                        let _ = chunk.split_to(usize::try_from(skip).unwrap_or_default());
+                        skip = 0;
+                        Some(Ok(chunk))
+                    }
+                }
+                Err(err) => Some(Err(Error::from(err))),
+            };
+            futures_util::future::ready(item)
+        });
+        Ok(Box::pin(stream))
+    }
+
     /// 列出文件。
     ///
     /// # Errors
@@ -255,7 +373,7 @@ impl Files {
             .send_with_http_options(request, http_options)
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let mut result = response.json::<ListFilesResponse>().await?;
@@ -322,7 +440,7 @@ impl Files {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<File>().await?)
     }
@@ -357,7 +475,7 @@ impl Files {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let text = response.text().await.unwrap_or_default();
@@ -414,7 +532,7 @@ impl Files {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
@@ -438,16 +556,19 @@ impl Files {
 
     /// 轮询直到文件状态变为 ACTIVE。
     ///
+    /// 既可传入 [`WaitForFileConfig`]（固定间隔，默认 300 秒超时），也可传入带退避的 [`PollConfig`]。
+    ///
     /// # Errors
     /// 当请求失败、文件失败或超时返回错误。
     pub async fn wait_for_active(
         &self,
         name_or_uri: impl AsRef<str>,
-        config: WaitForFileConfig,
+        config: impl Into<PollConfig>,
     ) -> Result<File> {
         ensure_gemini_backend(&self.inner)?;
 
-        let start = Instant::now();
+        let config = config.into();
+        let mut poller = config.start();
         loop {
             let file = self.get(name_or_uri.as_ref()).await?;
             match file.state {
@@ -462,15 +583,9 @@ impl Files {
                 _ => {}
             }
 
-            if let Some(timeout) = config.timeout {
-                if start.elapsed() >= timeout {
-                    return Err(Error::Timeout {
-                        message: "Timed out waiting for file to become ACTIVE".into(),
-                    });
-                }
-            }
-
-            tokio::time::sleep(config.poll_interval).await;
+            poller
+                .wait("Timed out waiting for file to become ACTIVE")
+                .await?;
         }
     }
 
@@ -510,7 +625,7 @@ impl Files {
             .send_with_http_options(request, http_options)
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
@@ -552,6 +667,7 @@ impl Files {
         .await
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
     async fn upload_reader(
         &self,
         upload_url: &str,
@@ -608,7 +724,7 @@ impl Files {
             .await?;
 
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let upload_status = response
@@ -633,6 +749,7 @@ impl Files {
     }
 }
 
+/// 等待文件变为 ACTIVE 的轮询配置（固定间隔）。
 #[derive(Debug, Clone)]
 pub struct WaitForFileConfig {
     pub poll_interval: Duration,
@@ -648,6 +765,101 @@ impl Default for WaitForFileConfig {
     }
 }
 
+impl From<WaitForFileConfig> for PollConfig {
+    fn from(config: WaitForFileConfig) -> Self {
+        Self {
+            timeout: config.timeout,
+            ..Self::fixed(config.poll_interval)
+        }
+    }
+}
+
+/// 已上传文件的缓存，按内容的 SHA-256（小写十六进制）索引。
+pub trait UploadCache: Send + Sync {
+    fn get(&self, sha256: &str) -> Option<File>;
+    fn insert(&self, sha256: String, file: File);
+}
+
+/// 进程内 [`UploadCache`]。
+#[derive(Debug, Default)]
+pub struct InMemoryUploadCache {
+    entries: Mutex<HashMap<String, File>>,
+}
+
+impl InMemoryUploadCache {
+    /// 创建空缓存。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 移除某个哈希对应的缓存项（例如文件已过期或被删除）。
+    pub fn remove(&self, sha256: &str) -> Option<File> {
+        self.lock().remove(sha256)
+    }
+
+    /// 缓存项数量。
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// 是否为空。
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, File>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl UploadCache for InMemoryUploadCache {
+    fn get(&self, sha256: &str) -> Option<File> {
+        self.lock().get(sha256).cloned()
+    }
+
+    fn insert(&self, sha256: String, file: File) {
+        self.lock().insert(sha256, file);
+    }
+}
+
+/// 计算字节内容的 SHA-256（小写十六进制）。
+#[must_use]
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex_digest(Sha256::digest(data).as_slice())
+}
+
+/// 流式计算文件内容的 SHA-256（小写十六进制）。
+///
+/// # Errors
+/// 当读取文件失败时返回错误。
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sha256_file(path: impl AsRef<Path>) -> Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex_digest(hasher.finalize().as_slice()))
+}
+
+fn hex_digest(digest: &[u8]) -> String {
+    let mut output = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(output, "{byte:02x}");
+    }
+    output
+}
+
 #[cfg(test)]
 fn finalize_upload(status: &str, file: Option<File>) -> Result<File> {
     upload::finalize_upload(status, file)
@@ -709,13 +921,11 @@ fn build_files_upload_url(
     inner: &ClientInner,
     http_options: Option<&rust_genai_types::http::HttpOptions>,
 ) -> String {
-    let base = http_options
-        .and_then(|opts| opts.base_url.as_deref())
-        .unwrap_or(&inner.api_client.base_url);
+    let base = inner.api_client.upload_base(http_options);
     let version = http_options
         .and_then(|opts| opts.api_version.as_deref())
         .unwrap_or(&inner.api_client.api_version);
-    format!("{base}upload/{version}/files")
+    format!("{base}{version}/files")
 }
 
 fn build_files_list_url(
@@ -810,6 +1020,9 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
@@ -871,6 +1084,150 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_upload_with_cache_skips_duplicate_content() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/upload/v1beta/files"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-goog-upload-url", format!("{}/session", server.uri())),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/session"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-goog-upload-status", "final")
+                    .set_body_json(json!({"file": {"name": "files/abc"}})),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let files = client.files();
+        let cache = InMemoryUploadCache::new();
+        let config = || UploadFileConfig {
+            mime_type: Some("text/plain".into()),
+            ..Default::default()
+        };
+
+        let first = files
+            .upload_with_cache(b"same bytes".to_vec(), config(), &cache)
+            .await
+            .unwrap();
+        let second = files
+            .upload_with_cache(b"same bytes".to_vec(), config(), &cache)
+            .await
+            .unwrap();
+        assert_eq!(first.name, second.name);
+        assert_eq!(cache.len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("note.txt");
+        std::fs::write(&file_path, b"same bytes").unwrap();
+        let from_path = files
+            .upload_from_path_with_cache(&file_path, UploadFileConfig::default(), &cache)
+            .await
+            .unwrap();
+        assert_eq!(from_path.name.as_deref(), Some("files/abc"));
+        assert_eq!(cache.len(), 1);
+
+        files
+            .upload_with_cache(b"other bytes".to_vec(), config(), &cache)
+            .await
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sha256_helpers_agree() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("data.bin");
+        let data = vec![7u8; 200 * 1024];
+        std::fs::write(&file_path, &data).unwrap();
+        assert_eq!(sha256_file(&file_path).await.unwrap(), sha256_hex(&data));
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_upload_url_overrides() {
+        let client = Client::builder()
+            .api_key("test-key")
+            .upload_base_url("https://proxy.example.com/files-upload")
+            .build()
+            .unwrap();
+        let files = client.files();
+        assert_eq!(
+            build_files_upload_url(&files.inner, None),
+            "https://proxy.example.com/files-upload/v1beta/files"
+        );
+        let per_call = rust_genai_types::http::HttpOptions {
+            upload_base_url: Some("https://call.example.com/up/".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_files_upload_url(&files.inner, Some(&per_call)),
+            "https://call.example.com/up/v1beta/files"
+        );
+        let per_call_base = rust_genai_types::http::HttpOptions {
+            base_url: Some("https://base.example.com/".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_files_upload_url(&files.inner, Some(&per_call_base)),
+            "https://base.example.com/upload/v1beta/files"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_uses_custom_upload_base() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/proxy/upload/v1beta/files"))
+            .and(wiremock::matchers::header("x-goog-upload-command", "start"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-goog-upload-url", format!("{}/session", server.uri())),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/session"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-goog-upload-status", "final")
+                    .set_body_json(json!({"file": {"name": "files/abc"}})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .api_key("test-key")
+            .base_url(format!("{}/api", server.uri()))
+            .upload_base_url(format!("{}/proxy/upload", server.uri()))
+            .build()
+            .unwrap();
+        let file = client
+            .files()
+            .upload(vec![1, 2, 3], "text/plain")
+            .await
+            .unwrap();
+        assert_eq!(file.name.as_deref(), Some("files/abc"));
+    }
+
     #[test]
     fn test_normalize_upload_and_list_params() {
         assert_eq!(normalize_upload_name("files/abc"), "files/abc");
@@ -1294,6 +1651,13 @@ mod tests {
         assert!(matches!(err, Error::Timeout { .. }));
     }
 
+    #[test]
+    fn test_wait_for_file_config_converts_with_default_timeout() {
+        let config = PollConfig::from(WaitForFileConfig::default());
+        assert_eq!(config.timeout, Some(Duration::from_secs(300)));
+        assert_eq!(config.interval_for(5), Duration::from_secs(2));
+    }
+
     #[test]
     fn test_add_list_query_params_invalid_url() {
         let err = add_list_query_params("http://[::1", &ListFilesConfig::default()).unwrap_err();
@@ -8,7 +8,7 @@ use futures_util::Stream;
 use reqwest::header::{HeaderName, HeaderValue, ACCEPT};
 use rust_genai_types::interactions::{
     CancelInteractionConfig, CreateInteractionConfig, DeleteInteractionConfig,
-    GetInteractionConfig, Interaction, InteractionSseEvent,
+    GetInteractionConfig, Interaction, InteractionInput, InteractionSseEvent,
 };
 use serde_json::Value;
 
@@ -59,7 +59,7 @@ impl Interactions {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_interaction_response(response).await
     }
@@ -90,13 +90,44 @@ impl Interactions {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
-        let stream = parse_sse_stream_with::<InteractionSseEvent>(response);
+        let stream = parse_sse_stream_with::<InteractionSseEvent>(response)
+            .with_parse_error_context(self.inner.clone(), "interactions");
         Ok(Box::pin(stream))
     }
 
+    /// 继续已有 Interaction：通过 `previous_interaction_id` 引用服务端保存的上下文，无需重发完整历史。
+    ///
+    /// `config` 提供 model/agent 等参数，其 `input` 会被 `input` 覆盖。
+    ///
+    /// # Errors
+    /// 当 Interaction ID 为空、请求失败或响应解析失败时返回错误。
+    pub async fn continue_interaction(
+        &self,
+        interaction_id: impl AsRef<str>,
+        input: impl Into<InteractionInput>,
+        config: CreateInteractionConfig,
+    ) -> Result<Interaction> {
+        let config = continuation_config(interaction_id.as_ref(), input.into(), config)?;
+        self.create_with_config(config).await
+    }
+
+    /// 继续已有 Interaction（流式 SSE）。
+    ///
+    /// # Errors
+    /// 当 Interaction ID 为空、请求失败或响应解析失败时返回错误。
+    pub async fn continue_interaction_stream(
+        &self,
+        interaction_id: impl AsRef<str>,
+        input: impl Into<InteractionInput>,
+        config: CreateInteractionConfig,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<InteractionSseEvent>> + Send>>> {
+        let config = continuation_config(interaction_id.as_ref(), input.into(), config)?;
+        self.create_stream(config).await
+    }
+
     /// 获取 Interaction。
     ///
     /// # Errors
@@ -142,7 +173,7 @@ impl Interactions {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_interaction_response(response).await
     }
@@ -182,10 +213,11 @@ impl Interactions {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
-        let stream = parse_sse_stream_with::<InteractionSseEvent>(response);
+        let stream = parse_sse_stream_with::<InteractionSseEvent>(response)
+            .with_parse_error_context(self.inner.clone(), "interactions");
         Ok(Box::pin(stream))
     }
 
@@ -219,7 +251,7 @@ impl Interactions {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(())
     }
@@ -254,7 +286,7 @@ impl Interactions {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_interaction_response(response).await
     }
@@ -277,6 +309,25 @@ fn normalize_interaction_name(name: &str) -> String {
     }
 }
 
+fn continuation_config(
+    interaction_id: &str,
+    input: InteractionInput,
+    mut config: CreateInteractionConfig,
+) -> Result<CreateInteractionConfig> {
+    let interaction_id = interaction_id.trim();
+    let interaction_id = interaction_id
+        .strip_prefix("interactions/")
+        .unwrap_or(interaction_id);
+    if interaction_id.is_empty() {
+        return Err(Error::InvalidConfig {
+            message: "interaction_id is required to continue an interaction".into(),
+        });
+    }
+    config.input = input;
+    config.previous_interaction_id = Some(interaction_id.to_string());
+    Ok(config)
+}
+
 fn build_interactions_url(
     inner: &ClientInner,
     http_options: Option<&rust_genai_types::http::HttpOptions>,
@@ -359,11 +410,14 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
 
-async fn parse_interaction_response(response: reqwest::Response) -> Result<Interaction> {
+async fn parse_interaction_response(response: crate::transport::Response) -> Result<Interaction> {
     let text = response.text().await.unwrap_or_default();
     if text.trim().is_empty() {
         return Ok(Interaction::default());
@@ -412,6 +466,26 @@ mod tests {
     use super::*;
     use crate::test_support::test_client_inner;
 
+    #[test]
+    fn test_continuation_config_sets_previous_interaction_id() {
+        let config = continuation_config(
+            "interactions/int_1",
+            "next".into(),
+            CreateInteractionConfig::new("gemini-3-flash-preview", "ignored"),
+        )
+        .unwrap();
+        assert_eq!(config.previous_interaction_id.as_deref(), Some("int_1"));
+        assert_eq!(serde_json::to_value(&config.input).unwrap(), "next");
+
+        let err = continuation_config(
+            " ",
+            "next".into(),
+            CreateInteractionConfig::new("gemini-3-flash-preview", "hi"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+    }
+
     #[test]
     fn test_normalize_names_and_urls() {
         assert_eq!(
@@ -1,8 +1,26 @@
 //! Core client crate for the Rust Gemini SDK.
+//!
+//! Under `wasm32` the `wasm` feature swaps in a `fetch`-based transport; WebSocket sessions
+//! (`live`, `live_music`), file-path helpers, OAuth/ADC credentials and the blocking client
+//! are native-only.
+
+// The wasm32 response type already yields `crate::Error`, so conversions needed for reqwest's
+// errors natively are no-ops there.
+#![cfg_attr(
+    target_arch = "wasm32",
+    allow(clippy::needless_question_mark, clippy::useless_conversion)
+)]
+
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building rust-genai for wasm32 requires the `wasm` feature");
 
 pub mod afc;
+pub mod audio;
+#[cfg(not(target_arch = "wasm32"))]
 mod auth;
 pub mod batches;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
 pub mod caches;
 pub mod chats;
 pub mod client;
@@ -14,19 +32,29 @@ pub mod file_search_stores;
 pub mod files;
 mod http_response;
 pub mod interactions;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod live;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod live_music;
 #[cfg(feature = "mcp")]
 pub mod mcp;
 pub mod model_capabilities;
 pub mod models;
 pub mod operations;
+pub mod pagination;
+pub mod poll;
+mod rt;
 pub mod sse;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod thinking;
+mod token_cache;
 pub mod tokenizer;
 pub mod tokens;
+pub mod transport;
 pub mod tunings;
 mod upload;
+pub mod vector_index;
 pub mod webhooks;
 
 #[cfg(test)]
@@ -34,5 +62,8 @@ mod test_support;
 
 pub use rust_genai_types as types;
 
-pub use client::{Backend, Client, ClientBuilder, Credentials, HttpOptions, VertexConfig};
+pub use client::{
+    Backend, Client, ClientBuilder, Credentials, HttpOptions, ResponseParseFailure,
+    ResponseParseHook, VertexConfig,
+};
 pub use error::{Error, Result};
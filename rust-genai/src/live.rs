@@ -5,6 +5,7 @@ use std::sync::{Arc, Mutex};
 use futures_util::{SinkExt, StreamExt};
 use reqwest::Url;
 use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
 use tokio::time::{timeout, Duration};
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
@@ -12,7 +13,7 @@ use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue};
 use tokio_tungstenite::tungstenite::Message;
 
 use rust_genai_types::config::GenerationConfig;
-use rust_genai_types::content::{Blob, Content};
+use rust_genai_types::content::{Blob, Content, FunctionResponse};
 use rust_genai_types::live_types::{
     AudioTranscriptionConfig, ContextWindowCompressionConfig, LiveClientContent, LiveClientMessage,
     LiveClientRealtimeInput, LiveClientSetup, LiveConnectConfig, LiveSendClientContentParameters,
@@ -25,6 +26,9 @@ use crate::client::{Backend, ClientInner};
 use crate::error::{Error, Result};
 use crate::live_music::LiveMusic;
 
+/// [`LiveSession::close`] 等待发送队列写出的默认超时。
+pub const DEFAULT_CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Clone)]
 pub struct Live {
     pub(crate) inner: Arc<ClientInner>,
@@ -69,6 +73,7 @@ pub struct LiveSessionBuilder {
     inner: Arc<ClientInner>,
     model: String,
     config: LiveConnectConfig,
+    close_timeout: Duration,
 }
 
 impl LiveSessionBuilder {
@@ -77,6 +82,7 @@ impl LiveSessionBuilder {
             inner,
             model,
             config: LiveConnectConfig::default(),
+            close_timeout: DEFAULT_CLOSE_TIMEOUT,
         }
     }
 
@@ -94,6 +100,13 @@ impl LiveSessionBuilder {
         self
     }
 
+    /// 设置系统指令内容（可包含多个 part）。
+    #[must_use]
+    pub fn with_system_instruction_content(mut self, instruction: Content) -> Self {
+        self.config.system_instruction = Some(instruction);
+        self
+    }
+
     /// 设置工具列表。
     #[must_use]
     pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
@@ -158,12 +171,19 @@ impl LiveSessionBuilder {
         self
     }
 
+    /// 设置 [`LiveSession::close`] 等待发送队列写出的超时（默认 [`DEFAULT_CLOSE_TIMEOUT`]）。
+    #[must_use]
+    pub const fn with_close_timeout(mut self, close_timeout: Duration) -> Self {
+        self.close_timeout = close_timeout;
+        self
+    }
+
     /// 连接并创建会话。
     ///
     /// # Errors
     /// 当连接失败或配置无效时返回错误。
     pub async fn connect(self) -> Result<LiveSession> {
-        connect_live_session(self.inner, self.model, self.config).await
+        connect_live_session(self.inner, self.model, self.config, self.close_timeout).await
     }
 }
 
@@ -171,12 +191,20 @@ impl LiveSessionBuilder {
 pub struct LiveSession {
     outgoing_tx: mpsc::UnboundedSender<LiveClientMessage>,
     incoming_rx: mpsc::UnboundedReceiver<Result<LiveServerMessage>>,
-    shutdown_tx: Option<oneshot::Sender<()>>,
+    shutdown_tx: Option<oneshot::Sender<ShutdownRequest>>,
+    message_loop: Option<JoinHandle<()>>,
+    close_timeout: Duration,
     pub session_id: Option<String>,
     resumption_state: Arc<Mutex<LiveSessionResumptionState>>,
     go_away_time_left: Arc<Mutex<Option<String>>>,
 }
 
+/// 关闭请求：`flush` 为真时先写出发送队列中的消息，`done` 回传被丢弃的消息数。
+struct ShutdownRequest {
+    flush: bool,
+    done: oneshot::Sender<usize>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct LiveSessionResumptionState {
     pub handle: Option<String>,
@@ -283,21 +311,69 @@ impl LiveSession {
         self.send_async(message).await
     }
 
+    /// 发送长耗时工具的中间结果（强制 `willContinue = true`），不阻塞模型继续对话。
+    ///
+    /// 同一调用可多次发送中间结果，最后使用 [`Self::send_final_tool_response`] 结束。
+    ///
+    /// # Errors
+    /// 当发送失败或连接中断时返回错误。
+    pub async fn send_interim_tool_response(&self, mut response: FunctionResponse) -> Result<()> {
+        response.will_continue = Some(true);
+        self.send_tool_response(LiveSendToolResponseParameters {
+            function_responses: Some(vec![response]),
+        })
+        .await
+    }
+
+    /// 发送长耗时工具的最终结果（强制 `willContinue = false`）。
+    ///
+    /// # Errors
+    /// 当发送失败或连接中断时返回错误。
+    pub async fn send_final_tool_response(&self, mut response: FunctionResponse) -> Result<()> {
+        response.will_continue = Some(false);
+        self.send_tool_response(LiveSendToolResponseParameters {
+            function_responses: Some(vec![response]),
+        })
+        .await
+    }
+
     /// 接收服务器消息。
     pub async fn receive(&mut self) -> Option<Result<LiveServerMessage>> {
         self.incoming_rx.recv().await
     }
 
-    /// 关闭会话。
+    /// 关闭会话：先写出发送队列中尚未发送的消息，再发送 WebSocket Close 帧。
+    ///
+    /// 写出耗时超过 [`LiveSessionBuilder::with_close_timeout`]（默认
+    /// [`DEFAULT_CLOSE_TIMEOUT`]）时放弃剩余消息并断开连接。
     ///
     /// # Errors
-    /// 当发送关闭信号失败时返回错误。
+    /// 当连接在排队消息全部写出前中断时返回 [`Error::ChannelClosed`]；
+    /// 超时返回 [`Error::Timeout`]。
     pub async fn close(mut self) -> Result<()> {
-        if let Some(tx) = self.shutdown_tx.take() {
-            let _ = tx.send(());
+        let close_timeout = self.close_timeout;
+        match timeout(close_timeout, self.shutdown(true)).await {
+            Ok(0) => Ok(()),
+            Ok(_) => Err(Error::ChannelClosed),
+            Err(_) => {
+                if let Some(message_loop) = self.message_loop.take() {
+                    message_loop.abort();
+                }
+                Err(Error::Timeout {
+                    message: format!(
+                        "Timed out flushing Live session after {}ms",
+                        close_timeout.as_millis()
+                    ),
+                })
+            }
         }
-        tokio::task::yield_now().await;
-        Ok(())
+    }
+
+    /// 立即关闭会话，丢弃发送队列中尚未写出的消息。
+    ///
+    /// 返回被丢弃的消息数。
+    pub async fn close_now(mut self) -> usize {
+        self.shutdown(false).await
     }
 
     /// 获取最新的会话恢复状态。
@@ -337,12 +413,30 @@ impl LiveSession {
         tokio::task::yield_now().await;
         Ok(())
     }
+
+    async fn shutdown(&mut self, flush: bool) -> usize {
+        let Some(tx) = self.shutdown_tx.take() else {
+            return 0;
+        };
+        let (done_tx, done_rx) = oneshot::channel();
+        if tx
+            .send(ShutdownRequest {
+                flush,
+                done: done_tx,
+            })
+            .is_err()
+        {
+            return 0;
+        }
+        done_rx.await.unwrap_or(0)
+    }
 }
 
 async fn connect_live_session(
     inner: Arc<ClientInner>,
     model: String,
     config: LiveConnectConfig,
+    close_timeout: Duration,
 ) -> Result<LiveSession> {
     if config.http_options.is_some() {
         return Err(Error::InvalidConfig {
@@ -431,7 +525,7 @@ async fn connect_live_session(
         ),
     })??;
 
-    tokio::spawn(message_loop(
+    let message_loop = tokio::spawn(message_loop(
         write,
         read,
         outgoing_rx,
@@ -445,6 +539,8 @@ async fn connect_live_session(
         outgoing_tx,
         incoming_rx,
         shutdown_tx: Some(shutdown_tx),
+        message_loop: Some(message_loop),
+        close_timeout,
         session_id,
         resumption_state,
         go_away_time_left,
@@ -662,7 +758,7 @@ async fn message_loop(
     >,
     mut outgoing_rx: mpsc::UnboundedReceiver<LiveClientMessage>,
     incoming_tx: mpsc::UnboundedSender<Result<LiveServerMessage>>,
-    mut shutdown_rx: oneshot::Receiver<()>,
+    mut shutdown_rx: oneshot::Receiver<ShutdownRequest>,
     resumption_state: Arc<Mutex<LiveSessionResumptionState>>,
     go_away_time_left: Arc<Mutex<Option<String>>>,
 ) {
@@ -709,8 +805,29 @@ async fn message_loop(
                     None => break,
                 }
             }
-            _ = &mut shutdown_rx => {
+            request = &mut shutdown_rx => {
+                outgoing_rx.close();
+                let flush = request.as_ref().is_ok_and(|request| request.flush);
+                let mut dropped = 0;
+                let mut write_failed = false;
+                while let Ok(message) = outgoing_rx.try_recv() {
+                    if !flush || write_failed {
+                        dropped += 1;
+                        continue;
+                    }
+                    let Ok(payload) = serde_json::to_string(&message) else {
+                        dropped += 1;
+                        continue;
+                    };
+                    if write.send(Message::Text(payload.into())).await.is_err() {
+                        dropped += 1;
+                        write_failed = true;
+                    }
+                }
                 let _ = write.send(Message::Close(None)).await;
+                if let Ok(request) = request {
+                    let _ = request.done.send(dropped);
+                }
                 break;
             }
         }
@@ -799,6 +916,30 @@ mod tests {
         assert!(request.headers().contains_key("x-goog-api-key"));
     }
 
+    #[test]
+    fn test_build_live_setup_multi_part_system_instruction() {
+        let inner = Arc::new(test_client_inner_with_api_key(
+            Backend::GeminiApi,
+            Some("key"),
+        ));
+        let instruction = Content {
+            role: None,
+            parts: vec![
+                rust_genai_types::content::Part::text("You are a DJ."),
+                rust_genai_types::content::Part::text("Keep answers short."),
+            ],
+        };
+        let builder = LiveSessionBuilder::new(inner, "gemini-3.1-flash-live-preview".to_string())
+            .with_system_instruction_content(instruction);
+        let setup = build_live_setup(&builder.model, &builder.config);
+        assert_eq!(
+            serde_json::to_value(&setup).unwrap()["systemInstruction"],
+            serde_json::json!({
+                "parts": [{"text": "You are a DJ."}, {"text": "Keep answers short."}]
+            })
+        );
+    }
+
     #[test]
     fn test_live_builder_and_music_accessors() {
         let inner = Arc::new(test_client_inner_with_api_key(
@@ -886,6 +1027,8 @@ mod tests {
             outgoing_tx,
             incoming_rx,
             shutdown_tx: None,
+            message_loop: None,
+            close_timeout: DEFAULT_CLOSE_TIMEOUT,
             session_id: None,
             resumption_state: state,
             go_away_time_left: go_away,
@@ -1049,6 +1192,8 @@ mod tests {
             outgoing_tx,
             incoming_rx,
             shutdown_tx: Some(shutdown_tx),
+            message_loop: None,
+            close_timeout: DEFAULT_CLOSE_TIMEOUT,
             session_id: Some("session".to_string()),
             resumption_state: Arc::new(Mutex::new(LiveSessionResumptionState::default())),
             go_away_time_left: Arc::new(Mutex::new(None)),
@@ -1104,8 +1249,41 @@ mod tests {
         let msg = outgoing_rx.recv().await.unwrap();
         assert!(msg.tool_response.is_some());
 
+        let ack = tokio::spawn(async move {
+            let request = shutdown_rx.await.unwrap();
+            assert!(request.flush);
+            request.done.send(0).unwrap();
+        });
         session.close().await.unwrap();
-        assert!(shutdown_rx.await.is_ok());
+        ack.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_live_session_close_times_out_on_stalled_flush() {
+        let (outgoing_tx, _outgoing_rx) = mpsc::unbounded_channel();
+        let (_incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let (loop_alive_tx, loop_alive_rx) = oneshot::channel::<()>();
+        // 模拟写出卡住的消息循环：收到关闭请求后不再回应。
+        let message_loop = tokio::spawn(async move {
+            let _alive = loop_alive_tx;
+            let _request = shutdown_rx.await;
+            std::future::pending::<()>().await;
+        });
+        let session = LiveSession {
+            outgoing_tx,
+            incoming_rx,
+            shutdown_tx: Some(shutdown_tx),
+            message_loop: Some(message_loop),
+            close_timeout: Duration::from_secs(2),
+            session_id: None,
+            resumption_state: Arc::new(Mutex::new(LiveSessionResumptionState::default())),
+            go_away_time_left: Arc::new(Mutex::new(None)),
+        };
+
+        let err = session.close().await.unwrap_err();
+        assert!(matches!(err, Error::Timeout { .. }));
+        assert!(loop_alive_rx.await.is_err());
     }
 
     #[tokio::test]
@@ -1117,6 +1295,8 @@ mod tests {
             outgoing_tx,
             incoming_rx,
             shutdown_tx: None,
+            message_loop: None,
+            close_timeout: DEFAULT_CLOSE_TIMEOUT,
             session_id: None,
             resumption_state: Arc::new(Mutex::new(LiveSessionResumptionState::default())),
             go_away_time_left: Arc::new(Mutex::new(None)),
@@ -1139,6 +1319,8 @@ mod tests {
             outgoing_tx,
             incoming_rx,
             shutdown_tx: None,
+            message_loop: None,
+            close_timeout: DEFAULT_CLOSE_TIMEOUT,
             session_id: None,
             resumption_state: state,
             go_away_time_left: go_away,
@@ -1162,7 +1344,7 @@ mod tests {
             http_options: Some(rust_genai_types::http::HttpOptions::default()),
             ..Default::default()
         };
-        let err = connect_live_session(inner, "model".to_string(), config)
+        let err = connect_live_session(inner, "model".to_string(), config, DEFAULT_CLOSE_TIMEOUT)
             .await
             .err()
             .unwrap();
@@ -1172,17 +1354,27 @@ mod tests {
             Backend::VertexAi,
             Some("key"),
         ));
-        let err = connect_live_session(inner, "model".to_string(), LiveConnectConfig::default())
-            .await
-            .err()
-            .unwrap();
+        let err = connect_live_session(
+            inner,
+            "model".to_string(),
+            LiveConnectConfig::default(),
+            DEFAULT_CLOSE_TIMEOUT,
+        )
+        .await
+        .err()
+        .unwrap();
         assert!(matches!(err, Error::InvalidConfig { .. }));
 
         let inner = Arc::new(test_client_inner_with_api_key(Backend::GeminiApi, None));
-        let err = connect_live_session(inner, "model".to_string(), LiveConnectConfig::default())
-            .await
-            .err()
-            .unwrap();
+        let err = connect_live_session(
+            inner,
+            "model".to_string(),
+            LiveConnectConfig::default(),
+            DEFAULT_CLOSE_TIMEOUT,
+        )
+        .await
+        .err()
+        .unwrap();
         assert!(matches!(err, Error::InvalidConfig { .. }));
     }
 
@@ -1,8 +1,8 @@
 //! Live Music API surface.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, Stream, StreamExt};
 use reqwest::Url;
 use tokio::sync::{mpsc, oneshot};
 use tokio_tungstenite::connect_async;
@@ -11,8 +11,8 @@ use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue};
 use tokio_tungstenite::tungstenite::Message;
 
 use rust_genai_types::live_music_types::{
-    LiveMusicClientContent, LiveMusicClientMessage, LiveMusicClientSetup,
-    LiveMusicGenerationConfig, LiveMusicPlaybackControl, LiveMusicServerMessage, WeightedPrompt,
+    AudioChunk, LiveMusicClientMessage, LiveMusicClientSetup, LiveMusicGenerationConfig,
+    LiveMusicPlaybackControl, LiveMusicServerMessage, Scale, WeightedPrompt,
 };
 
 use crate::client::{Backend, ClientInner};
@@ -41,6 +41,7 @@ pub struct LiveMusicSession {
     outgoing_tx: mpsc::UnboundedSender<LiveMusicClientMessage>,
     incoming_rx: mpsc::UnboundedReceiver<Result<LiveMusicServerMessage>>,
     shutdown_tx: Option<oneshot::Sender<()>>,
+    generation_config: Mutex<LiveMusicGenerationConfig>,
 }
 
 impl LiveMusicSession {
@@ -54,18 +55,11 @@ impl LiveMusicSession {
                 message: "weighted_prompts must contain at least one entry".into(),
             });
         }
-        let message = LiveMusicClientMessage {
-            setup: None,
-            client_content: Some(LiveMusicClientContent {
-                weighted_prompts: Some(prompts),
-            }),
-            music_generation_config: None,
-            playback_control: None,
-        };
-        self.send_async(message).await
+        self.send_async(LiveMusicClientMessage::from_weighted_prompts(prompts))
+            .await
     }
 
-    /// 设置音乐生成配置。
+    /// 设置音乐生成配置（整体替换当前配置）。
     ///
     /// # Errors
     /// 当发送失败时返回错误。
@@ -73,13 +67,37 @@ impl LiveMusicSession {
         &self,
         config: Option<LiveMusicGenerationConfig>,
     ) -> Result<()> {
-        let message = LiveMusicClientMessage {
-            setup: None,
-            client_content: None,
-            music_generation_config: Some(config.unwrap_or_default()),
-            playback_control: None,
-        };
-        self.send_async(message).await
+        let config = config.unwrap_or_default();
+        *self.lock_generation_config() = config.clone();
+        self.send_async(LiveMusicClientMessage::from_music_generation_config(config))
+            .await
+    }
+
+    /// 设置节拍（BPM），保留其余生成配置。
+    ///
+    /// 服务端需在 [`Self::reset_context`] 之后才会应用新的 BPM。
+    ///
+    /// # Errors
+    /// 当 BPM 超出 60-200 或发送失败时返回错误。
+    pub async fn set_bpm(&self, bpm: i32) -> Result<()> {
+        if !(60..=200).contains(&bpm) {
+            return Err(Error::InvalidConfig {
+                message: format!("bpm must be between 60 and 200, got {bpm}"),
+            });
+        }
+        self.update_generation_config(|config| config.bpm = Some(bpm))
+            .await
+    }
+
+    /// 设置音阶，保留其余生成配置。
+    ///
+    /// 服务端需在 [`Self::reset_context`] 之后才会应用新的音阶。
+    ///
+    /// # Errors
+    /// 当发送失败时返回错误。
+    pub async fn set_scale(&self, scale: Scale) -> Result<()> {
+        self.update_generation_config(|config| config.scale = Some(scale))
+            .await
     }
 
     /// 播放。
@@ -120,6 +138,27 @@ impl LiveMusicSession {
         self.incoming_rx.recv().await
     }
 
+    /// 以流的形式接收生成的音频 chunk（忽略不含音频的消息）。
+    pub fn audio_chunks(&mut self) -> impl Stream<Item = Result<AudioChunk>> + '_ {
+        futures_util::stream::unfold(&mut self.incoming_rx, |rx| async move {
+            let message = rx.recv().await?;
+            Some((message, rx))
+        })
+        .flat_map(|message| {
+            let chunks: Vec<Result<AudioChunk>> = match message {
+                Ok(message) => message
+                    .server_content
+                    .and_then(|content| content.audio_chunks)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(Ok)
+                    .collect(),
+                Err(err) => vec![Err(err)],
+            };
+            futures_util::stream::iter(chunks)
+        })
+    }
+
     /// 关闭会话。
     ///
     /// # Errors
@@ -133,13 +172,27 @@ impl LiveMusicSession {
     }
 
     async fn send_playback(&self, control: LiveMusicPlaybackControl) -> Result<()> {
-        let message = LiveMusicClientMessage {
-            setup: None,
-            client_content: None,
-            music_generation_config: None,
-            playback_control: Some(control),
+        self.send_async(LiveMusicClientMessage::from_playback_control(control))
+            .await
+    }
+
+    async fn update_generation_config(
+        &self,
+        update: impl FnOnce(&mut LiveMusicGenerationConfig),
+    ) -> Result<()> {
+        let config = {
+            let mut config = self.lock_generation_config();
+            update(&mut config);
+            config.clone()
         };
-        self.send_async(message).await
+        self.send_async(LiveMusicClientMessage::from_music_generation_config(config))
+            .await
+    }
+
+    fn lock_generation_config(&self) -> std::sync::MutexGuard<'_, LiveMusicGenerationConfig> {
+        self.generation_config
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
     }
 
     fn send(&self, message: LiveMusicClientMessage) -> Result<()> {
@@ -242,6 +295,7 @@ async fn connect_live_music_session(
         outgoing_tx,
         incoming_rx,
         shutdown_tx: Some(shutdown_tx),
+        generation_config: Mutex::default(),
     })
 }
 
@@ -419,6 +473,7 @@ mod tests {
             outgoing_tx,
             incoming_rx,
             shutdown_tx: Some(shutdown_tx),
+            generation_config: Mutex::default(),
         };
 
         let err = session.set_weighted_prompts(vec![]).await.unwrap_err();
@@ -476,6 +531,71 @@ mod tests {
         assert!(shutdown_rx.await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_live_music_session_bpm_scale_and_audio_stream() {
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel();
+        let (incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+        let mut session = LiveMusicSession {
+            outgoing_tx,
+            incoming_rx,
+            shutdown_tx: None,
+            generation_config: Mutex::default(),
+        };
+
+        session
+            .set_music_generation_config(Some(LiveMusicGenerationConfig {
+                density: Some(0.5),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        outgoing_rx.recv().await.unwrap();
+
+        assert!(matches!(
+            session.set_bpm(20).await.unwrap_err(),
+            Error::InvalidConfig { .. }
+        ));
+        session.set_bpm(120).await.unwrap();
+        session.set_scale(Scale::DMajorBMinor).await.unwrap();
+        outgoing_rx.recv().await.unwrap();
+        let msg = outgoing_rx.recv().await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&msg).unwrap(),
+            serde_json::json!({
+                "musicGenerationConfig": {
+                    "bpm": 120,
+                    "density": 0.5,
+                    "scale": "D_MAJOR_B_MINOR"
+                }
+            })
+        );
+
+        let chunk = |data: u8| AudioChunk {
+            data: Some(vec![data]),
+            mime_type: Some("audio/l16".to_string()),
+            source_metadata: None,
+        };
+        incoming_tx
+            .send(Ok(LiveMusicServerMessage {
+                server_content: Some(rust_genai_types::live_music_types::LiveMusicServerContent {
+                    audio_chunks: Some(vec![chunk(1), chunk(2)]),
+                }),
+                ..Default::default()
+            }))
+            .unwrap();
+        incoming_tx
+            .send(Ok(LiveMusicServerMessage::default()))
+            .unwrap();
+        incoming_tx.send(Err(Error::ChannelClosed)).unwrap();
+        drop(incoming_tx);
+
+        let items: Vec<_> = session.audio_chunks().collect().await;
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().unwrap().data, Some(vec![1]));
+        assert_eq!(items[1].as_ref().unwrap().data, Some(vec![2]));
+        assert!(matches!(items[2], Err(Error::ChannelClosed)));
+    }
+
     #[tokio::test]
     async fn test_live_music_session_send_channel_closed() {
         let (outgoing_tx, outgoing_rx) = mpsc::unbounded_channel();
@@ -485,6 +605,7 @@ mod tests {
             outgoing_tx,
             incoming_rx,
             shutdown_tx: None,
+            generation_config: Mutex::default(),
         };
         let err = session.play().await.unwrap_err();
         assert!(matches!(err, Error::ChannelClosed));
@@ -2,15 +2,19 @@
 //!
 //! This module is experimental and behind the `mcp` feature.
 
+use std::any::Any;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::future::Future;
 use std::hash::BuildHasher;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures_util::future::BoxFuture;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
-use rmcp::model::{CallToolRequestParam, Tool as McpTool};
-use rmcp::service::{Peer, RoleClient};
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool as McpTool};
+use rmcp::service::{Peer, RoleClient, RunningService, Service, ServiceError};
 use rust_genai_types::content::{FunctionCall, FunctionResponse, Part};
 use rust_genai_types::enums::Behavior;
 use rust_genai_types::tool::{FunctionDeclaration, Tool};
@@ -26,6 +30,9 @@ pub const MCP_USAGE_HEADER: &str = "x-goog-api-client";
 
 static MCP_TOOL_USAGE: AtomicBool = AtomicBool::new(false);
 
+const DEFAULT_MAX_RECONNECT_ATTEMPTS: u32 = 3;
+const DEFAULT_RECONNECT_BACKOFF: Duration = Duration::from_millis(200);
+
 /// MCP 可调用工具配置。
 #[derive(Debug, Clone, Default)]
 pub struct McpCallableToolConfig {
@@ -33,6 +40,67 @@ pub struct McpCallableToolConfig {
     pub behavior: Option<Behavior>,
     /// 远程调用超时时间。
     pub timeout: Option<Duration>,
+    /// 通过 [`McpConnector`] 建立连接的超时时间。
+    pub connect_timeout: Option<Duration>,
+    /// 单次（重）连接的最大尝试次数（仅对 [`McpCallableTool::with_connectors`] 生效，默认 3）。
+    ///
+    /// 调用中途断线时，仅重试 annotations 声明为只读或幂等的工具。
+    pub max_reconnect_attempts: Option<u32>,
+    /// 连接失败后首次重试前的等待时间，之后每次翻倍（默认 200ms）。
+    pub reconnect_backoff: Option<Duration>,
+    /// 将图片、音频与二进制资源结果作为 `FunctionResponse.parts` 内联数据返回（默认 false）。
+    ///
+    /// 仅在目标模型支持 FunctionResponse 媒体（如 Gemini 3）时开启；默认整体编码为 JSON。
+    pub multimodal_responses: Option<bool>,
+}
+
+/// 由 [`McpConnector`] 建立的 MCP 客户端连接；持有底层服务，drop 时关闭连接。
+pub struct McpConnection {
+    peer: Peer<RoleClient>,
+    _service: Box<dyn Any + Send + Sync>,
+}
+
+impl McpConnection {
+    /// 连接对应的 `Peer`。
+    #[must_use]
+    pub const fn peer(&self) -> &Peer<RoleClient> {
+        &self.peer
+    }
+}
+
+impl<S: Service<RoleClient>> From<RunningService<RoleClient, S>> for McpConnection {
+    fn from(service: RunningService<RoleClient, S>) -> Self {
+        Self {
+            peer: service.peer().clone(),
+            _service: Box::new(service),
+        }
+    }
+}
+
+impl fmt::Debug for McpConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McpConnection")
+            .field("peer", &self.peer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// MCP 连接器：用于建立连接，并在连接断开后重建。
+///
+/// 闭包 `Fn() -> impl Future<Output = Result<McpConnection>>` 自动实现该 trait。
+pub trait McpConnector: Send + Sync {
+    /// 建立新的 MCP 客户端连接。
+    fn connect(&self) -> BoxFuture<'_, Result<McpConnection>>;
+}
+
+impl<F, Fut> McpConnector for F
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<McpConnection>> + Send + 'static,
+{
+    fn connect(&self) -> BoxFuture<'_, Result<McpConnection>> {
+        Box::pin(self())
+    }
 }
 
 /// 追加 MCP 使用标记到 headers（可用于观测/遥测）。
@@ -128,20 +196,68 @@ fn mcp_tool_to_declaration(tool: &McpTool, config: &McpCallableToolConfig) -> Fu
     }
 }
 
+/// 单个 MCP 客户端连接；持有连接器时可在断开后重建。
+#[derive(Clone)]
+struct McpClientSlot {
+    peer: Option<Peer<RoleClient>>,
+    connector: Option<Arc<dyn McpConnector>>,
+    connection: Option<Arc<McpConnection>>,
+}
+
+impl fmt::Debug for McpClientSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McpClientSlot")
+            .field("peer", &self.peer)
+            .field("reconnectable", &self.connector.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
 /// MCP 可调用工具（桥接 MCP 与 Gemini Function Calling）。
 #[derive(Debug, Clone)]
 pub struct McpCallableTool {
-    clients: Vec<Peer<RoleClient>>,
+    clients: Vec<McpClientSlot>,
     config: McpCallableToolConfig,
     mcp_tools: Vec<McpTool>,
-    function_name_to_client: HashMap<String, Peer<RoleClient>>,
+    function_name_to_client: HashMap<String, usize>,
     initialized: bool,
 }
 
 impl McpCallableTool {
     /// 创建新的 `McpCallableTool`。
+    ///
+    /// 直接传入的 `Peer` 断开后不会自动重连；需要重连时使用 [`Self::with_connectors`]。
     #[must_use]
     pub fn new(clients: Vec<Peer<RoleClient>>, config: McpCallableToolConfig) -> Self {
+        let clients = clients
+            .into_iter()
+            .map(|peer| McpClientSlot {
+                peer: Some(peer),
+                connector: None,
+                connection: None,
+            })
+            .collect();
+        Self::from_slots(clients, config)
+    }
+
+    /// 使用连接器创建 `McpCallableTool`：首次使用时建立连接，连接断开后按退避策略自动重连。
+    #[must_use]
+    pub fn with_connectors(
+        connectors: Vec<Arc<dyn McpConnector>>,
+        config: McpCallableToolConfig,
+    ) -> Self {
+        let clients = connectors
+            .into_iter()
+            .map(|connector| McpClientSlot {
+                peer: None,
+                connector: Some(connector),
+                connection: None,
+            })
+            .collect();
+        Self::from_slots(clients, config)
+    }
+
+    fn from_slots(clients: Vec<McpClientSlot>, config: McpCallableToolConfig) -> Self {
         mark_mcp_tool_usage();
         Self {
             clients,
@@ -156,7 +272,7 @@ impl McpCallableTool {
     ///
     /// # Errors
     ///
-    /// 当拉取 MCP 工具失败或工具名称冲突时返回错误。
+    /// 当连接或拉取 MCP 工具失败、或工具名称冲突时返回错误。
     pub async fn initialize(&mut self) -> Result<()> {
         if self.initialized {
             return Ok(());
@@ -164,8 +280,14 @@ impl McpCallableTool {
 
         let mut tools = Vec::new();
         let mut function_map = HashMap::new();
-        for client in &self.clients {
-            let client_tools = client.list_all_tools().await?;
+        for index in 0..self.clients.len() {
+            let peer = self.peer(index).await?;
+            let client_tools = match peer.list_all_tools().await {
+                Err(source) if self.should_reconnect(index, &source) => {
+                    self.reconnect(index).await?.list_all_tools().await?
+                }
+                result => result?,
+            };
             for tool in client_tools {
                 let name = tool.name.to_string();
                 if function_map.contains_key(&name) {
@@ -175,7 +297,7 @@ impl McpCallableTool {
                         ),
                     });
                 }
-                function_map.insert(name, client.clone());
+                function_map.insert(name, index);
                 tools.push(tool);
             }
         }
@@ -198,7 +320,7 @@ impl McpCallableTool {
     /// 执行 MCP 工具调用并返回 `FunctionResponse` Parts。
     ///
     /// # Errors
-    /// 当初始化失败、调用工具失败或响应解析失败时返回错误。
+    /// 当初始化失败、调用工具失败、重连失败或响应解析失败时返回错误。
     pub async fn call_tool(&mut self, function_calls: &[FunctionCall]) -> Result<Vec<Part>> {
         self.initialize().await?;
         let mut parts = Vec::new();
@@ -206,7 +328,7 @@ impl McpCallableTool {
             let Some(name) = call.name.as_ref() else {
                 continue;
             };
-            let Some(client) = self.function_name_to_client.get(name) else {
+            let Some(&index) = self.function_name_to_client.get(name) else {
                 continue;
             };
 
@@ -216,33 +338,130 @@ impl McpCallableTool {
                 arguments,
             };
 
-            let result = match self.config.timeout {
-                Some(timeout) => {
-                    match tokio::time::timeout(timeout, client.call_tool(request)).await {
-                        Ok(result) => result?,
-                        Err(_) => {
-                            return Err(Error::Timeout {
-                                message: format!("Timed out calling MCP tool: {name}"),
-                            })
-                        }
-                    }
+            // 请求可能已送达服务端，断线后仅对声明只读/幂等的工具重试，其余直接返回错误，
+            // 下次调用时再重连。
+            let peer = self.peer(index).await?;
+            let result = match call_mcp_tool(&peer, request.clone(), self.config.timeout).await {
+                Err(Error::Mcp { source })
+                    if self.should_reconnect(index, &source) && self.is_retry_safe(name) =>
+                {
+                    let peer = self.reconnect(index).await?;
+                    call_mcp_tool(&peer, request, self.config.timeout).await?
                 }
-                None => client.call_tool(request).await?,
+                result => result?,
             };
 
-            let response_value = mcp_result_to_value(&result)?;
-            let function_response = FunctionResponse {
-                will_continue: None,
-                scheduling: None,
-                parts: None,
-                id: call.id.clone(),
-                name: Some(name.clone()),
-                response: Some(response_value),
+            let mut function_response = if self.config.multimodal_responses.unwrap_or(false) {
+                FunctionResponse::from_mcp_response_multimodal(name.clone(), &result)?
+            } else {
+                FunctionResponse::from_mcp_response(name.clone(), &result)?
             };
+            function_response.id.clone_from(&call.id);
             parts.push(Part::function_response(function_response));
         }
         Ok(parts)
     }
+
+    /// 返回可用的 `Peer`；可重连的连接未建立或已断开时先（重新）连接。
+    async fn peer(&mut self, index: usize) -> Result<Peer<RoleClient>> {
+        let slot = &self.clients[index];
+        match &slot.peer {
+            Some(peer) if slot.connector.is_none() || !peer.is_transport_closed() => {
+                Ok(peer.clone())
+            }
+            _ => self.reconnect(index).await,
+        }
+    }
+
+    /// 工具是否通过 annotations 声明为只读或幂等（可安全重试）。
+    fn is_retry_safe(&self, name: &str) -> bool {
+        self.mcp_tools
+            .iter()
+            .find(|tool| tool.name == name)
+            .and_then(|tool| tool.annotations.as_ref())
+            .is_some_and(|annotations| {
+                annotations.read_only_hint == Some(true) || annotations.is_idempotent()
+            })
+    }
+
+    fn should_reconnect(&self, index: usize, error: &ServiceError) -> bool {
+        self.clients[index].connector.is_some()
+            && matches!(
+                error,
+                ServiceError::TransportClosed
+                    | ServiceError::TransportSend(_)
+                    | ServiceError::Cancelled { .. }
+            )
+    }
+
+    async fn reconnect(&mut self, index: usize) -> Result<Peer<RoleClient>> {
+        let Some(connector) = self.clients[index].connector.clone() else {
+            return Err(Error::Mcp {
+                source: ServiceError::TransportClosed,
+            });
+        };
+        let max_attempts = self
+            .config
+            .max_reconnect_attempts
+            .unwrap_or(DEFAULT_MAX_RECONNECT_ATTEMPTS)
+            .max(1);
+        let mut backoff = self
+            .config
+            .reconnect_backoff
+            .unwrap_or(DEFAULT_RECONNECT_BACKOFF);
+        let mut last_error = String::new();
+        for attempt in 1..=max_attempts {
+            match connect_mcp(connector.as_ref(), self.config.connect_timeout).await {
+                Ok(connection) => {
+                    let peer = connection.peer().clone();
+                    let slot = &mut self.clients[index];
+                    slot.peer = Some(peer.clone());
+                    slot.connection = Some(Arc::new(connection));
+                    return Ok(peer);
+                }
+                Err(err) => last_error = err.to_string(),
+            }
+            if attempt < max_attempts {
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+            }
+        }
+        Err(Error::McpReconnectFailed {
+            attempts: max_attempts,
+            message: last_error,
+        })
+    }
+}
+
+async fn connect_mcp(
+    connector: &dyn McpConnector,
+    timeout: Option<Duration>,
+) -> Result<McpConnection> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, connector.connect())
+            .await
+            .map_err(|_| Error::Timeout {
+                message: "Timed out connecting to MCP server".into(),
+            })?,
+        None => connector.connect().await,
+    }
+}
+
+async fn call_mcp_tool(
+    peer: &Peer<RoleClient>,
+    request: CallToolRequestParam,
+    timeout: Option<Duration>,
+) -> Result<CallToolResult> {
+    let name = request.name.to_string();
+    match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, peer.call_tool(request)).await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(Error::Timeout {
+                message: format!("Timed out calling MCP tool: {name}"),
+            }),
+        },
+        None => Ok(peer.call_tool(request).await?),
+    }
 }
 
 impl CallableTool for McpCallableTool {
@@ -268,25 +487,13 @@ fn normalize_mcp_arguments(
     }
 }
 
-fn mcp_result_to_value(result: &rmcp::model::CallToolResult) -> Result<Value> {
-    let value = serde_json::to_value(result)?;
-    let is_error = result.is_error.unwrap_or(false);
-    if is_error {
-        let mut wrapper = Map::new();
-        wrapper.insert("error".to_string(), value);
-        Ok(Value::Object(wrapper))
-    } else {
-        Ok(value)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use rmcp::model::{
-        CallToolResult, ClientInfo, ClientNotification, ClientRequest, ClientResult,
+        CallToolResult, ClientInfo, ClientNotification, ClientRequest, ClientResult, Content,
         ListToolsResult, ServerInfo, ServerNotification, ServerRequest, ServerResult,
-        Tool as McpTool,
+        Tool as McpTool, ToolAnnotations,
     };
     use rmcp::service::{
         serve_client, serve_server, NotificationContext, Peer, RequestContext, RoleClient,
@@ -296,6 +503,8 @@ mod tests {
     use rmcp::ErrorData as McpError;
     use rust_genai_types::content::PartKind;
     use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Mutex;
     use std::time::Duration;
     use tokio::sync::mpsc;
 
@@ -384,13 +593,19 @@ mod tests {
     }
 
     #[test]
-    fn test_mcp_result_to_value_wraps_error() {
+    fn test_mcp_response_wraps_error() {
         let ok_result = CallToolResult::structured(json!({"ok": true}));
-        let ok_value = mcp_result_to_value(&ok_result).unwrap();
+        let ok_value = FunctionResponse::from_mcp_response("ok", &ok_result)
+            .unwrap()
+            .response
+            .unwrap();
         assert!(ok_value.get("error").is_none());
 
         let err_result = CallToolResult::structured_error(json!({"message": "boom"}));
-        let err_value = mcp_result_to_value(&err_result).unwrap();
+        let err_value = FunctionResponse::from_mcp_response("fail", &err_result)
+            .unwrap()
+            .response
+            .unwrap();
         assert!(err_value.get("error").is_some());
     }
 
@@ -464,6 +679,10 @@ mod tests {
                         let result = match name {
                             "ok" => CallToolResult::structured(json!({"ok": true})),
                             "fail" => CallToolResult::structured_error(json!({"error": "boom"})),
+                            "chart" => CallToolResult::success(vec![
+                                Content::text("chart rendered"),
+                                Content::image("iVBORw==", "image/png"),
+                            ]),
                             "slow" => {
                                 tokio::time::sleep(Duration::from_millis(50)).await;
                                 CallToolResult::structured(json!({"slow": true}))
@@ -542,6 +761,165 @@ mod tests {
         (peer, client_service, server_service)
     }
 
+    type ServerHandles = Arc<Mutex<Vec<rmcp::service::RunningService<RoleServer, TestServer>>>>;
+
+    /// 每次连接启动新的进程内 server；第 `fail_after` 次及之后的连接直接失败。
+    fn test_connector(
+        tools: Vec<McpTool>,
+        connects: Arc<AtomicUsize>,
+        fail_after: usize,
+        servers: ServerHandles,
+    ) -> Arc<dyn McpConnector> {
+        Arc::new(move || {
+            let tools = tools.clone();
+            let connects = connects.clone();
+            let servers = servers.clone();
+            async move {
+                if connects.fetch_add(1, Ordering::SeqCst) >= fail_after {
+                    return Err(Error::Mcp {
+                        source: ServiceError::TransportClosed,
+                    });
+                }
+                let (client, server) = connect_test_client(tools).await;
+                servers.lock().unwrap().push(server);
+                Ok(client)
+            }
+        })
+    }
+
+    async fn connect_test_client(
+        tools: Vec<McpTool>,
+    ) -> (
+        McpConnection,
+        rmcp::service::RunningService<RoleServer, TestServer>,
+    ) {
+        let (client_tx, client_rx) = mpsc::unbounded_channel::<TxJsonRpcMessage<RoleClient>>();
+        let (server_tx, server_rx) = mpsc::unbounded_channel::<TxJsonRpcMessage<RoleServer>>();
+        let server_task = tokio::spawn(serve_server(
+            TestServer::new(tools),
+            ChannelTransport::new(server_tx, client_rx),
+        ));
+        let client = serve_client(TestClient, ChannelTransport::new(client_tx, server_rx))
+            .await
+            .unwrap();
+        (client.into(), server_task.await.unwrap().unwrap())
+    }
+
+    async fn drop_servers(servers: &ServerHandles) {
+        let running = std::mem::take(&mut *servers.lock().unwrap());
+        for server in running {
+            server.cancel().await.unwrap();
+        }
+    }
+
+    fn ok_call(id: &str) -> Vec<FunctionCall> {
+        vec![FunctionCall {
+            id: Some(id.into()),
+            name: Some("ok".into()),
+            args: None,
+            partial_args: None,
+            will_continue: None,
+        }]
+    }
+
+    #[tokio::test]
+    async fn test_mcp_callable_tool_reconnects_after_drop() {
+        let connects = Arc::new(AtomicUsize::new(0));
+        let servers = ServerHandles::default();
+        let connector = test_connector(
+            vec![McpTool::new("ok", "desc", test_schema())],
+            connects.clone(),
+            usize::MAX,
+            servers.clone(),
+        );
+        let config = McpCallableToolConfig {
+            reconnect_backoff: Some(Duration::from_millis(1)),
+            ..Default::default()
+        };
+        let mut tool = McpCallableTool::with_connectors(vec![connector], config);
+
+        assert_eq!(tool.call_tool(&ok_call("call-5")).await.unwrap().len(), 1);
+        assert_eq!(connects.load(Ordering::SeqCst), 1);
+
+        drop_servers(&servers).await;
+        assert_eq!(tool.call_tool(&ok_call("call-6")).await.unwrap().len(), 1);
+        assert_eq!(connects.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_callable_tool_reconnect_gives_up_after_retries() {
+        let connects = Arc::new(AtomicUsize::new(0));
+        let servers = ServerHandles::default();
+        let connector = test_connector(
+            vec![McpTool::new("ok", "desc", test_schema())],
+            connects.clone(),
+            1,
+            servers.clone(),
+        );
+        let config = McpCallableToolConfig {
+            max_reconnect_attempts: Some(2),
+            reconnect_backoff: Some(Duration::from_millis(1)),
+            ..Default::default()
+        };
+        let mut tool = McpCallableTool::with_connectors(vec![connector], config);
+        tool.initialize().await.unwrap();
+
+        drop_servers(&servers).await;
+        let result = tool.call_tool(&ok_call("call-7")).await;
+        assert!(matches!(
+            result,
+            Err(Error::McpReconnectFailed { attempts: 2, .. })
+        ));
+        assert_eq!(connects.load(Ordering::SeqCst), 3);
+    }
+
+    fn slow_call(id: &str) -> Vec<FunctionCall> {
+        vec![FunctionCall {
+            id: Some(id.into()),
+            name: Some("slow".into()),
+            args: None,
+            partial_args: None,
+            will_continue: None,
+        }]
+    }
+
+    /// 在 `slow` 调用进行中断开服务端，返回调用结果与累计连接次数。
+    async fn call_slow_while_disconnecting(tool: McpTool) -> (Result<Vec<Part>>, usize) {
+        let connects = Arc::new(AtomicUsize::new(0));
+        let servers = ServerHandles::default();
+        let connector = test_connector(vec![tool], connects.clone(), usize::MAX, servers.clone());
+        let config = McpCallableToolConfig {
+            reconnect_backoff: Some(Duration::from_millis(1)),
+            ..Default::default()
+        };
+        let mut tool = McpCallableTool::with_connectors(vec![connector], config);
+        tool.initialize().await.unwrap();
+
+        let calls = slow_call("call-slow");
+        let (result, ()) = tokio::join!(tool.call_tool(&calls), async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop_servers(&servers).await;
+        });
+        (result, connects.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_mcp_callable_tool_does_not_retry_interrupted_call() {
+        let (result, connects) =
+            call_slow_while_disconnecting(McpTool::new("slow", "desc", test_schema())).await;
+        assert!(matches!(result, Err(Error::Mcp { .. })));
+        assert_eq!(connects, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_callable_tool_retries_interrupted_idempotent_call() {
+        let tool = McpTool::new("slow", "desc", test_schema())
+            .annotate(ToolAnnotations::new().idempotent(true));
+        let (result, connects) = call_slow_while_disconnecting(tool).await;
+        assert_eq!(result.unwrap().len(), 1);
+        assert_eq!(connects, 2);
+    }
+
     #[tokio::test]
     async fn test_mcp_callable_tool_roundtrip() {
         let tools = vec![McpTool::new("ok", "desc", test_schema())];
@@ -572,6 +950,63 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_mcp_callable_tool_preserves_image_content() {
+        let tools = vec![McpTool::new("chart", "desc", test_schema())];
+        let (peer, _client_service, _server_service) = setup_peer(tools).await;
+
+        let config = McpCallableToolConfig {
+            multimodal_responses: Some(true),
+            ..Default::default()
+        };
+        let mut tool = McpCallableTool::new(vec![peer], config);
+        let calls = vec![FunctionCall {
+            id: Some("call-4".into()),
+            name: Some("chart".into()),
+            args: None,
+            partial_args: None,
+            will_continue: None,
+        }];
+        let parts = tool.call_tool(&calls).await.unwrap();
+        match &parts[0].kind {
+            PartKind::FunctionResponse { function_response } => {
+                assert_eq!(function_response.id.as_deref(), Some("call-4"));
+                let media = function_response.parts.as_ref().unwrap();
+                let blob = media[0].inline_data.as_ref().unwrap();
+                assert_eq!(blob.mime_type, "image/png");
+                assert_eq!(blob.data, vec![0x89, 0x50, 0x4e, 0x47]);
+                let response = function_response.response.as_ref().unwrap();
+                assert_eq!(response["content"][0]["text"], "chart rendered");
+            }
+            _ => panic!("expected function response part"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mcp_callable_tool_encodes_image_content_as_json_by_default() {
+        let tools = vec![McpTool::new("chart", "desc", test_schema())];
+        let (peer, _client_service, _server_service) = setup_peer(tools).await;
+
+        let mut tool = McpCallableTool::new(vec![peer], McpCallableToolConfig::default());
+        let calls = vec![FunctionCall {
+            id: Some("call-5".into()),
+            name: Some("chart".into()),
+            args: None,
+            partial_args: None,
+            will_continue: None,
+        }];
+        let parts = tool.call_tool(&calls).await.unwrap();
+        match &parts[0].kind {
+            PartKind::FunctionResponse { function_response } => {
+                assert!(function_response.parts.is_none());
+                let response = function_response.response.as_ref().unwrap();
+                assert_eq!(response["content"].as_array().unwrap().len(), 2);
+                assert_eq!(response["content"][1]["mimeType"], "image/png");
+            }
+            _ => panic!("expected function response part"),
+        }
+    }
+
     #[tokio::test]
     async fn test_mcp_callable_tool_error_and_timeout() {
         let tools = vec![
@@ -582,7 +1017,7 @@ mod tests {
 
         let config = McpCallableToolConfig {
             timeout: Some(Duration::from_millis(5)),
-            behavior: None,
+            ..Default::default()
         };
         let mut tool = McpCallableTool::new(vec![peer], config);
 
@@ -1,22 +1,31 @@
 //! Model capability checks and feature gating.
 
+use std::ops::RangeInclusive;
+
+use rust_genai_types::config::GenerationConfig;
 use rust_genai_types::content::{Content, PartKind};
+use rust_genai_types::enums::Modality;
 use rust_genai_types::tool::Tool;
 
 use crate::error::{Error, Result};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ModelCapabilities {
-    flags: u8,
+    flags: u16,
 }
 
 impl ModelCapabilities {
-    const FUNCTION_RESPONSE_MEDIA: u8 = 1 << 0;
-    const CODE_EXECUTION_IMAGES: u8 = 1 << 1;
-    const NATIVE_AUDIO: u8 = 1 << 2;
-    const THINKING: u8 = 1 << 3;
+    const FUNCTION_RESPONSE_MEDIA: u16 = 1 << 0;
+    const CODE_EXECUTION_IMAGES: u16 = 1 << 1;
+    const NATIVE_AUDIO: u16 = 1 << 2;
+    const THINKING: u16 = 1 << 3;
+    const IMAGE_OUTPUT: u16 = 1 << 4;
+    const AUDIO_OUTPUT: u16 = 1 << 5;
+    const SEED: u16 = 1 << 6;
+    const LOGPROBS: u16 = 1 << 7;
+    const COMBINED_TOOLS: u16 = 1 << 8;
 
-    const fn new(flags: u8) -> Self {
+    const fn new(flags: u16) -> Self {
         Self { flags }
     }
 
@@ -39,6 +48,32 @@ impl ModelCapabilities {
     pub const fn supports_thinking(self) -> bool {
         self.flags & Self::THINKING != 0
     }
+
+    #[must_use]
+    pub const fn supports_image_output(self) -> bool {
+        self.flags & Self::IMAGE_OUTPUT != 0
+    }
+
+    #[must_use]
+    pub const fn supports_audio_output(self) -> bool {
+        self.flags & Self::AUDIO_OUTPUT != 0
+    }
+
+    #[must_use]
+    pub const fn supports_seed(self) -> bool {
+        self.flags & Self::SEED != 0
+    }
+
+    #[must_use]
+    pub const fn supports_logprobs(self) -> bool {
+        self.flags & Self::LOGPROBS != 0
+    }
+
+    /// 是否支持在同一请求中混用函数声明与内置工具（Google 搜索、代码执行等）。
+    #[must_use]
+    pub const fn supports_combined_tools(self) -> bool {
+        self.flags & Self::COMBINED_TOOLS != 0
+    }
 }
 
 #[must_use]
@@ -50,16 +85,40 @@ pub fn capabilities_for(model: &str) -> ModelCapabilities {
     if is_gemini_3 {
         flags |= ModelCapabilities::FUNCTION_RESPONSE_MEDIA;
         flags |= ModelCapabilities::CODE_EXECUTION_IMAGES;
+        flags |= ModelCapabilities::COMBINED_TOOLS;
     }
     if supports_native_audio {
         flags |= ModelCapabilities::NATIVE_AUDIO;
     }
-    if is_gemini_3 || name.contains("thinking") {
+    if is_gemini_3 || name.starts_with("gemini-2.5") || name.contains("thinking") {
         flags |= ModelCapabilities::THINKING;
     }
+    if name.contains("image") || name == "gemini-2.0-flash-exp" {
+        flags |= ModelCapabilities::IMAGE_OUTPUT;
+    }
+    if supports_native_audio || name.contains("tts") {
+        flags |= ModelCapabilities::AUDIO_OUTPUT;
+    }
+    let is_speech = supports_native_audio || name.contains("tts") || name.contains("live");
+    if !is_speech {
+        flags |= ModelCapabilities::SEED;
+    }
+    if !is_speech && !is_gemini_3 && !name.contains("image") && !name.contains("thinking") {
+        flags |= ModelCapabilities::LOGPROBS;
+    }
     ModelCapabilities::new(flags)
 }
 
+/// 模型创建上下文缓存所需的最少输入 token 数（Pro 系列 4096，其余 1024）。
+#[must_use]
+pub fn min_cache_tokens(model: &str) -> usize {
+    if normalize_model_name(model).contains("-pro") {
+        4096
+    } else {
+        1024
+    }
+}
+
 /// # Errors
 /// 当模型不支持功能响应多媒体时返回错误。
 pub fn validate_function_response_media(model: &str, contents: &[Content]) -> Result<()> {
@@ -94,6 +153,240 @@ pub fn validate_code_execution_image_inputs(
     Ok(())
 }
 
+/// # Errors
+/// 当 `response_modalities` 请求了已知 Gemini 模型不支持的图像/音频输出时返回错误。
+///
+/// 非 `gemini-*` 名称（调优模型、端点等）无法判断能力，直接放行。
+pub fn validate_response_modalities(
+    model: &str,
+    generation_config: Option<&GenerationConfig>,
+) -> Result<()> {
+    let Some(modalities) = generation_config.and_then(|config| config.response_modalities.as_ref())
+    else {
+        return Ok(());
+    };
+    if !normalize_model_name(model).starts_with("gemini-") {
+        return Ok(());
+    }
+    let caps = capabilities_for(model);
+    for modality in modalities {
+        let supported = match modality {
+            Modality::Image => caps.supports_image_output(),
+            Modality::Audio => caps.supports_audio_output(),
+            Modality::Text | Modality::ModalityUnspecified => true,
+        };
+        if !supported {
+            return Err(Error::InvalidConfig {
+                message: format!("Model {model} does not support {modality:?} response modality"),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 已知 Gemini 模型的输入 token 上限；非 `gemini-*` 名称无法判断，返回 `None`。
+#[must_use]
+pub fn input_token_limit(model: &str) -> Option<usize> {
+    let name = normalize_model_name(model);
+    if !name.starts_with("gemini-") {
+        return None;
+    }
+    let limit = if name.contains("tts") {
+        8_192
+    } else if name.contains("image") {
+        32_768
+    } else if name.contains("native-audio") {
+        131_072
+    } else if name.starts_with("gemini-1.5-pro") {
+        2_097_152
+    } else {
+        1_048_576
+    };
+    Some(limit)
+}
+
+/// 已知 Gemini 模型允许的 `thinking_budget` 范围（不含表示动态预算的 `-1`）。
+///
+/// 已知不支持思考的模型（`gemini-1.x`、非 thinking 的 `gemini-2.0`）仅允许 `0`；
+/// 别名（如 `gemini-flash-latest`）、调优模型等无法判断的名称返回 `None`。
+#[must_use]
+pub fn thinking_budget_range(model: &str) -> Option<RangeInclusive<i32>> {
+    let name = normalize_model_name(model);
+    if !capabilities_for(&name).supports_thinking() {
+        return is_known_non_thinking_model(&name).then_some(0..=0);
+    }
+    if name.contains("-pro") {
+        Some(128..=32_768)
+    } else {
+        Some(0..=24_576)
+    }
+}
+
+fn is_known_non_thinking_model(name: &str) -> bool {
+    name.starts_with("gemini-1.") || (name.starts_with("gemini-2.0") && !name.contains("thinking"))
+}
+
+/// # Errors
+/// 当 `thinking_budget` 超出模型允许范围（或模型不支持思考）时返回错误。
+pub fn validate_thinking_budget(
+    model: &str,
+    generation_config: Option<&GenerationConfig>,
+) -> Result<()> {
+    let Some(budget) = generation_config
+        .and_then(|config| config.thinking_config.as_ref())
+        .and_then(|thinking| thinking.thinking_budget)
+    else {
+        return Ok(());
+    };
+    let Some(range) = thinking_budget_range(model) else {
+        return Ok(());
+    };
+    if range == (0..=0) {
+        if budget != 0 {
+            return Err(Error::InvalidConfig {
+                message: format!(
+                    "Model {model} does not support thinking; thinking_budget must be 0, got {budget}"
+                ),
+            });
+        }
+        return Ok(());
+    }
+    if budget != -1 && !range.contains(&budget) {
+        return Err(Error::InvalidConfig {
+            message: format!(
+                "thinking_budget for model {model} must be -1 or between {} and {}, got {budget}",
+                range.start(),
+                range.end()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// # Errors
+/// 当模型不支持 `seed` 或 `response_logprobs`/`logprobs` 却设置了对应字段时返回错误。
+///
+/// 非 `gemini-*` 名称无法判断能力，直接放行。
+pub fn validate_sampling_features(
+    model: &str,
+    generation_config: Option<&GenerationConfig>,
+) -> Result<()> {
+    let Some(config) = generation_config else {
+        return Ok(());
+    };
+    if !normalize_model_name(model).starts_with("gemini-") {
+        return Ok(());
+    }
+    let caps = capabilities_for(model);
+    if config.seed.is_some() && !caps.supports_seed() {
+        return Err(Error::InvalidConfig {
+            message: format!("Model {model} does not support seed"),
+        });
+    }
+    let wants_logprobs = config.response_logprobs == Some(true) || config.logprobs.is_some();
+    if wants_logprobs && !caps.supports_logprobs() {
+        return Err(Error::InvalidConfig {
+            message: format!("Model {model} does not support response_logprobs/logprobs"),
+        });
+    }
+    Ok(())
+}
+
+/// 检查必然无效的工具冲突（每次请求前都会执行）。
+///
+/// # Errors
+/// 当 `file_search` 与其他工具混用，或 `google_search` 与 `google_search_retrieval`
+/// 同时出现时返回错误。
+pub fn validate_tool_conflicts(tools: Option<&[Tool]>) -> Result<()> {
+    let tools = tools.unwrap_or_default();
+    check_tool_conflicts(tools, has_function_declarations(tools))
+}
+
+/// 按模型名检查工具组合（启发式，需显式调用，例如通过 [`crate::afc::ToolSet::validate`]）。
+///
+/// 在 [`validate_tool_conflicts`] 的基础上，额外拒绝已知 Gemini 模型上函数声明与内置工具混用；
+/// 该判断仅基于模型名，可能落后于服务端实际能力。
+///
+/// # Errors
+/// 当工具组合不被支持时返回错误。
+pub fn validate_tool_combination(model: &str, tools: Option<&[Tool]>) -> Result<()> {
+    let tools = tools.unwrap_or_default();
+    check_tool_combination(model, tools, has_function_declarations(tools))
+}
+
+pub(crate) fn check_tool_combination(
+    model: &str,
+    tools: &[Tool],
+    has_functions: bool,
+) -> Result<()> {
+    check_tool_conflicts(tools, has_functions)?;
+    let builtin = builtin_tool_names(tools);
+    if has_functions
+        && !builtin.is_empty()
+        && normalize_model_name(model).starts_with("gemini-")
+        && !capabilities_for(model).supports_combined_tools()
+    {
+        return Err(Error::InvalidConfig {
+            message: format!(
+                "Model {model} does not support combining function declarations with built-in tools ({})",
+                builtin.join(", ")
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn check_tool_conflicts(tools: &[Tool], has_functions: bool) -> Result<()> {
+    let builtin = builtin_tool_names(tools);
+    if builtin.contains(&"file_search") && (builtin.len() > 1 || has_functions) {
+        return Err(Error::InvalidConfig {
+            message: "file_search cannot be combined with other tools".into(),
+        });
+    }
+    if builtin.contains(&"google_search") && builtin.contains(&"google_search_retrieval") {
+        return Err(Error::InvalidConfig {
+            message: "google_search and google_search_retrieval cannot be used together".into(),
+        });
+    }
+    Ok(())
+}
+
+fn has_function_declarations(tools: &[Tool]) -> bool {
+    tools.iter().any(|tool| {
+        tool.function_declarations
+            .as_ref()
+            .is_some_and(|declarations| !declarations.is_empty())
+    })
+}
+
+fn builtin_tool_names(tools: &[Tool]) -> Vec<&'static str> {
+    let mut names = Vec::new();
+    for tool in tools {
+        let present = [
+            ("google_search", tool.google_search.is_some()),
+            (
+                "google_search_retrieval",
+                tool.google_search_retrieval.is_some(),
+            ),
+            ("google_maps", tool.google_maps.is_some()),
+            ("code_execution", tool.code_execution.is_some()),
+            ("url_context", tool.url_context.is_some()),
+            ("file_search", tool.file_search.is_some()),
+            ("retrieval", tool.retrieval.is_some()),
+            (
+                "enterprise_web_search",
+                tool.enterprise_web_search.is_some(),
+            ),
+        ];
+        for (name, is_set) in present {
+            if is_set && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
 fn normalize_model_name(model: &str) -> String {
     model.rsplit('/').next().unwrap_or(model).to_string()
 }
@@ -146,7 +439,9 @@ mod tests {
         Content, FunctionResponse, FunctionResponseBlob, FunctionResponseFileData,
         FunctionResponsePart, Part, Role,
     };
-    use rust_genai_types::tool::Tool;
+    use rust_genai_types::tool::{
+        CodeExecution, FileSearch, FunctionDeclaration, GoogleSearch, GoogleSearchRetrieval, Tool,
+    };
     use serde_json::json;
 
     #[test]
@@ -161,6 +456,133 @@ mod tests {
         assert!(!caps.supports_function_response_media());
     }
 
+    #[test]
+    fn validate_response_modalities_checks_output_capabilities() {
+        let config =
+            GenerationConfig::default().with_response_modalities([Modality::Text, Modality::Image]);
+        assert!(validate_response_modalities("gemini-2.5-flash-image", Some(&config)).is_ok());
+        assert!(validate_response_modalities("gemini-2.5-flash", Some(&config)).is_err());
+        assert!(validate_response_modalities("tunedModels/custom", Some(&config)).is_ok());
+
+        let audio = GenerationConfig::default().with_response_modalities([Modality::Audio]);
+        assert!(validate_response_modalities("gemini-2.5-flash-preview-tts", Some(&audio)).is_ok());
+        assert!(validate_response_modalities("gemini-2.5-pro", Some(&audio)).is_err());
+        assert!(validate_response_modalities("gemini-2.5-pro", None).is_ok());
+    }
+
+    #[test]
+    fn validate_thinking_budget_checks_model_ranges() {
+        let config = |budget| GenerationConfig::default().with_thinking_budget(budget);
+        assert!(validate_thinking_budget("gemini-2.5-flash", Some(&config(1024))).is_ok());
+        assert!(validate_thinking_budget("gemini-2.5-flash", Some(&config(-1))).is_ok());
+        assert!(validate_thinking_budget("gemini-2.5-flash", Some(&config(30_000))).is_err());
+        assert!(validate_thinking_budget("models/gemini-2.5-pro", Some(&config(0))).is_err());
+        assert!(validate_thinking_budget("gemini-2.0-flash", Some(&config(0))).is_ok());
+        assert!(validate_thinking_budget("gemini-2.0-flash", Some(&config(1024))).is_err());
+        assert!(validate_thinking_budget("tunedModels/custom", Some(&config(99_999))).is_ok());
+        assert!(validate_thinking_budget("gemini-flash-latest", Some(&config(1024))).is_ok());
+        assert!(validate_thinking_budget("gemini-1.5-pro", Some(&config(1024))).is_err());
+        assert!(validate_thinking_budget("gemini-2.0-flash", None).is_ok());
+    }
+
+    #[test]
+    fn validate_sampling_features_checks_seed_and_logprobs() {
+        let seed = GenerationConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+        assert!(validate_sampling_features("gemini-2.5-flash", Some(&seed)).is_ok());
+        assert!(validate_sampling_features("gemini-2.5-flash-preview-tts", Some(&seed)).is_err());
+
+        let logprobs = GenerationConfig {
+            response_logprobs: Some(true),
+            logprobs: Some(3),
+            ..Default::default()
+        };
+        assert!(validate_sampling_features("models/gemini-2.0-flash", Some(&logprobs)).is_ok());
+        assert!(validate_sampling_features("gemini-2.5-flash-image", Some(&logprobs)).is_err());
+        assert!(validate_sampling_features("gemini-3-pro-preview", Some(&logprobs)).is_err());
+        assert!(validate_sampling_features("tunedModels/custom", Some(&logprobs)).is_ok());
+
+        let disabled = GenerationConfig {
+            response_logprobs: Some(false),
+            ..Default::default()
+        };
+        assert!(validate_sampling_features("gemini-3-pro-preview", Some(&disabled)).is_ok());
+        assert!(validate_sampling_features("gemini-3-pro-preview", None).is_ok());
+    }
+
+    #[test]
+    fn validate_tool_combination_checks_conflicts() {
+        let function = Tool {
+            function_declarations: Some(vec![FunctionDeclaration {
+                name: "lookup".into(),
+                description: None,
+                parameters: None,
+                parameters_json_schema: None,
+                response: None,
+                response_json_schema: None,
+                behavior: None,
+            }]),
+            ..Default::default()
+        };
+        let search = Tool {
+            google_search: Some(GoogleSearch::default()),
+            ..Default::default()
+        };
+        let tools = [function, search.clone()];
+        let err = validate_tool_combination("gemini-2.5-flash", Some(&tools)).unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidConfig { message } if message.contains("google_search"))
+        );
+        assert!(validate_tool_combination("gemini-3-pro-preview", Some(&tools)).is_ok());
+        assert!(validate_tool_combination("tunedModels/custom", Some(&tools)).is_ok());
+
+        let code = Tool {
+            code_execution: Some(CodeExecution::default()),
+            ..Default::default()
+        };
+        let builtin_only = [search.clone(), code];
+        assert!(validate_tool_combination("gemini-2.5-flash", Some(&builtin_only)).is_ok());
+
+        let file_search = Tool {
+            file_search: Some(FileSearch::default()),
+            ..Default::default()
+        };
+        let with_file_search = [search.clone(), file_search];
+        assert!(
+            validate_tool_combination("gemini-3-pro-preview", Some(&with_file_search)).is_err()
+        );
+
+        let legacy = Tool {
+            google_search_retrieval: Some(GoogleSearchRetrieval::default()),
+            ..Default::default()
+        };
+        assert!(validate_tool_conflicts(Some(&[search.clone(), legacy.clone()])).is_err());
+        assert!(validate_tool_combination("gemini-1.5-flash", Some(&[search, legacy])).is_err());
+        assert!(validate_tool_combination("gemini-2.5-flash", None).is_ok());
+        assert!(validate_tool_conflicts(Some(&tools)).is_ok());
+        assert!(validate_tool_conflicts(Some(&with_file_search)).is_err());
+    }
+
+    #[test]
+    fn input_token_limit_by_model_family() {
+        assert_eq!(
+            input_token_limit("models/gemini-2.5-flash"),
+            Some(1_048_576)
+        );
+        assert_eq!(input_token_limit("gemini-1.5-pro-002"), Some(2_097_152));
+        assert_eq!(input_token_limit("gemini-2.5-flash-image"), Some(32_768));
+        assert_eq!(input_token_limit("tunedModels/custom"), None);
+    }
+
+    #[test]
+    fn min_cache_tokens_by_model_family() {
+        assert_eq!(min_cache_tokens("gemini-2.5-flash"), 1024);
+        assert_eq!(min_cache_tokens("models/gemini-2.5-pro"), 4096);
+        assert_eq!(min_cache_tokens("gemini-3-pro-preview"), 4096);
+    }
+
     #[test]
     fn validate_function_response_media_blocks_unsupported_models() {
         let response = FunctionResponse {
@@ -5,10 +5,14 @@ use std::hash::BuildHasher;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
-use futures_util::{Stream, StreamExt};
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::{Stream, StreamExt, TryStreamExt};
 use rust_genai_types::content::{Content, FunctionCall, Part, PartKind, Role};
 use rust_genai_types::converters;
+use rust_genai_types::enums::FinishReason;
+use rust_genai_types::grounding::{CitationMetadata, GroundingMetadata};
 use rust_genai_types::models::{
     ComputeTokensConfig, ComputeTokensRequest, ComputeTokensResponse, CountTokensConfig,
     CountTokensRequest, CountTokensResponse, DeleteModelConfig, DeleteModelResponse,
@@ -19,30 +23,48 @@ use rust_genai_types::models::{
     ReferenceImage, SegmentImageConfig, SegmentImageResponse, SegmentImageSource,
     UpdateModelConfig,
 };
-use rust_genai_types::response::{GenerateContentResponse, GenerateContentResponseUsageMetadata};
+use rust_genai_types::response::{
+    GenerateContentResponse, GenerateContentResponseUsageMetadata, PromptFeedback,
+};
 use serde::de::DeserializeOwned;
 
 use crate::afc::{
     call_callable_tools, max_remote_calls, resolve_callable_tools, should_append_history,
-    should_disable_afc, validate_afc_config, validate_afc_tools, CallableTool,
+    should_disable_afc, should_error_on_max_remote_calls, stream_buffer_size, total_timeout,
+    total_timeout_error, validate_afc_config, validate_afc_tools, with_total_timeout, CallableTool,
 };
 use crate::client::{Backend, ClientInner};
-use crate::error::{Error, Result};
+use crate::error::{ContentBlockReason, Error, Result};
 use crate::http_response::{
     sdk_http_response_from_headers, sdk_http_response_from_headers_and_body,
 };
 use crate::model_capabilities::{
-    validate_code_execution_image_inputs, validate_function_response_media,
+    input_token_limit, validate_code_execution_image_inputs, validate_function_response_media,
+    validate_response_modalities, validate_sampling_features, validate_thinking_budget,
+    validate_tool_conflicts,
 };
-use crate::sse::{parse_sse_stream, parse_sse_stream_with_done_signal};
+use crate::pagination::paginate;
+use crate::sse::SseJsonStream;
 use crate::thinking::{validate_temperature, ThoughtSignatureValidator};
+use crate::token_cache::TokenCountCache;
 use crate::tokenizer::TokenEstimator;
 use serde_json::Value;
 
 mod builders;
 mod http;
+mod json_repair;
 mod media;
 pub(crate) mod parsers;
+mod roles;
+mod stream_accumulator;
+mod stream_restart;
+
+use media::validate_media_limits;
+pub use media::{
+    image_dimensions, validate_image_limits, validate_image_source, validate_video_limits,
+    MAX_IMAGE_DIMENSION, MAX_INLINE_IMAGE_BYTES, MAX_INLINE_VIDEO_BYTES,
+};
+pub use stream_accumulator::StreamAccumulator;
 
 use builders::{
     build_edit_image_body, build_embed_body_gemini, build_embed_body_vertex,
@@ -51,13 +73,15 @@ use builders::{
 };
 use http::{
     apply_http_options, build_model_get_url, build_model_get_url_with_options,
-    build_model_method_url, build_models_list_url, merge_extra_body,
+    build_model_method_url, build_model_method_url_with_options, build_models_list_url,
+    merge_extra_body, stream_idle_timeout,
 };
 use parsers::{
     convert_vertex_embed_response, parse_edit_image_response, parse_generate_images_response,
     parse_generate_videos_operation, parse_recontext_image_response, parse_segment_image_response,
     parse_upscale_image_response,
 };
+use roles::check_content_roles;
 
 #[derive(Clone)]
 pub struct Models {
@@ -78,10 +102,114 @@ pub enum GenerateContentStreamEvent {
     Done(GenerateContentResponse),
 }
 
+/// Model identifier accepted by [`Models`] methods.
+///
+/// Plain strings are normalized to `models/...` (Gemini API) or
+/// `publishers/google/models/...` (Vertex AI); [`RawModel`] values are used verbatim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelName {
+    name: String,
+    raw: bool,
+}
+
+impl ModelName {
+    /// Returns the model identifier as provided by the caller.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` when the name bypasses the automatic prefix transform.
+    #[must_use]
+    pub const fn is_raw(&self) -> bool {
+        self.raw
+    }
+}
+
+impl<T: Into<String>> From<T> for ModelName {
+    fn from(name: T) -> Self {
+        Self {
+            name: name.into(),
+            raw: false,
+        }
+    }
+}
+
+impl From<&Self> for ModelName {
+    fn from(name: &Self) -> Self {
+        name.clone()
+    }
+}
+
+/// Fully-qualified model resource used verbatim in request URLs.
+///
+/// Use this for third-party publisher models (e.g. `publishers/meta/models/...`)
+/// or custom endpoints that must not receive the default `models/` prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawModel(pub String);
+
+impl RawModel {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+impl From<RawModel> for ModelName {
+    fn from(model: RawModel) -> Self {
+        Self {
+            name: model.0,
+            raw: true,
+        }
+    }
+}
+
+/// `generate_content` 将要发送的请求，由 [`Models::generate_content_request_preview`] 返回。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestPreview {
+    /// 完整解析后的请求 URL（含 base URL、API 版本与模型路径）。
+    pub url: String,
+    /// 对应后端格式的 JSON 请求体，已合并 `extra_body` 覆盖项。
+    pub body: Value,
+}
+
+/// Typed item yielded by automatic-function-calling streams.
+#[derive(Debug, Clone)]
+pub enum StreamItem {
+    /// Response chunk generated by the model.
+    ModelChunk(GenerateContentResponse),
+    /// Function calls requested by the model, emitted before the tools run.
+    ToolCall(Vec<FunctionCall>),
+    /// Synthetic `function` turn carrying the local tool results.
+    ToolResult(GenerateContentResponse),
+}
+
+/// [`Models::generate_content_stream_callback`] 返回的后台任务句柄。
+///
+/// 丢弃句柄不会停止任务；需要中止时调用 [`StreamCallbackHandle::cancel`]。
+#[derive(Debug)]
+pub struct StreamCallbackHandle {
+    task: crate::rt::JoinHandle<()>,
+    abort: AbortHandle,
+}
+
+impl StreamCallbackHandle {
+    /// 取消后台流式任务：停止读取流，不再调用 `on_chunk`，并以 [`Error::Cancelled`] 调用 `on_done`
+    /// （任务已结束时无效果）。
+    pub fn cancel(&self) {
+        self.abort.abort();
+    }
+
+    /// 后台任务是否已结束（完成或被取消）。
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
 pub struct GenerateContentEventStream {
     inner: Pin<Box<dyn Stream<Item = Result<GenerateContentResponse>> + Send>>,
     pending: VecDeque<GenerateContentStreamEvent>,
-    aggregate_response: Option<GenerateContentResponse>,
+    accumulator: StreamAccumulator,
     saw_done: Arc<AtomicBool>,
     finished: bool,
 }
@@ -94,7 +222,7 @@ impl GenerateContentEventStream {
         Self {
             inner,
             pending: VecDeque::new(),
-            aggregate_response: None,
+            accumulator: StreamAccumulator::new(),
             saw_done,
             finished: false,
         }
@@ -112,7 +240,7 @@ impl GenerateContentEventStream {
 
             match self.inner.next().await {
                 Some(Ok(response)) => {
-                    merge_stream_response(&mut self.aggregate_response, &response);
+                    self.accumulator.push(&response);
                     enqueue_stream_events(&mut self.pending, response);
                 }
                 Some(Err(err)) => {
@@ -121,13 +249,12 @@ impl GenerateContentEventStream {
                 }
                 None => {
                     self.finished = true;
+                    let accumulator = std::mem::take(&mut self.accumulator);
                     if self.saw_done.load(Ordering::Relaxed) {
-                        if let Some(mut response) = self.aggregate_response.take() {
-                            normalize_stream_candidate_order(&mut response);
+                        if let Some(response) = accumulator.into_response() {
                             return Ok(Some(GenerateContentStreamEvent::Done(response)));
                         }
                     }
-                    self.aggregate_response.take();
                     return Ok(None);
                 }
             }
@@ -159,6 +286,23 @@ fn enqueue_stream_events(
     pending.push_back(GenerateContentStreamEvent::Response(response));
 }
 
+/// 拦截原因只在首个 chunk 中给出，后续 chunk 的 `prompt_feedback` 不应覆盖它。
+fn merge_prompt_feedback(existing: &mut Option<PromptFeedback>, next: &PromptFeedback) {
+    let Some(existing) = existing.as_mut() else {
+        *existing = Some(next.clone());
+        return;
+    };
+    if existing.block_reason.is_none() {
+        existing.block_reason = next.block_reason;
+        existing
+            .block_reason_message
+            .clone_from(&next.block_reason_message);
+    }
+    if !next.safety_ratings.is_empty() {
+        existing.safety_ratings.clone_from(&next.safety_ratings);
+    }
+}
+
 fn merge_stream_response(
     aggregate: &mut Option<GenerateContentResponse>,
     response: &GenerateContentResponse,
@@ -172,7 +316,9 @@ fn merge_stream_response(
         usage_metadata: response.usage_metadata.clone(),
         model_version: response.model_version.clone(),
         response_id: response.response_id.clone(),
+        extra: HashMap::new(),
     });
+    aggregate.extra.extend(response.extra.clone());
 
     if response.sdk_http_response.is_some() {
         aggregate.sdk_http_response = response.sdk_http_response.clone();
@@ -184,8 +330,8 @@ fn merge_stream_response(
         aggregate.automatic_function_calling_history =
             response.automatic_function_calling_history.clone();
     }
-    if response.prompt_feedback.is_some() {
-        aggregate.prompt_feedback = response.prompt_feedback.clone();
+    if let Some(feedback) = &response.prompt_feedback {
+        merge_prompt_feedback(&mut aggregate.prompt_feedback, feedback);
     }
     if let Some(next_usage) = &response.usage_metadata {
         match &mut aggregate.usage_metadata {
@@ -226,6 +372,23 @@ fn merge_stream_response(
     }
 }
 
+/// 将聚合响应中的流式 `partial_args` 组装为完整的 `args`。
+fn finalize_stream_function_calls(response: &mut GenerateContentResponse) {
+    let parts = response
+        .candidates
+        .iter_mut()
+        .filter_map(|candidate| candidate.content.as_mut())
+        .flat_map(|content| content.parts.iter_mut());
+    for part in parts {
+        if let PartKind::FunctionCall { function_call } = &mut part.kind {
+            if function_call.partial_args.is_some() {
+                function_call.args = function_call.assembled_args();
+                function_call.partial_args = None;
+            }
+        }
+    }
+}
+
 fn merge_usage_metadata(
     existing: &mut GenerateContentResponseUsageMetadata,
     next: &GenerateContentResponseUsageMetadata,
@@ -368,12 +531,18 @@ fn merge_candidate(
         }
     }
 
-    if next.citation_metadata.is_some() {
-        existing.citation_metadata = next.citation_metadata.clone();
+    if let Some(next_citations) = &next.citation_metadata {
+        match &mut existing.citation_metadata {
+            Some(existing_citations) => {
+                merge_citation_metadata(existing_citations, next_citations);
+            }
+            None => existing.citation_metadata = Some(next_citations.clone()),
+        }
     }
     if next.finish_message.is_some() {
         existing.finish_message = next.finish_message.clone();
     }
+    existing.extra.extend(next.extra.clone());
     if next.token_count.is_some() {
         existing.token_count = next.token_count;
     }
@@ -383,8 +552,13 @@ fn merge_candidate(
     if next.avg_logprobs.is_some() {
         existing.avg_logprobs = next.avg_logprobs;
     }
-    if next.grounding_metadata.is_some() {
-        existing.grounding_metadata = next.grounding_metadata.clone();
+    if let Some(next_grounding) = &next.grounding_metadata {
+        match &mut existing.grounding_metadata {
+            Some(existing_grounding) => {
+                merge_grounding_metadata(existing_grounding, next_grounding)
+            }
+            None => existing.grounding_metadata = Some(next_grounding.clone()),
+        }
     }
     if next.index.is_some() {
         existing.index = next.index;
@@ -400,6 +574,94 @@ fn merge_candidate(
     }
 }
 
+fn merge_citation_metadata(existing: &mut CitationMetadata, next: &CitationMetadata) {
+    let Some(next_citations) = &next.citations else {
+        return;
+    };
+    let citations = existing.citations.get_or_insert_with(Vec::new);
+    for citation in next_citations {
+        let duplicate = citations.iter().any(|item| {
+            item.start_index == citation.start_index
+                && item.end_index == citation.end_index
+                && item.uri == citation.uri
+        });
+        if !duplicate {
+            citations.push(citation.clone());
+        }
+    }
+}
+
+/// 合并流式 grounding 元数据：来源去重追加，`grounding_supports` 中的来源索引重映射到合并后的列表。
+fn merge_grounding_metadata(existing: &mut GroundingMetadata, next: &GroundingMetadata) {
+    let chunk_positions: Vec<i32> = next
+        .grounding_chunks
+        .iter()
+        .map(|chunk| {
+            let position = existing
+                .grounding_chunks
+                .iter()
+                .position(|item| item.uri() == chunk.uri() && item.title() == chunk.title())
+                .unwrap_or_else(|| {
+                    existing.grounding_chunks.push(chunk.clone());
+                    existing.grounding_chunks.len() - 1
+                });
+            i32::try_from(position).unwrap_or(i32::MAX)
+        })
+        .collect();
+
+    for support in &next.grounding_supports {
+        let mut support = support.clone();
+        for index in &mut support.grounding_chunk_indices {
+            if let Some(position) = usize::try_from(*index)
+                .ok()
+                .and_then(|index| chunk_positions.get(index))
+            {
+                *index = *position;
+            }
+        }
+        let duplicate = existing.grounding_supports.iter().any(|item| {
+            item.segment.part_index == support.segment.part_index
+                && item.segment.start_index == support.segment.start_index
+                && item.segment.end_index == support.segment.end_index
+                && item.grounding_chunk_indices == support.grounding_chunk_indices
+        });
+        if !duplicate {
+            existing.grounding_supports.push(support);
+        }
+    }
+
+    for query in &next.web_search_queries {
+        if !existing.web_search_queries.contains(query) {
+            existing.web_search_queries.push(query.clone());
+        }
+    }
+    if let Some(next_queries) = &next.retrieval_queries {
+        let queries = existing.retrieval_queries.get_or_insert_with(Vec::new);
+        for query in next_queries {
+            if !queries.contains(query) {
+                queries.push(query.clone());
+            }
+        }
+    }
+    if let Some(next_uris) = &next.source_flagging_uris {
+        let uris = existing.source_flagging_uris.get_or_insert_with(Vec::new);
+        for uri in next_uris {
+            if !uris.iter().any(|item| item.source_id == uri.source_id) {
+                uris.push(uri.clone());
+            }
+        }
+    }
+    if next.search_entry_point.is_some() {
+        existing.search_entry_point = next.search_entry_point.clone();
+    }
+    if next.retrieval_metadata.is_some() {
+        existing.retrieval_metadata = next.retrieval_metadata.clone();
+    }
+    if next.google_maps_widget_context_token.is_some() {
+        existing.google_maps_widget_context_token = next.google_maps_widget_context_token.clone();
+    }
+}
+
 fn merge_content_parts(existing_parts: &mut Vec<Part>, next_parts: &[Part]) {
     for (position, part) in next_parts.iter().enumerate() {
         if let Some(existing_position) =
@@ -546,9 +808,13 @@ fn function_calls_share_target(existing: &FunctionCall, next: &FunctionCall) ->
         false
     };
 
-    if shared_id || shared_name {
+    if shared_id {
         return true;
     }
+    if shared_name {
+        // 同名的完整调用（包括无参数调用）是独立的调用，只有仍在流式输出的调用才能继续合并。
+        return function_call_is_open(existing);
+    }
 
     if !function_call_has_identifier(next) {
         return function_call_accepts_identifierless_delta(existing);
@@ -561,6 +827,10 @@ fn function_calls_share_target(existing: &FunctionCall, next: &FunctionCall) ->
     false
 }
 
+fn function_call_is_open(call: &FunctionCall) -> bool {
+    call.will_continue == Some(true) || call.partial_args.is_some()
+}
+
 fn function_call_has_identifier(call: &FunctionCall) -> bool {
     call.id.is_some() || call.name.is_some()
 }
@@ -597,6 +867,40 @@ fn merge_function_call(existing: &mut FunctionCall, next: &FunctionCall) {
     }
 }
 
+/// 使用 `cached_content` 时，系统指令、工具与安全设置必须已包含在缓存中；API 会拒绝同时设置。
+fn validate_cached_content_config(config: &GenerateContentConfig) -> Result<()> {
+    if config.cached_content.is_none() {
+        return Ok(());
+    }
+    let conflicts: Vec<&str> = [
+        ("system_instruction", config.system_instruction.is_some()),
+        (
+            "tools",
+            config.tools.as_ref().is_some_and(|tools| !tools.is_empty()),
+        ),
+        ("tool_config", config.tool_config.is_some()),
+        (
+            "safety_settings",
+            config
+                .safety_settings
+                .as_ref()
+                .is_some_and(|settings| !settings.is_empty()),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, set)| set.then_some(field))
+    .collect();
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    Err(Error::InvalidConfig {
+        message: format!(
+            "cached_content cannot be combined with {}; set them when creating the cache instead",
+            conflicts.join(", ")
+        ),
+    })
+}
+
 fn prepare_json_generation_config(
     mut config: GenerateContentConfig,
     schema: Option<Value>,
@@ -636,13 +940,15 @@ fn prepare_json_generation_config(
 
 struct CallableStreamContext<S> {
     models: Models,
-    model: String,
+    model: ModelName,
     contents: Vec<Content>,
     request_config: GenerateContentConfig,
     callable_tools: Vec<Box<dyn CallableTool>>,
     function_map: HashMap<String, usize, S>,
     max_calls: usize,
+    error_on_max_calls: bool,
     append_history: bool,
+    total_timeout: Option<Duration>,
 }
 
 fn build_synthetic_afc_response(
@@ -663,6 +969,7 @@ fn build_synthetic_afc_response(
             logprobs_result: None,
             safety_ratings: Vec::new(),
             url_context_metadata: None,
+            extra: HashMap::new(),
         }],
         create_time: None,
         automatic_function_calling_history: None,
@@ -670,6 +977,7 @@ fn build_synthetic_afc_response(
         usage_metadata: None,
         model_version: None,
         response_id: None,
+        extra: HashMap::new(),
     };
 
     if !history.is_empty() {
@@ -679,36 +987,69 @@ fn build_synthetic_afc_response(
     response
 }
 
+/// 对流式响应应用 `error_on_blocked_content`：首个被拦截的块替换为
+/// [`Error::ContentBlocked`]，随后结束流。
+fn reject_blocked_chunks<S>(
+    stream: S,
+) -> Pin<Box<dyn Stream<Item = Result<GenerateContentResponse>> + Send>>
+where
+    S: Stream<Item = Result<GenerateContentResponse>> + Send + 'static,
+{
+    Box::pin(stream.scan(false, |blocked, item| {
+        if *blocked {
+            return futures_util::future::ready(None);
+        }
+        let item = item.and_then(|chunk| match ContentBlockReason::from_response(&chunk) {
+            Some(reason) => {
+                *blocked = true;
+                Err(Error::ContentBlocked { reason })
+            }
+            None => Ok(chunk),
+        });
+        futures_util::future::ready(Some(item))
+    }))
+}
+
 async fn forward_stream_items(
     mut stream: Pin<Box<dyn Stream<Item = Result<GenerateContentResponse>> + Send>>,
-    tx: &tokio::sync::mpsc::Sender<Result<GenerateContentResponse>>,
+    tx: &tokio::sync::mpsc::Sender<Result<StreamItem>>,
 ) -> Option<(Vec<FunctionCall>, Vec<Content>)> {
-    let mut function_calls: Vec<FunctionCall> = Vec::new();
-    let mut response_contents: Vec<Content> = Vec::new();
+    // Merge chunks so function calls streamed across several chunks (partial args) are
+    // executed once with their complete arguments.
+    let mut accumulator = StreamAccumulator::new();
 
     while let Some(item) = stream.next().await {
         if let Ok(response) = &item {
-            if let Some(content) = response.candidates.first().and_then(|c| c.content.clone()) {
-                for part in &content.parts {
-                    if let Some(call) = part.function_call_ref() {
-                        function_calls.push(call.clone());
-                    }
-                }
-                response_contents.push(content);
-            }
+            accumulator.push(response);
         }
 
-        if tx.send(item).await.is_err() {
+        if tx.send(item.map(StreamItem::ModelChunk)).await.is_err() {
             return None;
         }
     }
 
-    Some((function_calls, response_contents))
+    let Some(aggregate) = accumulator.into_response() else {
+        return Some((Vec::new(), Vec::new()));
+    };
+    let Some(content) = aggregate
+        .candidates
+        .into_iter()
+        .next()
+        .and_then(|candidate| candidate.content)
+    else {
+        return Some((Vec::new(), Vec::new()));
+    };
+    let function_calls = content
+        .parts
+        .iter()
+        .filter_map(|part| part.function_call_ref().cloned())
+        .collect();
+    Some((function_calls, vec![content]))
 }
 
 fn spawn_callable_stream_loop<S>(
     ctx: CallableStreamContext<S>,
-    tx: tokio::sync::mpsc::Sender<Result<GenerateContentResponse>>,
+    tx: tokio::sync::mpsc::Sender<Result<StreamItem>>,
 ) where
     S: BuildHasher + Sync + Send + 'static,
 {
@@ -720,75 +1061,112 @@ fn spawn_callable_stream_loop<S>(
         mut callable_tools,
         function_map,
         max_calls,
+        error_on_max_calls,
         append_history,
+        total_timeout,
     } = ctx;
-    tokio::spawn(async move {
-        let mut conversation = contents;
-        let mut history: Vec<Content> = Vec::new();
-        let mut remaining_calls = max_calls;
+    let closed = tx.clone();
+    let run =
+        async move {
+            let mut conversation = contents;
+            let mut history: Vec<Content> = Vec::new();
+            let mut remaining_calls = max_calls;
+
+            loop {
+                let stream = match models
+                    .generate_content_stream(&model, conversation.clone(), request_config.clone())
+                    .await
+                {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        let _ = tx.send(Err(err)).await;
+                        break;
+                    }
+                };
 
-        loop {
-            if remaining_calls == 0 {
-                break;
-            }
+                let Some((function_calls, response_contents)) =
+                    forward_stream_items(stream, &tx).await
+                else {
+                    return;
+                };
 
-            let stream = match models
-                .generate_content_stream(&model, conversation.clone(), request_config.clone())
-                .await
-            {
-                Ok(stream) => stream,
-                Err(err) => {
-                    let _ = tx.send(Err(err)).await;
+                if function_calls.is_empty() {
                     break;
                 }
-            };
 
-            let Some((function_calls, response_contents)) = forward_stream_items(stream, &tx).await
-            else {
-                return;
-            };
+                if remaining_calls == 0 {
+                    if error_on_max_calls {
+                        let _ = tx
+                            .send(Err(Error::MaxToolCallsExceeded { limit: max_calls }))
+                            .await;
+                    }
+                    break;
+                }
 
-            if function_calls.is_empty() {
-                break;
-            }
+                if tx
+                    .send(Ok(StreamItem::ToolCall(function_calls.clone())))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
 
-            let response_parts = match call_callable_tools(
-                &mut callable_tools,
-                &function_map,
-                &function_calls,
-            )
-            .await
-            {
-                Ok(parts) => parts,
-                Err(err) => {
-                    let _ = tx.send(Err(err)).await;
+                let response_parts =
+                    match call_callable_tools(&mut callable_tools, &function_map, &function_calls)
+                        .await
+                    {
+                        Ok(parts) => parts,
+                        Err(err) => {
+                            let _ = tx.send(Err(err)).await;
+                            break;
+                        }
+                    };
+
+                if response_parts.is_empty() {
                     break;
                 }
-            };
 
-            if response_parts.is_empty() {
-                break;
-            }
+                let call_content = build_function_call_content(&function_calls);
+                let response_content = Content::from_parts(response_parts.clone(), Role::Function);
 
-            let call_content = build_function_call_content(&function_calls);
-            let response_content = Content::from_parts(response_parts.clone(), Role::Function);
+                if append_history {
+                    if history.is_empty() {
+                        history.extend(conversation.clone());
+                    }
+                    history.push(call_content.clone());
+                    history.push(response_content.clone());
+                }
 
-            if append_history {
-                if history.is_empty() {
-                    history.extend(conversation.clone());
+                // 流式聚合出的模型轮次已包含这些函数调用（及其 thought signature），不再重复追加。
+                conversation.extend(response_contents);
+                conversation.push(response_content.clone());
+                remaining_calls = remaining_calls.saturating_sub(1);
+
+                let synthetic = build_synthetic_afc_response(response_content, &history);
+                if tx
+                    .send(Ok(StreamItem::ToolResult(synthetic)))
+                    .await
+                    .is_err()
+                {
+                    return;
                 }
-                history.push(call_content.clone());
-                history.push(response_content.clone());
             }
-
-            conversation.extend(response_contents);
-            conversation.push(call_content);
-            conversation.push(response_content.clone());
-            remaining_calls = remaining_calls.saturating_sub(1);
-
-            let synthetic = build_synthetic_afc_response(response_content, &history);
-            if tx.send(Ok(synthetic)).await.is_err() {
-                return;
+        };
+    let deadline = async move {
+        match total_timeout {
+            Some(timeout) => crate::rt::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+    // Stop in-flight requests and tool calls as soon as the consumer drops the stream.
+    crate::rt::spawn(async move {
+        tokio::select! {
+            () = run => {}
+            () = closed.closed() => {}
+            () = deadline => {
+                if let Some(timeout) = total_timeout {
+                    let _ = closed.send(Err(total_timeout_error(timeout))).await;
+                }
             }
         }
     });
@@ -806,13 +1184,28 @@ impl Models {
     /// 当请求失败、配置校验失败或响应解析失败时返回错误。
     pub async fn generate_content(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
     ) -> Result<GenerateContentResponse> {
         self.generate_content_with_config(model, contents, GenerateContentConfig::default())
             .await
     }
 
+    /// 以单条用户文本提示生成内容。
+    ///
+    /// # Errors
+    ///
+    /// 当请求失败、配置校验失败或响应解析失败时返回错误。
+    pub async fn generate_text(
+        &self,
+        model: impl Into<ModelName>,
+        prompt: impl Into<String>,
+        config: GenerateContentConfig,
+    ) -> Result<GenerateContentResponse> {
+        self.generate_content_with_config(model, vec![Content::user(prompt)], config)
+            .await
+    }
+
     /// 生成并解析 JSON 响应。
     ///
     /// # Errors
@@ -820,7 +1213,7 @@ impl Models {
     /// 当请求失败、响应没有文本内容或 JSON 解析失败时返回错误。
     pub async fn generate_json<T: DeserializeOwned>(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
     ) -> Result<T> {
         self.generate_json_with_config(model, contents, GenerateContentConfig::default())
@@ -834,20 +1227,17 @@ impl Models {
     /// 当请求失败、响应没有文本内容或 JSON 解析失败时返回错误。
     pub async fn generate_json_with_config<T: DeserializeOwned>(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
         config: GenerateContentConfig,
     ) -> Result<T> {
+        let repair = config.repair_truncated_json.unwrap_or(false);
         let config = prepare_json_generation_config(config, None)?;
 
         let response = self
             .generate_content_with_config(model, contents, config)
             .await?;
-        let text = first_candidate_text(&response).ok_or_else(|| Error::Parse {
-            message: "Expected text response containing JSON".into(),
-        })?;
-
-        Ok(serde_json::from_str(&text)?)
+        parse_json_response(&response, repair)
     }
 
     /// 生成并解析 JSON 响应，同时自动附加 JSON Schema。
@@ -860,7 +1250,7 @@ impl Models {
     #[cfg(feature = "schemars")]
     pub async fn generate_json_with_schema<T>(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
     ) -> Result<T>
     where
@@ -884,42 +1274,76 @@ impl Models {
     #[cfg(feature = "schemars")]
     pub async fn generate_json_with_schema_with_config<T>(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
         config: GenerateContentConfig,
     ) -> Result<T>
     where
         T: DeserializeOwned + schemars::JsonSchema,
     {
+        let repair = config.repair_truncated_json.unwrap_or(false);
         let schema = serde_json::to_value(schemars::schema_for!(T))?;
         let config = prepare_json_generation_config(config, Some(schema))?;
         let response = self
             .generate_content_with_config(model, contents, config)
             .await?;
-        let text = first_candidate_text(&response).ok_or_else(|| Error::Parse {
-            message: "Expected text response containing JSON".into(),
-        })?;
-
-        Ok(serde_json::from_str(&text)?)
+        parse_json_response(&response, repair)
     }
 
-    /// 生成内容（自定义配置）。
+    /// 预览 `generate_content` 将发送的请求（URL 与请求体），不实际发送。
+    ///
+    /// 会执行与 [`Models::generate_content_with_config`] 相同的校验、后端转换与模型名解析，
+    /// 适合调试或 golden-file 测试。
     ///
     /// # Errors
     ///
-    /// 当请求失败、配置校验失败或响应解析失败时返回错误。
-    pub async fn generate_content_with_config(
+    /// 当配置校验失败或请求体构建失败时返回错误。
+    pub fn generate_content_request_preview(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
-        config: GenerateContentConfig,
-    ) -> Result<GenerateContentResponse> {
-        let should_return_http_response = config.should_return_http_response.unwrap_or(false);
+        mut config: GenerateContentConfig,
+    ) -> Result<RequestPreview> {
+        let http_options = config.http_options.take();
         let model = model.into();
-        validate_temperature(&model, &config)?;
-        ThoughtSignatureValidator::new(&model).validate(&contents)?;
-        validate_function_response_media(&model, &contents)?;
-        validate_code_execution_image_inputs(&model, &contents, config.tools.as_deref())?;
+        let (url, body) = self.build_generate_content_request(
+            &model,
+            contents,
+            config,
+            "generateContent",
+            http_options.as_ref(),
+        )?;
+        Ok(RequestPreview { url, body })
+    }
+
+    /// 校验配置并构建 `generateContent` / `streamGenerateContent` 的 URL 与请求体。
+    fn build_generate_content_request(
+        &self,
+        model: &ModelName,
+        mut contents: Vec<Content>,
+        config: GenerateContentConfig,
+        method: &str,
+        http_options: Option<&rust_genai_types::http::HttpOptions>,
+    ) -> Result<(String, Value)> {
+        if let Some(mode) = config.content_role_check {
+            check_content_roles(&mut contents, mode)?;
+        }
+        validate_temperature(model.as_str(), &config)?;
+        ThoughtSignatureValidator::new(model.as_str()).validate(&contents)?;
+        validate_function_response_media(model.as_str(), &contents)?;
+        validate_code_execution_image_inputs(model.as_str(), &contents, config.tools.as_deref())?;
+        validate_thinking_budget(model.as_str(), config.generation_config.as_ref())?;
+        if self.inner.validate_model_capabilities {
+            validate_response_modalities(model.as_str(), config.generation_config.as_ref())?;
+            validate_sampling_features(model.as_str(), config.generation_config.as_ref())?;
+        }
+        validate_tool_conflicts(config.tools.as_deref())?;
+        if let Some(generation_config) = &config.generation_config {
+            generation_config
+                .validate(model.as_str())
+                .map_err(|message| Error::InvalidConfig { message })?;
+        }
+        validate_cached_content_config(&config)?;
 
         let backend = self.inner.config.backend;
         if backend == Backend::GeminiApi && config.model_armor_config.is_some() {
@@ -942,19 +1366,56 @@ impl Models {
             tools: config.tools,
             tool_config: config.tool_config,
             cached_content: config.cached_content,
-            labels: config.labels,
+            labels: self.inner.config.merge_labels(config.labels),
         };
 
-        let url = build_model_method_url(&self.inner, &model, "generateContent")?;
-        let body = match backend {
+        let url = build_model_method_url_with_options(&self.inner, model, method, http_options)?;
+        let mut body = match backend {
             Backend::GeminiApi => converters::generate_content_request_to_mldev(&request)?,
             Backend::VertexAi => converters::generate_content_request_to_vertex(&request)?,
         };
+        if let Some(options) = http_options {
+            merge_extra_body(&mut body, options)?;
+        }
+        Ok((url, body))
+    }
 
-        let request = self.inner.http.post(url).json(&body);
-        let response = self.inner.send(request).await?;
+    /// 生成内容（自定义配置）。
+    ///
+    /// # Errors
+    ///
+    /// 当请求失败、配置校验失败或响应解析失败时返回错误。
+    pub async fn generate_content_with_config(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        mut config: GenerateContentConfig,
+    ) -> Result<GenerateContentResponse> {
+        let should_return_http_response = config.should_return_http_response.unwrap_or(false);
+        let error_on_blocked_content = config.error_on_blocked_content.unwrap_or(false);
+        let http_options = config.http_options.take();
+        let model = model.into();
+        let backend = self.inner.config.backend;
+        if self.inner.validate_input_length {
+            self.check_input_length(model.clone(), &contents, &config, None)
+                .await?;
+        }
+        let (url, body) = self.build_generate_content_request(
+            &model,
+            contents,
+            config,
+            "generateContent",
+            http_options.as_ref(),
+        )?;
+
+        let mut request = self.inner.http.post(url).json(&body);
+        request = apply_http_options(request, http_options.as_ref())?;
+        let response = self
+            .inner
+            .send_with_http_options(request, http_options.as_ref())
+            .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         if should_return_http_response {
@@ -968,13 +1429,30 @@ impl Models {
                 usage_metadata: None,
                 model_version: None,
                 response_id: None,
+                extra: HashMap::new(),
             });
         }
         let value = response.json::<Value>().await?;
-        let mut result = match backend {
-            Backend::GeminiApi => converters::generate_content_response_from_mldev(value)?,
-            Backend::VertexAi => converters::generate_content_response_from_vertex(value)?,
+        let raw = self.inner.keeps_raw_response().then(|| value.to_string());
+        let converted = match backend {
+            Backend::GeminiApi => converters::generate_content_response_from_mldev(value),
+            Backend::VertexAi => converters::generate_content_response_from_vertex(value),
         };
+        let mut result = match converted {
+            Ok(result) => result,
+            Err(err) => {
+                return Err(self.inner.response_parse_error(
+                    "generateContent",
+                    raw.as_deref(),
+                    err,
+                ));
+            }
+        };
+        if error_on_blocked_content {
+            if let Some(reason) = ContentBlockReason::from_response(&result) {
+                return Err(Error::ContentBlocked { reason });
+            }
+        }
         result.sdk_http_response = Some(sdk_http_response_from_headers(&headers));
         Ok(result)
     }
@@ -986,7 +1464,7 @@ impl Models {
     /// 当配置校验失败、自动函数调用执行失败或请求失败时返回错误。
     pub async fn generate_content_with_callable_tools(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
         config: GenerateContentConfig,
         mut callable_tools: Vec<Box<dyn CallableTool>>,
@@ -1023,77 +1501,126 @@ impl Models {
         validate_afc_tools(&callable_info.function_map, config.tools.as_deref())?;
 
         let max_calls = max_remote_calls(&config);
+        let error_on_max_calls = should_error_on_max_remote_calls(&config);
         let append_history = should_append_history(&config);
-        let mut history: Vec<Content> = Vec::new();
-        let mut conversation = contents.clone();
-        let mut remaining_calls = max_calls;
-        let mut response = self
-            .generate_content_with_config(&model, conversation.clone(), request_config.clone())
-            .await?;
+        let afc_loop = async {
+            let mut history: Vec<Content> = Vec::new();
+            let mut conversation = contents.clone();
+            let mut remaining_calls = max_calls;
+            let mut response = self
+                .generate_content_with_config(&model, conversation.clone(), request_config.clone())
+                .await?;
 
-        loop {
-            let function_calls: Vec<FunctionCall> =
-                response.function_calls().into_iter().cloned().collect();
+            loop {
+                let function_calls: Vec<FunctionCall> =
+                    response.function_calls().into_iter().cloned().collect();
 
-            if function_calls.is_empty() {
-                if append_history && !history.is_empty() {
-                    response.automatic_function_calling_history = Some(history);
+                if function_calls.is_empty() {
+                    if append_history && !history.is_empty() {
+                        response.automatic_function_calling_history = Some(history);
+                    }
+                    return Ok(response);
                 }
-                return Ok(response);
-            }
 
-            if remaining_calls == 0 {
-                break;
-            }
+                if remaining_calls == 0 {
+                    if error_on_max_calls {
+                        return Err(Error::MaxToolCallsExceeded { limit: max_calls });
+                    }
+                    break;
+                }
 
-            let response_parts = call_callable_tools(
-                &mut callable_tools,
-                &callable_info.function_map,
-                &function_calls,
-            )
-            .await?;
-            if response_parts.is_empty() {
-                break;
-            }
+                let response_parts = call_callable_tools(
+                    &mut callable_tools,
+                    &callable_info.function_map,
+                    &function_calls,
+                )
+                .await?;
+                if response_parts.is_empty() {
+                    break;
+                }
 
-            let call_content = build_function_call_content(&function_calls);
-            let response_content = Content::from_parts(response_parts.clone(), Role::Function);
+                let call_content = build_function_call_content(&function_calls);
+                let response_content = Content::from_parts(response_parts.clone(), Role::Function);
 
-            if append_history {
-                if history.is_empty() {
-                    history.extend(conversation.clone());
+                if append_history {
+                    if history.is_empty() {
+                        history.extend(conversation.clone());
+                    }
+                    history.push(call_content.clone());
+                    history.push(response_content.clone());
                 }
-                history.push(call_content.clone());
-                history.push(response_content.clone());
-            }
 
-            conversation.push(call_content);
-            conversation.push(response_content);
-            remaining_calls = remaining_calls.saturating_sub(1);
+                conversation.push(call_content);
+                conversation.push(response_content);
+                remaining_calls = remaining_calls.saturating_sub(1);
 
-            response = self
-                .generate_content_with_config(&model, conversation.clone(), request_config.clone())
-                .await?;
-        }
+                response = self
+                    .generate_content_with_config(
+                        &model,
+                        conversation.clone(),
+                        request_config.clone(),
+                    )
+                    .await?;
+            }
 
-        if append_history && !history.is_empty() {
-            response.automatic_function_calling_history = Some(history);
-        }
-        Ok(response)
+            if append_history && !history.is_empty() {
+                response.automatic_function_calling_history = Some(history);
+            }
+            Ok::<_, Error>(response)
+        };
+        with_total_timeout(total_timeout(&config), afc_loop).await
     }
 
     /// 生成内容（流式 + 自动函数调用）。
     ///
+    /// 工具调用结果以合成的 `function` 角色响应插入流中；如需区分模型输出与工具执行，
+    /// 请使用 [`Models::generate_content_stream_items_with_callable_tools`]。
+    ///
     /// # Errors
     ///
     /// 当配置校验失败、自动函数调用执行失败或请求失败时返回错误。
     pub async fn generate_content_stream_with_callable_tools(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
         config: GenerateContentConfig,
-        mut callable_tools: Vec<Box<dyn CallableTool>>,
+        callable_tools: Vec<Box<dyn CallableTool>>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<GenerateContentResponse>> + Send>>> {
+        let items = self
+            .generate_content_stream_items_with_callable_tools(
+                model,
+                contents,
+                config,
+                callable_tools,
+            )
+            .await?;
+        let output = items.filter_map(|item| {
+            futures_util::future::ready(match item {
+                Ok(StreamItem::ModelChunk(response) | StreamItem::ToolResult(response)) => {
+                    Some(Ok(response))
+                }
+                Ok(StreamItem::ToolCall(_)) => None,
+                Err(err) => Some(Err(err)),
+            })
+        });
+        Ok(Box::pin(output))
+    }
+
+    /// 生成内容（流式 + 自动函数调用），按类型区分流中的条目。
+    ///
+    /// 模型输出以 [`StreamItem::ModelChunk`] 返回；模型请求函数调用后先产出
+    /// [`StreamItem::ToolCall`]，本地工具执行完成后产出 [`StreamItem::ToolResult`]。
+    ///
+    /// # Errors
+    ///
+    /// 当配置校验失败、自动函数调用执行失败或请求失败时返回错误。
+    pub async fn generate_content_stream_items_with_callable_tools(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        config: GenerateContentConfig,
+        mut callable_tools: Vec<Box<dyn CallableTool>>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamItem>> + Send>>> {
         if config.should_return_http_response.unwrap_or(false) {
             return Err(Error::InvalidConfig {
                 message: "should_return_http_response is not supported in callable tools methods"
@@ -1102,7 +1629,12 @@ impl Models {
         }
         let model = model.into();
         if callable_tools.is_empty() {
-            return self.generate_content_stream(model, contents, config).await;
+            let stream = self
+                .generate_content_stream(model, contents, config)
+                .await?;
+            return Ok(Box::pin(
+                stream.map(|item| item.map(StreamItem::ModelChunk)),
+            ));
         }
 
         validate_afc_config(&config)?;
@@ -1117,16 +1649,20 @@ impl Models {
         request_config.tools = Some(merged_tools);
 
         if should_disable_afc(&config, has_callable) {
-            return self
+            let stream = self
                 .generate_content_stream(model, contents, request_config)
-                .await;
+                .await?;
+            return Ok(Box::pin(
+                stream.map(|item| item.map(StreamItem::ModelChunk)),
+            ));
         }
 
         validate_afc_tools(&function_map, config.tools.as_deref())?;
 
         let max_calls = max_remote_calls(&config);
+        let error_on_max_calls = should_error_on_max_remote_calls(&config);
         let append_history = should_append_history(&config);
-        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        let (tx, rx) = tokio::sync::mpsc::channel(stream_buffer_size(&config));
         let models = self.clone();
         let ctx = CallableStreamContext {
             models,
@@ -1136,7 +1672,9 @@ impl Models {
             callable_tools,
             function_map,
             max_calls,
+            error_on_max_calls,
             append_history,
+            total_timeout: total_timeout(&config),
         };
         spawn_callable_stream_loop(ctx, tx);
 
@@ -1147,6 +1685,21 @@ impl Models {
         Ok(Box::pin(output))
     }
 
+    /// 以单条用户文本提示生成内容（流式）。
+    ///
+    /// # Errors
+    ///
+    /// 当请求失败、配置校验失败或响应解析失败时返回错误。
+    pub async fn generate_text_stream(
+        &self,
+        model: impl Into<ModelName>,
+        prompt: impl Into<String>,
+        config: GenerateContentConfig,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<GenerateContentResponse>> + Send>>> {
+        self.generate_content_stream(model, vec![Content::user(prompt)], config)
+            .await
+    }
+
     /// 生成内容（流式）。
     ///
     /// # Errors
@@ -1154,62 +1707,114 @@ impl Models {
     /// 当请求失败、配置校验失败或响应解析失败时返回错误。
     pub async fn generate_content_stream(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
-        config: GenerateContentConfig,
+        mut config: GenerateContentConfig,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<GenerateContentResponse>> + Send>>> {
         if config.should_return_http_response.unwrap_or(false) {
             return Err(Error::InvalidConfig {
                 message: "should_return_http_response is not supported in streaming methods".into(),
             });
         }
+        let http_options = config.http_options.take();
+        let restart_attempts = config.stream_restart_attempts.unwrap_or(0);
+        let error_on_blocked_content = config.error_on_blocked_content.unwrap_or(false);
         let model = model.into();
-        validate_temperature(&model, &config)?;
-        ThoughtSignatureValidator::new(&model).validate(&contents)?;
-        validate_function_response_media(&model, &contents)?;
-        validate_code_execution_image_inputs(&model, &contents, config.tools.as_deref())?;
-
-        let backend = self.inner.config.backend;
-        if backend == Backend::GeminiApi && config.model_armor_config.is_some() {
-            return Err(Error::InvalidConfig {
-                message: "model_armor_config is not supported in Gemini API".into(),
-            });
-        }
-        if config.model_armor_config.is_some() && config.safety_settings.is_some() {
-            return Err(Error::InvalidConfig {
-                message: "model_armor_config cannot be combined with safety_settings".into(),
-            });
+        if self.inner.validate_input_length {
+            self.check_input_length(model.clone(), &contents, &config, None)
+                .await?;
         }
-
-        let request = GenerateContentRequest {
+        let (mut url, body) = self.build_generate_content_request(
+            &model,
             contents,
-            system_instruction: config.system_instruction,
-            generation_config: config.generation_config,
-            safety_settings: config.safety_settings,
-            model_armor_config: config.model_armor_config,
-            tools: config.tools,
-            tool_config: config.tool_config,
-            cached_content: config.cached_content,
-            labels: config.labels,
-        };
-
-        let mut url = build_model_method_url(&self.inner, &model, "streamGenerateContent")?;
+            config,
+            "streamGenerateContent",
+            http_options.as_ref(),
+        )?;
         url.push_str("?alt=sse");
 
-        let body = match backend {
-            Backend::GeminiApi => converters::generate_content_request_to_mldev(&request)?,
-            Backend::VertexAi => converters::generate_content_request_to_vertex(&request)?,
-        };
+        let mut stream = self
+            .open_generate_content_stream(&url, &body, http_options.as_ref())
+            .await?;
+        if restart_attempts > 0 {
+            stream = Box::pin(stream_restart::restarting_stream(
+                self.clone(),
+                url,
+                body,
+                http_options,
+                stream,
+                restart_attempts,
+            ));
+        }
+        if error_on_blocked_content {
+            stream = reject_blocked_chunks(stream);
+        }
+        Ok(stream)
+    }
 
-        let request = self.inner.http.post(url).json(&body);
-        let response = self.inner.send(request).await?;
+    /// 在后台驱动流式生成，并通过回调交付结果（便于 FFI/GUI 集成）。
+    ///
+    /// 每个响应块调用一次 `on_chunk`；流结束时以 `Ok(())` 调用 `on_done`，
+    /// 建立请求或读取流失败时以对应错误调用 `on_done`，通过 [`StreamCallbackHandle::cancel`]
+    /// 取消时以 [`Error::Cancelled`] 调用。`on_done` 恰好调用一次。必须在 Tokio 运行时中调用。
+    pub fn generate_content_stream_callback<C, D>(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        config: GenerateContentConfig,
+        mut on_chunk: C,
+        on_done: D,
+    ) -> StreamCallbackHandle
+    where
+        C: FnMut(GenerateContentResponse) + Send + 'static,
+        D: FnOnce(Result<()>) + Send + 'static,
+    {
+        let models = self.clone();
+        let model = model.into();
+        let (abort, registration) = AbortHandle::new_pair();
+        let task = crate::rt::spawn(async move {
+            let run = async move {
+                let mut stream = models
+                    .generate_content_stream(model, contents, config)
+                    .await?;
+                while let Some(chunk) = stream.next().await {
+                    on_chunk(chunk?);
+                }
+                Ok(())
+            };
+            let result = Abortable::new(run, registration)
+                .await
+                .unwrap_or(Err(Error::Cancelled));
+            on_done(result);
+        });
+        StreamCallbackHandle { task, abort }
+    }
+
+    /// 发送 `streamGenerateContent` 请求并解析 SSE 响应。
+    async fn open_generate_content_stream(
+        &self,
+        url: &str,
+        body: &Value,
+        http_options: Option<&rust_genai_types::http::HttpOptions>,
+    ) -> Result<stream_restart::ResponseStream> {
+        let mut request = self.inner.http.post(url).json(body);
+        request = apply_http_options(request, http_options)?;
+        let response = self
+            .inner
+            .send_with_http_options(request, http_options)
+            .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
         let sdk_http_response = sdk_http_response_from_headers(&headers);
-        let stream = parse_sse_stream(response).map(move |item| {
+        let mut stream = SseJsonStream::<GenerateContentResponse>::new(response)
+            .with_parse_error_context(self.inner.clone(), "streamGenerateContent");
+        if let Some(idle_timeout) = stream_idle_timeout(http_options) {
+            stream = stream.with_idle_timeout(idle_timeout);
+        }
+        let stream = stream.map(move |item| {
             item.map(|mut resp| {
                 resp.sdk_http_response = Some(sdk_http_response.clone());
                 resp
@@ -1218,6 +1823,28 @@ impl Models {
         Ok(Box::pin(stream))
     }
 
+    /// 生成内容（流式），仅产出每个 chunk 新增的文本（跳过 thought 与函数调用）。
+    ///
+    /// # Errors
+    ///
+    /// 当请求失败或配置校验失败时返回错误。
+    pub async fn stream_text(
+        &self,
+        model: impl Into<ModelName>,
+        contents: Vec<Content>,
+        config: GenerateContentConfig,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<String>> + Send>>> {
+        let stream = self
+            .generate_content_stream(model, contents, config)
+            .await?;
+        Ok(Box::pin(stream.filter_map(|item| async move {
+            match item {
+                Ok(response) => response.all_text().map(Ok),
+                Err(err) => Some(Err(err)),
+            }
+        })))
+    }
+
     /// 生成内容事件流。
     ///
     /// # Errors
@@ -1225,9 +1852,9 @@ impl Models {
     /// 当请求失败、配置校验失败或响应解析失败时返回错误。
     pub async fn generate_content_event_stream(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
-        config: GenerateContentConfig,
+        mut config: GenerateContentConfig,
     ) -> Result<GenerateContentEventStream> {
         let should_return_http_response = config.should_return_http_response.unwrap_or(false);
         if should_return_http_response {
@@ -1235,66 +1862,54 @@ impl Models {
                 message: "should_return_http_response is not supported in streaming".into(),
             });
         }
+        let http_options = config.http_options.take();
+        let error_on_blocked_content = config.error_on_blocked_content.unwrap_or(false);
 
         let model = model.into();
-        validate_temperature(&model, &config)?;
-        ThoughtSignatureValidator::new(&model).validate(&contents)?;
-        validate_function_response_media(&model, &contents)?;
-        validate_code_execution_image_inputs(&model, &contents, config.tools.as_deref())?;
-
-        let backend = self.inner.config.backend;
-        if backend == Backend::GeminiApi && config.model_armor_config.is_some() {
-            return Err(Error::InvalidConfig {
-                message: "model_armor_config is not supported in Gemini API".into(),
-            });
-        }
-        if config.model_armor_config.is_some() && config.safety_settings.is_some() {
-            return Err(Error::InvalidConfig {
-                message: "model_armor_config cannot be combined with safety_settings".into(),
-            });
-        }
-
-        let request = GenerateContentRequest {
+        let (url, body) = self.build_generate_content_request(
+            &model,
             contents,
-            system_instruction: config.system_instruction,
-            generation_config: config.generation_config,
-            safety_settings: config.safety_settings,
-            model_armor_config: config.model_armor_config,
-            tools: config.tools,
-            tool_config: config.tool_config,
-            cached_content: config.cached_content,
-            labels: config.labels,
-        };
+            config,
+            "streamGenerateContent",
+            http_options.as_ref(),
+        )?;
 
-        let url = build_model_method_url(&self.inner, &model, "streamGenerateContent")?;
-        let body = match backend {
-            Backend::GeminiApi => converters::generate_content_request_to_mldev(&request)?,
-            Backend::VertexAi => converters::generate_content_request_to_vertex(&request)?,
-        };
-
-        let request = self
+        let mut request = self
             .inner
             .http
             .post(format!("{url}?alt=sse"))
             .header(reqwest::header::ACCEPT, "text/event-stream")
             .json(&body);
-        let response = self.inner.send(request).await?;
+        request = apply_http_options(request, http_options.as_ref())?;
+        let response = self
+            .inner
+            .send_with_http_options(request, http_options.as_ref())
+            .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
         let sdk_http_response = sdk_http_response_from_headers(&headers);
         let saw_done = Arc::new(AtomicBool::new(false));
-        let stream =
-            parse_sse_stream_with_done_signal(response, saw_done.clone()).map(move |item| {
-                item.map(|mut resp: GenerateContentResponse| {
-                    resp.sdk_http_response = Some(sdk_http_response.clone());
-                    resp
-                })
-            });
+        let mut stream = SseJsonStream::with_done_signal(response, Some(saw_done.clone()))
+            .with_parse_error_context(self.inner.clone(), "streamGenerateContent");
+        if let Some(idle_timeout) = stream_idle_timeout(http_options.as_ref()) {
+            stream = stream.with_idle_timeout(idle_timeout);
+        }
+        let stream = stream.map(move |item| {
+            item.map(|mut resp: GenerateContentResponse| {
+                resp.sdk_http_response = Some(sdk_http_response.clone());
+                resp
+            })
+        });
+        let stream = if error_on_blocked_content {
+            reject_blocked_chunks(stream)
+        } else {
+            Box::pin(stream)
+        };
 
-        Ok(GenerateContentEventStream::new(Box::pin(stream), saw_done))
+        Ok(GenerateContentEventStream::new(stream, saw_done))
     }
 
     /// 生成嵌入向量（默认配置）。
@@ -1304,7 +1919,7 @@ impl Models {
     /// 当请求失败或响应解析失败时返回错误。
     pub async fn embed_content(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
     ) -> Result<EmbedContentResponse> {
         self.embed_content_with_config(model, contents, EmbedContentConfig::default())
@@ -1318,7 +1933,7 @@ impl Models {
     /// 当请求失败、配置不合法或响应解析失败时返回错误。
     pub async fn embed_content_with_config(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
         config: EmbedContentConfig,
     ) -> Result<EmbedContentResponse> {
@@ -1338,23 +1953,22 @@ impl Models {
         let request = self.inner.http.post(url).json(&body);
         let response = self.inner.send(request).await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
-        match self.inner.config.backend {
-            Backend::GeminiApi => {
-                let mut result = response.json::<EmbedContentResponse>().await?;
-                result.sdk_http_response = Some(sdk_http_response_from_headers(&headers));
-                Ok(result)
-            }
-            Backend::VertexAi => {
-                let value = response.json::<Value>().await?;
-                let mut result = convert_vertex_embed_response(&value)?;
-                result.sdk_http_response = Some(sdk_http_response_from_headers(&headers));
-                Ok(result)
-            }
-        }
+        let value = response.json::<Value>().await?;
+        let raw = self.inner.keeps_raw_response().then(|| value.to_string());
+        let converted = match self.inner.config.backend {
+            Backend::GeminiApi => serde_json::from_value::<EmbedContentResponse>(value),
+            Backend::VertexAi => convert_vertex_embed_response(&value),
+        };
+        let mut result = converted.map_err(|err| {
+            self.inner
+                .response_parse_error("embedContent", raw.as_deref(), err)
+        })?;
+        result.sdk_http_response = Some(sdk_http_response_from_headers(&headers));
+        Ok(result)
     }
 
     /// 计数 tokens（默认配置）。
@@ -1364,7 +1978,7 @@ impl Models {
     /// 当请求失败或响应解析失败时返回错误。
     pub async fn count_tokens(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
     ) -> Result<CountTokensResponse> {
         self.count_tokens_with_config(model, contents, CountTokensConfig::default())
@@ -1378,7 +1992,7 @@ impl Models {
     /// 当请求失败、配置不合法或响应解析失败时返回错误。
     pub async fn count_tokens_with_config(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
         config: CountTokensConfig,
     ) -> Result<CountTokensResponse> {
@@ -1390,23 +2004,39 @@ impl Models {
         };
 
         let backend = self.inner.config.backend;
-        let url = build_model_method_url(&self.inner, &model.into(), "countTokens")?;
+        let url = build_model_method_url(&self.inner, model.into(), "countTokens")?;
         let body = match backend {
             Backend::GeminiApi => converters::count_tokens_request_to_mldev(&request)?,
             Backend::VertexAi => converters::count_tokens_request_to_vertex(&request)?,
         };
+        let cache = self
+            .inner
+            .token_count_cache
+            .as_ref()
+            .map(|cache| (cache, TokenCountCache::key(url.as_str(), &body)));
+        if let Some(cached) = cache.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            return Ok(cached);
+        }
         let request = self.inner.http.post(url).json(&body);
         let response = self.inner.send(request).await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let value = response.json::<Value>().await?;
-        let mut result = match backend {
-            Backend::GeminiApi => converters::count_tokens_response_from_mldev(value)?,
-            Backend::VertexAi => converters::count_tokens_response_from_vertex(value)?,
+        let raw = self.inner.keeps_raw_response().then(|| value.to_string());
+        let converted = match backend {
+            Backend::GeminiApi => converters::count_tokens_response_from_mldev(value),
+            Backend::VertexAi => converters::count_tokens_response_from_vertex(value),
         };
+        let mut result = converted.map_err(|err| {
+            self.inner
+                .response_parse_error("countTokens", raw.as_deref(), err)
+        })?;
         result.sdk_http_response = Some(sdk_http_response_from_headers(&headers));
+        if let Some((cache, key)) = cache {
+            cache.insert(key, result.clone());
+        }
         Ok(result)
     }
 
@@ -1417,7 +2047,7 @@ impl Models {
     /// 当后端不支持或请求失败时返回错误。
     pub async fn compute_tokens(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
     ) -> Result<ComputeTokensResponse> {
         self.compute_tokens_with_config(model, contents, ComputeTokensConfig::default())
@@ -1431,7 +2061,7 @@ impl Models {
     /// 当后端不支持、配置不合法或请求失败时返回错误。
     pub async fn compute_tokens_with_config(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         contents: Vec<Content>,
         config: ComputeTokensConfig,
     ) -> Result<ComputeTokensResponse> {
@@ -1442,7 +2072,12 @@ impl Models {
         }
 
         let request = ComputeTokensRequest { contents };
-        let url = build_model_method_url(&self.inner, &model.into(), "computeTokens")?;
+        let url = build_model_method_url_with_options(
+            &self.inner,
+            model.into(),
+            "computeTokens",
+            config.http_options.as_ref(),
+        )?;
         let mut body = converters::compute_tokens_request_to_vertex(&request)?;
         if let Some(options) = config.http_options.as_ref() {
             merge_extra_body(&mut body, options)?;
@@ -1456,7 +2091,7 @@ impl Models {
             .send_with_http_options(request, config.http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let value = response.json::<Value>().await?;
@@ -1476,6 +2111,7 @@ impl Models {
             sdk_http_response: None,
             total_tokens: Some(total),
             cached_content_token_count: None,
+            extra: HashMap::new(),
         }
     }
 
@@ -1493,6 +2129,7 @@ impl Models {
             sdk_http_response: None,
             total_tokens: Some(total),
             cached_content_token_count: None,
+            extra: HashMap::new(),
         }
     }
 
@@ -1503,7 +2140,7 @@ impl Models {
     /// 当请求失败或响应解析失败时返回错误。
     pub async fn count_tokens_or_estimate(
         &self,
-        model: impl Into<String> + Send,
+        model: impl Into<ModelName> + Send,
         contents: Vec<Content>,
         config: CountTokensConfig,
         estimator: Option<&(dyn TokenEstimator + Sync)>,
@@ -1514,6 +2151,44 @@ impl Models {
         self.count_tokens_with_config(model, contents, config).await
     }
 
+    /// 生成前的输入长度预检：用 `count_tokens`（或本地估算器）计数，超过模型输入上限时
+    /// 返回 [`Error::InputTooLong`]，避免发送注定被服务端拒绝的请求。
+    ///
+    /// 上限来自 [`input_token_limit`]；未知模型（调优模型、端点等）直接放行且不计数。
+    /// 启用 `ClientBuilder::validate_input_length` 后，`generate_content*` 会自动执行此预检。
+    ///
+    /// # Errors
+    ///
+    /// 当输入超过上限，或计数请求失败时返回错误。
+    pub async fn check_input_length(
+        &self,
+        model: impl Into<ModelName> + Send,
+        contents: &[Content],
+        config: &GenerateContentConfig,
+        estimator: Option<&(dyn TokenEstimator + Sync)>,
+    ) -> Result<()> {
+        let model = model.into();
+        let Some(limit) = input_token_limit(model.as_str()) else {
+            return Ok(());
+        };
+        let count_config = CountTokensConfig {
+            system_instruction: config.system_instruction.clone(),
+            tools: config.tools.clone(),
+            generation_config: config.generation_config.clone(),
+        };
+        let counted = self
+            .count_tokens_or_estimate(model, contents.to_vec(), count_config, estimator)
+            .await?;
+        let tokens = counted
+            .total_tokens
+            .and_then(|total| usize::try_from(total).ok())
+            .unwrap_or(0);
+        if tokens > limit {
+            return Err(Error::InputTooLong { tokens, limit });
+        }
+        Ok(())
+    }
+
     /// 生成图像（Imagen）。
     ///
     /// # Errors
@@ -1521,7 +2196,7 @@ impl Models {
     /// 当请求失败、配置不合法或响应解析失败时返回错误。
     pub async fn generate_images(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         prompt: impl Into<String>,
         mut config: GenerateImagesConfig,
     ) -> Result<GenerateImagesResponse> {
@@ -1532,7 +2207,12 @@ impl Models {
         if let Some(options) = http_options.as_ref() {
             merge_extra_body(&mut body, options)?;
         }
-        let url = build_model_method_url(&self.inner, &model, "predict")?;
+        let url = build_model_method_url_with_options(
+            &self.inner,
+            &model,
+            "predict",
+            http_options.as_ref(),
+        )?;
 
         let mut request = self.inner.http.post(url).json(&body);
         request = apply_http_options(request, http_options.as_ref())?;
@@ -1542,7 +2222,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
@@ -1559,7 +2239,7 @@ impl Models {
     /// 当后端不支持、请求失败或响应解析失败时返回错误。
     pub async fn edit_image(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         prompt: impl Into<String>,
         reference_images: Vec<ReferenceImage>,
         mut config: EditImageConfig,
@@ -1573,11 +2253,24 @@ impl Models {
         let http_options = config.http_options.take();
         let model = model.into();
         let prompt = prompt.into();
+        if self.inner.validate_media_inputs {
+            validate_media_limits(
+                reference_images
+                    .iter()
+                    .filter_map(|image| image.reference_image.as_ref()),
+                [],
+            )?;
+        }
         let mut body = build_edit_image_body(&prompt, &reference_images, &config)?;
         if let Some(options) = http_options.as_ref() {
             merge_extra_body(&mut body, options)?;
         }
-        let url = build_model_method_url(&self.inner, &model, "predict")?;
+        let url = build_model_method_url_with_options(
+            &self.inner,
+            &model,
+            "predict",
+            http_options.as_ref(),
+        )?;
 
         let mut request = self.inner.http.post(url).json(&body);
         request = apply_http_options(request, http_options.as_ref())?;
@@ -1587,7 +2280,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
@@ -1604,7 +2297,7 @@ impl Models {
     /// 当后端不支持、请求失败或响应解析失败时返回错误。
     pub async fn upscale_image(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         image: Image,
         upscale_factor: impl Into<String>,
         mut config: rust_genai_types::models::UpscaleImageConfig,
@@ -1618,11 +2311,19 @@ impl Models {
         let http_options = config.http_options.take();
         let model = model.into();
         let upscale_factor = upscale_factor.into();
+        if self.inner.validate_media_inputs {
+            validate_media_limits([&image], [])?;
+        }
         let mut body = build_upscale_image_body(&image, &upscale_factor, &config)?;
         if let Some(options) = http_options.as_ref() {
             merge_extra_body(&mut body, options)?;
         }
-        let url = build_model_method_url(&self.inner, &model, "predict")?;
+        let url = build_model_method_url_with_options(
+            &self.inner,
+            &model,
+            "predict",
+            http_options.as_ref(),
+        )?;
 
         let mut request = self.inner.http.post(url).json(&body);
         request = apply_http_options(request, http_options.as_ref())?;
@@ -1632,7 +2333,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
@@ -1649,7 +2350,7 @@ impl Models {
     /// 当后端不支持、请求失败或响应解析失败时返回错误。
     pub async fn recontext_image(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         source: RecontextImageSource,
         mut config: RecontextImageConfig,
     ) -> Result<RecontextImageResponse> {
@@ -1661,11 +2362,24 @@ impl Models {
 
         let http_options = config.http_options.take();
         let model = model.into();
+        if self.inner.validate_media_inputs {
+            let product_images = source
+                .product_images
+                .iter()
+                .flatten()
+                .filter_map(|product| product.product_image.as_ref());
+            validate_media_limits(source.person_image.iter().chain(product_images), [])?;
+        }
         let mut body = build_recontext_image_body(&source, &config)?;
         if let Some(options) = http_options.as_ref() {
             merge_extra_body(&mut body, options)?;
         }
-        let url = build_model_method_url(&self.inner, &model, "predict")?;
+        let url = build_model_method_url_with_options(
+            &self.inner,
+            &model,
+            "predict",
+            http_options.as_ref(),
+        )?;
 
         let mut request = self.inner.http.post(url).json(&body);
         request = apply_http_options(request, http_options.as_ref())?;
@@ -1675,7 +2389,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let value = response.json::<Value>().await?;
@@ -1689,7 +2403,7 @@ impl Models {
     /// 当后端不支持、请求失败或响应解析失败时返回错误。
     pub async fn segment_image(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         source: SegmentImageSource,
         mut config: SegmentImageConfig,
     ) -> Result<SegmentImageResponse> {
@@ -1701,11 +2415,23 @@ impl Models {
 
         let http_options = config.http_options.take();
         let model = model.into();
+        if self.inner.validate_media_inputs {
+            let scribble = source
+                .scribble_image
+                .as_ref()
+                .and_then(|scribble| scribble.image.as_ref());
+            validate_media_limits(source.image.iter().chain(scribble), [])?;
+        }
         let mut body = build_segment_image_body(&source, &config)?;
         if let Some(options) = http_options.as_ref() {
             merge_extra_body(&mut body, options)?;
         }
-        let url = build_model_method_url(&self.inner, &model, "predict")?;
+        let url = build_model_method_url_with_options(
+            &self.inner,
+            &model,
+            "predict",
+            http_options.as_ref(),
+        )?;
 
         let mut request = self.inner.http.post(url).json(&body);
         request = apply_http_options(request, http_options.as_ref())?;
@@ -1715,7 +2441,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let value = response.json::<Value>().await?;
@@ -1729,17 +2455,39 @@ impl Models {
     /// 当请求失败、配置不合法或响应解析失败时返回错误。
     pub async fn generate_videos(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         source: GenerateVideosSource,
         mut config: GenerateVideosConfig,
     ) -> Result<GenerateVideosOperation> {
         let http_options = config.http_options.take();
         let model = model.into();
+        if self.inner.validate_media_inputs {
+            let reference_images = config
+                .reference_images
+                .iter()
+                .flatten()
+                .filter_map(|reference| reference.image.as_ref());
+            let mask = config.mask.as_ref().and_then(|mask| mask.image.as_ref());
+            validate_media_limits(
+                source
+                    .image
+                    .iter()
+                    .chain(config.last_frame.as_ref())
+                    .chain(reference_images)
+                    .chain(mask),
+                source.video.as_ref(),
+            )?;
+        }
         let mut body = build_generate_videos_body(self.inner.config.backend, &source, &config)?;
         if let Some(options) = http_options.as_ref() {
             merge_extra_body(&mut body, options)?;
         }
-        let url = build_model_method_url(&self.inner, &model, "predictLongRunning")?;
+        let url = build_model_method_url_with_options(
+            &self.inner,
+            &model,
+            "predictLongRunning",
+            http_options.as_ref(),
+        )?;
 
         let mut request = self.inner.http.post(url).json(&body);
         request = apply_http_options(request, http_options.as_ref())?;
@@ -1749,7 +2497,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let value = response.json::<Value>().await?;
@@ -1763,7 +2511,7 @@ impl Models {
     /// 当请求失败或配置不合法时返回错误。
     pub async fn generate_videos_with_prompt(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         prompt: impl Into<String>,
         config: GenerateVideosConfig,
     ) -> Result<GenerateVideosOperation> {
@@ -1793,7 +2541,7 @@ impl Models {
         let request = self.inner.http.get(url);
         let response = self.inner.send(request).await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let mut result = response.json::<ListModelsResponse>().await?;
@@ -1801,6 +2549,19 @@ impl Models {
         Ok(result)
     }
 
+    /// 发送一次 `pageSize=1` 的模型列表请求以预热连接；忽略响应状态码。
+    pub(crate) async fn warmup(&self) -> Result<()> {
+        let config = ListModelsConfig {
+            page_size: Some(1),
+            ..Default::default()
+        };
+        let url = build_models_list_url(&self.inner, &config)?;
+        let response = self.inner.send(self.inner.http.get(url)).await?;
+        // 读完响应体后连接才会回到连接池。
+        response.bytes().await?;
+        Ok(())
+    }
+
     /// 列出所有模型（自动翻页）。
     ///
     /// # Errors
@@ -1815,21 +2576,44 @@ impl Models {
     /// # Errors
     ///
     /// 当请求失败、配置不合法或响应解析失败时返回错误。
-    pub async fn all_with_config(&self, mut config: ListModelsConfig) -> Result<Vec<Model>> {
-        let mut models = Vec::new();
-        loop {
-            let response = self.list_with_config(config.clone()).await?;
-            if let Some(items) = response.models {
-                models.extend(items);
+    pub async fn all_with_config(&self, config: ListModelsConfig) -> Result<Vec<Model>> {
+        self.list_stream(config).try_collect().await
+    }
+
+    /// 列出所有声明支持指定方法的模型（自动翻页，按 [`Model::supports_method`] 过滤）。
+    ///
+    /// # Errors
+    ///
+    /// 当请求失败或响应解析失败时返回错误。
+    pub async fn supporting(&self, method: &str) -> Result<Vec<Model>> {
+        let models = self.all().await?;
+        Ok(models
+            .into_iter()
+            .filter(|model| model.supports_method(method))
+            .collect())
+    }
+
+    /// 以流的形式逐个产出模型（自动翻页）。
+    ///
+    /// 任一页请求失败时产出该错误并结束流。
+    pub fn list_stream(
+        &self,
+        config: ListModelsConfig,
+    ) -> impl Stream<Item = Result<Model>> + Send + '_ {
+        paginate(move |page_token| {
+            let mut page_config = config.clone();
+            if page_token.is_some() {
+                page_config.page_token = page_token;
             }
-            match response.next_page_token {
-                Some(token) if !token.is_empty() => {
-                    config.page_token = Some(token);
-                }
-                _ => break,
+            async move {
+                self.list_with_config(page_config).await.map(|response| {
+                    (
+                        response.models.unwrap_or_default(),
+                        response.next_page_token,
+                    )
+                })
             }
-        }
-        Ok(models)
+        })
     }
 
     /// 获取单个模型信息。
@@ -1837,12 +2621,12 @@ impl Models {
     /// # Errors
     ///
     /// 当请求失败或响应解析失败时返回错误。
-    pub async fn get(&self, model: impl Into<String>) -> Result<Model> {
-        let url = build_model_get_url(&self.inner, &model.into())?;
+    pub async fn get(&self, model: impl Into<ModelName>) -> Result<Model> {
+        let url = build_model_get_url(&self.inner, model.into())?;
         let request = self.inner.http.get(url);
         let response = self.inner.send(request).await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let result = response.json::<Model>().await?;
         Ok(result)
@@ -1855,12 +2639,12 @@ impl Models {
     /// 当请求失败、配置不合法或响应解析失败时返回错误。
     pub async fn update(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         mut config: UpdateModelConfig,
     ) -> Result<Model> {
         let http_options = config.http_options.take();
         let url =
-            build_model_get_url_with_options(&self.inner, &model.into(), http_options.as_ref())?;
+            build_model_get_url_with_options(&self.inner, model.into(), http_options.as_ref())?;
 
         let mut body = serde_json::to_value(&config)?;
         if let Some(options) = http_options.as_ref() {
@@ -1874,7 +2658,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<Model>().await?)
     }
@@ -1886,12 +2670,12 @@ impl Models {
     /// 当请求失败或响应解析失败时返回错误。
     pub async fn delete(
         &self,
-        model: impl Into<String>,
+        model: impl Into<ModelName>,
         mut config: DeleteModelConfig,
     ) -> Result<DeleteModelResponse> {
         let http_options = config.http_options.take();
         let url =
-            build_model_get_url_with_options(&self.inner, &model.into(), http_options.as_ref())?;
+            build_model_get_url_with_options(&self.inner, model.into(), http_options.as_ref())?;
 
         let mut request = self.inner.http.delete(url);
         request = apply_http_options(request, http_options.as_ref())?;
@@ -1901,7 +2685,7 @@ impl Models {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         if response.content_length().unwrap_or(0) == 0 {
@@ -1919,6 +2703,33 @@ impl Models {
     }
 }
 
+fn parse_json_response<T: DeserializeOwned>(
+    response: &GenerateContentResponse,
+    repair: bool,
+) -> Result<T> {
+    let text = first_candidate_text(response).ok_or_else(|| Error::Parse {
+        message: "Expected text response containing JSON".into(),
+    })?;
+    let err = match serde_json::from_str(&text) {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+    if response.finish_reason() != Some(FinishReason::MaxTokens) {
+        return Err(err.into());
+    }
+    if repair {
+        if let Some(value) = json_repair::repair_truncated_json(&text) {
+            return Ok(serde_json::from_value(value)?);
+        }
+    }
+    Err(Error::OutputTruncated {
+        message: format!(
+            "JSON response was cut off by max_output_tokens ({err}); raise max_output_tokens \
+             or set repair_truncated_json"
+        ),
+    })
+}
+
 fn first_candidate_text(response: &GenerateContentResponse) -> Option<String> {
     let mut text = String::new();
     let content = response.candidates.first()?.content.as_ref()?;
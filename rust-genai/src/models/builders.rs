@@ -8,14 +8,15 @@ use rust_genai_types::models::{
 };
 use serde_json::{Map, Number, Value};
 
-use super::http::transform_model_name;
+use super::http::resolve_model_name;
 use super::media::{
     image_to_mldev, image_to_vertex, reference_image_to_vertex, video_mask_to_vertex,
     video_reference_image_to_value, video_to_mldev, video_to_vertex,
 };
+use super::ModelName;
 
 pub(super) fn build_embed_body_gemini(
-    model: &str,
+    model: impl Into<ModelName>,
     contents: &[Content],
     config: &EmbedContentConfig,
 ) -> Result<Value> {
@@ -25,16 +26,17 @@ pub(super) fn build_embed_body_gemini(
         });
     }
 
+    let model = resolve_model_name(Backend::GeminiApi, &model.into());
     let mut requests: Vec<Value> = Vec::new();
     for content in contents {
         let mut obj = Map::new();
-        obj.insert(
-            "model".to_string(),
-            Value::String(transform_model_name(Backend::GeminiApi, model)),
-        );
+        obj.insert("model".to_string(), Value::String(model.clone()));
         obj.insert("content".to_string(), serde_json::to_value(content)?);
         if let Some(task_type) = &config.task_type {
-            obj.insert("taskType".to_string(), Value::String(task_type.clone()));
+            obj.insert(
+                "taskType".to_string(),
+                Value::String(task_type.as_str().to_string()),
+            );
         }
         if let Some(title) = &config.title {
             obj.insert("title".to_string(), Value::String(title.clone()));
@@ -64,7 +66,10 @@ pub(super) fn build_embed_body_vertex(
         let mut obj = Map::new();
         obj.insert("content".to_string(), serde_json::to_value(content)?);
         if let Some(task_type) = &config.task_type {
-            obj.insert("task_type".to_string(), Value::String(task_type.clone()));
+            obj.insert(
+                "task_type".to_string(),
+                Value::String(task_type.as_str().to_string()),
+            );
         }
         if let Some(title) = &config.title {
             obj.insert("title".to_string(), Value::String(title.clone()));
@@ -321,7 +326,7 @@ pub(super) fn build_upscale_image_body(
 ) -> Result<Value> {
     let mut instances = Vec::new();
     let mut instance = Map::new();
-    instance.insert("image".to_string(), image_to_vertex(image));
+    instance.insert("image".to_string(), image_to_vertex(image)?);
     instances.push(Value::Object(instance));
 
     let mut root = Map::new();
@@ -403,7 +408,7 @@ pub(super) fn build_recontext_image_body(
     }
     if let Some(person_image) = &source.person_image {
         let mut person = Map::new();
-        person.insert("image".to_string(), image_to_vertex(person_image));
+        person.insert("image".to_string(), image_to_vertex(person_image)?);
         instance.insert("personImage".to_string(), Value::Object(person));
     }
     if let Some(product_images) = &source.product_images {
@@ -411,7 +416,7 @@ pub(super) fn build_recontext_image_body(
         for item in product_images {
             if let Some(image) = &item.product_image {
                 let mut product = Map::new();
-                product.insert("image".to_string(), image_to_vertex(image));
+                product.insert("image".to_string(), image_to_vertex(image)?);
                 products.push(Value::Object(product));
             }
         }
@@ -488,12 +493,12 @@ pub(super) fn build_segment_image_body(
         instance.insert("prompt".to_string(), Value::String(prompt.clone()));
     }
     if let Some(image) = &source.image {
-        instance.insert("image".to_string(), image_to_vertex(image));
+        instance.insert("image".to_string(), image_to_vertex(image)?);
     }
     if let Some(scribble) = &source.scribble_image {
         if let Some(image) = &scribble.image {
             let mut scribble_map = Map::new();
-            scribble_map.insert("image".to_string(), image_to_vertex(image));
+            scribble_map.insert("image".to_string(), image_to_vertex(image)?);
             instance.insert("scribble".to_string(), Value::Object(scribble_map));
         }
     }
@@ -555,7 +560,7 @@ fn build_generate_videos_instance(
     if let Some(image) = &source.image {
         let value = match backend {
             Backend::GeminiApi => image_to_mldev(image)?,
-            Backend::VertexAi => image_to_vertex(image),
+            Backend::VertexAi => image_to_vertex(image)?,
         };
         instance.insert("image".to_string(), value);
     }
@@ -569,7 +574,7 @@ fn build_generate_videos_instance(
     if let Some(last_frame) = &config.last_frame {
         let value = match backend {
             Backend::GeminiApi => image_to_mldev(last_frame)?,
-            Backend::VertexAi => image_to_vertex(last_frame),
+            Backend::VertexAi => image_to_vertex(last_frame)?,
         };
         instance.insert("lastFrame".to_string(), value);
     }
@@ -699,7 +704,7 @@ mod tests {
     use rust_genai_types::content::Content;
     use rust_genai_types::enums::{
         EditMode, ImagePromptLanguage, PersonGeneration, ReferenceImageType, SafetyFilterLevel,
-        SegmentMode, VideoCompressionQuality, VideoGenerationMaskMode,
+        SegmentMode, TaskType, VideoCompressionQuality, VideoGenerationMaskMode,
         VideoGenerationReferenceType,
     };
     use rust_genai_types::models::{
@@ -761,11 +766,48 @@ mod tests {
         assert!(body.get("parameters").is_some());
     }
 
+    #[test]
+    fn test_image_bodies_reject_ambiguous_image_source() {
+        let ambiguous = Image {
+            gcs_uri: Some("gs://input.png".to_string()),
+            image_bytes: Some(vec![1, 2, 3]),
+            ..Default::default()
+        };
+        let segment = SegmentImageSource {
+            prompt: None,
+            image: Some(ambiguous.clone()),
+            scribble_image: None,
+        };
+        let err = build_segment_image_body(&segment, &SegmentImageConfig::default()).unwrap_err();
+        match err {
+            Error::InvalidConfig { message } => {
+                assert!(message.contains("both gcs_uri and image_bytes"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let recontext = RecontextImageSource {
+            person_image: Some(ambiguous.clone()),
+            ..Default::default()
+        };
+        let err =
+            build_recontext_image_body(&recontext, &RecontextImageConfig::default()).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+
+        let err =
+            build_upscale_image_body(&ambiguous, "x2", &UpscaleImageConfig::default()).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+
+        let err = build_upscale_image_body(&Image::default(), "x2", &UpscaleImageConfig::default())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+    }
+
     #[test]
     fn test_build_embed_body_gemini_and_vertex() {
         let contents = vec![Content::text("hello"), Content::text("world")];
         let config = EmbedContentConfig {
-            task_type: Some("retrieval".to_string()),
+            task_type: Some(TaskType::RetrievalDocument),
             title: Some("Title".to_string()),
             output_dimensionality: Some(8),
             ..Default::default()
@@ -779,7 +821,7 @@ mod tests {
         );
         assert_eq!(
             requests[0].get("taskType").and_then(Value::as_str),
-            Some("retrieval")
+            Some("RETRIEVAL_DOCUMENT")
         );
 
         let bad_config = EmbedContentConfig {
@@ -790,7 +832,7 @@ mod tests {
         assert!(matches!(err, Error::InvalidConfig { .. }));
 
         let vertex_config = EmbedContentConfig {
-            task_type: Some("retrieval".to_string()),
+            task_type: Some(TaskType::RetrievalDocument),
             title: Some("Title".to_string()),
             mime_type: Some("text/plain".to_string()),
             auto_truncate: Some(true),
@@ -799,6 +841,10 @@ mod tests {
         let body = build_embed_body_vertex(&contents, &vertex_config).unwrap();
         let instances = body.get("instances").and_then(Value::as_array).unwrap();
         assert_eq!(instances.len(), 2);
+        assert_eq!(
+            instances[0].get("task_type").and_then(Value::as_str),
+            Some("RETRIEVAL_DOCUMENT")
+        );
         let params = body.get("parameters").and_then(Value::as_object).unwrap();
         assert_eq!(
             params.get("autoTruncate").and_then(Value::as_bool),
@@ -5,10 +5,12 @@ use rust_genai_types::models::ListModelsConfig;
 use serde_json::Value;
 use std::time::Duration;
 
+use super::ModelName;
+
 pub(super) fn transform_model_name(backend: Backend, model: &str) -> String {
     match backend {
         Backend::GeminiApi => {
-            if model.starts_with("models/") {
+            if model.starts_with("models/") || model.starts_with("tunedModels/") {
                 model.to_string()
             } else {
                 format!("models/{model}")
@@ -24,16 +26,42 @@ pub(super) fn transform_model_name(backend: Backend, model: &str) -> String {
     }
 }
 
+/// Resolves a model identifier into its resource path; raw names are used verbatim.
+pub(super) fn resolve_model_name(backend: Backend, model: &ModelName) -> String {
+    if model.is_raw() {
+        model.as_str().to_string()
+    } else {
+        transform_model_name(backend, model.as_str())
+    }
+}
+
 pub(super) fn build_model_method_url(
     inner: &ClientInner,
-    model: &str,
+    model: impl Into<ModelName>,
     method: &str,
 ) -> Result<String> {
-    let model = transform_model_name(inner.config.backend, model);
-    let base = &inner.api_client.base_url;
-    let version = &inner.api_client.api_version;
+    build_model_method_url_with_options(inner, model, method, None)
+}
+
+pub(super) fn build_model_method_url_with_options(
+    inner: &ClientInner,
+    model: impl Into<ModelName>,
+    method: &str,
+    http_options: Option<&rust_genai_types::http::HttpOptions>,
+) -> Result<String> {
+    let model = resolve_model_name(inner.config.backend, &model.into());
+    let base = http_options
+        .and_then(|opts| opts.base_url.as_deref())
+        .unwrap_or(&inner.api_client.base_url);
+    let version = http_options
+        .and_then(|opts| opts.api_version.as_deref())
+        .unwrap_or(&inner.api_client.api_version);
     let url = match inner.config.backend {
         Backend::GeminiApi => format!("{base}{version}/{model}:{method}"),
+        // Fully-qualified resources (e.g. tuned model endpoints) already carry project/location.
+        Backend::VertexAi if model.starts_with("projects/") => {
+            format!("{base}{version}/{model}:{method}")
+        }
         Backend::VertexAi => {
             let vertex =
                 inner
@@ -52,36 +80,19 @@ pub(super) fn build_model_method_url(
     Ok(url)
 }
 
-pub(super) fn build_model_get_url(inner: &ClientInner, model: &str) -> Result<String> {
-    let model = transform_model_name(inner.config.backend, model);
-    let base = &inner.api_client.base_url;
-    let version = &inner.api_client.api_version;
-    let url = match inner.config.backend {
-        Backend::GeminiApi => format!("{base}{version}/{model}"),
-        Backend::VertexAi => {
-            let vertex =
-                inner
-                    .config
-                    .vertex_config
-                    .as_ref()
-                    .ok_or_else(|| Error::InvalidConfig {
-                        message: "Vertex config missing".into(),
-                    })?;
-            format!(
-                "{base}{version}/projects/{}/locations/{}/{}",
-                vertex.project, vertex.location, model
-            )
-        }
-    };
-    Ok(url)
+pub(super) fn build_model_get_url(
+    inner: &ClientInner,
+    model: impl Into<ModelName>,
+) -> Result<String> {
+    build_model_get_url_with_options(inner, model, None)
 }
 
 pub(super) fn build_model_get_url_with_options(
     inner: &ClientInner,
-    model: &str,
+    model: impl Into<ModelName>,
     http_options: Option<&rust_genai_types::http::HttpOptions>,
 ) -> Result<String> {
-    let model = transform_model_name(inner.config.backend, model);
+    let model = resolve_model_name(inner.config.backend, &model.into());
     let base = http_options
         .and_then(|opts| opts.base_url.as_deref())
         .unwrap_or(&inner.api_client.base_url);
@@ -90,6 +101,7 @@ pub(super) fn build_model_get_url_with_options(
         .unwrap_or(&inner.api_client.api_version);
     let url = match inner.config.backend {
         Backend::GeminiApi => format!("{base}{version}/{model}"),
+        Backend::VertexAi if model.starts_with("projects/") => format!("{base}{version}/{model}"),
         Backend::VertexAi => {
             let vertex =
                 inner
@@ -176,10 +188,21 @@ pub(super) fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
 
+pub(super) fn stream_idle_timeout(
+    http_options: Option<&rust_genai_types::http::HttpOptions>,
+) -> Option<Duration> {
+    http_options
+        .and_then(|options| options.stream_idle_timeout)
+        .map(Duration::from_millis)
+}
+
 pub(super) fn merge_extra_body(
     body: &mut Value,
     http_options: &rust_genai_types::http::HttpOptions,
@@ -221,6 +244,46 @@ mod tests {
             transform_model_name(Backend::VertexAi, "gemini-1.5-pro"),
             "publishers/google/models/gemini-1.5-pro"
         );
+        assert_eq!(
+            transform_model_name(Backend::GeminiApi, "tunedModels/my-model"),
+            "tunedModels/my-model"
+        );
+    }
+
+    #[test]
+    fn test_raw_model_bypasses_transform() {
+        let raw = ModelName::from(crate::models::RawModel::new("publishers/meta/models/llama"));
+        assert_eq!(
+            resolve_model_name(Backend::GeminiApi, &raw),
+            "publishers/meta/models/llama"
+        );
+        assert_eq!(
+            resolve_model_name(
+                Backend::VertexAi,
+                &ModelName::from(crate::models::RawModel::new("endpoints/1"))
+            ),
+            "endpoints/1"
+        );
+        assert_eq!(
+            resolve_model_name(Backend::VertexAi, &ModelName::from("endpoints/1")),
+            "publishers/google/models/endpoints/1"
+        );
+    }
+
+    #[test]
+    fn test_vertex_fully_qualified_model_urls() {
+        let inner = crate::test_support::test_client_inner(Backend::VertexAi);
+        let url = build_model_method_url(
+            &inner,
+            "projects/proj/locations/loc/endpoints/123",
+            "generateContent",
+        )
+        .unwrap();
+        assert!(url.ends_with("/v1beta1/projects/proj/locations/loc/endpoints/123:generateContent"));
+        assert!(!url.contains("locations/loc/projects/"));
+
+        let url = build_model_get_url(&inner, "projects/proj/locations/loc/models/456").unwrap();
+        assert!(url.ends_with("/v1beta1/projects/proj/locations/loc/models/456"));
     }
 
     #[test]
@@ -247,6 +310,17 @@ mod tests {
         let url =
             build_model_get_url_with_options(&models.inner, "gemini-1.5-pro", Some(&opts)).unwrap();
         assert_eq!(url, "https://example.com/v1/models/gemini-1.5-pro");
+        let url = build_model_method_url_with_options(
+            &models.inner,
+            "gemini-1.5-pro",
+            "generateContent",
+            Some(&opts),
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "https://example.com/v1/models/gemini-1.5-pro:generateContent"
+        );
 
         let url = build_models_list_url(
             &models.inner,
@@ -275,6 +349,21 @@ mod tests {
         assert!(matches!(err, Error::InvalidConfig { .. }));
     }
 
+    #[test]
+    fn test_apply_http_options_extra_query() {
+        let client = reqwest::Client::new();
+        let request = client.get("https://example.com/v1beta/models?pageSize=2");
+        let options = rust_genai_types::http::HttpOptions {
+            extra_query: Some(vec![("alt".to_string(), "sse".to_string())]),
+            ..Default::default()
+        };
+        let request = apply_http_options(request, Some(&options))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.url().query(), Some("pageSize=2&alt=sse"));
+    }
+
     #[test]
     fn test_vertex_missing_config_errors() {
         let inner = test_vertex_inner_missing_config();
@@ -0,0 +1,120 @@
+//! Best-effort repair of JSON cut off by `max_output_tokens`.
+
+use serde_json::Value;
+
+/// Closes open strings/brackets of a truncated JSON document, backing off to the previous
+/// element boundary while the result still does not parse.
+pub(super) fn repair_truncated_json(text: &str) -> Option<Value> {
+    let mut end = text.len();
+    loop {
+        let candidate = close_json(&text[..end]);
+        if let Ok(value) = serde_json::from_str(&candidate) {
+            return Some(value);
+        }
+        end = last_separator_outside_string(&text[..end])?;
+    }
+}
+
+fn close_json(text: &str) -> String {
+    let mut closers = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for ch in text.chars() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '{' => closers.push('}'),
+            '[' => closers.push(']'),
+            '}' | ']' => {
+                closers.pop();
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = text.to_string();
+    if in_string {
+        if escaped {
+            out.pop();
+        }
+        out.push('"');
+    }
+    let trimmed_len = out.trim_end().len();
+    out.truncate(trimmed_len);
+    if out.ends_with(',') {
+        out.pop();
+    }
+    if out.ends_with(':') {
+        out.push_str("null");
+    }
+    out.extend(closers.iter().rev());
+    out
+}
+
+/// Byte offset of the last `,` (or opening bracket, kept) outside a string literal.
+fn last_separator_outside_string(text: &str) -> Option<usize> {
+    let mut last = None;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, ch) in text.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            ',' => last = Some(index),
+            '{' | '[' => last = Some(index + 1),
+            _ => {}
+        }
+    }
+    last.filter(|&index| index < text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn repairs_common_truncations() {
+        assert_eq!(
+            repair_truncated_json(r#"{"name": "Ada", "tags": ["a", "b"#),
+            Some(json!({"name": "Ada", "tags": ["a", "b"]}))
+        );
+        assert_eq!(
+            repair_truncated_json(r#"{"name": "Ada", "age": "#),
+            Some(json!({"name": "Ada", "age": null}))
+        );
+        assert_eq!(
+            repair_truncated_json(r#"{"name": "Ada", "active": tr"#),
+            Some(json!({"name": "Ada"}))
+        );
+        assert_eq!(
+            repair_truncated_json(r#"[{"a": 1}, {"a": 2}, {"#),
+            Some(json!([{"a": 1}, {"a": 2}, {}]))
+        );
+        assert_eq!(
+            repair_truncated_json(r#"{"quote": "say \"#),
+            Some(json!({"quote": "say "}))
+        );
+    }
+
+    #[test]
+    fn gives_up_on_garbage() {
+        assert_eq!(repair_truncated_json("not json"), None);
+    }
+}
@@ -8,12 +8,198 @@ use rust_genai_types::models::{
     Image, ReferenceImage, Video, VideoGenerationMask, VideoGenerationReferenceImage,
 };
 
-pub(super) fn image_to_mldev(image: &Image) -> Result<Value> {
-    if image.gcs_uri.is_some() {
+/// 校验图像来源是否被指定后端支持，便于在发送请求前发现配置错误。
+///
+/// # Errors
+/// 当图像既无 `gcs_uri` 也无 `image_bytes`、两者同时设置，或在 Gemini API 上使用 `gcs_uri`
+/// 时返回错误。
+pub fn validate_image_source(image: &Image, backend: Backend) -> Result<()> {
+    if image.gcs_uri.is_none() && image.image_bytes.is_none() {
+        return Err(Error::InvalidConfig {
+            message: "Image has no source; set gcs_uri or image_bytes".into(),
+        });
+    }
+    ensure_single_source(image)?;
+    if backend == Backend::GeminiApi {
+        ensure_no_gcs_uri(image)?;
+    }
+    Ok(())
+}
+
+/// Imagen/Veo 内联图像允许的最大字节数（与内联请求 20 MB 上限一致）。
+pub const MAX_INLINE_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Veo 内联视频允许的最大字节数（与内联请求 20 MB 上限一致）。
+pub const MAX_INLINE_VIDEO_BYTES: usize = 20 * 1024 * 1024;
+
+/// Imagen/Veo 输入图像单边允许的最大像素数。
+pub const MAX_IMAGE_DIMENSION: u32 = 4096;
+
+/// 校验内联图像的字节数与尺寸是否在 Imagen/Veo 的限制内。
+///
+/// 尺寸通过解析 PNG/JPEG/GIF/WebP 文件头获取；无法识别的格式只校验字节数。
+///
+/// # Errors
+/// 当图像字节数超过 [`MAX_INLINE_IMAGE_BYTES`] 或任一边超过 [`MAX_IMAGE_DIMENSION`] 时返回错误。
+pub fn validate_image_limits(image: &Image) -> Result<()> {
+    let Some(bytes) = &image.image_bytes else {
+        return Ok(());
+    };
+    if bytes.len() > MAX_INLINE_IMAGE_BYTES {
+        return Err(Error::InvalidConfig {
+            message: format!(
+                "Inline image is {} bytes, exceeding the {MAX_INLINE_IMAGE_BYTES} byte limit",
+                bytes.len()
+            ),
+        });
+    }
+    if let Some((width, height)) = image_dimensions(bytes) {
+        if width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION {
+            return Err(Error::InvalidConfig {
+                message: format!(
+                    "Inline image is {width}x{height}, exceeding the {MAX_IMAGE_DIMENSION}px dimension limit"
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 校验内联视频的字节数是否在 Veo 的限制内。
+///
+/// # Errors
+/// 当视频字节数超过 [`MAX_INLINE_VIDEO_BYTES`] 时返回错误。
+pub fn validate_video_limits(video: &Video) -> Result<()> {
+    let Some(bytes) = &video.video_bytes else {
+        return Ok(());
+    };
+    if bytes.len() > MAX_INLINE_VIDEO_BYTES {
+        return Err(Error::InvalidConfig {
+            message: format!(
+                "Inline video is {} bytes, exceeding the {MAX_INLINE_VIDEO_BYTES} byte limit",
+                bytes.len()
+            ),
+        });
+    }
+    Ok(())
+}
+
+pub(super) fn validate_media_limits<'a>(
+    images: impl IntoIterator<Item = &'a Image>,
+    videos: impl IntoIterator<Item = &'a Video>,
+) -> Result<()> {
+    images.into_iter().try_for_each(validate_image_limits)?;
+    videos.into_iter().try_for_each(validate_video_limits)
+}
+
+/// 从 PNG/JPEG/GIF/WebP 文件头解析图像宽高；无法识别时返回 `None`。
+#[must_use]
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((be_u32(bytes, 16)?, be_u32(bytes, 20)?));
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some((u32::from(le_u16(bytes, 6)?), u32::from(le_u16(bytes, 8)?)));
+    }
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        return jpeg_dimensions(bytes);
+    }
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return webp_dimensions(bytes);
+    }
+    None
+}
+
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut offset = 2;
+    while offset + 4 <= bytes.len() {
+        if bytes[offset] != 0xFF {
+            return None;
+        }
+        let marker = bytes[offset + 1];
+        if marker == 0xFF {
+            offset += 1;
+            continue;
+        }
+        let length = usize::from(be_u16(bytes, offset + 2)?);
+        // SOF0..SOF15，排除 DHT(C4)、JPG(C8)、DAC(CC)。
+        if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let height = be_u16(bytes, offset + 5)?;
+            let width = be_u16(bytes, offset + 7)?;
+            return Some((u32::from(width), u32::from(height)));
+        }
+        offset += 2 + length;
+    }
+    None
+}
+
+fn webp_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        b"VP8 " => {
+            let width = le_u16(bytes, 26)? & 0x3FFF;
+            let height = le_u16(bytes, 28)? & 0x3FFF;
+            Some((u32::from(width), u32::from(height)))
+        }
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => {
+            let width = le_u24(bytes, 24)? + 1;
+            let height = le_u24(bytes, 27)? + 1;
+            Some((width, height))
+        }
+        _ => None,
+    }
+}
+
+fn be_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn be_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn le_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn le_u24(bytes: &[u8], offset: usize) -> Option<u32> {
+    let slice = bytes.get(offset..offset + 3)?;
+    Some(u32::from(slice[0]) | u32::from(slice[1]) << 8 | u32::from(slice[2]) << 16)
+}
+
+fn ensure_single_source(image: &Image) -> Result<()> {
+    if image.gcs_uri.is_some() && image.image_bytes.is_some() {
         return Err(Error::InvalidConfig {
-            message: "gcs_uri is not supported in Gemini API".into(),
+            message: "Image has both gcs_uri and image_bytes set; provide exactly one source"
+                .into(),
         });
     }
+    Ok(())
+}
+
+fn ensure_no_gcs_uri(image: &Image) -> Result<()> {
+    if let Some(uri) = &image.gcs_uri {
+        return Err(Error::InvalidConfig {
+            message: format!(
+                "Image gcs_uri ({uri}) is not supported in Gemini API; use inline bytes \
+                 (e.g. Image::from_path) or the Vertex AI backend"
+            ),
+        });
+    }
+    Ok(())
+}
+
+pub(super) fn image_to_mldev(image: &Image) -> Result<Value> {
+    validate_image_source(image, Backend::GeminiApi)?;
     let mut map = Map::new();
     if let Some(bytes) = &image.image_bytes {
         map.insert(
@@ -27,7 +213,8 @@ pub(super) fn image_to_mldev(image: &Image) -> Result<Value> {
     Ok(Value::Object(map))
 }
 
-pub(super) fn image_to_vertex(image: &Image) -> Value {
+pub(super) fn image_to_vertex(image: &Image) -> Result<Value> {
+    validate_image_source(image, Backend::VertexAi)?;
     let mut map = Map::new();
     if let Some(gcs_uri) = &image.gcs_uri {
         map.insert("gcsUri".to_string(), Value::String(gcs_uri.clone()));
@@ -41,7 +228,7 @@ pub(super) fn image_to_vertex(image: &Image) -> Value {
     if let Some(mime) = &image.mime_type {
         map.insert("mimeType".to_string(), Value::String(mime.clone()));
     }
-    Value::Object(map)
+    Ok(Value::Object(map))
 }
 
 pub(super) fn video_to_mldev(video: &Video) -> Value {
@@ -95,7 +282,7 @@ pub(super) fn reference_image_to_vertex(image: &ReferenceImage) -> Result<Value>
     if let Some(reference_image) = &image.reference_image {
         map.insert(
             "referenceImage".to_string(),
-            image_to_vertex(reference_image),
+            image_to_vertex(reference_image)?,
         );
     }
     if let Some(reference_id) = image.reference_id {
@@ -142,7 +329,7 @@ pub(super) fn video_reference_image_to_value(
     if let Some(image) = &reference.image {
         let value = match backend {
             Backend::GeminiApi => image_to_mldev(image)?,
-            Backend::VertexAi => image_to_vertex(image),
+            Backend::VertexAi => image_to_vertex(image)?,
         };
         map.insert("image".to_string(), value);
     }
@@ -158,7 +345,7 @@ pub(super) fn video_reference_image_to_value(
 pub(super) fn video_mask_to_vertex(mask: &VideoGenerationMask) -> Result<Value> {
     let mut map = Map::new();
     if let Some(image) = &mask.image {
-        map.insert("image".to_string(), image_to_vertex(image));
+        map.insert("image".to_string(), image_to_vertex(image)?);
     }
     if let Some(mode) = mask.mask_mode {
         map.insert("maskMode".to_string(), serde_json::to_value(mode)?);
@@ -179,6 +366,87 @@ mod tests {
         SubjectReferenceConfig, Video, VideoGenerationMask, VideoGenerationReferenceImage,
     };
 
+    #[test]
+    fn test_validate_image_source() {
+        let image = Image::from_gcs("gs://bucket/cat.png");
+        let err = validate_image_source(&image, Backend::GeminiApi).unwrap_err();
+        match err {
+            Error::InvalidConfig { message } => {
+                assert!(message.contains("gs://bucket/cat.png"));
+                assert!(message.contains("not supported in Gemini API"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        validate_image_source(&image, Backend::VertexAi).unwrap();
+
+        let image = Image::from_bytes(vec![1, 2], "image/png");
+        validate_image_source(&image, Backend::GeminiApi).unwrap();
+
+        let err = validate_image_source(&Image::default(), Backend::VertexAi).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+    }
+
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_image_dimensions_parses_headers() {
+        assert_eq!(image_dimensions(&png_header(640, 480)), Some((640, 480)));
+
+        let gif = [b"GIF89a".as_slice(), &[0x20, 0x03, 0x58, 0x02]].concat();
+        assert_eq!(image_dimensions(&gif), Some((800, 600)));
+
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x02,
+            0xD0, 0x05, 0x00,
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((1280, 720)));
+
+        let mut webp = b"RIFF\x00\x00\x00\x00WEBPVP8X".to_vec();
+        webp.extend_from_slice(&[0; 8]);
+        webp.extend_from_slice(&[0xFF, 0x0F, 0x00, 0xFF, 0x07, 0x00]);
+        assert_eq!(image_dimensions(&webp), Some((4096, 2048)));
+
+        assert_eq!(image_dimensions(b"not an image"), None);
+    }
+
+    #[test]
+    fn test_validate_media_limits() {
+        validate_image_limits(&Image::from_bytes(png_header(1024, 1024), "image/png")).unwrap();
+        let err = validate_image_limits(&Image::from_bytes(png_header(8000, 600), "image/png"))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { message } if message.contains("8000x600")));
+        let err = validate_image_limits(&Image::from_bytes(
+            vec![0; MAX_INLINE_IMAGE_BYTES + 1],
+            "image/png",
+        ))
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+        validate_image_limits(&Image::from_gcs("gs://bucket/cat.png")).unwrap();
+
+        let video = Video {
+            video_bytes: Some(vec![0; MAX_INLINE_VIDEO_BYTES + 1]),
+            ..Default::default()
+        };
+        assert!(validate_video_limits(&video).is_err());
+        assert!(validate_video_limits(&Video::default()).is_ok());
+    }
+
+    #[test]
+    fn test_image_from_path_guesses_mime_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("photo.JPG");
+        std::fs::write(&path, [0xff, 0xd8]).unwrap();
+        let image = Image::from_path(&path).unwrap();
+        assert_eq!(image.image_bytes, Some(vec![0xff, 0xd8]));
+        assert_eq!(image.mime_type.as_deref(), Some("image/jpeg"));
+        assert!(image.gcs_uri.is_none());
+    }
+
     #[test]
     fn test_media_converters_basic() {
         let image = Image {
@@ -186,9 +454,26 @@ mod tests {
             image_bytes: Some(vec![1, 2]),
             mime_type: Some("image/png".to_string()),
         };
-        let vertex_image = image_to_vertex(&image);
+        let err = image_to_vertex(&image).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+        let err = image_to_mldev(&image).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+
+        let image = Image {
+            image_bytes: None,
+            ..image
+        };
+        let vertex_image = image_to_vertex(&image).unwrap();
         assert!(vertex_image.get("gcsUri").is_some());
+        let err = image_to_mldev(&image).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
 
+        let image = Image {
+            mime_type: Some("image/png".to_string()),
+            ..Default::default()
+        };
+        let err = image_to_vertex(&image).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
         let err = image_to_mldev(&image).unwrap_err();
         assert!(matches!(err, Error::InvalidConfig { .. }));
 
@@ -11,7 +11,9 @@ use rust_genai_types::models::{
 use rust_genai_types::operations::OperationError;
 use serde_json::Value;
 
-pub(super) fn convert_vertex_embed_response(value: &Value) -> Result<EmbedContentResponse> {
+pub(super) fn convert_vertex_embed_response(
+    value: &Value,
+) -> serde_json::Result<EmbedContentResponse> {
     let predictions = value
         .get("predictions")
         .and_then(|pred| pred.as_array())
@@ -21,7 +23,14 @@ pub(super) fn convert_vertex_embed_response(value: &Value) -> Result<EmbedConten
     let mut embeddings: Vec<ContentEmbedding> = Vec::new();
     for item in predictions {
         if let Some(embedding_value) = item.get("embeddings") {
-            let embedding: ContentEmbedding = serde_json::from_value(embedding_value.clone())?;
+            let mut embedding: ContentEmbedding = serde_json::from_value(embedding_value.clone())?;
+            // Some models report statistics next to `embeddings` instead of inside it.
+            if embedding.statistics.is_none() {
+                embedding.statistics = item
+                    .get("statistics")
+                    .map(|stats| serde_json::from_value(stats.clone()))
+                    .transpose()?;
+            }
             embeddings.push(embedding);
         }
     }
@@ -31,10 +40,21 @@ pub(super) fn convert_vertex_embed_response(value: &Value) -> Result<EmbedConten
         .map(|meta| serde_json::from_value(meta.clone()))
         .transpose()?;
 
+    let extra = value
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "predictions" | "metadata"))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+
     Ok(EmbedContentResponse {
         sdk_http_response: None,
         embeddings: Some(embeddings),
         metadata,
+        extra,
     })
 }
 
@@ -457,6 +477,25 @@ mod tests {
         assert!(response.metadata.is_some());
     }
 
+    #[test]
+    fn test_vertex_embed_response_truncation_statistics() {
+        let response = convert_vertex_embed_response(&json!({
+            "predictions": [
+                {"embeddings": {"values": [0.1], "statistics": {"truncated": true, "token_count": 2048}}},
+                {"embeddings": {"values": [0.2]}, "statistics": {"truncated": false, "token_count": 3}},
+                {"embeddings": {"values": [0.3]}}
+            ]
+        }))
+        .unwrap();
+        let embeddings = response.embeddings.as_ref().unwrap();
+        assert_eq!(embeddings[0].truncated(), Some(true));
+        assert_eq!(embeddings[0].token_count(), Some(2048.0));
+        assert_eq!(embeddings[1].truncated(), Some(false));
+        assert_eq!(embeddings[1].token_count(), Some(3.0));
+        assert_eq!(embeddings[2].truncated(), None);
+        assert!(response.any_truncated());
+    }
+
     #[test]
     fn test_parse_recontext_and_segment_responses() {
         let value =
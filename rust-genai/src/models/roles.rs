@@ -0,0 +1,175 @@
+//! Content role checks for function call / response turns.
+
+use rust_genai_types::content::{Content, PartKind, Role};
+use rust_genai_types::models::ContentRoleCheck;
+
+use crate::error::{Error, Result};
+
+/// 检查 `contents` 的角色序列与函数调用/响应配对，`Normalize` 模式下就地修正角色。
+pub(super) fn check_content_roles(contents: &mut [Content], mode: ContentRoleCheck) -> Result<()> {
+    for index in 0..contents.len() {
+        let (has_call, has_response) = function_parts(&contents[index]);
+        if has_call && has_response {
+            return Err(invalid(format!(
+                "contents[{index}] mixes function calls and function responses; \
+                 put responses in a separate `function` content"
+            )));
+        }
+        let expected = if has_call {
+            Role::Model
+        } else if has_response {
+            Role::Function
+        } else {
+            continue;
+        };
+        let content = &mut contents[index];
+        if !role_allowed(content.role, has_call) {
+            if mode == ContentRoleCheck::Normalize {
+                content.role = Some(expected);
+            } else {
+                return Err(invalid(format!(
+                    "contents[{index}] contains function {} but has role `{}`; expected {}",
+                    if has_call { "calls" } else { "responses" },
+                    role_name(content.role),
+                    if has_call {
+                        "`model`"
+                    } else {
+                        "`function` or `user`"
+                    },
+                )));
+            }
+        }
+        if has_response {
+            check_response_pairing(contents, index)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_response_pairing(contents: &[Content], index: usize) -> Result<()> {
+    let calls: Vec<_> = index
+        .checked_sub(1)
+        .map(|previous| &contents[previous])
+        .into_iter()
+        .flat_map(|content| &content.parts)
+        .filter_map(|part| match &part.kind {
+            PartKind::FunctionCall { function_call } => Some(function_call.name.as_deref()),
+            _ => None,
+        })
+        .collect();
+    for part in &contents[index].parts {
+        let PartKind::FunctionResponse { function_response } = &part.kind else {
+            continue;
+        };
+        let name = function_response.name.as_deref();
+        if !calls.contains(&name) {
+            return Err(invalid(format!(
+                "contents[{index}] function response `{}` has no matching function call in the \
+                 preceding model content",
+                name.unwrap_or_default()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 函数调用只能出现在 `model` 内容中；函数响应的 `function` 与 `user` 两种角色 API 都接受。
+const fn role_allowed(role: Option<Role>, has_call: bool) -> bool {
+    if has_call {
+        matches!(role, Some(Role::Model))
+    } else {
+        matches!(role, Some(Role::Function | Role::User))
+    }
+}
+
+fn function_parts(content: &Content) -> (bool, bool) {
+    content
+        .parts
+        .iter()
+        .fold((false, false), |(call, response), part| match part.kind {
+            PartKind::FunctionCall { .. } => (true, response),
+            PartKind::FunctionResponse { .. } => (call, true),
+            _ => (call, response),
+        })
+}
+
+const fn role_name(role: Option<Role>) -> &'static str {
+    match role {
+        Some(Role::User) => "user",
+        Some(Role::Model) => "model",
+        Some(Role::Function) => "function",
+        None => "unset",
+    }
+}
+
+const fn invalid(message: String) -> Error {
+    Error::InvalidConfig { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_genai_types::content::{FunctionCall, FunctionResponse, Part};
+    use serde_json::json;
+
+    fn conversation(response_role: Role, response_name: &str) -> Vec<Content> {
+        vec![
+            Content::text("weather?"),
+            Content::from_parts(
+                vec![Part::function_call(FunctionCall {
+                    id: None,
+                    name: Some("get_weather".into()),
+                    args: Some(json!({"city": "Paris"})),
+                    partial_args: None,
+                    will_continue: None,
+                })],
+                Role::Model,
+            ),
+            Content::from_parts(
+                vec![Part::function_response(FunctionResponse {
+                    will_continue: None,
+                    scheduling: None,
+                    parts: None,
+                    id: None,
+                    name: Some(response_name.into()),
+                    response: Some(json!({"temp": 21})),
+                })],
+                response_role,
+            ),
+        ]
+    }
+
+    #[test]
+    fn validate_flags_misroled_function_response() {
+        let mut contents = conversation(Role::Model, "get_weather");
+        let err = check_content_roles(&mut contents, ContentRoleCheck::Validate).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: contents[2] contains function responses but has role `model`; \
+             expected `function` or `user`"
+        );
+
+        for role in [Role::User, Role::Function] {
+            let mut contents = conversation(role, "get_weather");
+            check_content_roles(&mut contents, ContentRoleCheck::Validate).unwrap();
+            assert_eq!(contents[2].role, Some(role));
+        }
+    }
+
+    #[test]
+    fn normalize_fixes_roles_but_not_pairing() {
+        let mut contents = conversation(Role::Model, "get_weather");
+        check_content_roles(&mut contents, ContentRoleCheck::Normalize).unwrap();
+        assert_eq!(contents[2].role, Some(Role::Function));
+
+        let mut contents = conversation(Role::User, "get_weather");
+        check_content_roles(&mut contents, ContentRoleCheck::Normalize).unwrap();
+        assert_eq!(contents[2].role, Some(Role::User));
+
+        let mut contents = conversation(Role::Function, "get_time");
+        let err = check_content_roles(&mut contents, ContentRoleCheck::Normalize).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`get_time` has no matching function call"));
+    }
+}
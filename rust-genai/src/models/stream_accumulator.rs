@@ -0,0 +1,116 @@
+//! Public accumulator for `streamGenerateContent` chunks with multiple candidates.
+
+use rust_genai_types::response::{Candidate, GenerateContentResponse};
+
+use super::{
+    finalize_stream_function_calls, merge_stream_response, normalize_stream_candidate_order,
+};
+
+/// 按候选 `index` 累积流式 chunk，得到与非流式调用一致的完整响应。
+///
+/// 每个候选的文本、函数调用（含流式 `partial_args`）与元数据按 `index` 分别合并；
+/// 未携带 `index` 的候选按出现位置对齐。
+#[derive(Debug, Clone, Default)]
+pub struct StreamAccumulator {
+    aggregate: Option<GenerateContentResponse>,
+}
+
+impl StreamAccumulator {
+    /// 创建空的累积器。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 合并一个流式 chunk。
+    pub fn push(&mut self, chunk: &GenerateContentResponse) {
+        merge_stream_response(&mut self.aggregate, chunk);
+    }
+
+    /// 是否尚未合并任何 chunk。
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.aggregate.is_none()
+    }
+
+    /// 按候选 `index` 查找已累积的候选（未带 `index` 的候选按位置匹配）。
+    #[must_use]
+    pub fn candidate(&self, index: i32) -> Option<&Candidate> {
+        let candidates = &self.aggregate.as_ref()?.candidates;
+        candidates
+            .iter()
+            .find(|candidate| candidate.index == Some(index))
+            .or_else(|| {
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|position| candidates.get(position))
+                    .filter(|candidate| candidate.index.is_none())
+            })
+    }
+
+    /// 已累积的候选数量。
+    #[must_use]
+    pub fn candidate_count(&self) -> usize {
+        self.aggregate
+            .as_ref()
+            .map_or(0, |response| response.candidates.len())
+    }
+
+    /// 结束累积：候选按 `index` 排序并组装流式函数调用参数。
+    #[must_use]
+    pub fn into_response(self) -> Option<GenerateContentResponse> {
+        let mut response = self.aggregate?;
+        normalize_stream_candidate_order(&mut response);
+        finalize_stream_function_calls(&mut response);
+        Some(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chunk(candidates: serde_json::Value) -> GenerateContentResponse {
+        serde_json::from_value(json!({ "candidates": candidates })).unwrap()
+    }
+
+    #[test]
+    fn accumulates_two_streamed_candidates_by_index() {
+        let mut accumulator = StreamAccumulator::new();
+        accumulator.push(&chunk(json!([
+            {"index": 1, "content": {"role": "model", "parts": [{"text": "Bon"}]}},
+            {"index": 0, "content": {"role": "model", "parts": [{"text": "Hel"}]}}
+        ])));
+        accumulator.push(&chunk(json!([
+            {"index": 0, "content": {"role": "model", "parts": [{"text": "lo"}]}}
+        ])));
+        accumulator.push(&chunk(json!([
+            {"index": 1, "content": {"role": "model", "parts": [{"text": "jour"}]},
+             "finishReason": "STOP"}
+        ])));
+
+        assert_eq!(accumulator.candidate_count(), 2);
+        assert_eq!(
+            accumulator
+                .candidate(1)
+                .and_then(Candidate::all_text)
+                .as_deref(),
+            Some("Bonjour")
+        );
+
+        let response = accumulator.into_response().unwrap();
+        let texts: Vec<_> = response
+            .candidates
+            .iter()
+            .map(|candidate| (candidate.index, candidate.all_text()))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                (Some(0), Some("Hello".to_string())),
+                (Some(1), Some("Bonjour".to_string()))
+            ]
+        );
+    }
+}
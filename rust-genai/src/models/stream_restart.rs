@@ -0,0 +1,193 @@
+//! Best-effort restart of `streamGenerateContent` after a mid-stream transport error.
+//!
+//! The API cannot resume a generation, so a restart re-sends the same request and skips
+//! the first candidate's text that was already emitted. A regenerated answer may diverge
+//! from the original; only the byte count of emitted text is tracked.
+
+use std::pin::Pin;
+
+use futures_util::{Stream, StreamExt};
+use rust_genai_types::content::PartKind;
+use rust_genai_types::http::HttpOptions;
+use rust_genai_types::response::GenerateContentResponse;
+use serde_json::Value;
+
+use super::Models;
+use crate::error::{Error, Result};
+
+pub(super) type ResponseStream =
+    Pin<Box<dyn Stream<Item = Result<GenerateContentResponse>> + Send>>;
+
+struct RestartState {
+    models: Models,
+    url: String,
+    body: Value,
+    http_options: Option<HttpOptions>,
+    stream: Option<ResponseStream>,
+    restarts_left: u32,
+    dedup: TextDedup,
+    completed: bool,
+    finished: bool,
+}
+
+/// 包装首个流：传输错误且尚未收到 `finish_reason` 时重新请求，最多 `attempts` 次。
+pub(super) fn restarting_stream(
+    models: Models,
+    url: String,
+    body: Value,
+    http_options: Option<HttpOptions>,
+    first: ResponseStream,
+    attempts: u32,
+) -> impl Stream<Item = Result<GenerateContentResponse>> + Send {
+    let state = RestartState {
+        models,
+        url,
+        body,
+        http_options,
+        stream: Some(first),
+        restarts_left: attempts,
+        dedup: TextDedup::default(),
+        completed: false,
+        finished: false,
+    };
+    futures_util::stream::unfold(state, |mut state| async move {
+        loop {
+            if state.finished {
+                return None;
+            }
+            let Some(stream) = state.stream.as_mut() else {
+                match state
+                    .models
+                    .open_generate_content_stream(
+                        &state.url,
+                        &state.body,
+                        state.http_options.as_ref(),
+                    )
+                    .await
+                {
+                    Ok(stream) => {
+                        state.stream = Some(stream);
+                        state.dedup.restart();
+                    }
+                    Err(err) => {
+                        state.finished = true;
+                        return Some((Err(err), state));
+                    }
+                }
+                continue;
+            };
+            match stream.next().await {
+                None => return None,
+                Some(Ok(mut chunk)) => {
+                    if chunk
+                        .candidates
+                        .iter()
+                        .any(|candidate| candidate.finish_reason.is_some())
+                    {
+                        state.completed = true;
+                    }
+                    if state.dedup.apply(&mut chunk) {
+                        return Some((Ok(chunk), state));
+                    }
+                }
+                Some(Err(err))
+                    if is_transport_error(&err) && !state.completed && state.restarts_left > 0 =>
+                {
+                    state.restarts_left -= 1;
+                    state.stream = None;
+                }
+                Some(Err(err)) => {
+                    state.finished = true;
+                    return Some((Err(err), state));
+                }
+            }
+        }
+    })
+}
+
+const fn is_transport_error(err: &Error) -> bool {
+    matches!(err, Error::HttpClient { .. } | Error::Timeout { .. })
+}
+
+/// 按字节数跟踪第一个候选已产出的文本，重启后跳过重复前缀。
+#[derive(Default)]
+struct TextDedup {
+    emitted: usize,
+    replayed: usize,
+}
+
+impl TextDedup {
+    const fn restart(&mut self) {
+        self.replayed = 0;
+    }
+
+    /// 去除 chunk 中已产出的部分；返回该 chunk 是否仍需产出。
+    fn apply(&mut self, chunk: &mut GenerateContentResponse) -> bool {
+        let finished = chunk
+            .candidates
+            .first()
+            .is_some_and(|candidate| candidate.finish_reason.is_some());
+        let Some(content) = chunk
+            .candidates
+            .first_mut()
+            .and_then(|candidate| candidate.content.as_mut())
+        else {
+            return true;
+        };
+        if content.parts.is_empty() {
+            return true;
+        }
+        content.parts.retain_mut(|part| {
+            let in_replay = self.replayed < self.emitted;
+            match &mut part.kind {
+                PartKind::Text { text } if part.thought != Some(true) => {
+                    let len = text.len();
+                    let mut skip = self.emitted.saturating_sub(self.replayed).min(len);
+                    while !text.is_char_boundary(skip) {
+                        skip += 1;
+                    }
+                    self.replayed += len;
+                    if skip == len {
+                        return false;
+                    }
+                    text.drain(..skip);
+                    self.emitted += len - skip;
+                    true
+                }
+                _ => !in_replay,
+            }
+        });
+        !content.parts.is_empty() || finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chunk(text: &str) -> GenerateContentResponse {
+        serde_json::from_value(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": text}]}}]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn dedup_skips_replayed_prefix_after_restart() {
+        let mut dedup = TextDedup::default();
+        let mut first = chunk("Hello ");
+        assert!(dedup.apply(&mut first));
+        assert_eq!(first.text().as_deref(), Some("Hello "));
+
+        dedup.restart();
+        let mut replay = chunk("Hel");
+        assert!(!dedup.apply(&mut replay));
+        let mut overlap = chunk("lo wor");
+        assert!(dedup.apply(&mut overlap));
+        assert_eq!(overlap.text().as_deref(), Some("wor"));
+        let mut rest = chunk("ld");
+        assert!(dedup.apply(&mut rest));
+        assert_eq!(rest.text().as_deref(), Some("ld"));
+    }
+}
@@ -4,12 +4,12 @@ use crate::test_support::{
     test_client_inner_with_base as test_inner_with_base, test_vertex_inner_missing_config,
 };
 use futures_util::StreamExt;
-use rust_genai_types::config::{GenerationConfig, ThinkingConfig};
+use rust_genai_types::config::{GenerationConfig, SpeechConfig, ThinkingConfig};
 use rust_genai_types::content::{
     Content, FunctionCall, FunctionResponse, FunctionResponseBlob, FunctionResponsePart, Part,
     PartMediaResolution, PartialArg, Role, VideoMetadata,
 };
-use rust_genai_types::enums::{FunctionCallingMode, ThinkingLevel};
+use rust_genai_types::enums::{FunctionCallingMode, Modality, ThinkingLevel};
 use rust_genai_types::http::HttpOptions as TypesHttpOptions;
 use rust_genai_types::http::HttpResponse;
 use rust_genai_types::models::{
@@ -23,19 +23,20 @@ use rust_genai_types::response::{
     SafetyRating, UrlContextMetadata, UrlMetadata,
 };
 use rust_genai_types::tool::{
-    CodeExecution, FunctionCallingConfig, FunctionDeclaration, Tool, ToolConfig,
+    CodeExecution, FileSearch, FunctionCallingConfig, FunctionDeclaration, GoogleSearch, Tool,
+    ToolConfig,
 };
 use rust_genai_types::{
     enums::{
         BlockedReason, FinishReason, HarmCategory, HarmProbability, PartMediaResolutionLevel,
         UrlRetrievalStatus,
     },
-    grounding::{Citation, CitationMetadata, GroundingMetadata},
+    grounding::{Citation, CitationMetadata, GroundingChunk, GroundingMetadata},
     logprobs::{LogprobCandidate, LogprobsResult, TopCandidates},
 };
 use serde::Deserialize;
 use serde_json::json;
-use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::matchers::{body_json, body_partial_json, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[test]
@@ -353,6 +354,284 @@ async fn test_models_validation_errors() {
     assert!(matches!(err, Error::InvalidConfig { .. }));
 }
 
+#[tokio::test]
+async fn test_cached_content_conflicts_rejected_before_send() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let inner = test_inner_with_base(Backend::GeminiApi, &server.uri(), "v1beta");
+    let models = Models::new(Arc::new(inner));
+
+    let config = GenerateContentConfig {
+        cached_content: Some("cachedContents/abc".into()),
+        ..Default::default()
+    }
+    .with_system_instruction("Be terse");
+    let err = models
+        .generate_content_with_config(
+            "gemini-2.5-flash",
+            vec![Content::text("hi")],
+            config.clone(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidConfig { message } if message.contains("system_instruction"))
+    );
+    let Err(err) = models
+        .generate_content_stream("gemini-2.5-flash", vec![Content::text("hi")], config)
+        .await
+    else {
+        panic!("expected stream validation error");
+    };
+    assert!(matches!(err, Error::InvalidConfig { .. }));
+
+    let config = GenerateContentConfig {
+        cached_content: Some("cachedContents/abc".into()),
+        tools: Some(vec![Tool {
+            code_execution: Some(CodeExecution::default()),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+    let err = models
+        .generate_content_with_config("gemini-2.5-flash", vec![Content::text("hi")], config)
+        .await
+        .unwrap_err();
+    assert!(matches!(&err, Error::InvalidConfig { message } if message.contains("tools")));
+}
+
+#[test]
+fn test_generate_content_request_preview() {
+    let inner = test_inner_with_base(Backend::GeminiApi, "https://example.test", "v1beta");
+    let models = Models::new(Arc::new(inner));
+    let preview = models
+        .generate_content_request_preview(
+            "gemini-2.5-flash",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default().with_system_instruction("Be terse"),
+        )
+        .unwrap();
+    assert_eq!(
+        preview.url,
+        "https://example.test/v1beta/models/gemini-2.5-flash:generateContent"
+    );
+    assert_eq!(
+        preview.body["contents"],
+        json!([{"role": "user", "parts": [{"text": "hi"}]}])
+    );
+    assert_eq!(
+        preview.body["systemInstruction"]["parts"][0]["text"],
+        "Be terse"
+    );
+
+    let inner = test_inner_with_base(Backend::VertexAi, "https://example.test", "v1beta1");
+    let vertex = Models::new(Arc::new(inner));
+    let preview = vertex
+        .generate_content_request_preview(
+            "gemini-2.5-flash",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+        )
+        .unwrap();
+    assert!(preview
+        .url
+        .ends_with("/publishers/google/models/gemini-2.5-flash:generateContent"));
+
+    let err = models
+        .generate_content_request_preview(
+            "gemini-2.5-flash",
+            vec![Content::text("hi")],
+            GenerateContentConfig {
+                cached_content: Some("cachedContents/abc".into()),
+                ..Default::default()
+            }
+            .with_system_instruction("Be terse"),
+        )
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig { .. }));
+}
+
+#[test]
+fn test_generate_content_request_preview_includes_thinking_config() {
+    let inner = test_inner_with_base(Backend::GeminiApi, "https://example.test", "v1beta");
+    let models = Models::new(Arc::new(inner));
+    let preview = models
+        .generate_content_request_preview(
+            "gemini-2.5-flash",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default()
+                .with_thinking_budget(2048)
+                .with_include_thoughts(true),
+        )
+        .unwrap();
+    assert_eq!(
+        preview.body["generationConfig"]["thinkingConfig"],
+        json!({"thinkingBudget": 2048, "includeThoughts": true})
+    );
+
+    let err = models
+        .generate_content_request_preview(
+            "gemini-2.0-flash",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default().with_thinking_budget(2048),
+        )
+        .unwrap_err();
+    assert!(matches!(&err, Error::InvalidConfig { message } if message.contains("thinking")));
+}
+
+#[test]
+fn test_generate_content_request_preview_includes_speech_config() {
+    let inner = test_inner_with_base(Backend::GeminiApi, "https://example.test", "v1beta");
+    let models = Models::new(Arc::new(inner));
+    let preview = models
+        .generate_content_request_preview(
+            "gemini-2.5-flash-preview-tts",
+            vec![Content::text("Say hello")],
+            GenerateContentConfig::default()
+                .with_response_modalities([Modality::Audio])
+                .with_speech_config(SpeechConfig::prebuilt_voice("Kore").unwrap()),
+        )
+        .unwrap();
+    assert_eq!(
+        preview.body["generationConfig"]["speechConfig"],
+        json!({"voiceConfig": {"prebuiltVoiceConfig": {"voiceName": "Kore"}}})
+    );
+}
+
+#[test]
+fn test_generate_content_request_preview_rejects_unsupported_logprobs() {
+    let config = GenerateContentConfig {
+        generation_config: Some(GenerationConfig {
+            logprobs: Some(5),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let inner = test_inner_with_base(Backend::GeminiApi, "https://example.test", "v1beta");
+    let preview = Models::new(Arc::new(inner))
+        .generate_content_request_preview(
+            "gemini-2.5-flash-image",
+            vec![Content::text("hi")],
+            config,
+        )
+        .unwrap();
+    assert_eq!(preview.body["generationConfig"]["logprobs"], json!(5));
+
+    let mut inner = test_inner_with_base(Backend::GeminiApi, "https://example.test", "v1beta");
+    inner.validate_model_capabilities = true;
+    let models = Models::new(Arc::new(inner));
+    let config = GenerateContentConfig {
+        generation_config: Some(GenerationConfig {
+            response_logprobs: Some(true),
+            logprobs: Some(5),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let preview = models
+        .generate_content_request_preview(
+            "gemini-2.0-flash",
+            vec![Content::text("hi")],
+            config.clone(),
+        )
+        .unwrap();
+    assert_eq!(preview.body["generationConfig"]["logprobs"], json!(5));
+
+    let err = models
+        .generate_content_request_preview(
+            "gemini-2.5-flash-image",
+            vec![Content::text("hi")],
+            config,
+        )
+        .unwrap_err();
+    assert!(matches!(&err, Error::InvalidConfig { message } if message.contains("logprobs")));
+}
+
+#[test]
+fn test_generate_content_request_preview_only_rejects_definite_tool_conflicts() {
+    let inner = test_inner_with_base(Backend::GeminiApi, "https://example.test", "v1beta");
+    let models = Models::new(Arc::new(inner));
+    let function = Tool {
+        function_declarations: Some(vec![FunctionDeclaration {
+            name: "lookup".into(),
+            description: None,
+            parameters: None,
+            parameters_json_schema: None,
+            response: None,
+            response_json_schema: None,
+            behavior: None,
+        }]),
+        ..Default::default()
+    };
+    let search = Tool {
+        google_search: Some(GoogleSearch::default()),
+        ..Default::default()
+    };
+    let config = GenerateContentConfig {
+        tools: Some(vec![function, search.clone()]),
+        ..Default::default()
+    };
+    let preview = models
+        .generate_content_request_preview("gemini-2.5-flash", vec![Content::text("hi")], config)
+        .unwrap();
+    assert_eq!(preview.body["tools"].as_array().map(Vec::len), Some(2));
+
+    let config = GenerateContentConfig {
+        tools: Some(vec![
+            search,
+            Tool {
+                file_search: Some(FileSearch::default()),
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    };
+    let err = models
+        .generate_content_request_preview("gemini-3-pro-preview", vec![Content::text("hi")], config)
+        .unwrap_err();
+    assert!(matches!(&err, Error::InvalidConfig { message } if message.contains("file_search")));
+}
+
+#[tokio::test]
+async fn test_generate_content_merges_default_labels() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({
+            "labels": {"team": "ml", "env": "dev", "run": "42"}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "ok"}]}}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let mut inner = test_inner_with_base(Backend::VertexAi, &server.uri(), "v1beta1");
+    inner.config.default_labels = [("team", "ml"), ("env", "prod")]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let models = Models::new(Arc::new(inner));
+
+    let config = GenerateContentConfig {
+        labels: Some(
+            [("env", "dev"), ("run", "42")]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        ),
+        ..Default::default()
+    };
+    let response = models
+        .generate_content_with_config("gemini-2.5-flash", vec![Content::text("hi")], config)
+        .await
+        .unwrap();
+    assert_eq!(response.text().as_deref(), Some("ok"));
+}
+
 #[tokio::test]
 async fn test_models_generate_content_vertex_and_errors() {
     let server = MockServer::start().await;
@@ -401,6 +680,101 @@ async fn test_models_generate_content_vertex_and_errors() {
     assert!(matches!(err, Error::ApiError { .. }));
 }
 
+#[tokio::test]
+async fn test_generate_content_vertex_tuned_endpoint() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta1/projects/proj/locations/loc/endpoints/123:generateContent",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "tuned"}]}}]
+        })))
+        .mount(&server)
+        .await;
+
+    let inner = Arc::new(test_inner_with_base(
+        Backend::VertexAi,
+        &server.uri(),
+        "v1beta1",
+    ));
+    let job: rust_genai_types::tunings::TuningJob = serde_json::from_value(json!({
+        "state": "JOB_STATE_SUCCEEDED",
+        "tunedModel": {"endpoint": "projects/proj/locations/loc/endpoints/123"}
+    }))
+    .unwrap();
+    let model = crate::tunings::Tunings::new(inner.clone())
+        .generation_model_name(&job)
+        .unwrap();
+
+    let response = Models::new(inner)
+        .generate_content(model, vec![Content::text("hi")])
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("tuned".to_string()));
+}
+
+#[tokio::test]
+async fn test_generate_content_honors_per_call_http_options() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1alpha/models/gemini-1.5-pro:generateContent"))
+        .and(wiremock::matchers::header("x-per-call", "1"))
+        .and(body_json(json!({
+            "contents": [{"role": "user", "parts": [{"text": "hi"}]}],
+            "extra": true
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "alpha"}]}}]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1alpha/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(query_param("alt", "sse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(
+                    "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"streamed\"}]}}]}\n\n",
+                ),
+        )
+        .mount(&server)
+        .await;
+
+    let inner = test_inner_with_base(Backend::GeminiApi, &server.uri(), "v1beta");
+    let models = Models::new(Arc::new(inner));
+    let config = GenerateContentConfig {
+        http_options: Some(TypesHttpOptions {
+            api_version: Some("v1alpha".into()),
+            headers: Some([("x-per-call".to_string(), "1".to_string())].into()),
+            extra_body: Some(json!({"extra": true})),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let response = models
+        .generate_content_with_config("gemini-1.5-pro", vec![Content::text("hi")], config)
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("alpha".to_string()));
+
+    let config = GenerateContentConfig {
+        http_options: Some(TypesHttpOptions {
+            api_version: Some("v1alpha".into()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut stream = models
+        .generate_content_stream("gemini-1.5-pro", vec![Content::text("hi")], config)
+        .await
+        .unwrap();
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.text(), Some("streamed".to_string()));
+}
+
 #[tokio::test]
 async fn test_generate_content_stream_uses_gemini_request_converter() {
     let server = MockServer::start().await;
@@ -472,6 +846,46 @@ async fn test_generate_content_stream_uses_gemini_request_converter() {
     assert_eq!(first.text(), Some("ok".to_string()));
 }
 
+#[tokio::test]
+async fn test_stream_text_yields_text_deltas_only() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(query_param("alt", "sse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(
+                    "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"thinking\",\"thought\":true}]}}]}\n\n\
+                     data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"ok\"}]}}]}\n\n\
+                     data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"functionCall\":{\"name\":\"noop\",\"args\":{}}}]}}]}\n\n\
+                     data: [DONE]\n\n",
+                ),
+        )
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+
+    let items: Vec<String> = client
+        .models()
+        .stream_text(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(items, vec!["ok".to_string()]);
+}
+
 #[tokio::test]
 async fn test_generate_content_stream_uses_vertex_request_converter() {
     let server = MockServer::start().await;
@@ -652,6 +1066,64 @@ async fn test_generate_json_parses_concatenated_text_parts() {
     assert_eq!(parsed, JsonSmokeResponse { ok: true });
 }
 
+#[derive(Debug, Deserialize, PartialEq)]
+struct TruncatedPerson {
+    name: String,
+    tags: Vec<String>,
+}
+
+#[tokio::test]
+async fn test_generate_json_repairs_truncated_output() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [{"text": "{\"name\": \"Ada\", \"tags\": [\"math\", \"eng"}]
+                },
+                "finishReason": "MAX_TOKENS"
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+
+    let err = client
+        .models()
+        .generate_json::<TruncatedPerson>("gemini-1.5-pro", vec![Content::text("return json")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::OutputTruncated { .. }));
+
+    let config = GenerateContentConfig {
+        repair_truncated_json: Some(true),
+        ..Default::default()
+    };
+    let parsed = client
+        .models()
+        .generate_json_with_config::<TruncatedPerson>(
+            "gemini-1.5-pro",
+            vec![Content::text("return json")],
+            config,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        parsed,
+        TruncatedPerson {
+            name: "Ada".into(),
+            tags: vec!["math".into(), "eng".into()],
+        }
+    );
+}
+
 #[tokio::test]
 async fn test_generate_json_requires_text_response() {
     let server = MockServer::start().await;
@@ -951,6 +1423,104 @@ async fn test_generate_content_event_stream_done_is_aggregated() {
     assert!(sixth.is_none());
 }
 
+#[tokio::test]
+async fn test_generate_content_event_stream_done_merges_grounding_and_citations() {
+    let first = json!({"candidates": [{
+        "content": {"role": "model", "parts": [{"text": "Rust is fast. "}]},
+        "citationMetadata": {"citations": [{"startIndex": 0, "endIndex": 13, "uri": "https://a.example"}]},
+        "groundingMetadata": {
+            "webSearchQueries": ["rust speed"],
+            "groundingChunks": [{"web": {"uri": "https://a.example", "title": "A"}}],
+            "groundingSupports": [{
+                "groundingChunkIndices": [0],
+                "segment": {"partIndex": 0, "startIndex": 0, "endIndex": 13, "text": "Rust is fast."}
+            }]
+        }
+    }]});
+    let second = json!({"candidates": [{
+        "content": {"role": "model", "parts": [{"text": "It is safe."}]},
+        "citationMetadata": {"citations": [{"startIndex": 14, "endIndex": 25, "uri": "https://b.example"}]},
+        "groundingMetadata": {
+            "webSearchQueries": ["rust speed", "rust safety"],
+            "groundingChunks": [
+                {"web": {"uri": "https://b.example", "title": "B"}},
+                {"web": {"uri": "https://a.example", "title": "A"}}
+            ],
+            "groundingSupports": [{
+                "groundingChunkIndices": [0, 1],
+                "segment": {"partIndex": 0, "startIndex": 14, "endIndex": 25, "text": "It is safe."}
+            }]
+        },
+        "finishReason": "STOP"
+    }]});
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(query_param("alt", "sse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!(
+                    "data: {first}\n\ndata: {second}\n\ndata: [DONE]\n\n"
+                )),
+        )
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let mut stream = client
+        .models()
+        .generate_content_event_stream(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut done = None;
+    while let Some(event) = stream.next_event().await.unwrap() {
+        if let GenerateContentStreamEvent::Done(response) = event {
+            done = Some(response);
+        }
+    }
+    let response = done.expect("missing Done event");
+
+    let citation_uris: Vec<_> = response
+        .citations()
+        .iter()
+        .map(|citation| citation.uri.as_deref())
+        .collect();
+    assert_eq!(
+        citation_uris,
+        vec![Some("https://a.example"), Some("https://b.example")]
+    );
+    assert_eq!(response.web_search_queries(), ["rust speed", "rust safety"]);
+    let chunk_uris: Vec<_> = response
+        .grounding_chunks()
+        .iter()
+        .map(GroundingChunk::uri)
+        .collect();
+    assert_eq!(
+        chunk_uris,
+        vec![Some("https://a.example"), Some("https://b.example")]
+    );
+    let metadata = response.candidates[0].grounding_metadata.as_ref().unwrap();
+    assert_eq!(metadata.grounding_supports.len(), 2);
+    assert_eq!(
+        metadata.grounding_supports[1].grounding_chunk_indices,
+        vec![1, 0]
+    );
+    assert_eq!(
+        metadata.add_citations(&response.all_text().unwrap()),
+        "Rust is fast. [1] It is safe. [1,2]"
+    );
+}
+
 #[tokio::test]
 async fn test_generate_content_event_stream_skips_done_on_plain_eof() {
     let server = MockServer::start().await;
@@ -1129,6 +1699,7 @@ fn test_merge_stream_response_preserves_sparse_candidates_without_index() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
                 Candidate {
                     content: Some(Content::from_parts(vec![Part::text("second")], Role::Model)),
@@ -1142,6 +1713,7 @@ fn test_merge_stream_response_preserves_sparse_candidates_without_index() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
             ],
             create_time: None,
@@ -1150,6 +1722,7 @@ fn test_merge_stream_response_preserves_sparse_candidates_without_index() {
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1185,6 +1758,7 @@ fn test_merge_stream_response_preserves_sparse_candidates_without_index() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             }],
             create_time: None,
             automatic_function_calling_history: None,
@@ -1192,6 +1766,7 @@ fn test_merge_stream_response_preserves_sparse_candidates_without_index() {
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1236,6 +1811,7 @@ fn test_merge_stream_response_merges_multi_candidate_chunks_without_index() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
                 Candidate {
                     content: Some(Content::from_parts(vec![Part::text("Wor")], Role::Model)),
@@ -1249,6 +1825,7 @@ fn test_merge_stream_response_merges_multi_candidate_chunks_without_index() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
             ],
             create_time: None,
@@ -1257,6 +1834,7 @@ fn test_merge_stream_response_merges_multi_candidate_chunks_without_index() {
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1277,6 +1855,7 @@ fn test_merge_stream_response_merges_multi_candidate_chunks_without_index() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
                 Candidate {
                     content: Some(Content::from_parts(vec![Part::text("ld")], Role::Model)),
@@ -1290,6 +1869,7 @@ fn test_merge_stream_response_merges_multi_candidate_chunks_without_index() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
             ],
             create_time: None,
@@ -1298,6 +1878,7 @@ fn test_merge_stream_response_merges_multi_candidate_chunks_without_index() {
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1345,6 +1926,7 @@ fn test_merge_stream_response_merges_indexed_candidate_metadata() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
             Candidate {
                 content: None,
@@ -1358,6 +1940,7 @@ fn test_merge_stream_response_merges_indexed_candidate_metadata() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
         ],
         create_time: None,
@@ -1375,9 +1958,11 @@ fn test_merge_stream_response_merges_indexed_candidate_metadata() {
             tool_use_prompt_tokens_details: None,
             total_token_count: None,
             traffic_type: None,
+            ..Default::default()
         }),
         model_version: Some("v1".into()),
         response_id: Some("resp-0".into()),
+        ..Default::default()
     });
 
     merge_stream_response(
@@ -1437,6 +2022,7 @@ fn test_merge_stream_response_merges_indexed_candidate_metadata() {
                             ),
                         }]),
                     }),
+                    ..Default::default()
                 },
                 Candidate {
                     content: Some(Content::from_parts(vec![Part::text("fresh")], Role::Model)),
@@ -1450,6 +2036,7 @@ fn test_merge_stream_response_merges_indexed_candidate_metadata() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
             ],
             create_time: Some("2026-04-20T12:00:00Z".into()),
@@ -1458,6 +2045,7 @@ fn test_merge_stream_response_merges_indexed_candidate_metadata() {
                 block_reason: Some(BlockedReason::Other),
                 block_reason_message: Some("blocked".into()),
                 safety_ratings: Vec::new(),
+                ..Default::default()
             }),
             usage_metadata: Some(GenerateContentResponseUsageMetadata {
                 cache_tokens_details: None,
@@ -1471,9 +2059,11 @@ fn test_merge_stream_response_merges_indexed_candidate_metadata() {
                 tool_use_prompt_tokens_details: None,
                 total_token_count: Some(9),
                 traffic_type: None,
+                ..Default::default()
             }),
             model_version: Some("v2".into()),
             response_id: Some("resp-1".into()),
+            ..Default::default()
         },
     );
 
@@ -1557,6 +2147,7 @@ fn test_merge_stream_response_merges_single_candidate_when_index_appears_later()
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             }],
             create_time: None,
             automatic_function_calling_history: None,
@@ -1564,6 +2155,7 @@ fn test_merge_stream_response_merges_single_candidate_when_index_appears_later()
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1583,6 +2175,7 @@ fn test_merge_stream_response_merges_single_candidate_when_index_appears_later()
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             }],
             create_time: None,
             automatic_function_calling_history: None,
@@ -1590,6 +2183,7 @@ fn test_merge_stream_response_merges_single_candidate_when_index_appears_later()
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1618,6 +2212,7 @@ fn test_merge_stream_response_keeps_distinct_sparse_candidate_with_late_index()
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             }],
             create_time: None,
             automatic_function_calling_history: None,
@@ -1625,6 +2220,7 @@ fn test_merge_stream_response_keeps_distinct_sparse_candidate_with_late_index()
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1644,6 +2240,7 @@ fn test_merge_stream_response_keeps_distinct_sparse_candidate_with_late_index()
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             }],
             create_time: None,
             automatic_function_calling_history: None,
@@ -1651,6 +2248,7 @@ fn test_merge_stream_response_keeps_distinct_sparse_candidate_with_late_index()
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1696,6 +2294,7 @@ fn test_merge_stream_response_merges_late_index_into_sparse_multi_candidate() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
                 Candidate {
                     content: Some(Content::from_parts(vec![Part::text("sec")], Role::Model)),
@@ -1709,6 +2308,7 @@ fn test_merge_stream_response_merges_late_index_into_sparse_multi_candidate() {
                     logprobs_result: None,
                     safety_ratings: Vec::new(),
                     url_context_metadata: None,
+                    ..Default::default()
                 },
             ],
             create_time: None,
@@ -1717,6 +2317,7 @@ fn test_merge_stream_response_merges_late_index_into_sparse_multi_candidate() {
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1736,6 +2337,7 @@ fn test_merge_stream_response_merges_late_index_into_sparse_multi_candidate() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             }],
             create_time: None,
             automatic_function_calling_history: None,
@@ -1743,6 +2345,7 @@ fn test_merge_stream_response_merges_late_index_into_sparse_multi_candidate() {
             usage_metadata: None,
             model_version: None,
             response_id: None,
+            ..Default::default()
         },
     );
 
@@ -1788,6 +2391,7 @@ fn test_normalize_stream_candidate_order_reorders_indexed_candidates() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
             Candidate {
                 content: Some(Content::from_parts(vec![Part::text("first")], Role::Model)),
@@ -1801,6 +2405,7 @@ fn test_normalize_stream_candidate_order_reorders_indexed_candidates() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
         ],
         create_time: None,
@@ -1809,6 +2414,7 @@ fn test_normalize_stream_candidate_order_reorders_indexed_candidates() {
         usage_metadata: None,
         model_version: None,
         response_id: None,
+        ..Default::default()
     };
 
     normalize_stream_candidate_order(&mut response);
@@ -1835,6 +2441,7 @@ fn test_normalize_stream_candidate_order_preserves_unindexed_gap_positions() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
             Candidate {
                 content: Some(Content::from_parts(vec![Part::text("first")], Role::Model)),
@@ -1848,6 +2455,7 @@ fn test_normalize_stream_candidate_order_preserves_unindexed_gap_positions() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
         ],
         create_time: None,
@@ -1856,6 +2464,7 @@ fn test_normalize_stream_candidate_order_preserves_unindexed_gap_positions() {
         usage_metadata: None,
         model_version: None,
         response_id: None,
+        ..Default::default()
     };
 
     normalize_stream_candidate_order(&mut response);
@@ -1882,6 +2491,7 @@ fn test_normalize_stream_candidate_order_preserves_sparse_index_order() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
             Candidate {
                 content: Some(Content::from_parts(vec![Part::text("second")], Role::Model)),
@@ -1895,6 +2505,7 @@ fn test_normalize_stream_candidate_order_preserves_sparse_index_order() {
                 logprobs_result: None,
                 safety_ratings: Vec::new(),
                 url_context_metadata: None,
+                ..Default::default()
             },
         ],
         create_time: None,
@@ -1903,6 +2514,7 @@ fn test_normalize_stream_candidate_order_preserves_sparse_index_order() {
         usage_metadata: None,
         model_version: None,
         response_id: None,
+        ..Default::default()
     };
 
     normalize_stream_candidate_order(&mut response);
@@ -1973,7 +2585,7 @@ fn test_stream_merge_helpers_respect_context_and_targets() {
         name: Some("search".into()),
         args: None,
         partial_args: None,
-        will_continue: None,
+        will_continue: Some(true),
     };
     assert!(!function_calls_share_target(
         &lookup_call,
@@ -2002,6 +2614,19 @@ fn test_stream_merge_helpers_respect_context_and_targets() {
             will_continue: None,
         }
     ));
+    assert!(!function_calls_share_target(
+        &FunctionCall {
+            will_continue: None,
+            ..search_call.clone()
+        },
+        &FunctionCall {
+            id: None,
+            name: Some("search".into()),
+            args: None,
+            partial_args: None,
+            will_continue: None,
+        }
+    ));
     assert!(!function_calls_share_target(
         &FunctionCall {
             id: Some("call-1".into()),
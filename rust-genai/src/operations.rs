@@ -7,12 +7,15 @@ use reqwest::header::{HeaderName, HeaderValue};
 use rust_genai_types::file_search_stores::{ImportFileOperation, UploadToFileSearchStoreOperation};
 use rust_genai_types::models::GenerateVideosOperation;
 use rust_genai_types::operations::{
-    GetOperationConfig, ListOperationsConfig, ListOperationsResponse, Operation,
+    GetOperationConfig, ListOperationsConfig, ListOperationsResponse, Operation, Status,
 };
 use serde_json::Value;
 
 use crate::client::{Backend, ClientInner};
 use crate::error::{Error, Result};
+use crate::poll::PollConfig;
+
+const OPERATION_TIMEOUT_MESSAGE: &str = "Timed out waiting for operation to complete";
 
 #[derive(Clone)]
 pub struct Operations {
@@ -93,7 +96,7 @@ impl Operations {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<Operation>().await?)
     }
@@ -125,7 +128,7 @@ impl Operations {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         Ok(response.json::<ListOperationsResponse>().await?)
     }
@@ -169,15 +172,29 @@ impl Operations {
     ///
     /// # Errors
     /// 当请求失败、操作缺少名称或轮询过程中响应解析失败时返回错误。
-    pub async fn wait(&self, mut operation: Operation) -> Result<Operation> {
+    pub async fn wait(&self, operation: Operation) -> Result<Operation> {
+        self.wait_with_config(operation, PollConfig::default())
+            .await
+    }
+
+    /// 等待操作完成（自定义轮询配置）。
+    ///
+    /// # Errors
+    /// 当请求失败、操作缺少名称、响应解析失败、超时或操作以错误状态结束时返回错误。
+    pub async fn wait_with_config(
+        &self,
+        mut operation: Operation,
+        config: PollConfig,
+    ) -> Result<Operation> {
         let name = operation.name.clone().ok_or_else(|| Error::InvalidConfig {
             message: "Operation name is empty".into(),
         })?;
+        let mut poller = config.start();
         while !operation.done.unwrap_or(false) {
-            tokio::time::sleep(Duration::from_secs(5)).await;
+            poller.wait(OPERATION_TIMEOUT_MESSAGE).await?;
             operation = self.get(&name).await?;
         }
-        Ok(operation)
+        operation_result(operation.error_status(), operation)
     }
 
     /// 获取 `GenerateVideos` 操作状态。
@@ -238,14 +255,28 @@ impl Operations {
     /// # Errors
     /// 当请求失败、操作缺少名称或轮询过程中响应解析失败时返回错误。
     pub async fn wait_generate_videos_operation(
+        &self,
+        operation: GenerateVideosOperation,
+    ) -> Result<GenerateVideosOperation> {
+        self.wait_generate_videos_operation_with_config(operation, PollConfig::default())
+            .await
+    }
+
+    /// 等待 `GenerateVideos` 操作完成（自定义轮询配置）。
+    ///
+    /// # Errors
+    /// 当请求失败、操作缺少名称、响应解析失败、超时或操作以错误状态结束时返回错误。
+    pub async fn wait_generate_videos_operation_with_config(
         &self,
         mut operation: GenerateVideosOperation,
+        config: PollConfig,
     ) -> Result<GenerateVideosOperation> {
         let name = operation.name.clone().ok_or_else(|| Error::InvalidConfig {
             message: "Operation name is empty".into(),
         })?;
+        let mut poller = config.start();
         while !operation.done.unwrap_or(false) {
-            tokio::time::sleep(Duration::from_secs(5)).await;
+            poller.wait(OPERATION_TIMEOUT_MESSAGE).await?;
             operation = self
                 .get_generate_videos_operation(GenerateVideosOperation {
                     name: Some(name.clone()),
@@ -253,7 +284,7 @@ impl Operations {
                 })
                 .await?;
         }
-        Ok(operation)
+        operation_result(operation.error_status(), operation)
     }
 
     /// 获取上传到 FileSearchStore 的操作状态（Gemini API only）。
@@ -301,14 +332,31 @@ impl Operations {
     /// # Errors
     /// 当请求失败、操作缺少名称或轮询过程中响应解析失败时返回错误。
     pub async fn wait_upload_to_file_search_store_operation(
+        &self,
+        operation: UploadToFileSearchStoreOperation,
+    ) -> Result<UploadToFileSearchStoreOperation> {
+        self.wait_upload_to_file_search_store_operation_with_config(
+            operation,
+            PollConfig::default(),
+        )
+        .await
+    }
+
+    /// 等待上传到 FileSearchStore 的操作完成（自定义轮询配置，Gemini API only）。
+    ///
+    /// # Errors
+    /// 当请求失败、操作缺少名称、响应解析失败、超时或操作以错误状态结束时返回错误。
+    pub async fn wait_upload_to_file_search_store_operation_with_config(
         &self,
         mut operation: UploadToFileSearchStoreOperation,
+        config: PollConfig,
     ) -> Result<UploadToFileSearchStoreOperation> {
         let name = operation.name.clone().ok_or_else(|| Error::InvalidConfig {
             message: "Operation name is empty".into(),
         })?;
+        let mut poller = config.start();
         while !operation.done.unwrap_or(false) {
-            tokio::time::sleep(Duration::from_secs(5)).await;
+            poller.wait(OPERATION_TIMEOUT_MESSAGE).await?;
             operation = self
                 .get_upload_to_file_search_store_operation(UploadToFileSearchStoreOperation {
                     name: Some(name.clone()),
@@ -316,7 +364,7 @@ impl Operations {
                 })
                 .await?;
         }
-        Ok(operation)
+        operation_result(operation.error_status(), operation)
     }
 
     /// 获取导入文件到 FileSearchStore 的操作状态（Gemini API only）。
@@ -360,14 +408,28 @@ impl Operations {
     /// # Errors
     /// 当请求失败、操作缺少名称或轮询过程中响应解析失败时返回错误。
     pub async fn wait_import_file_operation(
+        &self,
+        operation: ImportFileOperation,
+    ) -> Result<ImportFileOperation> {
+        self.wait_import_file_operation_with_config(operation, PollConfig::default())
+            .await
+    }
+
+    /// 等待导入文件到 FileSearchStore 的操作完成（自定义轮询配置，Gemini API only）。
+    ///
+    /// # Errors
+    /// 当请求失败、操作缺少名称、响应解析失败、超时或操作以错误状态结束时返回错误。
+    pub async fn wait_import_file_operation_with_config(
         &self,
         mut operation: ImportFileOperation,
+        config: PollConfig,
     ) -> Result<ImportFileOperation> {
         let name = operation.name.clone().ok_or_else(|| Error::InvalidConfig {
             message: "Operation name is empty".into(),
         })?;
+        let mut poller = config.start();
         while !operation.done.unwrap_or(false) {
-            tokio::time::sleep(Duration::from_secs(5)).await;
+            poller.wait(OPERATION_TIMEOUT_MESSAGE).await?;
             operation = self
                 .get_import_file_operation(ImportFileOperation {
                     name: Some(name.clone()),
@@ -375,7 +437,15 @@ impl Operations {
                 })
                 .await?;
         }
-        Ok(operation)
+        operation_result(operation.error_status(), operation)
+    }
+}
+
+/// 已完成但携带错误状态的操作转为 [`Error::OperationFailed`]。
+fn operation_result<T>(status: Option<Status>, operation: T) -> Result<T> {
+    match status {
+        Some(status) => Err(Error::OperationFailed { status }),
+        None => Ok(operation),
     }
 }
 
@@ -455,7 +525,7 @@ async fn fetch_predict_operation_value(
 
     let response = inner.send_with_http_options(request, http_options).await?;
     if !response.status().is_success() {
-        return Err(Error::api_error_from_response(response, None).await);
+        return Err(Error::api_error_with_endpoint(response).await);
     }
     Ok(response.json::<Value>().await?)
 }
@@ -472,7 +542,7 @@ async fn get_operation_value(
 
     let response = inner.send_with_http_options(request, http_options).await?;
     if !response.status().is_success() {
-        return Err(Error::api_error_from_response(response, None).await);
+        return Err(Error::api_error_with_endpoint(response).await);
     }
     Ok(response.json::<Value>().await?)
 }
@@ -566,6 +636,9 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
@@ -0,0 +1,95 @@
+//! Shared page-token pagination adapter for `list_stream` helpers (models, tunings, ...).
+
+use std::collections::VecDeque;
+use std::future::Future;
+
+use futures_util::stream::{self, Stream};
+
+use crate::error::Result;
+
+/// 单页结果：本页条目与下一页 token（`None` 或空串表示最后一页）。
+pub type Page<T> = (Vec<T>, Option<String>);
+
+/// 将按 `pageToken` 翻页的列表接口包装为逐条产出的流。
+///
+/// `fetch_page` 接收下一页 token（首页为 `None`）并返回该页条目与 `nextPageToken`。
+/// 任一页请求失败时产出该错误并结束流。
+pub fn paginate<T, F, Fut>(fetch_page: F) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Page<T>>>,
+{
+    struct State<T, F> {
+        fetch_page: F,
+        buffer: VecDeque<T>,
+        next: Option<Option<String>>,
+    }
+
+    let state = State {
+        fetch_page,
+        buffer: VecDeque::new(),
+        next: Some(None),
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.buffer.pop_front() {
+                return Some((Ok(item), state));
+            }
+            let token = state.next.take()?;
+            match (state.fetch_page)(token).await {
+                Ok((items, next_page_token)) => {
+                    state.buffer.extend(items);
+                    state.next = next_page_token.filter(|token| !token.is_empty()).map(Some);
+                }
+                Err(err) => return Some((Err(err), state)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn paginate_follows_tokens_until_exhausted() {
+        let mut seen = Vec::new();
+        let items: Vec<u32> = paginate(|token| {
+            seen.push(token.clone());
+            async move {
+                Ok(match token.as_deref() {
+                    None => (vec![1, 2], Some("p2".to_string())),
+                    Some("p2") => (vec![], Some("p3".to_string())),
+                    _ => (vec![3], Some(String::new())),
+                })
+            }
+        })
+        .map(|item| item.unwrap())
+        .collect()
+        .await;
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(
+            seen,
+            vec![None, Some("p2".to_string()), Some("p3".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn paginate_stops_after_error() {
+        let results: Vec<Result<u32>> = paginate(|token| async move {
+            match token {
+                None => Ok((vec![1], Some("next".to_string()))),
+                Some(_) => Err(Error::InvalidConfig {
+                    message: "boom".into(),
+                }),
+            }
+        })
+        .collect()
+        .await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+}
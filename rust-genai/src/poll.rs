@@ -0,0 +1,187 @@
+//! Shared polling configuration for `wait` helpers (operations, batches, tunings, files).
+
+use std::time::Duration;
+
+use rust_genai_types::enums::JobState;
+use rust_genai_types::operations::Status;
+use serde_json::Value;
+use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+
+/// 轮询配置：首次间隔按 `backoff_multiplier` 指数增长，上限为 `max_interval`。
+#[derive(Debug, Clone)]
+pub struct PollConfig {
+    /// 首次轮询间隔。
+    pub interval: Duration,
+    /// 轮询间隔上限。
+    pub max_interval: Duration,
+    /// 每次轮询后间隔的增长倍数（小于 1 时按 1 处理）。
+    pub backoff_multiplier: f64,
+    /// 总等待时长上限；`None` 表示不超时。
+    pub timeout: Option<Duration>,
+    /// 每次等待额外叠加的随机抖动上限。
+    pub jitter: Duration,
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(30),
+            backoff_multiplier: 1.5,
+            timeout: None,
+            jitter: Duration::from_millis(250),
+        }
+    }
+}
+
+impl PollConfig {
+    /// 固定间隔、无抖动的轮询配置。
+    #[must_use]
+    pub const fn fixed(interval: Duration) -> Self {
+        Self {
+            interval,
+            max_interval: interval,
+            backoff_multiplier: 1.0,
+            timeout: None,
+            jitter: Duration::ZERO,
+        }
+    }
+
+    /// 设置总等待时长上限。
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 第 `attempt` 次（从 0 开始）轮询前的等待间隔，不含抖动。
+    #[must_use]
+    pub fn interval_for(&self, attempt: u32) -> Duration {
+        let base = self.interval.as_secs_f64();
+        if base == 0.0 {
+            return Duration::ZERO;
+        }
+        let max = self.max_interval.as_secs_f64().max(base);
+        let multiplier = self.backoff_multiplier.max(1.0);
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+        let secs = (base * multiplier.powi(exponent)).min(max);
+        Duration::from_secs_f64(secs)
+    }
+
+    pub(crate) fn start(&self) -> Poller<'_> {
+        Poller {
+            config: self,
+            started: Instant::now(),
+            attempt: 0,
+        }
+    }
+}
+
+/// 单次 `wait` 调用的轮询状态。
+pub(crate) struct Poller<'a> {
+    config: &'a PollConfig,
+    started: Instant,
+    attempt: u32,
+}
+
+impl Poller<'_> {
+    /// 超时则返回 `Error::Timeout`，否则睡眠到下一次轮询。
+    pub(crate) async fn wait(&mut self, timeout_message: &str) -> Result<()> {
+        let mut delay = self.config.interval_for(self.attempt) + self.jitter();
+        if let Some(timeout) = self.config.timeout {
+            let elapsed = self.started.elapsed();
+            if elapsed >= timeout {
+                return Err(Error::Timeout {
+                    message: timeout_message.into(),
+                });
+            }
+            delay = delay.min(timeout - elapsed);
+        }
+        self.attempt = self.attempt.saturating_add(1);
+        crate::rt::sleep(delay).await;
+        Ok(())
+    }
+
+    fn jitter(&self) -> Duration {
+        if self.config.jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        // Same cheap pseudo-random source as the retry jitter; no RNG dependency needed.
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        self.config
+            .jitter
+            .mul_f64(f64::from(nanos) / 1_000_000_000.0)
+    }
+}
+
+/// 以失败、取消或过期状态结束的任务转为 [`Error::OperationFailed`]。
+///
+/// 服务端未给出错误码时按状态映射为 `google.rpc.Code`（取消 1、过期 4、其余 2）。
+pub(crate) fn job_failed_error(
+    kind: &str,
+    state: JobState,
+    code: Option<i32>,
+    message: Option<&str>,
+    details: Vec<Value>,
+) -> Error {
+    let code = code.unwrap_or(match state {
+        JobState::JobStateCancelled => 1,
+        JobState::JobStateExpired => 4,
+        _ => 2,
+    });
+    let detail = message.unwrap_or("no error details");
+    Error::OperationFailed {
+        status: Status {
+            code,
+            message: format!("{kind} ended in state {state:?}: {detail}"),
+            details,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_schedule_grows_and_caps() {
+        let config = PollConfig {
+            interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(10),
+            backoff_multiplier: 2.0,
+            timeout: None,
+            jitter: Duration::ZERO,
+        };
+        let schedule: Vec<u64> = (0..7)
+            .map(|attempt| config.interval_for(attempt).as_secs())
+            .collect();
+        assert_eq!(schedule, vec![1, 2, 4, 8, 10, 10, 10]);
+        assert_eq!(config.interval_for(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn fixed_and_sub_unit_multiplier_stay_constant() {
+        let fixed = PollConfig::fixed(Duration::from_millis(500));
+        assert_eq!(fixed.interval_for(0), Duration::from_millis(500));
+        assert_eq!(fixed.interval_for(5), Duration::from_millis(500));
+
+        let shrinking = PollConfig {
+            backoff_multiplier: 0.5,
+            ..PollConfig::fixed(Duration::from_secs(1))
+        };
+        assert_eq!(shrinking.interval_for(3), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn poller_times_out() {
+        let config = PollConfig::fixed(Duration::from_millis(1)).with_timeout(Duration::ZERO);
+        let mut poller = config.start();
+        let err = poller.wait("Timed out").await.unwrap_err();
+        assert!(matches!(err, Error::Timeout { .. }));
+    }
+}
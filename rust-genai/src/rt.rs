@@ -0,0 +1,170 @@
+//! Async runtime shims: tokio natively, the browser event loop under `wasm32`.
+//!
+//! Timers and spawned tasks on `wasm32` run on the JS event loop via `spawn_local` and hand
+//! their results back through channels, so SDK futures and streams stay `Send` on both targets.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use tokio::task::{spawn, JoinHandle};
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use tokio::time::{sleep, sleep_until, timeout, Instant, Sleep};
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm::{sleep, sleep_until, spawn, timeout, Instant, JoinHandle, Sleep};
+
+/// Time left until `deadline` (e.g. a parsed HTTP date), or zero once it has passed.
+///
+/// Compares Unix offsets so it also works where `std::time::SystemTime::now` is unavailable.
+pub(crate) fn duration_until(deadline: std::time::SystemTime) -> std::time::Duration {
+    let now = web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .unwrap_or_default();
+    deadline
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .saturating_sub(now)
+}
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use std::fmt;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    use futures_util::future::Either;
+    use tokio::sync::oneshot;
+
+    pub(crate) use web_time::Instant;
+
+    /// Fires once after `duration` on the JS event loop.
+    fn timer(duration: Duration) -> oneshot::Receiver<()> {
+        let (tx, rx) = oneshot::channel();
+        let millis = u32::try_from(duration.as_millis()).unwrap_or(u32::MAX);
+        wasm_bindgen_futures::spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(millis).await;
+            let _ = tx.send(());
+        });
+        rx
+    }
+
+    /// Resettable timer future, mirroring `tokio::time::Sleep`.
+    pub(crate) struct Sleep {
+        deadline: Instant,
+        timer: Option<oneshot::Receiver<()>>,
+    }
+
+    impl Sleep {
+        pub(crate) fn reset(self: Pin<&mut Self>, deadline: Instant) {
+            let this = self.get_mut();
+            this.deadline = deadline;
+            this.timer = None;
+        }
+    }
+
+    impl Future for Sleep {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let this = self.get_mut();
+            loop {
+                let remaining = this.deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    this.timer = None;
+                    return Poll::Ready(());
+                }
+                let timer = this.timer.get_or_insert_with(|| timer(remaining));
+                match Pin::new(timer).poll(cx) {
+                    // Browser timers may fire slightly early; re-arm for the remainder.
+                    Poll::Ready(_) => this.timer = None,
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+    }
+
+    pub(crate) fn sleep(duration: Duration) -> Sleep {
+        sleep_until(Instant::now() + duration)
+    }
+
+    pub(crate) fn sleep_until(deadline: Instant) -> Sleep {
+        Sleep {
+            deadline,
+            timer: None,
+        }
+    }
+
+    /// Returned by [`timeout`] when the deadline passes first.
+    #[derive(Debug)]
+    pub(crate) struct Elapsed;
+
+    pub(crate) async fn timeout<F: Future>(
+        duration: Duration,
+        future: F,
+    ) -> Result<F::Output, Elapsed> {
+        let future = std::pin::pin!(future);
+        match futures_util::future::select(future, sleep(duration)).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(((), _)) => Err(Elapsed),
+        }
+    }
+
+    /// Handle to a task started with [`spawn`], mirroring `tokio::task::JoinHandle`.
+    pub(crate) struct JoinHandle<T> {
+        result: oneshot::Receiver<T>,
+        finished: Arc<AtomicBool>,
+    }
+
+    impl<T> JoinHandle<T> {
+        pub(crate) fn is_finished(&self) -> bool {
+            self.finished.load(Ordering::Acquire)
+        }
+    }
+
+    impl<T> fmt::Debug for JoinHandle<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("JoinHandle")
+                .field("finished", &self.is_finished())
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<T> Future for JoinHandle<T> {
+        type Output = Result<T, JoinError>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.result)
+                .poll(cx)
+                .map(|result| result.map_err(|_| JoinError))
+        }
+    }
+
+    /// The task stopped (e.g. panicked) before it produced a value.
+    #[derive(Debug)]
+    pub(crate) struct JoinError;
+
+    impl fmt::Display for JoinError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("task stopped before producing a value")
+        }
+    }
+
+    impl std::error::Error for JoinError {}
+
+    pub(crate) fn spawn<F>(future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let (tx, result) = oneshot::channel();
+        let finished = Arc::new(AtomicBool::new(false));
+        let task_finished = finished.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = tx.send(future.await);
+            task_finished.store(true, Ordering::Release);
+        });
+        JoinHandle { result, finished }
+    }
+}
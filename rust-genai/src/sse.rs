@@ -1,18 +1,23 @@
 //! SSE (Server-Sent Events) stream decoding utilities.
 
 use std::collections::VecDeque;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use bytes::{Buf, Bytes, BytesMut};
-use futures_util::Stream;
+use futures_util::{Stream, TryStreamExt};
 use memchr::memmem::Finder;
 use serde::de::DeserializeOwned;
 
+use crate::client::ClientInner;
 use crate::error::{Error, Result};
+use crate::rt::{Instant, Sleep};
+use crate::transport::Response;
 use rust_genai_types::response::GenerateContentResponse;
 
 /// SSE 事件。
@@ -155,11 +160,14 @@ const fn pick_min(best: Option<(usize, usize)>, pos: usize, len: usize) -> (usiz
 
 /// SSE JSON Stream 包装器（泛型）。
 pub struct SseJsonStream<T> {
-    stream: Pin<Box<dyn Stream<Item = std::result::Result<Bytes, reqwest::Error>> + Send>>,
+    stream: Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>,
     decoder: SseDecoder,
     pending: VecDeque<Result<ServerSentEvent>>,
     done: bool,
     done_signal: Option<Arc<AtomicBool>>,
+    idle_timeout: Option<Duration>,
+    idle_deadline: Option<Pin<Box<Sleep>>>,
+    parse_error_context: Option<(Arc<ClientInner>, &'static str)>,
     _marker: PhantomData<T>,
 }
 
@@ -168,25 +176,64 @@ impl<T> Unpin for SseJsonStream<T> {}
 impl<T> SseJsonStream<T> {
     /// 从 HTTP 响应创建 SSE 流。
     #[must_use]
-    pub fn new(response: reqwest::Response) -> Self {
+    pub fn new(response: Response) -> Self {
         Self::with_done_signal(response, None)
     }
 
     /// 从 HTTP 响应创建带显式完成标记的 SSE 流。
     #[must_use]
-    pub fn with_done_signal(
-        response: reqwest::Response,
-        done_signal: Option<Arc<AtomicBool>>,
-    ) -> Self {
+    pub fn with_done_signal(response: Response, done_signal: Option<Arc<AtomicBool>>) -> Self {
         Self {
-            stream: Box::pin(response.bytes_stream()),
+            stream: Box::pin(response.bytes_stream().map_err(Error::from)),
             decoder: SseDecoder::new(),
             pending: VecDeque::new(),
             done: false,
             done_signal,
+            idle_timeout: None,
+            idle_deadline: None,
+            parse_error_context: None,
             _marker: PhantomData,
         }
     }
+
+    /// 设置空闲超时：超过该时长未收到数据事件（注释与空心跳不计）时产出 [`Error::Timeout`] 并结束流。
+    #[must_use]
+    pub const fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// 事件反序列化失败时交由客户端处理（调用解析失败钩子等），`method` 为对应的 API 方法名。
+    #[must_use]
+    pub(crate) fn with_parse_error_context(
+        mut self,
+        inner: Arc<ClientInner>,
+        method: &'static str,
+    ) -> Self {
+        self.parse_error_context = Some((inner, method));
+        self
+    }
+
+    fn reset_idle_deadline(&mut self) {
+        if let (Some(timeout), Some(deadline)) = (self.idle_timeout, self.idle_deadline.as_mut()) {
+            deadline.as_mut().reset(Instant::now() + timeout);
+        }
+    }
+
+    fn poll_idle_deadline(&mut self, cx: &mut Context<'_>) -> Poll<Error> {
+        let Some(timeout) = self.idle_timeout else {
+            return Poll::Pending;
+        };
+        let deadline = self
+            .idle_deadline
+            .get_or_insert_with(|| Box::pin(crate::rt::sleep(timeout)));
+        match deadline.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Error::Timeout {
+                message: format!("No SSE event received within {}ms", timeout.as_millis()),
+            }),
+            Poll::Pending => Poll::Pending,
+        }
+    }
 }
 
 impl<T> Stream for SseJsonStream<T>
@@ -209,9 +256,29 @@ where
                             this.done = true;
                             continue;
                         }
+                        // 空 data（如 `event: ping` 心跳）不是 JSON 负载，直接跳过。
+                        if event.data.is_empty() {
+                            continue;
+                        }
 
-                        let parsed = serde_json::from_str::<T>(&event.data).map_err(Error::from)?;
-                        return Poll::Ready(Some(Ok(parsed)));
+                        this.reset_idle_deadline();
+                        // 200 响应中的 Google 错误信封（通常是首个事件）按 API 错误返回并结束流。
+                        if is_error_envelope(&event.data) {
+                            if let Some(err) = Error::api_error_from_stream_event(&event.data) {
+                                this.done = true;
+                                this.pending.clear();
+                                return Poll::Ready(Some(Err(err)));
+                            }
+                        }
+                        let parsed = serde_json::from_str::<T>(&event.data).map_err(|err| {
+                            match &this.parse_error_context {
+                                Some((inner, method)) => {
+                                    inner.response_parse_error(method, Some(&event.data), err)
+                                }
+                                None => err.into(),
+                            }
+                        });
+                        return Poll::Ready(Some(parsed));
                     }
                 }
             }
@@ -221,9 +288,15 @@ where
             }
 
             match this.stream.as_mut().poll_next(cx) {
-                Poll::Pending => return Poll::Pending,
+                Poll::Pending => {
+                    if let Poll::Ready(err) = this.poll_idle_deadline(cx) {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                    return Poll::Pending;
+                }
                 Poll::Ready(None) => return Poll::Ready(None),
-                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err.into()))),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
                 Poll::Ready(Some(Ok(bytes))) => {
                     let events = this.decoder.decode(&bytes);
                     for event in events {
@@ -235,16 +308,20 @@ where
     }
 }
 
-/// 便捷函数：从 reqwest Response 创建 SSE 流。
-pub fn parse_sse_stream(
-    response: reqwest::Response,
-) -> impl Stream<Item = Result<GenerateContentResponse>> {
+fn is_error_envelope(data: &str) -> bool {
+    data.trim_start()
+        .strip_prefix('{')
+        .is_some_and(|rest| rest.trim_start().starts_with("\"error\""))
+}
+
+/// 便捷函数：从 HTTP 响应创建 SSE 流。
+pub fn parse_sse_stream(response: Response) -> impl Stream<Item = Result<GenerateContentResponse>> {
     parse_sse_stream_with::<GenerateContentResponse>(response)
 }
 
 /// 泛型 SSE JSON 流解析器。
 #[must_use]
-pub fn parse_sse_stream_with<T>(response: reqwest::Response) -> SseJsonStream<T>
+pub fn parse_sse_stream_with<T>(response: Response) -> SseJsonStream<T>
 where
     T: DeserializeOwned,
 {
@@ -254,7 +331,7 @@ where
 /// 泛型 SSE JSON 流解析器，支持追踪显式 `[DONE]` 标记。
 #[must_use]
 pub fn parse_sse_stream_with_done_signal<T>(
-    response: reqwest::Response,
+    response: Response,
     done_signal: Arc<AtomicBool>,
 ) -> SseJsonStream<T>
 where
@@ -267,7 +344,7 @@ where
 mod tests {
     use super::*;
     use futures_util::StreamExt;
-    use serde_json::Value;
+    use serde_json::{json, Value};
     use wiremock::matchers::method;
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -410,6 +487,48 @@ mod tests {
         assert!(stream.next().await.is_none());
     }
 
+    #[tokio::test]
+    async fn test_sse_json_stream_skips_comments_and_keepalives() {
+        let server = MockServer::start().await;
+        let body = ": keepalive\n\ndata: {\"value\":1}\n\n:ping\n\n\n\nevent: ping\n\ndata:\n\n: still working\ndata: {\"value\":2}\n\n";
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .mount(&server)
+            .await;
+
+        let response = reqwest::Client::new()
+            .get(server.uri())
+            .send()
+            .await
+            .unwrap();
+        let values: Vec<Value> = parse_sse_stream_with::<Value>(response)
+            .map(|item| item.unwrap())
+            .collect()
+            .await;
+        assert_eq!(values, vec![json!({"value": 1}), json!({"value": 2})]);
+    }
+
+    #[tokio::test]
+    async fn test_sse_json_stream_idle_timeout() {
+        let chunks: Vec<std::result::Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from_static(b"data: {\"value\":1}\n\n")),
+            Ok(Bytes::from_static(b": heartbeat\n\n")),
+        ];
+        let body = futures_util::stream::iter(chunks).chain(futures_util::stream::pending());
+        let response =
+            reqwest::Response::from(http::Response::new(reqwest::Body::wrap_stream(body)));
+        let mut stream =
+            parse_sse_stream_with::<Value>(response).with_idle_timeout(Duration::from_millis(50));
+        assert_eq!(stream.next().await.unwrap().unwrap()["value"], 1);
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(matches!(err, Error::Timeout { .. }));
+        assert!(stream.next().await.is_none());
+    }
+
     #[tokio::test]
     async fn test_sse_json_stream_invalid_json() {
         let server = MockServer::start().await;
@@ -1,8 +1,10 @@
 use crate::client::{
     ApiClient, Backend, ClientConfig, ClientInner, Credentials, HttpOptions, VertexConfig,
 };
+use crate::transport::ReqwestTransport;
 use rust_genai_types::http::HttpRetryOptions;
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 static ENV_LOCK: Mutex<()> = Mutex::new(());
 
@@ -37,6 +39,7 @@ pub fn test_client_inner_with_api_key(backend: Backend, api_key: Option<&str>) -
             project: "proj".to_string(),
             location: "loc".to_string(),
             credentials: None,
+            api_host: None,
         })
     } else {
         None
@@ -54,6 +57,7 @@ pub fn test_client_inner_with_api_key(backend: Backend, api_key: Option<&str>) -
         },
         credentials: Credentials::ApiKey("test-key".into()),
         auth_scopes: Vec::new(),
+        default_labels: HashMap::new(),
     };
     let api_client = ApiClient::new(&config);
     ClientInner {
@@ -61,6 +65,15 @@ pub fn test_client_inner_with_api_key(backend: Backend, api_key: Option<&str>) -
         config,
         api_client,
         auth_provider: None,
+        transport: Arc::new(ReqwestTransport::new(reqwest::Client::new())),
+        token_count_cache: None,
+        request_signer: None,
+        response_parse_hook: None,
+        raw_body_in_parse_errors: false,
+        app_name: None,
+        validate_media_inputs: false,
+        validate_input_length: false,
+        validate_model_capabilities: false,
     }
 }
 
@@ -74,6 +87,7 @@ pub fn test_client_inner_with_base(
             project: "proj".to_string(),
             location: "loc".to_string(),
             credentials: None,
+            api_host: None,
         })
     } else {
         None
@@ -94,6 +108,7 @@ pub fn test_client_inner_with_base(
         http_options,
         credentials: Credentials::ApiKey("test-key".into()),
         auth_scopes: Vec::new(),
+        default_labels: HashMap::new(),
     };
     let api_client = ApiClient::new(&config);
     ClientInner {
@@ -101,6 +116,15 @@ pub fn test_client_inner_with_base(
         config,
         api_client,
         auth_provider: None,
+        transport: Arc::new(ReqwestTransport::new(reqwest::Client::new())),
+        token_count_cache: None,
+        request_signer: None,
+        response_parse_hook: None,
+        raw_body_in_parse_errors: false,
+        app_name: None,
+        validate_media_inputs: false,
+        validate_input_length: false,
+        validate_model_capabilities: false,
     }
 }
 
@@ -118,6 +142,7 @@ pub fn test_vertex_inner_missing_config() -> ClientInner {
         },
         credentials: Credentials::ApplicationDefault,
         auth_scopes: Vec::new(),
+        default_labels: HashMap::new(),
     };
     let api_client = ApiClient::new(&config);
     ClientInner {
@@ -125,5 +150,14 @@ pub fn test_vertex_inner_missing_config() -> ClientInner {
         config,
         api_client,
         auth_provider: None,
+        transport: Arc::new(ReqwestTransport::new(reqwest::Client::new())),
+        token_count_cache: None,
+        request_signer: None,
+        response_parse_hook: None,
+        raw_body_in_parse_errors: false,
+        app_name: None,
+        validate_media_inputs: false,
+        validate_input_length: false,
+        validate_model_capabilities: false,
     }
 }
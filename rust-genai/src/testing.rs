@@ -0,0 +1,176 @@
+//! In-process mock backend for unit tests (requires the `testing` feature).
+//!
+//! ```rust,no_run
+//! use rust_genai::testing::{mock_client, MockResponse};
+//! use serde_json::json;
+//!
+//! let client = mock_client(|request| {
+//!     assert!(request.url.path().ends_with(":generateContent"));
+//!     MockResponse::json(json!({
+//!         "candidates": [{"content": {"role": "model", "parts": [{"text": "hi"}]}}]
+//!     }))
+//! });
+//! ```
+
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::client::{Backend, Client};
+use crate::error::Result;
+use crate::transport::{Transport, TransportRequest, TransportResponse};
+
+/// Handler invoked for every request sent by a mock client.
+pub type MockHandler = dyn Fn(MockRequest) -> MockResponse + Send + Sync;
+
+/// Request captured by the mock backend.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    /// Buffered request body; empty for streaming uploads.
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    pub(crate) fn from_transport(request: TransportRequest) -> Self {
+        Self {
+            body: request
+                .body
+                .as_bytes()
+                .map(<[u8]>::to_vec)
+                .unwrap_or_default(),
+            method: request.method,
+            url: request.url,
+            headers: request.headers,
+        }
+    }
+
+    /// Parses the request body as JSON.
+    ///
+    /// # Errors
+    /// 当请求体不是合法 JSON 时返回错误。
+    pub fn json(&self) -> Result<Value> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// Response returned by a [`MockHandler`].
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    /// Creates an empty response with the given status.
+    #[must_use]
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Creates a `200 OK` JSON response.
+    #[must_use]
+    pub fn json(value: Value) -> Self {
+        Self::new(StatusCode::OK)
+            .header("content-type", "application/json")
+            .body(value.to_string())
+    }
+
+    /// Creates a `200 OK` server-sent events response from JSON chunks.
+    #[must_use]
+    pub fn sse(chunks: impl IntoIterator<Item = Value>) -> Self {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str("data: ");
+            body.push_str(&chunk.to_string());
+            body.push_str("\n\n");
+        }
+        Self::new(StatusCode::OK)
+            .header("content-type", "text/event-stream")
+            .body(body)
+    }
+
+    /// Sets the response status.
+    #[must_use]
+    pub const fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Adds a response header; invalid names or values are ignored.
+    #[must_use]
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            self.headers.insert(name, value);
+        }
+        self
+    }
+
+    /// Sets the response body.
+    #[must_use]
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub(crate) fn into_transport(self) -> TransportResponse {
+        TransportResponse {
+            status: self.status,
+            headers: self.headers,
+            body: self.body.into(),
+        }
+    }
+}
+
+/// Builds a Gemini API client whose requests are answered by `handler` in-process.
+///
+/// # Panics
+/// 构建固定配置的客户端不会失败；若失败则 panic。
+pub fn mock_client<F>(handler: F) -> Client
+where
+    F: Fn(MockRequest) -> MockResponse + Send + Sync + 'static,
+{
+    Client::builder()
+        .api_key("test-key")
+        .backend(Backend::GeminiApi)
+        .mock_handler(handler)
+        .build()
+        .expect("mock client config is valid")
+}
+
+/// [`Transport`] that answers every request with a [`MockHandler`].
+#[derive(Clone)]
+pub struct MockTransport {
+    handler: Arc<MockHandler>,
+}
+
+impl MockTransport {
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(MockRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        Self {
+            handler: Arc::new(handler),
+        }
+    }
+}
+
+impl Transport for MockTransport {
+    fn execute(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        let response = (self.handler)(MockRequest::from_transport(request)).into_transport();
+        Box::pin(async move { Ok(response) })
+    }
+}
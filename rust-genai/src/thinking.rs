@@ -1,7 +1,9 @@
 //! Thinking support and thought signature validation.
 
+use rust_genai_types::base64_serde;
 use rust_genai_types::content::{Content, PartKind, Role};
 use rust_genai_types::models::GenerateContentConfig;
+use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
 
@@ -65,6 +67,90 @@ impl ThoughtSignatureValidator {
     }
 }
 
+/// 从对话历史中剥离出的 thought signatures，按 content/part 下标记录以便原样还原。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThoughtSignatures {
+    #[serde(default)]
+    pub entries: Vec<ThoughtSignatureEntry>,
+}
+
+/// 单个 part 上的 thought signature。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThoughtSignatureEntry {
+    /// 所在 content 的下标。
+    pub content_index: usize,
+    /// 所在 part 的下标。
+    pub part_index: usize,
+    /// 签名（序列化为 base64）。
+    #[serde(with = "base64_serde")]
+    pub signature: Vec<u8>,
+}
+
+impl ThoughtSignatures {
+    /// 是否没有任何签名。
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 签名数量。
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// 剥离对话历史中的全部 thought signatures（用于签名不应落盘的持久化场景）。
+///
+/// 返回值需与历史一同保存（或单独存放），并在下一轮请求前通过
+/// [`restore_thought_signatures`] 还原。
+pub fn strip_thought_signatures(contents: &mut [Content]) -> ThoughtSignatures {
+    let mut entries = Vec::new();
+    for (content_index, content) in contents.iter_mut().enumerate() {
+        for (part_index, part) in content.parts.iter_mut().enumerate() {
+            if let Some(signature) = part.take_thought_signature() {
+                entries.push(ThoughtSignatureEntry {
+                    content_index,
+                    part_index,
+                    signature,
+                });
+            }
+        }
+    }
+    ThoughtSignatures { entries }
+}
+
+/// 将 [`strip_thought_signatures`] 剥离的签名还原到对话历史。
+///
+/// # Errors
+///
+/// 当签名下标超出历史范围（历史在剥离后被改动）时返回错误，且不修改历史。
+pub fn restore_thought_signatures(
+    contents: &mut [Content],
+    signatures: &ThoughtSignatures,
+) -> Result<()> {
+    for entry in &signatures.entries {
+        let exists = contents
+            .get(entry.content_index)
+            .is_some_and(|content| entry.part_index < content.parts.len());
+        if !exists {
+            return Err(Error::InvalidConfig {
+                message: format!(
+                    "Thought signature target contents[{}].parts[{}] does not exist",
+                    entry.content_index, entry.part_index
+                ),
+            });
+        }
+    }
+    for entry in &signatures.entries {
+        contents[entry.content_index].parts[entry.part_index]
+            .set_thought_signature(Some(entry.signature.clone()));
+    }
+    Ok(())
+}
+
 /// Gemini 3 温度检查。
 ///
 /// # Errors
@@ -138,6 +224,50 @@ mod tests {
         assert!(validator.validate(&contents).is_err());
     }
 
+    #[test]
+    fn test_strip_and_restore_thought_signatures_round_trip() {
+        let mut history = vec![
+            Content::user("Check flight AA100"),
+            Content::from_parts(
+                vec![
+                    Part::text("thinking").with_thought(true),
+                    Part::function_call(FunctionCall {
+                        id: None,
+                        name: Some("check_flight".into()),
+                        args: None,
+                        partial_args: None,
+                        will_continue: None,
+                    })
+                    .with_thought_signature(vec![7, 8, 9]),
+                ],
+                Role::Model,
+            ),
+        ];
+        assert_eq!(
+            history[1].parts[1].thought_signature(),
+            Some(&[7, 8, 9][..])
+        );
+
+        let signatures = strip_thought_signatures(&mut history);
+        assert_eq!(signatures.len(), 1);
+        let stored = serde_json::to_string(&history).unwrap();
+        assert!(!stored.contains("thoughtSignature"));
+        let stored_signatures = serde_json::to_string(&signatures).unwrap();
+
+        let mut loaded: Vec<Content> = serde_json::from_str(&stored).unwrap();
+        let loaded_signatures: ThoughtSignatures =
+            serde_json::from_str(&stored_signatures).unwrap();
+        restore_thought_signatures(&mut loaded, &loaded_signatures).unwrap();
+        assert_eq!(loaded[1].parts[1].thought_signature(), Some(&[7, 8, 9][..]));
+        assert!(loaded[1].parts[0].thought_signature().is_none());
+        ThoughtSignatureValidator::new("gemini-3-pro-preview")
+            .validate(&loaded)
+            .unwrap();
+
+        let err = restore_thought_signatures(&mut loaded[..1], &loaded_signatures).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+    }
+
     #[test]
     fn test_temperature_warning_gemini3() {
         let config = GenerateContentConfig {
@@ -0,0 +1,125 @@
+//! In-memory LRU cache for `countTokens` responses (opt-in via `ClientBuilder::token_count_cache`).
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use rust_genai_types::models::CountTokensResponse;
+use serde_json::Value;
+
+/// 以请求 URL（含模型）与请求体为键的 `countTokens` 结果缓存。
+#[derive(Debug)]
+pub(crate) struct TokenCountCache {
+    capacity: usize,
+    state: Mutex<TokenCountCacheState>,
+}
+
+/// 缓存键：保存完整的 URL 与请求体，哈希碰撞时不会返回其他请求的结果。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct TokenCountKey(Arc<str>);
+
+#[derive(Debug, Default)]
+struct TokenCountCacheState {
+    entries: HashMap<TokenCountKey, CountTokensResponse>,
+    order: VecDeque<TokenCountKey>,
+}
+
+impl TokenCountCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(TokenCountCacheState::default()),
+        }
+    }
+
+    /// 计算缓存键：URL 已包含模型名，请求体包含 contents 与 config。
+    ///
+    /// URL 不含空格，以空格分隔两部分不会产生歧义。
+    pub(crate) fn key(url: &str, body: &Value) -> TokenCountKey {
+        TokenCountKey(format!("{url} {body}").into())
+    }
+
+    pub(crate) fn get(&self, key: &TokenCountKey) -> Option<CountTokensResponse> {
+        let mut state = self.lock();
+        let response = state.entries.get(key).cloned()?;
+        state.touch(key);
+        Some(response)
+    }
+
+    pub(crate) fn insert(&self, key: TokenCountKey, response: CountTokensResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.lock();
+        if state.entries.insert(key.clone(), response).is_some() {
+            state.touch(&key);
+            return;
+        }
+        state.order.push_back(key);
+        while state.entries.len() > self.capacity {
+            let Some(oldest) = state.order.pop_front() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TokenCountCacheState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl TokenCountCacheState {
+    fn touch(&mut self, key: &TokenCountKey) {
+        if let Some(position) = self.order.iter().position(|entry| entry == key) {
+            self.order.remove(position);
+        }
+        self.order.push_back(key.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(total_tokens: i32) -> CountTokensResponse {
+        serde_json::from_value(json!({ "totalTokens": total_tokens })).unwrap()
+    }
+
+    #[test]
+    fn token_count_cache_evicts_least_recently_used() {
+        let cache = TokenCountCache::new(2);
+        let a = TokenCountCache::key("m:countTokens", &json!({"contents": "a"}));
+        let b = TokenCountCache::key("m:countTokens", &json!({"contents": "b"}));
+        let c = TokenCountCache::key("m:countTokens", &json!({"contents": "c"}));
+        assert_ne!(a, b);
+
+        cache.insert(a.clone(), response(1));
+        cache.insert(b.clone(), response(2));
+        assert_eq!(cache.get(&a).unwrap().total_tokens, Some(1));
+        cache.insert(c.clone(), response(3));
+
+        assert!(cache.get(&a).is_some());
+        assert!(cache.get(&b).is_none());
+        assert!(cache.get(&c).is_some());
+    }
+
+    #[test]
+    fn token_count_cache_key_keeps_url_and_body_distinct() {
+        let cache = TokenCountCache::new(4);
+        let a = TokenCountCache::key("m:countTokens", &json!({"contents": "a"}));
+        cache.insert(a.clone(), response(1));
+
+        assert_eq!(
+            a,
+            TokenCountCache::key("m:countTokens", &json!({"contents": "a"}))
+        );
+        let other_model = TokenCountCache::key("n:countTokens", &json!({"contents": "a"}));
+        assert!(cache.get(&other_model).is_none());
+        let other_body = TokenCountCache::key("m:countTokens", &json!({"contents": "a "}));
+        assert!(cache.get(&other_body).is_none());
+        assert_eq!(cache.get(&a).unwrap().total_tokens, Some(1));
+    }
+}
@@ -1,7 +1,7 @@
 //! Local token estimation utilities (optional).
 
 use rust_genai_types::config::GenerationConfig;
-use rust_genai_types::content::{Content, FunctionCall, FunctionResponse, PartKind};
+use rust_genai_types::content::{Content, FunctionCall, FunctionResponse, Part, PartKind};
 use rust_genai_types::models::CountTokensConfig;
 use rust_genai_types::tool::{FunctionDeclaration, Schema, Tool};
 use serde_json::Value;
@@ -58,6 +58,242 @@ impl TokenEstimator for SimpleTokenEstimator {
     }
 }
 
+/// 截断方向。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncateFrom {
+    /// 从开头裁剪，保留末尾内容。
+    Start,
+    /// 从末尾裁剪，保留开头内容。
+    #[default]
+    End,
+}
+
+/// 将内容裁剪到 `max_tokens` 预算内，返回 `(裁剪后的内容, 估算 token 数)`。
+///
+/// 按 `from` 指定的方向逐个处理 part：文本 part 会按字符截断到恰好放得下，放不下的
+/// part（包括非文本 part）整体丢弃；变为空的 `Content` 会被移除。所有内容都被移除后仍超出
+/// 预算时（例如估算器有固定开销），返回剩余内容及其估算值。
+pub fn truncate_contents<E: TokenEstimator + ?Sized>(
+    estimator: &E,
+    mut contents: Vec<Content>,
+    max_tokens: usize,
+    from: TruncateFrom,
+) -> (Vec<Content>, usize) {
+    loop {
+        let tokens = estimator.estimate_tokens(&contents);
+        if tokens <= max_tokens {
+            return (contents, tokens);
+        }
+        let has_parts = |content: &Content| !content.parts.is_empty();
+        let index = match from {
+            TruncateFrom::Start => contents.iter().position(has_parts),
+            TruncateFrom::End => contents.iter().rposition(has_parts),
+        };
+        let Some(index) = index else {
+            return (contents, tokens);
+        };
+        let part = match from {
+            TruncateFrom::Start => contents[index].parts.remove(0),
+            TruncateFrom::End => {
+                let last = contents[index].parts.len() - 1;
+                contents[index].parts.remove(last)
+            }
+        };
+        if let Some(kept) = fit_text_part(estimator, &mut contents, index, &part, max_tokens, from)
+        {
+            insert_part(&mut contents[index], kept, from);
+        } else if contents[index].parts.is_empty() {
+            contents.remove(index);
+        }
+    }
+}
+
+/// 二分查找能放入预算的最长文本截断；放不下任何字符时返回 `None`。
+fn fit_text_part<E: TokenEstimator + ?Sized>(
+    estimator: &E,
+    contents: &mut [Content],
+    index: usize,
+    part: &Part,
+    max_tokens: usize,
+    from: TruncateFrom,
+) -> Option<Part> {
+    let text = part.text_value()?;
+    let boundaries: Vec<usize> = text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let char_count = boundaries.len() - 1;
+    let truncated = |keep: usize| {
+        let mut candidate = part.clone();
+        let slice = match from {
+            TruncateFrom::Start => &text[boundaries[char_count - keep]..],
+            TruncateFrom::End => &text[..boundaries[keep]],
+        };
+        candidate.kind = PartKind::Text {
+            text: slice.to_string(),
+        };
+        candidate
+    };
+
+    let (mut low, mut high) = (0usize, char_count);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        let candidate = truncated(mid);
+        insert_part(&mut contents[index], candidate, from);
+        let fits = estimator.estimate_tokens(contents) <= max_tokens;
+        remove_part(&mut contents[index], from);
+        if fits {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    (low > 0).then(|| truncated(low))
+}
+
+fn insert_part(content: &mut Content, part: Part, from: TruncateFrom) {
+    match from {
+        TruncateFrom::Start => content.parts.insert(0, part),
+        TruncateFrom::End => content.parts.push(part),
+    }
+}
+
+fn remove_part(content: &mut Content, from: TruncateFrom) {
+    match from {
+        TruncateFrom::Start => {
+            content.parts.remove(0);
+        }
+        TruncateFrom::End => {
+            content.parts.pop();
+        }
+    }
+}
+
+/// 将长文本切分为多个用户 `Content` 轮次，每个轮次的估算 token 数不超过 `max_tokens`。
+///
+/// 优先在段落（空行）处切分，其次是句子，再次是单词；只有单个单词本身超出预算时才会按字符
+/// 切断。每个块首尾的空白会被去除。
+pub fn chunk_text<E: TokenEstimator + ?Sized>(
+    estimator: &E,
+    text: &str,
+    max_tokens: usize,
+) -> Vec<Content> {
+    let mut chunker = TextChunker {
+        estimator,
+        max_tokens,
+        chunks: Vec::new(),
+        current: String::new(),
+    };
+    chunker.pack(
+        split_units(text, SplitLevel::Paragraph),
+        SplitLevel::Paragraph,
+    );
+    chunker.flush();
+    chunker.chunks.into_iter().map(Content::text).collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SplitLevel {
+    Paragraph,
+    Sentence,
+    Word,
+    Char,
+}
+
+impl SplitLevel {
+    const fn finer(self) -> Option<Self> {
+        match self {
+            Self::Paragraph => Some(Self::Sentence),
+            Self::Sentence => Some(Self::Word),
+            Self::Word => Some(Self::Char),
+            Self::Char => None,
+        }
+    }
+}
+
+struct TextChunker<'a, E: ?Sized> {
+    estimator: &'a E,
+    max_tokens: usize,
+    chunks: Vec<String>,
+    current: String,
+}
+
+impl<E: TokenEstimator + ?Sized> TextChunker<'_, E> {
+    fn fits(&self, text: &str) -> bool {
+        self.estimator
+            .estimate_tokens(&[Content::text(text.trim())])
+            <= self.max_tokens
+    }
+
+    fn flush(&mut self) {
+        let chunk = self.current.trim();
+        if !chunk.is_empty() {
+            self.chunks.push(chunk.to_string());
+        }
+        self.current.clear();
+    }
+
+    fn pack(&mut self, units: Vec<&str>, level: SplitLevel) {
+        for unit in units {
+            let candidate = format!("{}{unit}", self.current);
+            if self.fits(&candidate) {
+                self.current = candidate;
+                continue;
+            }
+            self.flush();
+            if self.fits(unit) {
+                self.current.push_str(unit);
+            } else if let Some(finer) = level.finer() {
+                self.pack(split_units(unit, finer), finer);
+            } else {
+                // 单个字符也超出预算（估算器有固定开销），只能单独成块。
+                self.current.push_str(unit);
+                self.flush();
+            }
+        }
+    }
+}
+
+/// 按层级切分文本，切分出的单元首尾相接即为原文。
+fn split_units(text: &str, level: SplitLevel) -> Vec<&str> {
+    let mut units = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((offset, ch)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let end = offset + ch.len_utf8();
+        let boundary = match level {
+            SplitLevel::Paragraph => ch == '\n' && next == Some('\n'),
+            SplitLevel::Sentence => {
+                matches!(ch, '。' | '！' | '？' | '\n')
+                    || (matches!(ch, '.' | '!' | '?') && next.is_some_and(char::is_whitespace))
+            }
+            SplitLevel::Word => ch.is_whitespace() && !next.is_some_and(char::is_whitespace),
+            SplitLevel::Char => true,
+        };
+        if boundary {
+            // 段落与句子边界把随后的空白一并归入当前单元。
+            let mut end = end;
+            if matches!(level, SplitLevel::Paragraph | SplitLevel::Sentence) {
+                while let Some((offset, next)) = chars.peek().copied() {
+                    if !next.is_whitespace() {
+                        break;
+                    }
+                    end = offset + next.len_utf8();
+                    chars.next();
+                }
+            }
+            units.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        units.push(&text[start..]);
+    }
+    units
+}
+
 pub(crate) fn build_estimation_contents(
     contents: &[Content],
     config: &CountTokensConfig,
@@ -243,6 +479,49 @@ mod tests {
     use serde_json::json;
     use std::collections::HashMap;
 
+    #[test]
+    fn chunk_text_respects_budget_and_word_boundaries() {
+        let paragraph = "Rust gives you memory safety without a garbage collector. \
+                         Ownership rules are checked at compile time! Does that slow you down? \
+                         Rarely, once the borrow checker becomes familiar.";
+        let document = [paragraph; 6].join("\n\n");
+        let estimator = SimpleTokenEstimator;
+        let budget = 30;
+
+        let chunks = chunk_text(&estimator, &document, budget);
+        assert!(chunks.len() > 6);
+        let mut words = Vec::new();
+        for chunk in &chunks {
+            assert_eq!(chunk.role, Some(Role::User));
+            assert!(estimator.estimate_tokens(std::slice::from_ref(chunk)) <= budget);
+            let text = chunk.first_text().unwrap();
+            assert!(!text.starts_with(char::is_whitespace));
+            words.extend(text.split_whitespace().map(str::to_string));
+        }
+        let original: Vec<_> = document.split_whitespace().map(str::to_string).collect();
+        assert_eq!(words, original);
+
+        // Whole paragraphs stay together when the budget allows.
+        let chunks = chunk_text(&estimator, &document, 60);
+        assert_eq!(chunks.len(), 6);
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.first_text() == Some(paragraph)));
+    }
+
+    #[test]
+    fn chunk_text_splits_oversized_words_and_cjk_sentences() {
+        let estimator = SimpleTokenEstimator;
+        let chunks = chunk_text(&estimator, &"x".repeat(10), 1);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].first_text(), Some("xxxx"));
+
+        let chunks = chunk_text(&estimator, "第一句话。第二句话。", 4);
+        let texts: Vec<_> = chunks.iter().filter_map(Content::first_text).collect();
+        assert_eq!(texts, vec!["第一句话。", "第二句话。"]);
+        assert!(chunk_text(&estimator, "  ", 4).is_empty());
+    }
+
     #[test]
     fn simple_token_estimator_counts_various_parts() {
         let call = FunctionCall {
@@ -479,6 +758,51 @@ mod tests {
         let tokens = estimator.estimate_tokens(&[]);
         assert_eq!(tokens, 0);
     }
+
+    #[test]
+    fn truncate_contents_fits_small_budget() {
+        let estimator = SimpleTokenEstimator;
+        let long = "abcd".repeat(50);
+        let contents = vec![
+            Content::text("keep"),
+            Content::from_parts(
+                vec![
+                    Part::text(long.clone()),
+                    Part::inline_data(vec![0; 64], "image/png"),
+                ],
+                Role::User,
+            ),
+        ];
+        assert_eq!(estimator.estimate_tokens(&contents), 67);
+
+        let (trimmed, tokens) =
+            truncate_contents(&estimator, contents.clone(), 10, TruncateFrom::End);
+        assert_eq!(tokens, 10);
+        assert_eq!(trimmed.len(), 2);
+        assert_eq!(trimmed[0].first_text(), Some("keep"));
+        assert_eq!(trimmed[1].parts.len(), 1);
+        assert_eq!(trimmed[1].first_text(), Some(&long[..36]));
+
+        let (trimmed, tokens) = truncate_contents(&estimator, contents, 17, TruncateFrom::Start);
+        assert_eq!(tokens, 17);
+        assert_eq!(trimmed.len(), 1);
+        assert_eq!(trimmed[0].parts.len(), 2);
+        assert_eq!(trimmed[0].first_text(), Some(&long[196..]));
+    }
+
+    #[test]
+    fn truncate_contents_keeps_char_boundaries_and_drops_everything() {
+        let estimator = SimpleTokenEstimator;
+        let contents = vec![Content::text("日本語のテキスト")];
+        let (trimmed, tokens) =
+            truncate_contents(&estimator, contents.clone(), 2, TruncateFrom::Start);
+        assert_eq!(tokens, 2);
+        assert_eq!(trimmed[0].first_text(), Some("スト"));
+
+        let (trimmed, tokens) = truncate_contents(&estimator, contents, 0, TruncateFrom::End);
+        assert!(trimmed.is_empty());
+        assert_eq!(tokens, 0);
+    }
 }
 
 #[cfg(feature = "kitoken")]
@@ -695,6 +1019,7 @@ pub mod kitoken {
             Ok(ComputeTokensResponse {
                 sdk_http_response: None,
                 tokens_info: Some(tokens_info),
+                ..Default::default()
             })
         }
     }
@@ -0,0 +1,341 @@
+//! Pluggable HTTP transport.
+//!
+//! All SDK requests go through [`Transport::execute`] after auth headers are injected and
+//! before retry handling inspects the response. Transports exchange the crate-owned
+//! [`TransportRequest`] / [`TransportResponse`] types, so custom stacks (in-memory stubs,
+//! WASM fetch, ...) do not depend on reqwest. The default implementation is
+//! [`ReqwestTransport`] natively and [`FetchTransport`] under `wasm32` (`wasm` feature); custom
+//! stacks can be supplied with [`ClientBuilder::transport`](crate::ClientBuilder::transport).
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::stream::BoxStream;
+#[cfg(not(target_arch = "wasm32"))]
+use futures_util::StreamExt;
+use futures_util::TryStreamExt;
+use http::{HeaderMap, Method, StatusCode};
+#[cfg(not(target_arch = "wasm32"))]
+use http_body_util::BodyExt;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::ResponseBuilderExt;
+use reqwest::Url;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::Error;
+use crate::error::Result;
+use crate::rt::Instant;
+
+#[cfg(target_arch = "wasm32")]
+mod fetch;
+#[cfg(target_arch = "wasm32")]
+mod response;
+
+#[cfg(target_arch = "wasm32")]
+pub use fetch::FetchTransport;
+#[cfg(target_arch = "wasm32")]
+pub use response::Response;
+
+/// HTTP response returned by [`Client::send`](crate::Client::send) and consumed by the SDK's
+/// response handling: reqwest's response natively, a crate-owned one under `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type Response = reqwest::Response;
+
+/// Executes a fully-built request and returns the raw HTTP response.
+pub trait Transport: Send + Sync {
+    fn execute(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>>;
+}
+
+/// Request or response body exchanged with a [`Transport`].
+pub enum TransportBody {
+    /// Fully buffered body (empty for requests without a body).
+    Bytes(Bytes),
+    /// Streaming body, e.g. resumable upload chunks or server-sent events.
+    Stream(BoxStream<'static, Result<Bytes>>),
+}
+
+impl TransportBody {
+    /// Returns the buffered bytes, or `None` for streaming bodies.
+    #[must_use]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes),
+            Self::Stream(_) => None,
+        }
+    }
+
+    /// Reads the whole body into memory.
+    ///
+    /// # Errors
+    /// 当流式 body 读取失败时返回错误。
+    pub async fn collect(self) -> Result<Bytes> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes),
+            Self::Stream(stream) => {
+                let chunks: Vec<Bytes> = stream.try_collect().await?;
+                Ok(chunks.concat().into())
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from_reqwest(body: reqwest::Body) -> Self {
+        match body.as_bytes() {
+            Some(bytes) => Self::Bytes(Bytes::copy_from_slice(bytes)),
+            None => Self::Stream(body.into_data_stream().map_err(Error::from).boxed()),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn from_reqwest(body: reqwest::Body) -> Self {
+        // Browser request bodies are always buffered.
+        Self::Bytes(Bytes::copy_from_slice(body.as_bytes().unwrap_or_default()))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn into_reqwest(self) -> reqwest::Body {
+        match self {
+            Self::Bytes(bytes) => reqwest::Body::from(bytes),
+            Self::Stream(stream) => reqwest::Body::wrap_stream(stream),
+        }
+    }
+}
+
+impl Default for TransportBody {
+    fn default() -> Self {
+        Self::Bytes(Bytes::new())
+    }
+}
+
+impl fmt::Debug for TransportBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bytes(bytes) => f.debug_tuple("Bytes").field(&bytes.len()).finish(),
+            Self::Stream(_) => f.write_str("Stream"),
+        }
+    }
+}
+
+impl From<Bytes> for TransportBody {
+    fn from(bytes: Bytes) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for TransportBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes.into())
+    }
+}
+
+impl From<String> for TransportBody {
+    fn from(text: String) -> Self {
+        Self::Bytes(text.into())
+    }
+}
+
+impl From<&'static str> for TransportBody {
+    fn from(text: &'static str) -> Self {
+        Self::Bytes(Bytes::from_static(text.as_bytes()))
+    }
+}
+
+/// Outgoing request handed to a [`Transport`], with auth and SDK headers already set.
+#[derive(Debug)]
+pub struct TransportRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: TransportBody,
+    /// Per-request timeout configured through `HttpOptions`.
+    pub timeout: Option<Duration>,
+}
+
+impl TransportRequest {
+    pub(crate) fn from_reqwest(mut request: reqwest::Request) -> Self {
+        let timeout = request.timeout().copied();
+        let body = request
+            .body_mut()
+            .take()
+            .map(TransportBody::from_reqwest)
+            .unwrap_or_default();
+        Self {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: std::mem::take(request.headers_mut()),
+            body,
+            timeout,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn into_reqwest(self) -> reqwest::Request {
+        let mut request = reqwest::Request::new(self.method, self.url);
+        *request.headers_mut() = self.headers;
+        *request.timeout_mut() = self.timeout;
+        *request.body_mut() = Some(self.body.into_reqwest());
+        request
+    }
+}
+
+/// Raw HTTP response returned by a [`Transport`].
+#[derive(Debug)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: TransportBody,
+}
+
+impl TransportResponse {
+    /// Creates a response with the given status and body and no headers.
+    #[must_use]
+    pub fn new(status: StatusCode, body: impl Into<TransportBody>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from_reqwest(response: reqwest::Response) -> Self {
+        Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: TransportBody::Stream(response.bytes_stream().map_err(Error::from).boxed()),
+        }
+    }
+
+    /// Converts to a [`Response`] for the SDK's response handling; `url` is the request URL,
+    /// kept so API errors can report the endpoint.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn into_response(self, url: Url) -> Result<Response> {
+        let mut builder = http::Response::builder().status(self.status).url(url);
+        if let Some(headers) = builder.headers_mut() {
+            *headers = self.headers;
+        }
+        let response =
+            builder
+                .body(self.body.into_reqwest())
+                .map_err(|err| Error::InvalidConfig {
+                    message: format!("invalid transport response: {err}"),
+                })?;
+        Ok(reqwest::Response::from(response))
+    }
+
+    /// Converts to a [`Response`] for the SDK's response handling; `url` is the request URL,
+    /// kept so API errors can report the endpoint.
+    #[cfg(target_arch = "wasm32")]
+    #[allow(clippy::unnecessary_wraps)]
+    pub(crate) fn into_response(self, url: Url) -> Result<Response> {
+        Ok(Response::new(self, url))
+    }
+}
+
+/// Signs outgoing requests for gateways that require e.g. HMAC signatures.
+///
+/// Invoked for every attempt after auth headers are injected, with the method, URL and
+/// buffered body bytes (`None` for streaming bodies such as file uploads). The returned
+/// headers are added to the request, replacing same-named ones. This is independent of
+/// API key / OAuth authentication.
+pub trait RequestSigner: Send + Sync {
+    /// # Errors
+    /// Returning an error aborts the request with that error.
+    fn sign(
+        &self,
+        method: &reqwest::Method,
+        url: &reqwest::Url,
+        body: Option<&[u8]>,
+    ) -> Result<HeaderMap>;
+}
+
+/// Default transport backed by a [`reqwest::Client`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ReqwestTransport {
+    #[must_use]
+    pub const fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Transport for ReqwestTransport {
+    fn execute(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        Box::pin(async move {
+            let response = self.client.execute(request.into_reqwest()).await?;
+            Ok(TransportResponse::from_reqwest(response))
+        })
+    }
+}
+
+/// Wraps a user transport so it sees the client's default headers (API key, SDK headers),
+/// which reqwest would otherwise only add inside its own `execute`.
+pub(crate) struct DefaultHeadersTransport {
+    inner: Arc<dyn Transport>,
+    headers: HeaderMap,
+}
+
+impl DefaultHeadersTransport {
+    pub(crate) fn new(inner: Arc<dyn Transport>, headers: HeaderMap) -> Self {
+        Self { inner, headers }
+    }
+}
+
+impl Transport for DefaultHeadersTransport {
+    fn execute(&self, mut request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        for (name, value) in &self.headers {
+            if !request.headers.contains_key(name) {
+                request.headers.insert(name.clone(), value.clone());
+            }
+        }
+        self.inner.execute(request)
+    }
+}
+
+/// Token-bucket limiter (capacity 1): requests are released at most once every
+/// `60s / requests_per_minute`, so concurrent callers queue up instead of hitting 429s.
+///
+/// The next send time is only claimed once a request actually goes out: waiters hold the
+/// (FIFO) lock guard while sleeping, so a cancelled request releases its place in the queue
+/// without consuming a slot.
+pub(crate) struct RateLimitedTransport {
+    inner: Arc<dyn Transport>,
+    interval: Duration,
+    next_slot: tokio::sync::Mutex<Option<Instant>>,
+}
+
+impl RateLimitedTransport {
+    pub(crate) fn new(inner: Arc<dyn Transport>, requests_per_minute: u32) -> Self {
+        Self {
+            inner,
+            interval: Duration::from_secs(60) / requests_per_minute.max(1),
+            next_slot: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Waits for the next free slot and claims it.
+    async fn wait_for_slot(&self) {
+        let mut next_slot = self.next_slot.lock().await;
+        let slot = next_slot.map_or_else(Instant::now, |next| next.max(Instant::now()));
+        crate::rt::sleep_until(slot).await;
+        *next_slot = Some(slot + self.interval);
+    }
+}
+
+impl Transport for RateLimitedTransport {
+    fn execute(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        Box::pin(async move {
+            self.wait_for_slot().await;
+            self.inner.execute(request).await
+        })
+    }
+}
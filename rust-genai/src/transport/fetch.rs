@@ -0,0 +1,219 @@
+//! Browser transport backed by the Fetch API.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::StreamExt;
+use http::header::{HeaderName, HeaderValue};
+use http::{HeaderMap, StatusCode};
+use js_sys::{Promise, Reflect, Uint8Array};
+use tokio::sync::{mpsc, oneshot};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, ReadableStreamDefaultReader, RequestInit};
+
+use super::{Transport, TransportBody, TransportRequest, TransportResponse};
+use crate::error::{Error, Result};
+
+/// Body chunks buffered between the JS reader and the SDK before backpressure applies.
+const BODY_CHANNEL_CAPACITY: usize = 16;
+
+#[wasm_bindgen]
+extern "C" {
+    /// The global `fetch`, available in windows, workers and other JS runtimes alike.
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_request(request: &web_sys::Request) -> Promise;
+}
+
+/// Default transport under `wasm32` (`wasm` feature), sending requests with `fetch`.
+///
+/// Request bodies are buffered before sending; response bodies are streamed from the
+/// `ReadableStream`, so SSE endpoints work as they do natively. The timeout (per-request
+/// `HttpOptions` first, then [`FetchTransport::with_timeout`]) applies until the response
+/// headers arrive and aborts the fetch when it elapses.
+#[derive(Debug, Clone, Default)]
+pub struct FetchTransport {
+    timeout: Option<Duration>,
+}
+
+impl FetchTransport {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { timeout: None }
+    }
+
+    /// Sets the timeout for requests that do not carry their own.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl Transport for FetchTransport {
+    fn execute(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        let timeout = request.timeout.or(self.timeout);
+        Box::pin(async move {
+            let TransportRequest {
+                method,
+                url,
+                headers,
+                body,
+                ..
+            } = request;
+            let body = body.collect().await?;
+            // JS handles are not `Send`: drive the fetch on the event loop and hand back the
+            // (`Send`) response through a channel.
+            let (tx, rx) = oneshot::channel();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = fetch(method.as_str(), url.as_str(), &headers, &body, timeout).await;
+                let _ = tx.send(result);
+            });
+            rx.await.map_err(|_| Error::ChannelClosed)?
+        })
+    }
+}
+
+async fn fetch(
+    method: &str,
+    url: &str,
+    headers: &HeaderMap,
+    body: &Bytes,
+    timeout: Option<Duration>,
+) -> Result<TransportResponse> {
+    let js_headers = web_sys::Headers::new().map_err(|err| js_error("invalid headers", &err))?;
+    for (name, value) in headers {
+        let value = value.to_str().map_err(|_| Error::InvalidConfig {
+            message: format!("Header {name} is not valid for fetch"),
+        })?;
+        js_headers
+            .append(name.as_str(), value)
+            .map_err(|err| js_error("invalid headers", &err))?;
+    }
+    let abort = AbortController::new().map_err(|err| js_error("fetch failed", &err))?;
+    let init = RequestInit::new();
+    init.set_method(method);
+    init.set_headers(&js_headers);
+    init.set_signal(Some(&abort.signal()));
+    if !body.is_empty() {
+        init.set_body(&Uint8Array::from(body.as_ref()));
+    }
+    let request = web_sys::Request::new_with_str_and_init(url, &init)
+        .map_err(|err| js_error("invalid request", &err))?;
+
+    let pending = JsFuture::from(fetch_with_request(&request));
+    let response = match timeout {
+        Some(timeout) => {
+            if let Ok(response) = crate::rt::timeout(timeout, pending).await {
+                response
+            } else {
+                abort.abort();
+                return Err(Error::Timeout {
+                    message: format!("Request timed out after {}ms", timeout.as_millis()),
+                });
+            }
+        }
+        None => pending.await,
+    }
+    .map_err(|err| js_error("fetch failed", &err))?;
+    let response: web_sys::Response = response
+        .dyn_into()
+        .map_err(|err| js_error("fetch returned a non-Response value", &err))?;
+
+    let status = StatusCode::from_u16(response.status()).map_err(|err| Error::Parse {
+        message: format!("invalid fetch status: {err}"),
+    })?;
+    let headers = response_headers(&response.headers())?;
+    let body = match response.body() {
+        Some(stream) => {
+            let reader: ReadableStreamDefaultReader = stream.get_reader().unchecked_into();
+            TransportBody::Stream(body_stream(reader, abort))
+        }
+        None => TransportBody::default(),
+    };
+    Ok(TransportResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
+fn response_headers(js_headers: &web_sys::Headers) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    let entries = js_sys::try_iter(js_headers)
+        .map_err(|err| js_error("invalid response headers", &err))?
+        .into_iter()
+        .flatten();
+    for entry in entries {
+        let entry = entry.map_err(|err| js_error("invalid response headers", &err))?;
+        let pair: js_sys::Array = entry.unchecked_into();
+        let (Some(name), Some(value)) = (pair.get(0).as_string(), pair.get(1).as_string()) else {
+            continue;
+        };
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    Ok(headers)
+}
+
+/// Pumps the JS body reader into a channel; dropping the stream cancels the fetch.
+fn body_stream(
+    reader: ReadableStreamDefaultReader,
+    abort: AbortController,
+) -> futures_util::stream::BoxStream<'static, Result<Bytes>> {
+    let (tx, mut rx) = mpsc::channel(BODY_CHANNEL_CAPACITY);
+    wasm_bindgen_futures::spawn_local(async move {
+        loop {
+            let chunk = tokio::select! {
+                chunk = read_chunk(&reader) => chunk,
+                () = tx.closed() => {
+                    abort.abort();
+                    return;
+                }
+            };
+            let done = !matches!(chunk, Ok(Some(_)));
+            if let Some(chunk) = chunk.transpose() {
+                if tx.send(chunk).await.is_err() {
+                    abort.abort();
+                    return;
+                }
+            }
+            if done {
+                return;
+            }
+        }
+    });
+    futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx)).boxed()
+}
+
+/// Reads the next chunk; `Ok(None)` at end of body.
+async fn read_chunk(reader: &ReadableStreamDefaultReader) -> Result<Option<Bytes>> {
+    let result = JsFuture::from(reader.read())
+        .await
+        .map_err(|err| js_error("failed to read response body", &err))?;
+    let done = Reflect::get(&result, &JsValue::from_str("done"))
+        .map_err(|err| js_error("failed to read response body", &err))?;
+    if done.as_bool().unwrap_or(true) {
+        return Ok(None);
+    }
+    let value = Reflect::get(&result, &JsValue::from_str("value"))
+        .map_err(|err| js_error("failed to read response body", &err))?;
+    Ok(Some(Uint8Array::new(&value).to_vec().into()))
+}
+
+fn js_error(context: &str, value: &JsValue) -> Error {
+    let detail = value
+        .dyn_ref::<js_sys::Error>()
+        .map(|err| String::from(err.message()))
+        .or_else(|| value.as_string())
+        .unwrap_or_else(|| format!("{value:?}"));
+    Error::Io {
+        source: std::io::Error::other(format!("{context}: {detail}")),
+    }
+}
@@ -0,0 +1,121 @@
+//! Crate-owned HTTP response used by the SDK's response handling under `wasm32`.
+
+use std::fmt;
+
+use bytes::Bytes;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use http::{Extensions, HeaderMap, StatusCode};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+
+use super::{TransportBody, TransportResponse};
+use crate::error::Result;
+
+/// HTTP response returned by [`Client::send`](crate::Client::send) under `wasm32`.
+///
+/// Mirrors the subset of `reqwest::Response` the SDK relies on, built from the
+/// [`TransportResponse`] of the configured [`Transport`](super::Transport).
+pub struct Response {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: TransportBody,
+    url: Url,
+    extensions: Extensions,
+}
+
+impl Response {
+    pub(crate) fn new(response: TransportResponse, url: Url) -> Self {
+        Self {
+            status: response.status,
+            headers: response.headers,
+            body: response.body,
+            url,
+            extensions: Extensions::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    #[must_use]
+    pub const fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// The request URL.
+    #[must_use]
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    #[must_use]
+    pub const fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
+    }
+
+    /// `Content-Length` header value, if present.
+    #[must_use]
+    pub fn content_length(&self) -> Option<u64> {
+        self.headers
+            .get(http::header::CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// Reads the whole body.
+    ///
+    /// # Errors
+    /// 当读取响应体失败时返回错误。
+    pub async fn bytes(self) -> Result<Bytes> {
+        self.body.collect().await
+    }
+
+    /// Reads the whole body as UTF-8 text, replacing invalid sequences.
+    ///
+    /// # Errors
+    /// 当读取响应体失败时返回错误。
+    pub async fn text(self) -> Result<String> {
+        let bytes = self.bytes().await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Reads the whole body and deserializes it as JSON.
+    ///
+    /// # Errors
+    /// 当读取响应体或 JSON 解析失败时返回错误。
+    pub async fn json<T: DeserializeOwned>(self) -> Result<T> {
+        let bytes = self.bytes().await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Streams the body chunk by chunk.
+    pub fn bytes_stream(self) -> BoxStream<'static, Result<Bytes>> {
+        match self.body {
+            TransportBody::Bytes(bytes) if bytes.is_empty() => stream::empty().boxed(),
+            TransportBody::Bytes(bytes) => stream::once(async move { Ok(bytes) }).boxed(),
+            TransportBody::Stream(stream) => stream,
+        }
+    }
+}
+
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Response")
+            .field("url", &self.url.as_str())
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,20 +1,28 @@
 //! Tunings API surface.
 
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
+use futures_util::{Stream, TryStreamExt};
 use reqwest::header::{HeaderName, HeaderValue};
 use rust_genai_types::enums::{JobState, TuningMethod};
 use rust_genai_types::tunings::{
-    CancelTuningJobConfig, CancelTuningJobResponse, CreateTuningJobConfig, GetTuningJobConfig,
-    ListTuningJobsConfig, ListTuningJobsResponse, PreTunedModel, TunedModel, TuningDataset,
-    TuningJob,
+    CancelTuningJobConfig, CancelTuningJobResponse, CreateTuningJobConfig, DeleteTunedModelConfig,
+    DeleteTunedModelResponse, GetTuningJobConfig, ListTuningJobsConfig, ListTuningJobsResponse,
+    PreTunedModel, TunedModel, TuningDataset, TuningExample, TuningJob,
 };
 use serde_json::{json, Map, Value};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::AsyncBufReadExt;
 
-use crate::client::{Backend, ClientInner};
+use crate::client::{apply_request_id, Backend, ClientInner};
 use crate::error::{Error, Result};
 use crate::http_response::sdk_http_response_from_headers;
+use crate::pagination::paginate;
+use crate::poll::{job_failed_error, PollConfig};
 
 #[derive(Clone)]
 pub struct Tunings {
@@ -73,6 +81,7 @@ impl Tunings {
 
         let url = build_tuning_jobs_url(&self.inner, http_options.as_ref())?;
         let mut request = self.inner.http.post(url).json(&body);
+        request = apply_request_id(request, http_options.as_ref());
         request = apply_http_options(request, http_options.as_ref())?;
 
         let response = self
@@ -80,7 +89,7 @@ impl Tunings {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let value = response.json::<Value>().await?;
@@ -119,7 +128,7 @@ impl Tunings {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let value = response.json::<Value>().await?;
@@ -156,7 +165,7 @@ impl Tunings {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
 
         let headers = response.headers().clone();
@@ -181,27 +190,31 @@ impl Tunings {
     ///
     /// # Errors
     /// 当请求失败或响应解析失败时返回错误。
-    pub async fn all_with_config(
+    pub async fn all_with_config(&self, config: ListTuningJobsConfig) -> Result<Vec<TuningJob>> {
+        self.list_stream(config).try_collect().await
+    }
+
+    /// 以流的形式逐个产出调优任务（自动翻页）。
+    ///
+    /// 任一页请求失败时产出该错误并结束流。
+    pub fn list_stream(
         &self,
-        mut config: ListTuningJobsConfig,
-    ) -> Result<Vec<TuningJob>> {
-        let mut jobs = Vec::new();
-        let http_options = config.http_options.clone();
-        loop {
+        config: ListTuningJobsConfig,
+    ) -> impl Stream<Item = Result<TuningJob>> + Send + '_ {
+        paginate(move |page_token| {
             let mut page_config = config.clone();
-            page_config.http_options.clone_from(&http_options);
-            let response = self.list_with_config(page_config).await?;
-            if let Some(items) = response.tuning_jobs {
-                jobs.extend(items);
+            if page_token.is_some() {
+                page_config.page_token = page_token;
             }
-            match response.next_page_token {
-                Some(token) if !token.is_empty() => {
-                    config.page_token = Some(token);
-                }
-                _ => break,
+            async move {
+                self.list_with_config(page_config).await.map(|response| {
+                    (
+                        response.tuning_jobs.unwrap_or_default(),
+                        response.next_page_token,
+                    )
+                })
             }
-        }
-        Ok(jobs)
+        })
     }
 
     /// 取消调优任务。
@@ -233,7 +246,7 @@ impl Tunings {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         let headers = response.headers().clone();
         let text = response.text().await.unwrap_or_default();
@@ -245,6 +258,206 @@ impl Tunings {
         result.sdk_http_response = Some(sdk_http_response_from_headers(&headers));
         Ok(result)
     }
+
+    /// 轮询直到调优任务进入终止状态。
+    ///
+    /// # Errors
+    /// 当请求失败、任务失败/取消/过期或超时返回错误。
+    pub async fn wait(
+        &self,
+        name: impl AsRef<str>,
+        config: impl Into<PollConfig>,
+    ) -> Result<TuningJob> {
+        let config = config.into();
+        let mut poller = config.start();
+        loop {
+            let job = self.get(name.as_ref()).await?;
+            match job.state {
+                Some(JobState::JobStateSucceeded | JobState::JobStatePartiallySucceeded) => {
+                    return Ok(job)
+                }
+                Some(
+                    state @ (JobState::JobStateFailed
+                    | JobState::JobStateCancelled
+                    | JobState::JobStateExpired),
+                ) => return Err(tuning_job_error(state, &job)),
+                _ => {}
+            }
+
+            poller
+                .wait("Timed out waiting for tuning job to finish")
+                .await?;
+        }
+    }
+
+    /// 解析调优任务产出的模型名称，可直接用于 `generate_content`。
+    ///
+    /// Gemini API 返回 `tunedModels/...`；Vertex AI 返回部署后的
+    /// `projects/.../endpoints/...`。
+    ///
+    /// # Errors
+    /// 当任务尚未成功（`SUCCEEDED` / `PARTIALLY_SUCCEEDED`）或未产出可用的调优模型时返回错误。
+    pub fn generation_model_name(&self, job: &TuningJob) -> Result<String> {
+        if !matches!(
+            job.state,
+            Some(JobState::JobStateSucceeded | JobState::JobStatePartiallySucceeded)
+        ) {
+            return Err(Error::InvalidConfig {
+                message: format!("Tuning job has not succeeded yet (state: {:?})", job.state),
+            });
+        }
+        let tuned_model = job.tuned_model.as_ref();
+        let name = match self.inner.config.backend {
+            Backend::GeminiApi => tuned_model
+                .and_then(|model| model.model.clone())
+                .or_else(|| job.name.clone()),
+            Backend::VertexAi => tuned_model.and_then(|model| model.endpoint.clone()),
+        };
+        name.filter(|name| !name.is_empty())
+            .ok_or_else(|| Error::InvalidConfig {
+                message: "Tuning job has no tuned model endpoint yet".into(),
+            })
+    }
+
+    /// 删除调优后的模型。
+    ///
+    /// # Errors
+    /// 当请求失败或响应解析失败时返回错误。
+    pub async fn delete(&self, name: impl AsRef<str>) -> Result<DeleteTunedModelResponse> {
+        self.delete_with_config(name, DeleteTunedModelConfig::default())
+            .await
+    }
+
+    /// 删除调优后的模型（带配置）。
+    ///
+    /// # Errors
+    /// 当请求失败或响应解析失败时返回错误。
+    pub async fn delete_with_config(
+        &self,
+        name: impl AsRef<str>,
+        mut config: DeleteTunedModelConfig,
+    ) -> Result<DeleteTunedModelResponse> {
+        let http_options = config.http_options.take();
+        let name = normalize_tuning_job_name(&self.inner, name.as_ref())?;
+        let url = build_tuning_job_url(&self.inner, &name, http_options.as_ref());
+        let mut request = self.inner.http.delete(url);
+        request = apply_http_options(request, http_options.as_ref())?;
+
+        let response = self
+            .inner
+            .send_with_http_options(request, http_options.as_ref())
+            .await?;
+        if !response.status().is_success() {
+            return Err(Error::api_error_with_endpoint(response).await);
+        }
+        let headers = response.headers().clone();
+        if response.content_length().unwrap_or(0) == 0 {
+            return Ok(DeleteTunedModelResponse {
+                sdk_http_response: Some(sdk_http_response_from_headers(&headers)),
+            });
+        }
+        let mut result = response
+            .json::<DeleteTunedModelResponse>()
+            .await
+            .unwrap_or_default();
+        result.sdk_http_response = Some(sdk_http_response_from_headers(&headers));
+        Ok(result)
+    }
+}
+
+/// Gemini API 单次调优请求允许内联的示例数量上限（客户端校验）。
+pub const MAX_INLINE_TUNING_EXAMPLES: usize = 10_000;
+/// 单个调优示例输入的最大字符数。
+pub const MAX_TUNING_INPUT_CHARS: usize = 40_000;
+/// 单个调优示例输出的最大字符数。
+pub const MAX_TUNING_OUTPUT_CHARS: usize = 5_000;
+
+/// 校验 Gemini API 内联调优示例的数量与单条长度。
+///
+/// # Errors
+/// 当示例为空、超过 [`MAX_INLINE_TUNING_EXAMPLES`] 或单条超出字符上限时返回错误。
+pub fn validate_tuning_examples(examples: &[TuningExample]) -> Result<()> {
+    if examples.is_empty() {
+        return Err(Error::InvalidConfig {
+            message: "Tuning dataset contains no examples".into(),
+        });
+    }
+    if examples.len() > MAX_INLINE_TUNING_EXAMPLES {
+        return Err(Error::InvalidConfig {
+            message: format!(
+                "Tuning dataset has {} inline examples, exceeding the limit of {MAX_INLINE_TUNING_EXAMPLES}",
+                examples.len()
+            ),
+        });
+    }
+    for (index, example) in examples.iter().enumerate() {
+        let input_chars = example
+            .text_input
+            .as_deref()
+            .map_or(0, |s| s.chars().count());
+        if input_chars > MAX_TUNING_INPUT_CHARS {
+            return Err(Error::InvalidConfig {
+                message: format!(
+                    "Tuning example {index} text_input has {input_chars} characters, exceeding the limit of {MAX_TUNING_INPUT_CHARS}"
+                ),
+            });
+        }
+        let output_chars = example.output.as_deref().map_or(0, |s| s.chars().count());
+        if output_chars > MAX_TUNING_OUTPUT_CHARS {
+            return Err(Error::InvalidConfig {
+                message: format!(
+                    "Tuning example {index} output has {output_chars} characters, exceeding the limit of {MAX_TUNING_OUTPUT_CHARS}"
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 从 JSONL 文件加载 Gemini API 调优数据集（每行一个 `{"text_input": ..., "output": ...}`）。
+///
+/// Gemini API 不支持按文件引用调优数据，示例会以内联方式发送；Vertex AI 请将文件上传到
+/// GCS 后使用 [`TuningDataset::gcs_uri`]。
+///
+/// # Errors
+/// 当文件读取失败、某行不是合法示例或示例超出限制时返回错误（包含行号）。
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn load_tuning_dataset(path: impl AsRef<Path>) -> Result<TuningDataset> {
+    let path = path.as_ref();
+    let file = tokio::fs::File::open(path).await?;
+    let mut lines = tokio::io::BufReader::new(file).lines();
+    let mut line_number = 0usize;
+    let mut examples = Vec::new();
+    while let Some(line) = lines.next_line().await? {
+        line_number += 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let example: TuningExample =
+            serde_json::from_str(line).map_err(|err| Error::InvalidConfig {
+                message: format!("{} line {line_number}: {err}", path.display()),
+            })?;
+        examples.push(example);
+    }
+    validate_tuning_examples(&examples)?;
+    Ok(TuningDataset {
+        examples: Some(examples),
+        ..Default::default()
+    })
+}
+
+fn tuning_job_error(state: JobState, job: &TuningJob) -> Error {
+    let error = job.error.as_ref();
+    job_failed_error(
+        "Tuning job",
+        state,
+        error.and_then(|error| error.code),
+        error.and_then(|error| error.message.as_deref()),
+        error
+            .and_then(|error| error.details.clone())
+            .unwrap_or_default(),
+    )
 }
 
 fn validate_mldev_config(config: &CreateTuningJobConfig) -> Result<()> {
@@ -355,6 +568,7 @@ fn build_tune_body_mldev(
     }
 
     if let Some(examples) = training_dataset.examples {
+        validate_tuning_examples(&examples)?;
         let examples_value = serde_json::to_value(examples)?;
         body.insert(
             "examples".to_string(),
@@ -704,9 +918,28 @@ fn parse_tuning_job_from_mldev(value: &Value) -> Result<TuningJob> {
         service_account: None,
         tuned_model_display_name: None,
         veo_tuning_spec: None,
+        extra: unknown_fields(obj, MLDEV_TUNING_JOB_FIELDS),
     })
 }
 
+/// 已映射到 `TuningJob` 字段的 Gemini API `tunedModels` 字段。
+const MLDEV_TUNING_JOB_FIELDS: &[&str] = &[
+    "name",
+    "state",
+    "createTime",
+    "updateTime",
+    "description",
+    "baseModel",
+    "tuningTask",
+];
+
+fn unknown_fields(obj: &Map<String, Value>, known: &[&str]) -> HashMap<String, Value> {
+    obj.iter()
+        .filter(|(key, _)| !known.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
 fn parse_list_tuning_jobs_from_mldev(value: &Value) -> Result<ListTuningJobsResponse> {
     let obj = value.as_object().ok_or_else(|| Error::Parse {
         message: "ListTuningJobs response must be object".into(),
@@ -729,6 +962,7 @@ fn parse_list_tuning_jobs_from_mldev(value: &Value) -> Result<ListTuningJobsResp
         sdk_http_response: None,
         tuning_jobs,
         next_page_token,
+        extra: unknown_fields(obj, &["tunedModels", "nextPageToken"]),
     })
 }
 
@@ -893,6 +1127,9 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
@@ -1154,11 +1391,17 @@ mod tests {
             "createTime": "t1",
             "updateTime": "t2",
             "baseModel": "models/base",
-            "tuningTask": {"startTime": "s1", "completeTime": "s2"}
+            "tuningTask": {"startTime": "s1", "completeTime": "s2"},
+            "tunedModelSource": {"tunedModel": "tunedModels/0"}
         }))
         .unwrap();
         assert_eq!(job.state, Some(JobState::JobStateSucceeded));
         assert!(job.tuned_model.is_some());
+        assert_eq!(
+            job.extra.get("tunedModelSource"),
+            Some(&json!({"tunedModel": "tunedModels/0"}))
+        );
+        assert!(!job.extra.contains_key("tuningTask"));
 
         let list = parse_list_tuning_jobs_from_mldev(&json!({
             "tunedModels": [ { "name": "tunedModels/1" } ],
@@ -1435,6 +1678,56 @@ mod tests {
         assert!(matches!(err, Error::InvalidConfig { .. }));
     }
 
+    #[test]
+    fn test_build_tune_body_mldev_rejects_too_many_inline_examples() {
+        let inner = test_client_inner(Backend::GeminiApi);
+        let example = TuningExample {
+            text_input: Some("hi".to_string()),
+            output: Some("ok".to_string()),
+        };
+        let dataset = TuningDataset {
+            examples: Some(vec![example; MAX_INLINE_TUNING_EXAMPLES + 1]),
+            ..Default::default()
+        };
+        let err = build_tune_body_mldev(
+            &inner,
+            "gemini-1.5-pro",
+            dataset,
+            &CreateTuningJobConfig::default(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidConfig { message } if message.contains(&MAX_INLINE_TUNING_EXAMPLES.to_string())),
+            "{err}"
+        );
+
+        let long_output = TuningExample {
+            text_input: Some("hi".to_string()),
+            output: Some("x".repeat(MAX_TUNING_OUTPUT_CHARS + 1)),
+        };
+        let err = validate_tuning_examples(&[long_output]).unwrap_err();
+        assert!(err.to_string().contains("example 0 output"));
+    }
+
+    #[tokio::test]
+    async fn test_load_tuning_dataset_from_jsonl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("train.jsonl");
+        std::fs::write(
+            &path,
+            "{\"text_input\": \"1\", \"output\": \"2\"}\n\n{\"textInput\": \"3\", \"output\": \"4\"}\n",
+        )
+        .unwrap();
+        let dataset = load_tuning_dataset(&path).await.unwrap();
+        let examples = dataset.examples.unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].text_input.as_deref(), Some("3"));
+
+        std::fs::write(&path, "{\"text_input\": \"1\"}\nnot json\n").unwrap();
+        let err = load_tuning_dataset(&path).await.unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
     #[test]
     fn test_build_tune_body_vertex_rejects_fields() {
         let inner = test_client_inner(Backend::VertexAi);
@@ -1512,4 +1805,67 @@ mod tests {
         let built = request.build().unwrap();
         assert!(built.headers().contains_key("x-ok"));
     }
+
+    #[test]
+    fn test_tuning_job_error_surfaces_job_error() {
+        let job = TuningJob {
+            error: Some(rust_genai_types::tunings::GoogleRpcStatus {
+                code: Some(400),
+                details: None,
+                message: Some("bad dataset".to_string()),
+            }),
+            ..Default::default()
+        };
+        let err = tuning_job_error(JobState::JobStateFailed, &job);
+        assert!(matches!(
+            err,
+            Error::OperationFailed { ref status } if status.code == 400 && status.message.contains("bad dataset")
+        ));
+
+        let err = tuning_job_error(JobState::JobStateCancelled, &TuningJob::default());
+        assert!(matches!(err, Error::OperationFailed { ref status } if status.code == 1));
+    }
+
+    #[test]
+    fn test_generation_model_name_for_both_backends() {
+        let vertex = Tunings::new(Arc::new(test_client_inner(Backend::VertexAi)));
+        let job: TuningJob = serde_json::from_value(json!({
+            "name": "projects/proj/locations/loc/tuningJobs/1",
+            "state": "JOB_STATE_SUCCEEDED",
+            "tunedModel": {
+                "model": "projects/proj/locations/loc/models/456",
+                "endpoint": "projects/proj/locations/loc/endpoints/123"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            vertex.generation_model_name(&job).unwrap(),
+            "projects/proj/locations/loc/endpoints/123"
+        );
+        let err = vertex
+            .generation_model_name(&TuningJob::default())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig { .. }));
+
+        let gemini = Tunings::new(Arc::new(test_client_inner(Backend::GeminiApi)));
+        let job = parse_tuning_job_from_mldev(&json!({
+            "name": "tunedModels/demo",
+            "state": "ACTIVE"
+        }))
+        .unwrap();
+        assert_eq!(
+            gemini.generation_model_name(&job).unwrap(),
+            "tunedModels/demo"
+        );
+
+        for state in ["CREATING", "FAILED"] {
+            let job = parse_tuning_job_from_mldev(&json!({
+                "name": "tunedModels/demo",
+                "state": state
+            }))
+            .unwrap();
+            let err = gemini.generation_model_name(&job).unwrap_err();
+            assert!(matches!(err, Error::InvalidConfig { .. }));
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::error::{Error, Result};
 use std::future::Future;
+#[cfg(not(target_arch = "wasm32"))]
 use tokio::io::AsyncReadExt;
 
 pub const CHUNK_SIZE: usize = 8 * 1024 * 1024;
@@ -51,6 +52,7 @@ where
     })
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub async fn upload_reader_with<P, F, Fut, H>(
     reader: &mut tokio::fs::File,
     total_size: u64,
@@ -0,0 +1,187 @@
+//! Tiny in-memory vector index for embeddings returned by `embed_content`.
+
+use std::cmp::Ordering;
+
+use rust_genai_types::models::{ContentEmbedding, EmbedContentResponse};
+
+use crate::error::{Error, Result};
+
+/// 余弦相似度；任一向量为零向量或维度不一致时返回 0。
+#[must_use]
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// 按余弦相似度检索 top-k 的内存向量索引（线性扫描）。
+#[derive(Debug, Clone, Default)]
+pub struct VectorIndex {
+    entries: Vec<(String, Vec<f32>)>,
+}
+
+impl VectorIndex {
+    /// 创建空索引。
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 条目数量。
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否为空。
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 向量维度（首个条目决定；空索引为 `None`）。
+    #[must_use]
+    pub fn dimension(&self) -> Option<usize> {
+        self.entries.first().map(|(_, vector)| vector.len())
+    }
+
+    /// 插入一个向量。
+    ///
+    /// # Errors
+    /// 当向量为空或维度与已有条目不一致时返回错误。
+    pub fn insert(&mut self, id: impl Into<String>, vector: Vec<f32>) -> Result<()> {
+        if vector.is_empty() {
+            return Err(Error::InvalidConfig {
+                message: "Vector must not be empty".into(),
+            });
+        }
+        if let Some(dimension) = self.dimension() {
+            if vector.len() != dimension {
+                return Err(Error::InvalidConfig {
+                    message: format!(
+                        "Vector dimension {} does not match index dimension {dimension}",
+                        vector.len()
+                    ),
+                });
+            }
+        }
+        self.entries.push((id.into(), vector));
+        Ok(())
+    }
+
+    /// 插入一个 `ContentEmbedding`。
+    ///
+    /// # Errors
+    /// 当嵌入缺少 `values` 或维度不一致时返回错误。
+    pub fn insert_embedding(
+        &mut self,
+        id: impl Into<String>,
+        embedding: &ContentEmbedding,
+    ) -> Result<()> {
+        let values = embedding
+            .values
+            .clone()
+            .ok_or_else(|| Error::InvalidConfig {
+                message: "Embedding has no values".into(),
+            })?;
+        self.insert(id, values)
+    }
+
+    /// 按顺序将 `embed_content` 响应中的嵌入与 `ids` 配对插入。
+    ///
+    /// # Errors
+    /// 当 `ids` 与嵌入数量不一致、嵌入缺少 `values` 或维度不一致时返回错误。
+    pub fn extend_from_response<I, S>(
+        &mut self,
+        ids: I,
+        response: &EmbedContentResponse,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let embeddings = response.embeddings.as_deref().unwrap_or_default();
+        let ids: Vec<String> = ids.into_iter().map(Into::into).collect();
+        if ids.len() != embeddings.len() {
+            return Err(Error::InvalidConfig {
+                message: format!("Got {} ids for {} embeddings", ids.len(), embeddings.len()),
+            });
+        }
+        for (id, embedding) in ids.into_iter().zip(embeddings) {
+            self.insert_embedding(id, embedding)?;
+        }
+        Ok(())
+    }
+
+    /// 返回与 `query` 余弦相似度最高的 `k` 个条目，按分数降序排列。
+    #[must_use]
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<(&str, f32)> {
+        let mut scored: Vec<(&str, f32)> = self
+            .entries
+            .iter()
+            .map(|(id, vector)| (id.as_str(), cosine_similarity(query, vector)))
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        scored.truncate(k);
+        scored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn search_returns_nearest_neighbors() {
+        let mut index = VectorIndex::new();
+        index.insert("east", vec![1.0, 0.0]).unwrap();
+        index.insert("north", vec![0.0, 1.0]).unwrap();
+        index.insert("north-east", vec![1.0, 1.0]).unwrap();
+        index.insert("west", vec![-1.0, 0.0]).unwrap();
+
+        let hits = index.search(&[0.9, 0.1], 2);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].0, "east");
+        assert_eq!(hits[1].0, "north-east");
+        assert!(hits[0].1 > hits[1].1);
+
+        assert_eq!(index.search(&[0.0, 1.0], 10).len(), 4);
+        assert!(index.search(&[1.0, 0.0], 0).is_empty());
+        assert!(index.insert("bad", vec![1.0, 2.0, 3.0]).is_err());
+        assert!(index.insert("empty", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn cosine_similarity_edge_cases() {
+        assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]) - 1.0).abs() < 1e-6);
+        assert!((cosine_similarity(&[1.0, 0.0], &[-1.0, 0.0]) + 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]).abs() < f32::EPSILON);
+        assert!(cosine_similarity(&[1.0], &[1.0, 0.0]).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn extend_from_embed_content_response() {
+        let response: EmbedContentResponse = serde_json::from_value(json!({
+            "embeddings": [{"values": [1.0, 0.0]}, {"values": [0.0, 1.0]}]
+        }))
+        .unwrap();
+        let mut index = VectorIndex::new();
+        index
+            .extend_from_response(["cat", "dog"], &response)
+            .unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.search(&[0.1, 1.0], 1)[0].0, "dog");
+        assert!(index.extend_from_response(["only-one"], &response).is_err());
+    }
+}
@@ -44,7 +44,7 @@ impl Webhooks {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_json_or_default::<Webhook>(response).await
     }
@@ -74,7 +74,7 @@ impl Webhooks {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_json_or_default::<Webhook>(response).await
     }
@@ -109,7 +109,7 @@ impl Webhooks {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_json_or_default::<WebhookListResponse>(response).await
     }
@@ -144,7 +144,7 @@ impl Webhooks {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_json_or_default::<WebhookDeleteResponse>(response).await
     }
@@ -178,7 +178,7 @@ impl Webhooks {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_json_or_default::<Webhook>(response).await
     }
@@ -213,7 +213,7 @@ impl Webhooks {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_json_or_default::<WebhookPingResponse>(response).await
     }
@@ -251,7 +251,7 @@ impl Webhooks {
             .send_with_http_options(request, http_options.as_ref())
             .await?;
         if !response.status().is_success() {
-            return Err(Error::api_error_from_response(response, None).await);
+            return Err(Error::api_error_with_endpoint(response).await);
         }
         parse_json_or_default::<WebhookRotateSigningSecretResponse>(response).await
     }
@@ -407,11 +407,14 @@ fn apply_http_options(
                 request = request.header(name, value);
             }
         }
+        if let Some(extra_query) = &options.extra_query {
+            request = request.query(extra_query);
+        }
     }
     Ok(request)
 }
 
-async fn parse_json_or_default<T>(response: reqwest::Response) -> Result<T>
+async fn parse_json_or_default<T>(response: crate::transport::Response) -> Result<T>
 where
     T: serde::de::DeserializeOwned + Default,
 {
@@ -426,11 +429,13 @@ where
 mod tests {
     use super::*;
 
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     use reqwest::header::HeaderMap;
 
     use crate::client::{ApiClient, ClientConfig};
+    use crate::transport::ReqwestTransport;
     use crate::{ClientBuilder, Credentials, HttpOptions, VertexConfig};
 
     fn test_inner(backend: Backend) -> ClientInner {
@@ -443,16 +448,28 @@ mod tests {
                     project: "proj".to_string(),
                     location: "loc".to_string(),
                     credentials: None,
+                    api_host: None,
                 }),
                 http_options: HttpOptions::default(),
                 credentials: Credentials::ApiKey("test-key".to_string()),
                 auth_scopes: vec![],
+                default_labels: HashMap::new(),
             },
             api_client: ApiClient {
                 base_url: "https://example.com/".to_string(),
+                upload_base_url: "https://example.com/upload/".to_string(),
                 api_version: "v1beta".to_string(),
             },
             auth_provider: None,
+            transport: Arc::new(ReqwestTransport::new(reqwest::Client::new())),
+            token_count_cache: None,
+            request_signer: None,
+            response_parse_hook: None,
+            raw_body_in_parse_errors: false,
+            app_name: None,
+            validate_media_inputs: false,
+            validate_input_length: false,
+            validate_model_capabilities: false,
         }
     }
 
@@ -1,11 +1,17 @@
 use serde_json::json;
-use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::matchers::{
+    body_partial_json, header, method, path, query_param, query_param_is_missing,
+};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+use rust_genai::client::REQUEST_ID_HEADER;
+use rust_genai::poll::PollConfig;
 use rust_genai::types::batches::{
     BatchJobSource, CreateBatchJobConfig, InlinedRequest, ListBatchJobsConfig,
 };
 use rust_genai::types::content::Content;
+use rust_genai::types::enums::JobState;
+use rust_genai::types::http::{HttpOptions, HttpRetryOptions};
 
 mod support;
 use support::build_gemini_client_with_version;
@@ -180,3 +186,226 @@ async fn batches_api_flow() {
         .unwrap();
     assert_eq!(all.len(), 2);
 }
+
+#[tokio::test]
+async fn batches_wait_until_terminal() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1beta/batches/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/1",
+            "metadata": {"state": "JOB_STATE_RUNNING"}
+        })))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/batches/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/1",
+            "metadata": {"state": "JOB_STATE_SUCCEEDED"}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/batches/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/2",
+            "metadata": {"state": "JOB_STATE_PENDING"}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/batches/3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/3",
+            "metadata": {"state": "JOB_STATE_CANCELLED"}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let batches = client.batches();
+    let config = PollConfig::fixed(std::time::Duration::from_millis(5));
+
+    let job = batches.wait("1", config.clone()).await.unwrap();
+    assert_eq!(job.state, Some(JobState::JobStateSucceeded));
+
+    let err = batches
+        .wait(
+            "2",
+            config
+                .clone()
+                .with_timeout(std::time::Duration::from_millis(20)),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, rust_genai::Error::Timeout { .. }));
+
+    let err = batches.wait("3", config).await.unwrap_err();
+    assert!(matches!(
+        err,
+        rust_genai::Error::OperationFailed { ref status }
+            if status.code == 1 && status.message.contains("JobStateCancelled")
+    ));
+}
+
+#[tokio::test]
+async fn batches_create_request_id_stable_across_retry() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:batchGenerateContent"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:batchGenerateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "batches/1",
+            "metadata": {"state": "JOB_STATE_PENDING"}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let src = BatchJobSource {
+        inlined_requests: Some(vec![InlinedRequest {
+            contents: Some(vec![Content::text("hi")]),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+    let retry_options = HttpRetryOptions {
+        attempts: Some(2),
+        initial_delay: Some(0.0),
+        jitter: Some(0.0),
+        ..Default::default()
+    };
+
+    client
+        .batches()
+        .create(
+            "gemini-1.5-pro",
+            src.clone(),
+            CreateBatchJobConfig {
+                http_options: Some(HttpOptions {
+                    retry_options: Some(retry_options.clone()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    assert_eq!(received.len(), 2);
+    let ids: Vec<_> = received
+        .iter()
+        .map(|request| request.headers.get(REQUEST_ID_HEADER).cloned().unwrap())
+        .collect();
+    assert_eq!(ids[0], ids[1]);
+    assert_eq!(ids[0].to_str().unwrap().len(), 36);
+
+    server.reset().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:batchGenerateContent"))
+        .and(header(REQUEST_ID_HEADER, "my-request-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"name": "batches/2"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    client
+        .batches()
+        .create(
+            "gemini-1.5-pro",
+            src,
+            CreateBatchJobConfig {
+                http_options: Some(HttpOptions {
+                    request_id: Some("my-request-1".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn batches_create_from_file_uploads_jsonl() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/upload/v1beta/files"))
+        .and(header("x-goog-upload-command", "start"))
+        .and(header(
+            "x-goog-upload-header-content-type",
+            "application/jsonl",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-goog-upload-url", format!("{}/session", server.uri())),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/session"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-goog-upload-status", "final")
+                .set_body_json(json!({"file": {"name": "files/requests"}})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:batchGenerateContent"))
+        .and(body_partial_json(json!({
+            "batch": {"inputConfig": {"fileName": "files/requests"}}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"name": "batches/1"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let jsonl_path = dir.path().join("requests.jsonl");
+    tokio::fs::write(
+        &jsonl_path,
+        concat!(
+            r#"{"key": "a", "request": {"contents": [{"parts": [{"text": "hi"}]}]}}"#,
+            "\n\n",
+            r#"{"key": "b", "request": {"contents": [{"parts": [{"text": "yo"}]}]}}"#,
+            "\n",
+        ),
+    )
+    .await
+    .unwrap();
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let job = client
+        .batches()
+        .create_from_file(
+            "gemini-1.5-pro",
+            &jsonl_path,
+            CreateBatchJobConfig::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(job.name.as_deref(), Some("batches/1"));
+
+    let bad_path = dir.path().join("bad.jsonl");
+    tokio::fs::write(&bad_path, "{\"key\": \"a\"}\n")
+        .await
+        .unwrap();
+    let err = client
+        .batches()
+        .create_from_file("gemini-1.5-pro", &bad_path, CreateBatchJobConfig::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, rust_genai::Error::InvalidConfig { .. }));
+}
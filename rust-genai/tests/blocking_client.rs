@@ -0,0 +1,79 @@
+#![cfg(feature = "blocking")]
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use rust_genai::blocking::Client;
+use rust_genai::types::content::Content;
+
+// The mock server needs its own runtime; the blocking client never touches it.
+fn start_server() -> (tokio::runtime::Runtime, MockServer) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1beta/models/gemini-2.5-flash:generateContent"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "candidates": [{"content": {"role": "model", "parts": [{"text": "sync hi"}]}}]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1beta/models/gemini-2.5-flash:streamGenerateContent"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(concat!(
+                        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"a\"}]}}]}\n\n",
+                        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"b\"}]}}]}\n\n",
+                    )),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1beta/files/abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "name": "files/abc",
+                "mimeType": "text/plain"
+            })))
+            .mount(&server)
+            .await;
+        server
+    });
+    (runtime, server)
+}
+
+#[test]
+fn blocking_client_calls_unary_and_streaming_methods() {
+    let (_runtime, server) = start_server();
+    let client = Client::from_async(
+        rust_genai::Client::builder()
+            .api_key("test-key")
+            .base_url(server.uri())
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let response = client
+        .models()
+        .generate_content("gemini-2.5-flash", vec![Content::text("hi")])
+        .unwrap();
+    assert_eq!(response.text(), Some("sync hi".to_string()));
+
+    let texts: Vec<String> = client
+        .models()
+        .generate_content_stream(
+            "gemini-2.5-flash",
+            vec![Content::text("hi")],
+            Default::default(),
+        )
+        .unwrap()
+        .map(|item| item.unwrap().text().unwrap_or_default())
+        .collect();
+    assert_eq!(texts, vec!["a".to_string(), "b".to_string()]);
+
+    let file = client.files().get("files/abc").unwrap();
+    assert_eq!(file.name.as_deref(), Some("files/abc"));
+}
@@ -1,10 +1,13 @@
 use serde_json::json;
-use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::matchers::{body_partial_json, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+use std::time::Duration;
+
 use rust_genai::types::caches::{
     CreateCachedContentConfig, ListCachedContentsConfig, UpdateCachedContentConfig,
 };
+use rust_genai::types::content::Content;
 
 mod support;
 use support::build_gemini_client_with_version;
@@ -166,3 +169,63 @@ async fn caches_error_responses() {
     let err = caches.delete("bad").await.unwrap_err();
     assert!(matches!(err, rust_genai::Error::ApiError { .. }));
 }
+
+#[tokio::test]
+async fn caches_get_or_create_reuses_by_display_name() {
+    let server = MockServer::start().await;
+    let cache = json!({
+        "name": "cachedContents/shared",
+        "displayName": "shared-context",
+        "model": "models/gemini-2.5-flash",
+        "expireTime": "2999-01-01T00:00:00Z"
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/v1beta/cachedContents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "cachedContents": [{
+                "name": "cachedContents/expired",
+                "displayName": "shared-context",
+                "model": "models/gemini-2.5-flash",
+                "expireTime": "2020-01-01T00:00:00Z"
+            }]
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/cachedContents"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "cachedContents": [cache] })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/cachedContents"))
+        .and(body_partial_json(json!({
+            "displayName": "shared-context",
+            "model": "models/gemini-2.5-flash",
+            "ttl": "600s"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(cache.clone()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let contents = vec![Content::text("x".repeat(5000))];
+    let ttl = Duration::from_secs(600);
+
+    let created = client
+        .caches()
+        .get_or_create("shared-context", "gemini-2.5-flash", contents.clone(), ttl)
+        .await
+        .unwrap();
+    let reused = client
+        .caches()
+        .get_or_create("shared-context", "gemini-2.5-flash", contents, ttl)
+        .await
+        .unwrap();
+    assert_eq!(created.name.as_deref(), Some("cachedContents/shared"));
+    assert_eq!(reused.name, created.name);
+}
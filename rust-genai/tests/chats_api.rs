@@ -309,10 +309,29 @@ async fn chat_send_message_stream_with_callable_tools_applies_afc_history() {
 async fn chat_send_message_stream_with_callable_tools() {
     let server = MockServer::start().await;
     let sse_body = concat!(
-        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"functionCall\":{\"name\":\"echo\",\"args\":{\"msg\":\"hi\"}}}]}}]}\n\n",
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"functionCall\":{\"name\":\"echo\",\"args\":{\"msg\":\"hi\"}},\"thoughtSignature\":\"c2ln\"}]}}]}\n\n",
+        "data: [DONE]\n\n"
+    );
+    let done_body = concat!(
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"done\"}]}}]}\n\n",
         "data: [DONE]\n\n"
     );
 
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta/models/gemini-3-flash-preview:streamGenerateContent",
+        ))
+        .and(query_param("alt", "sse"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(done_body),
+        )
+        .with_priority(1)
+        .expect(1)
+        .mount(&server)
+        .await;
     Mock::given(method("POST"))
         .and(path(
             "/v1beta/models/gemini-3-flash-preview:streamGenerateContent",
@@ -323,6 +342,7 @@ async fn chat_send_message_stream_with_callable_tools() {
                 .insert_header("content-type", "text/event-stream")
                 .set_body_string(sse_body),
         )
+        .expect(1)
         .mount(&server)
         .await;
 
@@ -362,11 +382,17 @@ async fn chat_send_message_stream_with_callable_tools() {
     assert!(seen >= 1);
 
     let history = chat.history().await;
-    assert!(!history.is_empty());
+    let roles: Vec<_> = history.iter().map(|content| content.role).collect();
     assert_eq!(
-        history.last().unwrap().role,
-        Some(rust_genai::types::content::Role::Function)
+        roles,
+        vec![
+            Some(rust_genai::types::content::Role::User),
+            Some(rust_genai::types::content::Role::Model),
+            Some(rust_genai::types::content::Role::Function),
+            Some(rust_genai::types::content::Role::Model),
+        ]
     );
+    assert_eq!(history.last().unwrap().first_text(), Some("done"));
 }
 
 #[tokio::test]
@@ -1,9 +1,333 @@
 use serde_json::json;
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{header, header_exists, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use futures_util::StreamExt;
+use rust_genai::transport::{
+    RequestSigner, Transport, TransportBody, TransportRequest, TransportResponse,
+};
+use rust_genai::types::content::Content;
+use rust_genai::types::models::GenerateContentConfig;
 use rust_genai::Client;
 
+struct InMemoryTransport;
+
+impl Transport for InMemoryTransport {
+    fn execute(
+        &self,
+        request: TransportRequest,
+    ) -> BoxFuture<'_, rust_genai::Result<TransportResponse>> {
+        assert_eq!(request.method, http::Method::POST);
+        assert_eq!(
+            request.url.as_str(),
+            "https://generativelanguage.googleapis.com/v1beta/models/m:generateContent"
+        );
+        assert_eq!(request.headers["x-goog-api-key"], "test-key");
+        let sent: serde_json::Value =
+            serde_json::from_slice(request.body.as_bytes().unwrap()).unwrap();
+        assert_eq!(sent["contents"][0]["parts"][0]["text"], "hi");
+        let body = json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "in-memory"}]}}]
+        });
+        let response = TransportResponse::new(http::StatusCode::OK, body.to_string());
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[tokio::test]
+async fn custom_transport_replaces_reqwest() {
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(InMemoryTransport)
+        .build()
+        .unwrap();
+    let response = client
+        .models()
+        .generate_content("m", vec![Content::text("hi")])
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("in-memory".to_string()));
+}
+
+/// 记录每次请求发出时刻（tokio 虚拟时钟）的 embed 传输层。
+#[derive(Clone, Default)]
+struct RecordingEmbedTransport {
+    sent_at: Arc<Mutex<Vec<tokio::time::Instant>>>,
+}
+
+impl Transport for RecordingEmbedTransport {
+    fn execute(
+        &self,
+        _request: TransportRequest,
+    ) -> BoxFuture<'_, rust_genai::Result<TransportResponse>> {
+        self.sent_at
+            .lock()
+            .unwrap()
+            .push(tokio::time::Instant::now());
+        let body = json!({"embeddings": [{"values": [0.1, 0.2]}]});
+        let response = TransportResponse::new(http::StatusCode::OK, body.to_string());
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limit_spaces_out_concurrent_calls() {
+    let transport = RecordingEmbedTransport::default();
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(transport.clone())
+        .rate_limit(30)
+        .build()
+        .unwrap();
+    let models = client.models();
+    let started = tokio::time::Instant::now();
+
+    let calls = (0..4).map(|i| models.embed_content("m", vec![Content::text(format!("t{i}"))]));
+    for result in futures_util::future::join_all(calls).await {
+        result.unwrap();
+    }
+
+    let sent_at = transport.sent_at.lock().unwrap().clone();
+    assert_eq!(sent_at.len(), 4);
+    assert_eq!(sent_at[0], started);
+    for pair in sent_at.windows(2) {
+        assert_eq!(pair[1] - pair[0], Duration::from_secs(2));
+    }
+    assert!(Client::builder()
+        .api_key("test-key")
+        .rate_limit(0)
+        .build()
+        .is_err());
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limit_does_not_charge_cancelled_calls() {
+    let transport = RecordingEmbedTransport::default();
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(transport.clone())
+        .rate_limit(30)
+        .build()
+        .unwrap();
+    let models = client.models();
+    let started = tokio::time::Instant::now();
+
+    models
+        .embed_content("m", vec![Content::text("first")])
+        .await
+        .unwrap();
+    let cancelled = tokio::time::timeout(
+        Duration::from_secs(1),
+        models.embed_content("m", vec![Content::text("cancelled")]),
+    )
+    .await;
+    assert!(cancelled.is_err());
+    models
+        .embed_content("m", vec![Content::text("next")])
+        .await
+        .unwrap();
+
+    let sent_at = transport.sent_at.lock().unwrap().clone();
+    assert_eq!(sent_at, vec![started, started + Duration::from_secs(2)]);
+}
+
+/// 以方法、URL 与请求体长度拼出的假签名。
+struct FakeHmacSigner;
+
+impl RequestSigner for FakeHmacSigner {
+    fn sign(
+        &self,
+        method: &reqwest::Method,
+        url: &reqwest::Url,
+        body: Option<&[u8]>,
+    ) -> rust_genai::Result<reqwest::header::HeaderMap> {
+        let signature = format!("{method} {} {}", url.path(), body.map_or(0, <[u8]>::len));
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-signature", signature.parse().unwrap());
+        Ok(headers)
+    }
+}
+
+#[tokio::test]
+async fn request_signer_adds_signature_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/m:generateContent"))
+        .and(header_exists("x-signature"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "signed"}]}}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .request_signer(Arc::new(FakeHmacSigner))
+        .build()
+        .unwrap();
+    let response = client
+        .models()
+        .generate_content("m", vec![Content::text("hi")])
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("signed".to_string()));
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(
+        requests[0].headers["x-signature"],
+        format!(
+            "POST /v1beta/models/m:generateContent {}",
+            requests[0].body.len()
+        )
+        .as_str()
+    );
+}
+
+/// 第一次请求在输出 "Hello " 后断开，第二次请求返回完整文本。
+#[derive(Default)]
+struct FlakyStreamTransport {
+    calls: AtomicUsize,
+}
+
+impl Transport for FlakyStreamTransport {
+    fn execute(
+        &self,
+        request: TransportRequest,
+    ) -> BoxFuture<'_, rust_genai::Result<TransportResponse>> {
+        assert!(request
+            .url
+            .as_str()
+            .ends_with(":streamGenerateContent?alt=sse"));
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        let chunk = |text: &str, finish: bool| {
+            let mut candidate = json!({"content": {"role": "model", "parts": [{"text": text}]}});
+            if finish {
+                candidate["finishReason"] = json!("STOP");
+            }
+            Ok(bytes::Bytes::from(format!(
+                "data: {}\n\n",
+                json!({"candidates": [candidate]})
+            )))
+        };
+        let events = if call == 0 {
+            vec![
+                chunk("Hello ", false),
+                Err(std::io::Error::other("connection reset").into()),
+            ]
+        } else {
+            vec![
+                chunk("Hel", false),
+                chunk("lo wor", false),
+                chunk("ld", true),
+            ]
+        };
+        let body = TransportBody::Stream(futures_util::stream::iter(events).boxed());
+        let response = TransportResponse::new(http::StatusCode::OK, body);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[tokio::test]
+async fn stream_restart_skips_already_emitted_text() {
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(FlakyStreamTransport::default())
+        .build()
+        .unwrap();
+    let config = GenerateContentConfig {
+        stream_restart_attempts: Some(1),
+        ..Default::default()
+    };
+    let stream = client
+        .models()
+        .generate_content_stream("m", vec![Content::text("hi")], config)
+        .await
+        .unwrap();
+    let chunks: Vec<_> = stream.collect().await;
+    let text: String = chunks
+        .into_iter()
+        .map(|chunk| chunk.unwrap().text().unwrap_or_default())
+        .collect();
+    assert_eq!(text, "Hello world");
+}
+
+#[tokio::test]
+async fn stream_without_restart_surfaces_transport_error() {
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(FlakyStreamTransport::default())
+        .build()
+        .unwrap();
+    let stream = client
+        .models()
+        .generate_content_stream(
+            "m",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap();
+    let chunks: Vec<_> = stream.collect().await;
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks[1].is_err());
+}
+
+/// 输出一个 chunk 后不再产出数据，也不结束的流式传输层。
+struct StalledStreamTransport;
+
+impl Transport for StalledStreamTransport {
+    fn execute(
+        &self,
+        _request: TransportRequest,
+    ) -> BoxFuture<'_, rust_genai::Result<TransportResponse>> {
+        let chunk =
+            json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "Hello"}]}}]});
+        let first = Ok(bytes::Bytes::from(format!("data: {chunk}\n\n")));
+        let events = futures_util::stream::iter(vec![first]).chain(futures_util::stream::pending());
+        let body = TransportBody::Stream(events.boxed());
+        let response = TransportResponse::new(http::StatusCode::OK, body);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[tokio::test]
+async fn stream_callback_cancel_after_first_chunk_reports_cancelled() {
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(StalledStreamTransport)
+        .build()
+        .unwrap();
+    let (chunk_tx, mut chunk_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    let handle = client.models().generate_content_stream_callback(
+        "m",
+        vec![Content::text("hi")],
+        GenerateContentConfig::default(),
+        move |chunk| {
+            let _ = chunk_tx.send(chunk.text().unwrap_or_default());
+        },
+        move |result| {
+            let _ = done_tx.send(result);
+        },
+    );
+
+    assert_eq!(chunk_rx.recv().await.as_deref(), Some("Hello"));
+    handle.cancel();
+    let result = tokio::time::timeout(Duration::from_secs(1), done_rx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(result, Err(rust_genai::Error::Cancelled)));
+    assert!(chunk_rx.recv().await.is_none());
+}
+
 #[tokio::test]
 async fn api_key_header_is_inserted() {
     let server = MockServer::start().await;
@@ -73,3 +397,53 @@ async fn sdk_usage_header_is_inserted() {
         .unwrap();
     let _ = client.models().list().await.unwrap();
 }
+
+#[tokio::test]
+async fn sdk_client_headers_include_version_and_app_name() {
+    let server = MockServer::start().await;
+    let identifier = concat!(
+        "google-genai-sdk/",
+        env!("CARGO_PKG_VERSION"),
+        " gl-rust/unknown my-app/1.2.0"
+    );
+    Mock::given(method("GET"))
+        .and(path("/v1beta/models"))
+        .and(header("user-agent", identifier))
+        .and(header("x-goog-api-client", identifier))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": []
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .app_name("my-app/1.2.0")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let _ = client.models().list().await.unwrap();
+}
+
+#[tokio::test]
+async fn custom_user_agent_header_is_kept() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/models"))
+        .and(header("user-agent", "custom-agent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": []
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .header("user-agent", "custom-agent")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+    let _ = client.models().list().await.unwrap();
+}
@@ -1,5 +1,6 @@
+use futures_util::TryStreamExt;
 use serde_json::json;
-use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use rust_genai::files::WaitForFileConfig;
@@ -149,7 +150,10 @@ async fn files_error_responses() {
     assert!(matches!(err, rust_genai::Error::ApiError { .. }));
 
     let err = files.get("bad").await.unwrap_err();
-    assert!(matches!(err, rust_genai::Error::ApiError { .. }));
+    assert!(matches!(
+        err,
+        rust_genai::Error::ApiError { status: 500, message } if message == "/v1beta/files/bad: boom"
+    ));
 
     let err = files.delete("bad").await.unwrap_err();
     assert!(matches!(err, rust_genai::Error::ApiError { .. }));
@@ -175,6 +179,45 @@ async fn download_with_config() {
     assert_eq!(bytes, vec![9u8, 8]);
 }
 
+#[tokio::test]
+async fn download_stream_yields_body_and_honors_range() {
+    let server = MockServer::start().await;
+    let body: Vec<u8> = (0u8..=255).cycle().take(64 * 1024).collect();
+    Mock::given(method("GET"))
+        .and(path("/v1beta/files/file1:download"))
+        .and(header("range", "bytes=1000-"))
+        .respond_with(ResponseTemplate::new(206).set_body_bytes(body[1000..].to_vec()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/files/file1:download"))
+        .and(query_param("alt", "media"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client(&server.uri());
+    let files = client.files();
+
+    let chunks: Vec<_> = files
+        .download_stream("files/file1")
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(chunks.concat(), body);
+
+    let ranged: Vec<_> = files
+        .download_stream_from("file1", 1000)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(ranged.concat(), body[1000..]);
+}
+
 #[tokio::test]
 async fn wait_for_active_timeout() {
     let server = MockServer::start().await;
@@ -93,6 +93,63 @@ async fn interactions_api_flow() {
     interactions.delete("int_1").await.unwrap();
 }
 
+#[tokio::test]
+async fn interactions_continue_references_previous_interaction() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/interactions"))
+        .and(body_json(json!({
+            "model": "gemini-3-flash-preview",
+            "input": "and tomorrow?",
+            "previous_interaction_id": "int_1"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "int_2",
+            "model": "gemini-3-flash-preview"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/interactions"))
+        .and(body_json(json!({
+            "model": "gemini-3-flash-preview",
+            "input": "and tomorrow?",
+            "previous_interaction_id": "int_1",
+            "stream": true
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(concat!(
+                    "data: {\"event_type\":\"interaction.start\",\"event_id\":\"evt_1\",\"interaction\":{\"id\":\"int_3\",\"status\":\"in_progress\"}}\n\n",
+                    "data: [DONE]\n\n"
+                )),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let interactions = client.interactions();
+    let config = CreateInteractionConfig::new("gemini-3-flash-preview", "what's the weather?");
+
+    let continued = interactions
+        .continue_interaction("int_1", "and tomorrow?", config.clone())
+        .await
+        .unwrap();
+    assert_eq!(continued.id.as_deref(), Some("int_2"));
+
+    let mut stream = interactions
+        .continue_interaction_stream("interactions/int_1", "and tomorrow?", config)
+        .await
+        .unwrap();
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.event_type.as_deref(), Some("interaction.start"));
+}
+
 #[tokio::test]
 async fn interactions_error_responses_and_empty_body() {
     let server = MockServer::start().await;
@@ -1,6 +1,7 @@
 use futures_util::{SinkExt, StreamExt};
 use serde_json::json;
 use tokio::net::TcpListener;
+use tokio::sync::oneshot;
 use tokio_tungstenite::tungstenite::Message;
 
 use rust_genai::types;
@@ -138,6 +139,187 @@ async fn run_live_session_server(listener: TcpListener) {
     let _ = write.send(Message::Close(None)).await;
 }
 
+#[tokio::test]
+async fn live_session_close_flushes_queued_messages() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (start_tx, start_rx) = oneshot::channel();
+    let server = tokio::spawn(run_counting_server(listener, start_rx));
+    start_tx.send(()).unwrap();
+
+    let session = connect_test_session(addr).await;
+    for i in 0..20 {
+        session.send_text(format!("msg-{i}")).await.unwrap();
+    }
+    session.close().await.unwrap();
+
+    assert_eq!(server.await.unwrap(), 20);
+}
+
+#[tokio::test]
+async fn live_session_close_now_drops_queued_messages() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (start_tx, start_rx) = oneshot::channel();
+    let server = tokio::spawn(run_counting_server(listener, start_rx));
+
+    // The server does not read yet, so large frames back up in the client's send queue.
+    let session = connect_test_session(addr).await;
+    let total = 16;
+    for _ in 0..total {
+        session
+            .send_audio(vec![0; 2 * 1024 * 1024], "audio/pcm")
+            .await
+            .unwrap();
+    }
+    let close = tokio::spawn(session.close_now());
+    tokio::task::yield_now().await;
+    start_tx.send(()).unwrap();
+
+    let dropped = close.await.unwrap();
+    let received = server.await.unwrap();
+    assert!(dropped > 0);
+    assert_eq!(received + dropped, total);
+}
+
+#[tokio::test]
+async fn live_session_streams_interim_then_final_tool_response() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(run_capturing_server(listener, 2));
+
+    let session = connect_test_session(addr).await;
+    session
+        .send_interim_tool_response(
+            types::content::FunctionResponse::interim(
+                "call-1",
+                "long_job",
+                json!({"status": "working", "progress": 0.5}),
+            )
+            .with_scheduling(types::enums::FunctionResponseScheduling::Silent),
+        )
+        .await
+        .unwrap();
+    session
+        .send_final_tool_response(
+            types::content::FunctionResponse::final_response(
+                "call-1",
+                "long_job",
+                json!({"result": "done"}),
+            )
+            .with_scheduling(types::enums::FunctionResponseScheduling::WhenIdle),
+        )
+        .await
+        .unwrap();
+    session.close().await.unwrap();
+
+    let frames = server.await.unwrap();
+    assert_eq!(
+        frames,
+        vec![
+            json!({"toolResponse": {"functionResponses": [{
+                "willContinue": true,
+                "scheduling": "SILENT",
+                "id": "call-1",
+                "name": "long_job",
+                "response": {"status": "working", "progress": 0.5}
+            }]}}),
+            json!({"toolResponse": {"functionResponses": [{
+                "willContinue": false,
+                "scheduling": "WHEN_IDLE",
+                "id": "call-1",
+                "name": "long_job",
+                "response": {"result": "done"}
+            }]}}),
+        ]
+    );
+}
+
+async fn connect_test_session(addr: std::net::SocketAddr) -> rust_genai::live::LiveSession {
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(format!("http://{addr}"))
+        .build()
+        .unwrap();
+    client
+        .live()
+        .connect(
+            "gemini-3.1-flash-live-preview",
+            types::live_types::LiveConnectConfig::default(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Completes setup, waits for `start`, then counts client text frames until the close frame.
+async fn run_counting_server(listener: TcpListener, start: oneshot::Receiver<()>) -> usize {
+    let (stream, _) = listener.accept().await.unwrap();
+    let ws_stream = tokio_tungstenite::accept_async(stream).await.unwrap();
+    let (mut write, mut read) = ws_stream.split();
+    let _ = read.next().await;
+
+    let setup_complete = live_server_message(
+        Some(types::live_types::LiveServerSetupComplete {
+            session_id: Some("session-1".to_string()),
+        }),
+        None,
+        None,
+        None,
+    );
+    write
+        .send(Message::Text(
+            serde_json::to_string(&setup_complete).unwrap().into(),
+        ))
+        .await
+        .unwrap();
+
+    let _ = start.await;
+    let mut received = 0;
+    while let Some(Ok(message)) = read.next().await {
+        match message {
+            Message::Text(_) => received += 1,
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+    received
+}
+
+/// Completes setup, then returns the first `count` client text frames as JSON.
+async fn run_capturing_server(listener: TcpListener, count: usize) -> Vec<serde_json::Value> {
+    let (stream, _) = listener.accept().await.unwrap();
+    let ws_stream = tokio_tungstenite::accept_async(stream).await.unwrap();
+    let (mut write, mut read) = ws_stream.split();
+    let _ = read.next().await;
+
+    let setup_complete = live_server_message(
+        Some(types::live_types::LiveServerSetupComplete {
+            session_id: Some("session-1".to_string()),
+        }),
+        None,
+        None,
+        None,
+    );
+    write
+        .send(Message::Text(
+            serde_json::to_string(&setup_complete).unwrap().into(),
+        ))
+        .await
+        .unwrap();
+
+    let mut frames = Vec::new();
+    while let Some(Ok(message)) = read.next().await {
+        match message {
+            Message::Text(text) if frames.len() < count => {
+                frames.push(serde_json::from_str(&text).unwrap());
+            }
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+    frames
+}
+
 async fn send_live_session_messages(
     session: &rust_genai::live::LiveSession,
 ) -> rust_genai::Result<()> {
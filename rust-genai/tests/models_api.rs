@@ -1,22 +1,34 @@
 mod support;
 
+use std::sync::{Arc, Mutex};
+
 use futures_util::StreamExt;
 use serde_json::json;
-use wiremock::matchers::{body_string_contains, method, path, query_param, query_param_is_missing};
+use wiremock::matchers::{
+    body_partial_json, body_string_contains, method, path, query_param, query_param_is_missing,
+};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use rust_genai::afc::InlineCallableTool;
-use rust_genai::types::content::{Content, Role};
+use rust_genai::error::ContentBlockReason;
+use rust_genai::models::{RawModel, StreamAccumulator, StreamItem};
+use rust_genai::tokenizer::SimpleTokenEstimator;
+use rust_genai::types::config::GenerationConfig;
+use rust_genai::types::content::{Content, Part, Role};
+use rust_genai::types::enums::{BlockedReason, FinishReason, Modality};
 use rust_genai::types::http::HttpOptions as TypesHttpOptions;
 use rust_genai::types::models::{
     AutomaticFunctionCallingConfig, CountTokensConfig, DeleteModelConfig, GenerateContentConfig,
-    GenerateImagesConfig, GenerateVideosConfig, GenerateVideosSource, ListModelsConfig,
+    GenerateImagesConfig, GenerateVideosConfig, GenerateVideosSource, Image, ListModelsConfig,
     UpdateModelConfig,
 };
-use rust_genai::types::tool::FunctionDeclaration;
-use rust_genai::Error;
+use rust_genai::types::tool::{FunctionDeclaration, Schema};
+use rust_genai::{Client, Error};
 
-use support::build_gemini_client;
+use support::{
+    build_gemini_client, build_gemini_client_with_token_cache, disabled_retry_options,
+    mount_default_mock,
+};
 
 #[tokio::test]
 async fn test_generate_content_gemini_api() {
@@ -52,6 +64,260 @@ async fn test_generate_content_gemini_api() {
     assert_eq!(response.text(), Some("Hello".to_string()));
 }
 
+#[tokio::test]
+async fn test_generate_content_with_extra_query() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(query_param("experimental", "on"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "ok"}]}}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let config = GenerateContentConfig {
+        http_options: Some(TypesHttpOptions {
+            extra_query: Some(vec![("experimental".to_string(), "on".to_string())]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let response = client
+        .models()
+        .generate_content_with_config("gemini-1.5-pro", vec![Content::text("Test")], config)
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("ok".to_string()));
+}
+
+#[tokio::test]
+async fn test_generate_content_multi_part_system_instruction() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(body_partial_json(json!({
+            "systemInstruction": {
+                "parts": [{"text": "You are terse."}, {"text": "Answer in French."}]
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "ok"}]}}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let config = GenerateContentConfig::default().with_system_instruction_content(Content {
+        role: None,
+        parts: vec![
+            Part::text("You are terse."),
+            Part::text("Answer in French."),
+        ],
+    });
+
+    let response = client
+        .models()
+        .generate_content_with_config("gemini-1.5-pro", vec![Content::text("Test")], config)
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("ok".to_string()));
+}
+
+#[tokio::test]
+async fn test_generate_content_captures_request_id_header() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-goog-request-id", "req-abc-123")
+                .set_body_json(json!({
+                    "candidates": [{"content": {"role": "model", "parts": [{"text": "ok"}]}}],
+                    "responseId": "resp-1"
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:countTokens"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Goog-Request-Id", "req-count")
+                .set_body_json(json!({"totalTokens": 3})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let response = client
+        .models()
+        .generate_content("gemini-1.5-pro", vec![Content::text("Test")])
+        .await
+        .unwrap();
+    assert_eq!(response.request_id(), Some("req-abc-123"));
+    assert_eq!(response.response_id.as_deref(), Some("resp-1"));
+
+    let count = client
+        .models()
+        .count_tokens("gemini-1.5-pro", vec![Content::text("Test")])
+        .await
+        .unwrap();
+    assert_eq!(count.request_id(), Some("req-count"));
+}
+
+#[tokio::test]
+async fn test_generate_content_raw_model_used_verbatim() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta/publishers/meta/models/llama-3.1-405b:generateContent",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "ok"}]}}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let response = client
+        .models()
+        .generate_content(
+            RawModel::new("publishers/meta/models/llama-3.1-405b"),
+            vec![Content::text("Test")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("ok".to_string()));
+}
+
+#[tokio::test]
+async fn test_generate_content_error_on_blocked_content() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(body_string_contains("blocked prompt"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "promptFeedback": {"blockReason": "SAFETY"}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(body_string_contains("recited"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"finishReason": "RECITATION"}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let config = GenerateContentConfig {
+        error_on_blocked_content: Some(true),
+        ..Default::default()
+    };
+
+    let response = client
+        .models()
+        .generate_content("gemini-1.5-pro", vec![Content::text("blocked prompt")])
+        .await
+        .unwrap();
+    assert_eq!(response.blocked_reason(), Some(BlockedReason::Safety));
+
+    let err = client
+        .models()
+        .generate_content_with_config(
+            "gemini-1.5-pro",
+            vec![Content::text("blocked prompt")],
+            config.clone(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ContentBlocked {
+            reason: ContentBlockReason::Prompt(BlockedReason::Safety)
+        }
+    ));
+
+    let err = client
+        .models()
+        .generate_content_with_config("gemini-1.5-pro", vec![Content::text("recited")], config)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ContentBlocked {
+            reason: ContentBlockReason::Finish(FinishReason::Recitation)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_generate_content_stream_error_on_blocked_content() {
+    let mock_server = MockServer::start().await;
+    let sse_body = concat!(
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"Once\"}]}}]}\n\n",
+        "data: {\"candidates\":[{\"finishReason\":\"SAFETY\"}]}\n\n",
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"late\"}]}}]}\n\n",
+    );
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let config = GenerateContentConfig {
+        error_on_blocked_content: Some(true),
+        ..Default::default()
+    };
+    let chunks: Vec<_> = client
+        .models()
+        .generate_content_stream("gemini-1.5-pro", vec![Content::text("hi")], config)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].as_ref().unwrap().text(), Some("Once".to_string()));
+    assert!(matches!(
+        chunks[1],
+        Err(Error::ContentBlocked {
+            reason: ContentBlockReason::Finish(FinishReason::Safety)
+        })
+    ));
+
+    let chunks: Vec<_> = client
+        .models()
+        .generate_content_stream(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks.iter().all(Result::is_ok));
+}
+
 #[tokio::test]
 async fn test_generate_content_should_return_http_response() {
     let mock_server = MockServer::start().await;
@@ -185,183 +451,798 @@ async fn test_sse_streaming() {
 }
 
 #[tokio::test]
-async fn test_list_models_with_query_params() {
+async fn test_generate_content_stream_callback_accumulates_chunks() {
     let mock_server = MockServer::start().await;
-    let response_body = json!({
-        "models": []
-    });
+    let sse_body = concat!(
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"Hello\"}]}}]}\n\n",
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\" world\"}]}}]}\n\n",
+    );
 
-    Mock::given(method("GET"))
-        .and(path("/v1beta/models"))
-        .and(query_param("pageSize", "5"))
-        .and(query_param("pageToken", "token-1"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta/models/gemini-3-flash-preview:streamGenerateContent",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body),
+        )
         .mount(&mock_server)
         .await;
 
     let client = build_gemini_client(&mock_server.uri());
+    let chunks = Arc::new(Mutex::new(Vec::new()));
+    let collected = Arc::clone(&chunks);
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+
+    let _handle = client.models().generate_content_stream_callback(
+        "gemini-3-flash-preview",
+        vec![Content::text("Test")],
+        GenerateContentConfig::default(),
+        move |chunk| {
+            collected
+                .lock()
+                .unwrap()
+                .push(chunk.text().unwrap_or_default())
+        },
+        move |result| {
+            let _ = done_tx.send(result);
+        },
+    );
 
-    let _ = client
-        .models()
-        .list_with_config(ListModelsConfig {
-            page_size: Some(5),
-            page_token: Some("token-1".to_string()),
-            filter: None,
-            query_base: None,
-        })
-        .await
-        .unwrap();
+    done_rx.await.unwrap().unwrap();
+    assert_eq!(chunks.lock().unwrap().concat(), "Hello world");
 }
 
 #[tokio::test]
-async fn test_models_gemini_media_and_tokens() {
+async fn test_generate_content_stream_callback_cancel_stops_task() {
     let mock_server = MockServer::start().await;
-
     Mock::given(method("POST"))
-        .and(path("/v1beta/models/gemini-1.5-pro:batchEmbedContents"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "embeddings": [{"values": [0.1, 0.2]}]
-        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_delay(std::time::Duration::from_secs(5)),
+        )
         .mount(&mock_server)
         .await;
 
-    Mock::given(method("POST"))
-        .and(path("/v1beta/models/gemini-1.5-pro:countTokens"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "totalTokens": 4
-        })))
-        .mount(&mock_server)
-        .await;
+    let client = build_gemini_client(&mock_server.uri());
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    let handle = client.models().generate_content_stream_callback(
+        "gemini-3-flash-preview",
+        vec![Content::text("Test")],
+        GenerateContentConfig::default(),
+        |_| {},
+        move |result| {
+            let _ = done_tx.send(result);
+        },
+    );
+
+    handle.cancel();
+    let result = tokio::time::timeout(std::time::Duration::from_secs(1), done_rx)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(result, Err(Error::Cancelled)));
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    assert!(handle.is_finished());
+}
 
+#[tokio::test]
+async fn test_generate_content_stream_callback_reports_errors() {
+    let mock_server = MockServer::start().await;
     Mock::given(method("POST"))
-        .and(path("/v1beta/models/imagen-3.0:predict"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "predictions": [{"bytesBase64Encoded": "AQID", "mimeType": "image/png"}]
+        .and(path(
+            "/v1beta/models/gemini-3-flash-preview:streamGenerateContent",
+        ))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": {"code": 400, "message": "bad request", "status": "INVALID_ARGUMENT"}
         })))
         .mount(&mock_server)
         .await;
 
+    let client = build_gemini_client(&mock_server.uri());
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    let _handle = client.models().generate_content_stream_callback(
+        "gemini-3-flash-preview",
+        vec![Content::text("Test")],
+        GenerateContentConfig::default(),
+        |_| panic!("no chunks expected"),
+        move |result| {
+            let _ = done_tx.send(result);
+        },
+    );
+
+    let err = done_rx.await.unwrap().unwrap_err();
+    assert!(matches!(err, Error::ApiError { status: 400, .. }));
+}
+
+#[tokio::test]
+async fn test_generate_content_preserves_unknown_fields_in_extra() {
+    let mock_server = MockServer::start().await;
     Mock::given(method("POST"))
-        .and(path("/v1beta/models/veo-1.0:predictLongRunning"))
+        .and(path("/v1beta/models/gemini-2.5-flash:generateContent"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "name": "operations/1",
-            "response": {"generateVideoResponse": {"ok": true}}
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "ok"}]},
+                "futureCandidateSignal": {"score": 0.5}
+            }],
+            "usageMetadata": {"totalTokenCount": 3, "futureTokenCount": 1},
+            "futureResponseField": ["a", "b"]
         })))
         .mount(&mock_server)
         .await;
 
     let client = build_gemini_client(&mock_server.uri());
-
-    let embed = client
+    let response = client
         .models()
-        .embed_content("gemini-1.5-pro", vec![Content::text("hi")])
+        .generate_content("gemini-2.5-flash", vec![Content::text("hi")])
         .await
         .unwrap();
-    assert!(embed.embeddings.is_some());
-
-    let count = client
+    assert_eq!(response.text().as_deref(), Some("ok"));
+    assert_eq!(response.extra["futureResponseField"], json!(["a", "b"]));
+    assert_eq!(
+        response.candidates[0].extra["futureCandidateSignal"],
+        json!({"score": 0.5})
+    );
+    let usage = response.usage_metadata.as_ref().unwrap();
+    assert_eq!(usage.extra["futureTokenCount"], json!(1));
+
+    let round_trip = serde_json::to_value(&response).unwrap();
+    assert_eq!(round_trip["futureResponseField"], json!(["a", "b"]));
+}
+
+#[tokio::test]
+async fn test_prompt_feedback_block_reason_unary_and_stream() {
+    let mock_server = MockServer::start().await;
+    let feedback = json!({
+        "blockReason": "PROHIBITED_CONTENT",
+        "blockReasonMessage": "The prompt was blocked."
+    });
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-2.5-flash:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"promptFeedback": feedback})))
+        .mount(&mock_server)
+        .await;
+    let sse_body = format!(
+        "data: {}\n\ndata: {}\n\n",
+        json!({"promptFeedback": feedback}),
+        json!({
+            "promptFeedback": {"safetyRatings": []},
+            "usageMetadata": {"promptTokenCount": 4, "totalTokenCount": 4}
+        })
+    );
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta/models/gemini-2.5-flash:streamGenerateContent",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let response = client
         .models()
-        .count_tokens("gemini-1.5-pro", vec![Content::text("hi")])
+        .generate_content("gemini-2.5-flash", vec![Content::text("blocked")])
         .await
         .unwrap();
-    assert_eq!(count.total_tokens, Some(4));
+    assert_eq!(
+        response.blocked_reason(),
+        Some(BlockedReason::ProhibitedContent)
+    );
+    assert_eq!(
+        response.block_reason_message(),
+        Some("The prompt was blocked.")
+    );
 
-    let images = client
+    let mut stream = client
         .models()
-        .generate_images("imagen-3.0", "prompt", GenerateImagesConfig::default())
+        .generate_content_stream(
+            "gemini-2.5-flash",
+            vec![Content::text("blocked")],
+            GenerateContentConfig::default(),
+        )
         .await
         .unwrap();
-    assert_eq!(images.generated_images.len(), 1);
+    let mut accumulator = StreamAccumulator::new();
+    while let Some(chunk) = stream.next().await {
+        accumulator.push(&chunk.unwrap());
+    }
+    let streamed = accumulator.into_response().unwrap();
+    assert_eq!(
+        streamed.blocked_reason(),
+        Some(BlockedReason::ProhibitedContent)
+    );
+    assert_eq!(
+        streamed.block_reason_message(),
+        Some("The prompt was blocked.")
+    );
+    assert_eq!(
+        streamed
+            .usage_metadata
+            .and_then(|usage| usage.prompt_token_count),
+        Some(4)
+    );
+}
 
-    let op = client
+#[tokio::test]
+async fn test_sse_streaming_error_envelope_is_api_error() {
+    let mock_server = MockServer::start().await;
+    let sse_body = concat!(
+        "data: {\"error\": {\"code\": 503, \"message\": \"The model is overloaded.\", \"status\": \"UNAVAILABLE\"}}\n\n",
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"Hi\"}]}}]}\n\n",
+    );
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta/models/gemini-3-flash-preview:streamGenerateContent",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+
+    let mut stream = client
         .models()
-        .generate_videos(
-            "veo-1.0",
-            GenerateVideosSource {
-                prompt: Some("video".to_string()),
-                ..Default::default()
-            },
-            GenerateVideosConfig::default(),
+        .generate_content_stream(
+            "gemini-3-flash-preview",
+            vec![Content::text("Test")],
+            GenerateContentConfig::default(),
         )
         .await
         .unwrap();
-    assert_eq!(op.name.as_deref(), Some("operations/1"));
+
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ApiError { status: 503, ref message } if message == "The model is overloaded."
+    ));
+    assert_eq!(err.code().as_deref(), Some("UNAVAILABLE"));
+    assert!(err.is_retryable());
+    assert!(stream.next().await.is_none());
 }
 
 #[tokio::test]
-async fn test_models_gemini_crud() {
+async fn test_generate_content_response_modalities_text_and_image() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta/models/gemini-2.5-flash-image:generateContent",
+        ))
+        .and(body_partial_json(json!({
+            "generationConfig": {"responseModalities": ["TEXT", "IMAGE"]}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [
+                {"text": "A cat"},
+                {"inlineData": {"mimeType": "image/png", "data": "AQI="}}
+            ]}}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let config = GenerateContentConfig::default()
+        .with_response_modalities([Modality::Text, Modality::Image]);
+    let response = client
+        .models()
+        .generate_content_with_config(
+            "gemini-2.5-flash-image",
+            vec![Content::text("draw a cat")],
+            config.clone(),
+        )
+        .await
+        .unwrap();
+    let parts = response.modality_parts();
+    assert_eq!(parts.text, vec!["A cat"]);
+    assert_eq!(parts.images[0].data, vec![1, 2]);
+    assert!(parts.audio.is_empty());
+
+    // Without the opt-in, unknown image-capable models are left to the API.
+    client
+        .models()
+        .generate_content_request_preview(
+            "gemini-2.5-flash",
+            vec![Content::text("draw a cat")],
+            config.clone(),
+        )
+        .unwrap();
+
+    let checked = Client::builder()
+        .api_key("test-key")
+        .base_url(mock_server.uri())
+        .validate_model_capabilities(true)
+        .build()
+        .unwrap();
+    let err = checked
+        .models()
+        .generate_content_with_config(
+            "gemini-2.5-flash",
+            vec![Content::text("draw a cat")],
+            config,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig { .. }));
+}
+
+#[tokio::test]
+async fn test_list_models_with_query_params() {
+    let mock_server = MockServer::start().await;
+    let response_body = json!({
+        "models": []
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/v1beta/models"))
+        .and(query_param("pageSize", "5"))
+        .and(query_param("pageToken", "token-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+
+    let _ = client
+        .models()
+        .list_with_config(ListModelsConfig {
+            page_size: Some(5),
+            page_token: Some("token-1".to_string()),
+            filter: None,
+            query_base: None,
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_list_models_stream_drains_pages() {
     let mock_server = MockServer::start().await;
 
     Mock::given(method("GET"))
         .and(path("/v1beta/models"))
+        .and(query_param_is_missing("pageToken"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "models": [{"name": "models/gemini-1.5-pro"}]
+            "models": [{"name": "models/a"}],
+            "nextPageToken": "page-2"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/models"))
+        .and(query_param("pageToken", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": [{"name": "models/b"}, {"name": "models/c"}]
         })))
         .mount(&mock_server)
         .await;
 
+    let client = build_gemini_client(&mock_server.uri());
+    let names: Vec<String> = client
+        .models()
+        .list_stream(ListModelsConfig::default())
+        .map(|model| model.unwrap().name.unwrap())
+        .collect()
+        .await;
+    assert_eq!(names, vec!["models/a", "models/b", "models/c"]);
+}
+
+#[tokio::test]
+async fn test_models_supporting_filters_by_generation_method() {
+    let mock_server = MockServer::start().await;
+
     Mock::given(method("GET"))
-        .and(path("/v1beta/models/gemini-1.5-pro"))
+        .and(path("/v1beta/models"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "name": "models/gemini-1.5-pro"
+            "models": [
+                {
+                    "name": "models/gemini-2.5-flash",
+                    "supportedGenerationMethods": ["generateContent", "countTokens"]
+                },
+                {
+                    "name": "models/gemini-embedding-001",
+                    "supportedGenerationMethods": ["embedContent", "countTokens"]
+                },
+                {"name": "models/unknown"},
+                {
+                    "name": "models/gemini-live",
+                    "supportedGenerationMethods": ["bidiGenerateContent"]
+                }
+            ]
         })))
         .mount(&mock_server)
         .await;
 
-    Mock::given(method("PATCH"))
-        .and(path("/v1beta/models/gemini-1.5-pro"))
+    let client = build_gemini_client(&mock_server.uri());
+    let models = client.models().all().await.unwrap();
+    let generate: Vec<_> = models
+        .iter()
+        .filter(|model| model.supports_generate_content())
+        .filter_map(|model| model.name.as_deref())
+        .collect();
+    assert_eq!(generate, vec!["models/gemini-2.5-flash"]);
+    assert!(models[1].supports_embedding());
+    assert!(!models[2].supports_count_tokens());
+    assert!(models[3].supports_live());
+
+    let supporting = client.models().supporting("generateContent").await.unwrap();
+    assert_eq!(supporting.len(), 1);
+    assert_eq!(
+        supporting[0].name.as_deref(),
+        Some("models/gemini-2.5-flash")
+    );
+}
+
+#[tokio::test]
+async fn test_models_gemini_media_and_tokens() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:batchEmbedContents"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "name": "models/gemini-1.5-pro",
-            "displayName": "updated"
+            "embeddings": [{"values": [0.1, 0.2]}]
         })))
         .mount(&mock_server)
         .await;
 
-    Mock::given(method("DELETE"))
-        .and(path("/v1beta/models/gemini-1.5-pro"))
-        .respond_with(ResponseTemplate::new(200))
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:countTokens"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "totalTokens": 4
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/imagen-3.0:predict"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "predictions": [{"bytesBase64Encoded": "AQID", "mimeType": "image/png"}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/veo-1.0:predictLongRunning"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "operations/1",
+            "response": {"generateVideoResponse": {"ok": true}}
+        })))
         .mount(&mock_server)
         .await;
 
     let client = build_gemini_client(&mock_server.uri());
 
-    let list = client.models().list().await.unwrap();
-    assert_eq!(list.models.unwrap().len(), 1);
+    let embed = client
+        .models()
+        .embed_content("gemini-1.5-pro", vec![Content::text("hi")])
+        .await
+        .unwrap();
+    assert!(embed.embeddings.is_some());
 
-    let model = client.models().get("gemini-1.5-pro").await.unwrap();
-    assert_eq!(model.name.as_deref(), Some("models/gemini-1.5-pro"));
+    let count = client
+        .models()
+        .count_tokens("gemini-1.5-pro", vec![Content::text("hi")])
+        .await
+        .unwrap();
+    assert_eq!(count.total_tokens, Some(4));
 
-    let updated = client
+    let images = client
         .models()
-        .update("gemini-1.5-pro", UpdateModelConfig::default())
+        .generate_images("imagen-3.0", "prompt", GenerateImagesConfig::default())
         .await
         .unwrap();
-    assert_eq!(updated.display_name.as_deref(), Some("updated"));
+    assert_eq!(images.generated_images.len(), 1);
 
-    client
+    let op = client
         .models()
-        .delete("gemini-1.5-pro", DeleteModelConfig::default())
+        .generate_videos(
+            "veo-1.0",
+            GenerateVideosSource {
+                prompt: Some("video".to_string()),
+                ..Default::default()
+            },
+            GenerateVideosConfig::default(),
+        )
         .await
         .unwrap();
+    assert_eq!(op.name.as_deref(), Some("operations/1"));
 }
 
 #[tokio::test]
-async fn test_models_generate_content_callable_tools() {
+async fn test_generate_videos_rejects_oversized_inline_image_when_enabled() {
     let mock_server = MockServer::start().await;
-    let function_call_body = json!({
-        "candidates": [{
-            "content": {"role": "model", "parts": [{
-                "functionCall": {"name": "test_tool", "args": {"x": 1}}
-            }]}
-        }]
-    });
-
     Mock::given(method("POST"))
-        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
-        .and(body_string_contains("functionResponse"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "candidates": [{
-                "content": {"role": "model", "parts": [{"text": "done"}]}
+        .and(path(
+            "/v1beta/models/veo-3.0-generate-001:predictLongRunning",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"name": "operations/1"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+    png.extend_from_slice(&8192u32.to_be_bytes());
+    png.extend_from_slice(&1024u32.to_be_bytes());
+    let source = GenerateVideosSource {
+        prompt: Some("pan across".to_string()),
+        image: Some(Image::from_bytes(png, "image/png")),
+        ..Default::default()
+    };
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(mock_server.uri())
+        .retry_options(disabled_retry_options())
+        .validate_media_inputs(true)
+        .build()
+        .unwrap();
+    let err = client
+        .models()
+        .generate_videos(
+            "veo-3.0-generate-001",
+            source.clone(),
+            GenerateVideosConfig::default(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidConfig { ref message } if message.contains("8192x1024")));
+
+    // Validation is off by default, so the same request is sent.
+    let op = build_gemini_client(&mock_server.uri())
+        .models()
+        .generate_videos(
+            "veo-3.0-generate-001",
+            source,
+            GenerateVideosConfig::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(op.name.as_deref(), Some("operations/1"));
+}
+
+#[tokio::test]
+async fn test_models_gemini_crud() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1beta/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": [{"name": "models/gemini-1.5-pro"}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1beta/models/gemini-1.5-pro"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "models/gemini-1.5-pro"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v1beta/models/gemini-1.5-pro"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "models/gemini-1.5-pro",
+            "displayName": "updated"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/v1beta/models/gemini-1.5-pro"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+
+    let list = client.models().list().await.unwrap();
+    assert_eq!(list.models.unwrap().len(), 1);
+
+    let model = client.models().get("gemini-1.5-pro").await.unwrap();
+    assert_eq!(model.name.as_deref(), Some("models/gemini-1.5-pro"));
+
+    let updated = client
+        .models()
+        .update("gemini-1.5-pro", UpdateModelConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(updated.display_name.as_deref(), Some("updated"));
+
+    client
+        .models()
+        .delete("gemini-1.5-pro", DeleteModelConfig::default())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_callable_tool_invalid_args_reprompt_model() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(body_string_contains("functionResponse"))
+        .and(body_string_contains(
+            "Invalid arguments for get_weather: $.city: missing required field",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "which city?"}]}
+            }]
+        })))
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{
+                    "functionCall": {"name": "get_weather", "args": {"unit": "c"}}
+                }]}
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "get_weather".to_string(),
+        description: None,
+        parameters: Some(
+            Schema::object()
+                .property("city", Schema::string())
+                .property("unit", Schema::string())
+                .required("city")
+                .build(),
+        ),
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }])
+    .with_handler("get_weather", |_| async move {
+        panic!("handler must not run with invalid arguments")
+    });
+
+    let response = client
+        .models()
+        .generate_content_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("weather?")],
+            GenerateContentConfig::default(),
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("which city?".to_string()));
+}
+
+fn slow_tool(calls: Arc<Mutex<usize>>) -> InlineCallableTool {
+    InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "slow_tool".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }])
+    .with_handler("slow_tool", move |_| {
+        let calls = Arc::clone(&calls);
+        async move {
+            *calls.lock().unwrap() += 1;
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            Ok(json!({"ok": true}))
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_callable_tools_total_timeout_aborts_multi_round_loop() {
+    let mock_server = MockServer::start().await;
+    let function_call = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{"functionCall": {"name": "slow_tool", "args": {}}}]}
+        }]
+    });
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-2.5-flash:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(function_call.clone()))
+        .mount(&mock_server)
+        .await;
+    let sse_body = format!("data: {function_call}\n\n");
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1beta/models/gemini-2.5-flash:streamGenerateContent",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let config = GenerateContentConfig {
+        automatic_function_calling: Some(AutomaticFunctionCallingConfig {
+            maximum_remote_calls: Some(50),
+            total_timeout_ms: Some(350),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let calls = Arc::new(Mutex::new(0));
+    let started = std::time::Instant::now();
+    let err = client
+        .models()
+        .generate_content_with_callable_tools(
+            "gemini-2.5-flash",
+            vec![Content::text("loop")],
+            config.clone(),
+            vec![Box::new(slow_tool(Arc::clone(&calls)))],
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Timeout { ref message } if message.contains("350 ms")));
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    let rounds = *calls.lock().unwrap();
+    assert!(rounds < 50, "unexpected rounds: {rounds}");
+
+    let calls = Arc::new(Mutex::new(0));
+    let mut stream = client
+        .models()
+        .generate_content_stream_with_callable_tools(
+            "gemini-2.5-flash",
+            vec![Content::text("loop")],
+            config,
+            vec![Box::new(slow_tool(Arc::clone(&calls)))],
+        )
+        .await
+        .unwrap();
+    let mut last_err = None;
+    while let Some(item) = stream.next().await {
+        if let Err(err) = item {
+            last_err = Some(err);
+        }
+    }
+    assert!(matches!(last_err, Some(Error::Timeout { .. })));
+    assert!(*calls.lock().unwrap() < 50);
+}
+
+#[tokio::test]
+async fn test_models_generate_content_callable_tools() {
+    let mock_server = MockServer::start().await;
+    let function_call_body = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{
+                "functionCall": {"name": "test_tool", "args": {"x": 1}}
+            }]}
+        }]
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "done"}]}
             }]
         })))
         .with_priority(1)
@@ -375,8 +1256,814 @@ async fn test_models_generate_content_callable_tools() {
         .await;
 
     let client = build_gemini_client(&mock_server.uri());
-
-    let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+
+    let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "test_tool".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }]);
+    tool.register_handler("test_tool", |value| async move {
+        let x = value
+            .get("x")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(0);
+        Ok(json!({ "result": x + 1 }))
+    });
+
+    let config = GenerateContentConfig {
+        automatic_function_calling: Some(AutomaticFunctionCallingConfig {
+            maximum_remote_calls: Some(1),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let response = client
+        .models()
+        .generate_content_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            config,
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("done".to_string()));
+    assert!(response.automatic_function_calling_history.is_some());
+}
+
+#[tokio::test]
+async fn test_extend_history_after_callable_tools_keeps_tool_exchange_once() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(body_string_contains("second question"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "second answer"}]}}]
+        })))
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "done"}]}}]
+        })))
+        .with_priority(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{
+                "functionCall": {"name": "test_tool", "args": {"x": 1}}
+            }]}}]
+        })))
+        .with_priority(3)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "test_tool".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }]);
+    tool.register_handler("test_tool", |_| async move { Ok(json!({ "result": 2 })) });
+
+    let mut history = vec![Content::text("first question")];
+    let response = client
+        .models()
+        .generate_content_with_callable_tools(
+            "gemini-1.5-pro",
+            history.clone(),
+            GenerateContentConfig::default(),
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+    response.extend_history(&mut history);
+
+    let roles: Vec<_> = history.iter().map(|content| content.role).collect();
+    assert_eq!(
+        roles,
+        vec![
+            Some(Role::User),
+            Some(Role::Model),
+            Some(Role::Function),
+            Some(Role::Model)
+        ]
+    );
+    assert!(history[1].parts[0].function_call_ref().is_some());
+    assert_eq!(history[3].first_text(), Some("done"));
+
+    history.push(Content::text("second question"));
+    let response = client
+        .models()
+        .generate_content("gemini-1.5-pro", history.clone())
+        .await
+        .unwrap();
+    assert_eq!(response.text().as_deref(), Some("second answer"));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let body = String::from_utf8(requests.last().unwrap().body.clone()).unwrap();
+    assert_eq!(body.matches("\"functionCall\"").count(), 1);
+    assert_eq!(body.matches("\"functionResponse\"").count(), 1);
+}
+
+#[tokio::test]
+async fn test_callable_tools_error_on_max_remote_calls() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{
+                    "functionCall": {"name": "again", "args": {}}
+                }]}
+            }]
+        })))
+        .expect(4)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let build_tool = || {
+        let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+            name: "again".to_string(),
+            description: None,
+            parameters: None,
+            parameters_json_schema: None,
+            response: None,
+            response_json_schema: None,
+            behavior: None,
+        }]);
+        tool.register_handler("again", |_| async move { Ok(json!({"call": "again"})) });
+        tool
+    };
+    let config = |error_on_max_remote_calls| GenerateContentConfig {
+        automatic_function_calling: Some(AutomaticFunctionCallingConfig {
+            maximum_remote_calls: Some(1),
+            error_on_max_remote_calls,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let err = client
+        .models()
+        .generate_content_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            config(Some(true)),
+            vec![Box::new(build_tool())],
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::MaxToolCallsExceeded { limit: 1 }));
+
+    let response = client
+        .models()
+        .generate_content_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            config(None),
+            vec![Box::new(build_tool())],
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.function_calls().len(), 1);
+}
+
+#[tokio::test]
+async fn test_models_generate_content_stream_callable_tools() {
+    let mock_server = MockServer::start().await;
+    let payload = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{
+                "functionCall": {"name": "test_tool", "args": {"x": 1}}
+            }]}
+        }]
+    });
+    let sse_body = format!("data: {payload}\n\ndata: [DONE]\n\n");
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+
+    let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "test_tool".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }]);
+    tool.register_handler("test_tool", |_value| async move { Ok(json!({"ok": true})) });
+
+    let config = GenerateContentConfig {
+        automatic_function_calling: Some(AutomaticFunctionCallingConfig {
+            maximum_remote_calls: Some(1),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut stream = client
+        .models()
+        .generate_content_stream_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            config,
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+
+    let mut saw_function_role = false;
+    while let Some(item) = stream.next().await {
+        let response = item.unwrap();
+        if response
+            .candidates
+            .first()
+            .and_then(|c| c.content.as_ref())
+            .and_then(|c| c.role)
+            == Some(Role::Function)
+        {
+            saw_function_role = true;
+        }
+    }
+    assert!(saw_function_role);
+}
+
+#[tokio::test]
+async fn test_models_generate_content_stream_items_tool_result() {
+    let mock_server = MockServer::start().await;
+    let call_payload = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{
+                "functionCall": {"name": "test_tool", "args": {"x": 1}}
+            }]}
+        }]
+    });
+    let text_payload = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{"text": "done"}]}
+        }]
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {call_payload}\n\ndata: [DONE]\n\n")),
+        )
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {text_payload}\n\ndata: [DONE]\n\n")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+
+    let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "test_tool".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }]);
+    tool.register_handler("test_tool", |_value| async move { Ok(json!({"ok": true})) });
+
+    let mut stream = client
+        .models()
+        .generate_content_stream_items_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+
+    let mut kinds = Vec::new();
+    while let Some(item) = stream.next().await {
+        match item.unwrap() {
+            StreamItem::ModelChunk(response) => {
+                kinds.push(if response.text().is_some() {
+                    "text"
+                } else {
+                    "chunk"
+                });
+            }
+            StreamItem::ToolCall(calls) => {
+                assert_eq!(calls[0].name.as_deref(), Some("test_tool"));
+                kinds.push("call");
+            }
+            StreamItem::ToolResult(response) => {
+                let content = response.candidates[0].content.as_ref().unwrap();
+                assert_eq!(content.role, Some(Role::Function));
+                kinds.push("result");
+            }
+        }
+    }
+    assert_eq!(kinds, vec!["chunk", "call", "result", "text"]);
+}
+
+#[tokio::test]
+async fn test_stream_callable_tools_finishes_after_exactly_max_rounds() {
+    let mock_server = MockServer::start().await;
+    let call_payload = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{
+                "functionCall": {"name": "test_tool", "args": {}}
+            }]}
+        }]
+    });
+    let text_payload = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{"text": "done"}]}
+        }]
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {call_payload}\n\ndata: [DONE]\n\n")),
+        )
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {text_payload}\n\ndata: [DONE]\n\n")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "test_tool".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }]);
+    tool.register_handler("test_tool", |_value| async move { Ok(json!({"ok": true})) });
+    let config = GenerateContentConfig {
+        automatic_function_calling: Some(AutomaticFunctionCallingConfig {
+            maximum_remote_calls: Some(2),
+            error_on_max_remote_calls: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut stream = client
+        .models()
+        .generate_content_stream_items_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            config,
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+
+    let mut kinds = Vec::new();
+    while let Some(item) = stream.next().await {
+        kinds.push(match item.unwrap() {
+            StreamItem::ModelChunk(response) if response.text().is_some() => "text",
+            StreamItem::ModelChunk(_) => "chunk",
+            StreamItem::ToolCall(_) => "call",
+            StreamItem::ToolResult(_) => "result",
+        });
+    }
+    assert_eq!(
+        kinds,
+        vec!["chunk", "call", "result", "chunk", "call", "result", "text"]
+    );
+}
+
+#[tokio::test]
+async fn test_stream_callable_tools_assembles_partial_function_call_args() {
+    let mock_server = MockServer::start().await;
+    let first_chunk = json!({
+        "candidates": [{"content": {"role": "model", "parts": [{"functionCall": {
+            "name": "book",
+            "partialArgs": [{"jsonPath": "$.city", "stringValue": "Bei", "willContinue": true}],
+            "willContinue": true
+        }}]}}]
+    });
+    let second_chunk = json!({
+        "candidates": [{"content": {"role": "model", "parts": [{"functionCall": {
+            "partialArgs": [
+                {"jsonPath": "$.city", "stringValue": "jing"},
+                {"jsonPath": "$.nights", "numberValue": 3}
+            ],
+            "willContinue": false
+        }}]}}]
+    });
+    let done_chunk =
+        json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "booked"}]}}]});
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {done_chunk}\n\n")),
+        )
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {first_chunk}\n\ndata: {second_chunk}\n\n")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler_received = received.clone();
+    let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "book".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }])
+    .with_handler("book", move |args| {
+        handler_received.lock().unwrap().push(args);
+        async move { Ok(json!({"ok": true})) }
+    });
+
+    let mut stream = client
+        .models()
+        .generate_content_stream_items_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("book a hotel")],
+            GenerateContentConfig::default(),
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+    let mut tool_calls = Vec::new();
+    while let Some(item) = stream.next().await {
+        if let StreamItem::ToolCall(calls) = item.unwrap() {
+            tool_calls.extend(calls);
+        }
+    }
+    assert_eq!(tool_calls.len(), 1);
+    assert_eq!(tool_calls[0].name.as_deref(), Some("book"));
+    assert_eq!(
+        tool_calls[0].args,
+        Some(json!({"city": "Beijing", "nights": 3}))
+    );
+    assert!(tool_calls[0].partial_args.is_none());
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec![json!({"city": "Beijing", "nights": 3})]
+    );
+}
+
+#[tokio::test]
+async fn test_stream_callable_tools_passes_streamed_integer_args_schema_validation() {
+    let mock_server = MockServer::start().await;
+    let first_chunk = json!({
+        "candidates": [{"content": {"role": "model", "parts": [{"functionCall": {
+            "name": "book",
+            "partialArgs": [{"jsonPath": "$.city", "stringValue": "Paris"}],
+            "willContinue": true
+        }}]}}]
+    });
+    let second_chunk = json!({
+        "candidates": [{"content": {"role": "model", "parts": [{"functionCall": {
+            "partialArgs": [{"jsonPath": "$.nights", "numberValue": 2}],
+            "willContinue": false
+        }}]}}]
+    });
+    let done_chunk =
+        json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "booked"}]}}]});
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {done_chunk}\n\n")),
+        )
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {first_chunk}\n\ndata: {second_chunk}\n\n")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    #[derive(serde::Deserialize)]
+    struct Booking {
+        city: String,
+        nights: u32,
+    }
+
+    let client = build_gemini_client(&mock_server.uri());
+    let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler_received = received.clone();
+    let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "book".to_string(),
+        description: None,
+        parameters: Some(
+            Schema::object()
+                .property("city", Schema::string())
+                .property("nights", Schema::integer())
+                .required("city")
+                .required("nights")
+                .build(),
+        ),
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }])
+    .with_handler("book", move |args| {
+        let booking: Booking = serde_json::from_value(args).unwrap();
+        handler_received
+            .lock()
+            .unwrap()
+            .push((booking.city, booking.nights));
+        async move { Ok(json!({"ok": true})) }
+    });
+
+    let mut stream = client
+        .models()
+        .generate_content_stream_items_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("book two nights in Paris")],
+            GenerateContentConfig::default(),
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+    let mut text = String::new();
+    while let Some(item) = stream.next().await {
+        if let StreamItem::ModelChunk(chunk) = item.unwrap() {
+            text.push_str(&chunk.text().unwrap_or_default());
+        }
+    }
+    assert_eq!(text, "booked");
+    assert_eq!(*received.lock().unwrap(), vec![("Paris".to_string(), 2)]);
+}
+
+#[tokio::test]
+async fn test_stream_callable_tools_keeps_repeated_complete_calls_separate() {
+    let mock_server = MockServer::start().await;
+    let call = |city: &str| {
+        json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"functionCall": {
+                "name": "get_weather",
+                "args": {"city": city}
+            }}]}}]
+        })
+    };
+    let first_chunk = call("Paris");
+    let second_chunk = call("Tokyo");
+    let done_chunk =
+        json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "done"}]}}]});
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {done_chunk}\n\n")),
+        )
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {first_chunk}\n\ndata: {second_chunk}\n\n")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler_received = received.clone();
+    let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "get_weather".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }])
+    .with_handler("get_weather", move |args| {
+        handler_received.lock().unwrap().push(args);
+        async move { Ok(json!({"ok": true})) }
+    });
+
+    let mut stream = client
+        .models()
+        .generate_content_stream_items_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("weather in Paris and Tokyo")],
+            GenerateContentConfig::default(),
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+    let mut tool_calls = Vec::new();
+    while let Some(item) = stream.next().await {
+        if let StreamItem::ToolCall(calls) = item.unwrap() {
+            tool_calls.extend(calls);
+        }
+    }
+    assert_eq!(tool_calls.len(), 2);
+    assert_eq!(tool_calls[0].args, Some(json!({"city": "Paris"})));
+    assert_eq!(tool_calls[1].args, Some(json!({"city": "Tokyo"})));
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec![json!({"city": "Paris"}), json!({"city": "Tokyo"})]
+    );
+}
+
+#[tokio::test]
+async fn test_stream_callable_tools_keeps_repeated_argless_calls_separate() {
+    let mock_server = MockServer::start().await;
+    let call_chunk = json!({
+        "candidates": [{"content": {"role": "model", "parts": [{"functionCall": {"name": "now"}}]}}]
+    });
+    let done_chunk =
+        json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "done"}]}}]});
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {done_chunk}\n\n")),
+        )
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(format!("data: {call_chunk}\n\ndata: {call_chunk}\n\n")),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let handler_calls = calls.clone();
+    let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "now".to_string(),
+        description: None,
+        parameters: None,
+        parameters_json_schema: None,
+        response: None,
+        response_json_schema: None,
+        behavior: None,
+    }])
+    .with_handler("now", move |_args| {
+        handler_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        async move { Ok(json!({"time": "12:00"})) }
+    });
+
+    let mut stream = client
+        .models()
+        .generate_content_stream_items_with_callable_tools(
+            "gemini-1.5-pro",
+            vec![Content::text("what time is it, twice")],
+            GenerateContentConfig::default(),
+            vec![Box::new(tool)],
+        )
+        .await
+        .unwrap();
+    let mut tool_calls = Vec::new();
+    while let Some(item) = stream.next().await {
+        if let StreamItem::ToolCall(calls) = item.unwrap() {
+            tool_calls.extend(calls);
+        }
+    }
+    assert_eq!(tool_calls.len(), 2);
+    assert!(tool_calls
+        .iter()
+        .all(|call| call.name.as_deref() == Some("now")));
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_stream_items_slow_consumer_with_small_buffer() {
+    let mock_server = MockServer::start().await;
+    let text = |value: &str| json!({"candidates": [{"content": {"role": "model", "parts": [{"text": value}]}}]});
+    let call_payload = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{
+                "functionCall": {"name": "test_tool", "args": {}}
+            }]}
+        }]
+    });
+    let first_body = format!(
+        "data: {}\n\ndata: {}\n\ndata: {call_payload}\n\n",
+        text("a"),
+        text("b")
+    );
+    let second_body = format!("data: {}\n\ndata: {}\n\n", text("c"), text("d"));
+
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .and(body_string_contains("functionResponse"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(second_body),
+        )
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(first_body),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
         name: "test_tool".to_string(),
         description: None,
         parameters: None,
@@ -384,26 +2071,19 @@ async fn test_models_generate_content_callable_tools() {
         response: None,
         response_json_schema: None,
         behavior: None,
-    }]);
-    tool.register_handler("test_tool", |value| async move {
-        let x = value
-            .get("x")
-            .and_then(serde_json::Value::as_i64)
-            .unwrap_or(0);
-        Ok(json!({ "result": x + 1 }))
-    });
-
+    }])
+    .with_handler("test_tool", |_value| async move { Ok(json!({"ok": true})) });
     let config = GenerateContentConfig {
         automatic_function_calling: Some(AutomaticFunctionCallingConfig {
-            maximum_remote_calls: Some(1),
+            stream_buffer_size: Some(1),
             ..Default::default()
         }),
         ..Default::default()
     };
 
-    let response = client
+    let stream = client
         .models()
-        .generate_content_with_callable_tools(
+        .generate_content_stream_items_with_callable_tools(
             "gemini-1.5-pro",
             vec![Content::text("hi")],
             config,
@@ -411,78 +2091,98 @@ async fn test_models_generate_content_callable_tools() {
         )
         .await
         .unwrap();
-    assert_eq!(response.text(), Some("done".to_string()));
-    assert!(response.automatic_function_calling_history.is_some());
+    let items = tokio::time::timeout(
+        std::time::Duration::from_secs(10),
+        stream
+            .then(|item| async move {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                item.unwrap()
+            })
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .expect("slow consumer should not deadlock");
+    let texts: Vec<String> = items
+        .iter()
+        .filter_map(|item| match item {
+            StreamItem::ModelChunk(response) => response.text(),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts, vec!["a", "b", "c", "d"]);
+    assert_eq!(items.len(), 7);
 }
 
 #[tokio::test]
-async fn test_models_generate_content_stream_callable_tools() {
+async fn test_stream_items_dropped_receiver_stops_producer() {
     let mock_server = MockServer::start().await;
-    let payload = json!({
+    let call_payload = json!({
         "candidates": [{
             "content": {"role": "model", "parts": [{
-                "functionCall": {"name": "test_tool", "args": {"x": 1}}
+                "functionCall": {"name": "slow_tool", "args": {}}
             }]}
         }]
     });
-    let sse_body = format!("data: {payload}\n\ndata: [DONE]\n\n");
-
     Mock::given(method("POST"))
         .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
         .respond_with(
             ResponseTemplate::new(200)
                 .insert_header("content-type", "text/event-stream")
-                .set_body_string(sse_body),
+                .set_body_string(format!("data: {call_payload}\n\n")),
         )
+        .expect(1)
         .mount(&mock_server)
         .await;
 
     let client = build_gemini_client(&mock_server.uri());
-
-    let mut tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
-        name: "test_tool".to_string(),
+    let marker = std::sync::Arc::new(());
+    let tool_marker = marker.clone();
+    let tool = InlineCallableTool::from_declarations(vec![FunctionDeclaration {
+        name: "slow_tool".to_string(),
         description: None,
         parameters: None,
         parameters_json_schema: None,
         response: None,
         response_json_schema: None,
         behavior: None,
-    }]);
-    tool.register_handler("test_tool", |_value| async move { Ok(json!({"ok": true})) });
-
-    let config = GenerateContentConfig {
-        automatic_function_calling: Some(AutomaticFunctionCallingConfig {
-            maximum_remote_calls: Some(1),
-            ..Default::default()
-        }),
-        ..Default::default()
-    };
+    }])
+    .with_handler("slow_tool", move |_value| {
+        let _owned_by_tool = &tool_marker;
+        async move {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            Ok(json!({"ok": true}))
+        }
+    });
 
     let mut stream = client
         .models()
-        .generate_content_stream_with_callable_tools(
+        .generate_content_stream_items_with_callable_tools(
             "gemini-1.5-pro",
             vec![Content::text("hi")],
-            config,
+            GenerateContentConfig::default(),
             vec![Box::new(tool)],
         )
         .await
         .unwrap();
-
-    let mut saw_function_role = false;
-    while let Some(item) = stream.next().await {
-        let response = item.unwrap();
-        if response
-            .candidates
-            .first()
-            .and_then(|c| c.content.as_ref())
-            .and_then(|c| c.role)
-            == Some(Role::Function)
-        {
-            saw_function_role = true;
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(StreamItem::ModelChunk(_)))
+    ));
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(StreamItem::ToolCall(_)))
+    ));
+    assert_eq!(std::sync::Arc::strong_count(&marker), 2);
+    drop(stream);
+
+    // The producer task owns the tool; it is released once the task stops.
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while std::sync::Arc::strong_count(&marker) > 1 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
         }
-    }
-    assert!(saw_function_role);
+    })
+    .await
+    .expect("producer task should stop after the receiver is dropped");
 }
 
 #[tokio::test]
@@ -915,6 +2615,174 @@ async fn test_models_generate_media_errors_with_extra_body() {
     assert!(matches!(err, Error::ApiError { .. }));
 }
 
+#[tokio::test]
+async fn test_generate_content_rejects_schema_without_json_mime_type() {
+    let mock_server = MockServer::start().await;
+    let client = build_gemini_client(&mock_server.uri());
+    let config = GenerateContentConfig {
+        generation_config: Some(GenerationConfig {
+            response_schema: Some(Schema::string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let err = client
+        .models()
+        .generate_content_with_config("gemini-2.5-flash", vec![Content::text("hi")], config)
+        .await
+        .unwrap_err();
+    match err {
+        Error::InvalidConfig { message } => {
+            assert!(message.contains("response_mime_type \"application/json\""));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_generate_text_wraps_prompt() {
+    let mock_server = MockServer::start().await;
+    mount_default_mock(&mock_server).await;
+    let client = build_gemini_client(&mock_server.uri());
+    let models = client.models();
+
+    let response = models
+        .generate_text(
+            "gemini-2.5-flash",
+            "Say ok",
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.text().as_deref(), Some("ok"));
+
+    let stream = models
+        .generate_text_stream(
+            "gemini-2.5-flash",
+            "Say ok",
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap();
+    let chunks: Vec<_> = stream.collect().await;
+    assert_eq!(chunks[0].as_ref().unwrap().text().as_deref(), Some("ok"));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(
+        body["contents"],
+        json!([{"role": "user", "parts": [{"text": "Say ok"}]}])
+    );
+}
+
+#[tokio::test]
+async fn test_count_tokens_cache_skips_repeat_requests() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:countTokens"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "totalTokens": 3
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client_with_token_cache(&mock_server.uri(), 8);
+    let models = client.models();
+    for _ in 0..2 {
+        let counted = models
+            .count_tokens("gemini-1.5-pro", vec![Content::text("hi")])
+            .await
+            .unwrap();
+        assert_eq!(counted.total_tokens, Some(3));
+    }
+    models
+        .count_tokens("gemini-1.5-pro", vec![Content::text("other")])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_check_input_length_rejects_over_limit_prompt_before_generate() {
+    let mock_server = MockServer::start().await;
+    let model = "gemini-2.5-flash-preview-tts";
+    Mock::given(method("POST"))
+        .and(path(format!("/v1beta/models/{model}:countTokens")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"totalTokens": 12_500})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/v1beta/models/{model}:generateContent")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"candidates": []})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(mock_server.uri())
+        .validate_input_length(true)
+        .build()
+        .unwrap();
+    let models = client.models();
+    let contents = vec![Content::text("word ".repeat(10_000))];
+
+    let err = models
+        .generate_content(model, contents.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InputTooLong {
+            tokens: 12_500,
+            limit: 8_192
+        }
+    ));
+    let err = models
+        .generate_content_stream(model, contents.clone(), GenerateContentConfig::default())
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::InputTooLong { .. }));
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|request| request.url.path().ends_with(":countTokens")));
+
+    let config = GenerateContentConfig::default();
+    let err = models
+        .check_input_length(model, &contents, &config, Some(&SimpleTokenEstimator))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InputTooLong { tokens: 12_500, .. }));
+    let short = vec![Content::text("hi")];
+    models
+        .check_input_length(model, &short, &config, Some(&SimpleTokenEstimator))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_models_get_error_mentions_model() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/models/gemini-missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error": {"code": 404, "message": "Model not found", "status": "NOT_FOUND"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = build_gemini_client(&mock_server.uri());
+    let err = client.models().get("gemini-missing").await.unwrap_err();
+    assert!(err.to_string().contains("models/gemini-missing"));
+    assert!(err.to_string().contains("Model not found"));
+    assert_eq!(err.status().map(|status| status.as_u16()), Some(404));
+    assert_eq!(err.code().as_deref(), Some("NOT_FOUND"));
+}
+
 #[tokio::test]
 async fn test_models_count_tokens_or_estimate_remote() {
     let mock_server = MockServer::start().await;
@@ -939,3 +2807,203 @@ async fn test_models_count_tokens_or_estimate_remote() {
         .unwrap();
     assert_eq!(counted.total_tokens, Some(3));
 }
+
+#[tokio::test]
+async fn test_generate_content_invalid_response_invokes_parse_hook() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": "not-a-list"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let captured = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = captured.clone();
+    let client = rust_genai::Client::builder()
+        .api_key("test-key")
+        .base_url(mock_server.uri())
+        .retry_options(support::disabled_retry_options())
+        .on_response_parse_error(move |failure| {
+            sink.lock()
+                .unwrap()
+                .push((failure.method.to_string(), failure.raw_body.to_string()));
+        })
+        .build()
+        .unwrap();
+
+    let err = client
+        .models()
+        .generate_content("gemini-1.5-pro", vec![Content::text("hi")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Serialization { .. }));
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured.len(), 1);
+    assert_eq!(captured[0].0, "generateContent");
+    assert!(captured[0].1.contains("not-a-list"));
+}
+
+#[tokio::test]
+async fn test_generate_content_stream_invalid_chunk_invokes_parse_hook() {
+    let mock_server = MockServer::start().await;
+    let sse_body = concat!(
+        "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"Once\"}]}}]}\n\n",
+        "data: {\"candidates\":\"not-a-list\"}\n\n",
+    );
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:countTokens"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "totalTokens": "many"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let captured = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = captured.clone();
+    let client = rust_genai::Client::builder()
+        .api_key("test-key")
+        .base_url(mock_server.uri())
+        .retry_options(support::disabled_retry_options())
+        .on_response_parse_error(move |failure| {
+            sink.lock()
+                .unwrap()
+                .push((failure.method.to_string(), failure.raw_body.to_string()));
+        })
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = client
+        .models()
+        .generate_content_stream(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].as_ref().unwrap().text(), Some("Once".to_string()));
+    assert!(matches!(chunks[1], Err(Error::Serialization { .. })));
+
+    let err = client
+        .models()
+        .count_tokens("gemini-1.5-pro", vec![Content::text("hi")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Serialization { .. }));
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured.len(), 2);
+    assert_eq!(captured[0].0, "streamGenerateContent");
+    assert_eq!(captured[0].1, r#"{"candidates":"not-a-list"}"#);
+    assert_eq!(captured[1].0, "countTokens");
+    assert!(captured[1].1.contains("many"));
+}
+
+#[tokio::test]
+async fn test_generate_content_parse_error_includes_raw_body() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{"index": "first", "newServerField": {"x": 1}}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = rust_genai::Client::builder()
+        .api_key("test-key")
+        .base_url(mock_server.uri())
+        .retry_options(support::disabled_retry_options())
+        .raw_body_in_parse_errors(true)
+        .build()
+        .unwrap();
+
+    let err = client
+        .models()
+        .generate_content("gemini-1.5-pro", vec![Content::text("hi")])
+        .await
+        .unwrap_err();
+    let Error::Parse { message } = &err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert!(message.starts_with("failed to parse generateContent response: "));
+    assert!(message.contains(r#""index":"first""#));
+    assert!(message.contains(r#""newServerField":{"x":1}"#));
+}
+
+#[tokio::test]
+async fn test_generate_content_stream_parse_error_includes_raw_body() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:streamGenerateContent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string("data: {\"candidates\":[{\"index\":\"first\"}]}\n\n"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = rust_genai::Client::builder()
+        .api_key("test-key")
+        .base_url(mock_server.uri())
+        .retry_options(support::disabled_retry_options())
+        .raw_body_in_parse_errors(true)
+        .build()
+        .unwrap();
+
+    let chunks: Vec<_> = client
+        .models()
+        .generate_content_stream(
+            "gemini-1.5-pro",
+            vec![Content::text("hi")],
+            GenerateContentConfig::default(),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 1);
+    let Err(Error::Parse { message }) = &chunks[0] else {
+        panic!("unexpected chunk: {:?}", chunks[0]);
+    };
+    assert!(message.starts_with("failed to parse streamGenerateContent response: "));
+    assert!(message.contains(r#""index":"first""#));
+}
+
+#[tokio::test]
+async fn test_debug_response_env_does_not_change_parse_error_variant() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-pro:generateContent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": "not-a-list"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    std::env::set_var("RUST_GENAI_DEBUG_RESPONSE", "1");
+    let client = build_gemini_client(&mock_server.uri());
+    let err = client
+        .models()
+        .generate_content("gemini-1.5-pro", vec![Content::text("hi")])
+        .await
+        .unwrap_err();
+    std::env::remove_var("RUST_GENAI_DEBUG_RESPONSE");
+    assert!(matches!(err, Error::Serialization { .. }));
+}
@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use serde_json::json;
 use wiremock::matchers::{method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
-use rust_genai::types::operations::ListOperationsConfig;
+use rust_genai::poll::PollConfig;
+use rust_genai::types::operations::{ListOperationsConfig, Operation};
 
 mod support;
 use support::build_gemini_client_with_version;
@@ -95,3 +98,46 @@ async fn operations_list_surfaces_api_error() {
     assert_eq!(err.status().unwrap().as_u16(), 503);
     assert!(err.is_retryable());
 }
+
+#[tokio::test]
+async fn operations_wait_surfaces_typed_error_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/operations/failed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "operations/failed",
+            "done": true,
+            "error": {
+                "code": 3,
+                "message": "prompt rejected",
+                "details": [{"@type": "type.googleapis.com/google.rpc.ErrorInfo", "reason": "SAFETY"}]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let ops = client.operations();
+    let pending = Operation {
+        name: Some("operations/failed".into()),
+        done: Some(false),
+        ..Default::default()
+    };
+    let polled = ops.get("failed").await.unwrap();
+    let status = polled.error_status().unwrap();
+    assert_eq!(status.code, 3);
+    assert_eq!(status.message, "prompt rejected");
+    assert_eq!(status.details[0]["reason"], "SAFETY");
+
+    let err = ops
+        .wait_with_config(
+            pending,
+            PollConfig::fixed(Duration::from_millis(1)).with_timeout(Duration::from_secs(5)),
+        )
+        .await
+        .unwrap_err();
+    match err {
+        rust_genai::Error::OperationFailed { status: failed } => assert_eq!(failed, status),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
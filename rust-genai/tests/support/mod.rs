@@ -8,7 +8,7 @@ use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 use rust_genai::types::http::HttpRetryOptions;
 use rust_genai::Client;
 
-fn disabled_retry_options() -> HttpRetryOptions {
+pub fn disabled_retry_options() -> HttpRetryOptions {
     HttpRetryOptions {
         attempts: Some(1),
         ..Default::default()
@@ -34,6 +34,16 @@ pub fn build_gemini_client_with_version(base_url: &str, api_version: &str) -> Cl
         .unwrap()
 }
 
+pub fn build_gemini_client_with_token_cache(base_url: &str, capacity: usize) -> Client {
+    Client::builder()
+        .api_key("test-key")
+        .base_url(base_url)
+        .retry_options(disabled_retry_options())
+        .token_count_cache(capacity)
+        .build()
+        .unwrap()
+}
+
 pub async fn mount_default_mock(server: &MockServer) {
     let server_uri = server.uri();
     let upload_url = format!("{server_uri}/upload-session");
@@ -0,0 +1,71 @@
+#![cfg(feature = "testing")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use futures_util::StreamExt;
+use http::StatusCode;
+use serde_json::json;
+
+use rust_genai::testing::{mock_client, MockResponse};
+use rust_genai::types::content::Content;
+use rust_genai::Error;
+
+#[tokio::test]
+async fn mock_client_stubs_generate_content() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let seen = calls.clone();
+    let client = mock_client(move |request| {
+        seen.fetch_add(1, Ordering::SeqCst);
+        assert_eq!(request.method, http::Method::POST);
+        assert_eq!(
+            request.url.path(),
+            "/v1beta/models/gemini-2.5-flash:generateContent"
+        );
+        let body = request.json().unwrap();
+        assert_eq!(body["contents"][0]["parts"][0]["text"], "Hello");
+        MockResponse::json(json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "Hi there"}]}}]
+        }))
+    });
+
+    let response = client
+        .models()
+        .generate_content("gemini-2.5-flash", vec![Content::text("Hello")])
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("Hi there".to_string()));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn mock_client_supports_streaming_and_errors() {
+    let client = mock_client(|request| {
+        if request.url.path().ends_with(":streamGenerateContent") {
+            MockResponse::sse([
+                json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "a"}]}}]}),
+                json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "b"}]}}]}),
+            ])
+        } else {
+            MockResponse::new(StatusCode::BAD_REQUEST).body("bad request")
+        }
+    });
+
+    let stream = client
+        .models()
+        .generate_content_stream("m", vec![Content::text("x")], Default::default())
+        .await
+        .unwrap();
+    let texts: Vec<_> = stream
+        .map(|item| item.unwrap().text().unwrap_or_default())
+        .collect()
+        .await;
+    assert_eq!(texts, vec!["a".to_string(), "b".to_string()]);
+
+    let err = client
+        .models()
+        .generate_content("m", vec![Content::text("x")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ApiError { status: 400, .. }));
+}
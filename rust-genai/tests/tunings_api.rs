@@ -1,7 +1,12 @@
+use futures_util::StreamExt;
 use serde_json::json;
 use wiremock::matchers::{method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+use std::time::Duration;
+
+use rust_genai::poll::PollConfig;
+use rust_genai::types::enums::JobState;
 use rust_genai::types::http::HttpOptions;
 use rust_genai::types::tunings::{
     CreateTuningJobConfig, ListTuningJobsConfig, TuningDataset, TuningExample,
@@ -139,6 +144,40 @@ async fn tunings_api_flow() {
     assert_eq!(all.len(), 2);
 }
 
+#[tokio::test]
+async fn tunings_list_stream_drains_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/tunedModels"))
+        .and(query_param_is_missing("pageToken"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "tunedModels": [{"name": "tunedModels/1"}, {"name": "tunedModels/2"}],
+            "nextPageToken": "next"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/tunedModels"))
+        .and(query_param("pageToken", "next"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "tunedModels": [{"name": "tunedModels/3"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let names: Vec<String> = client
+        .tunings()
+        .list_stream(ListTuningJobsConfig::default())
+        .map(|job| job.unwrap().name.unwrap())
+        .collect()
+        .await;
+    assert_eq!(
+        names,
+        vec!["tunedModels/1", "tunedModels/2", "tunedModels/3"]
+    );
+}
+
 #[tokio::test]
 async fn tune_with_extra_body() {
     let server = MockServer::start().await;
@@ -173,3 +212,99 @@ async fn tune_with_extra_body() {
         .unwrap();
     assert_eq!(job.name.as_deref(), Some("tunedModels/extra"));
 }
+
+#[tokio::test]
+async fn tunings_delete_accepts_empty_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1beta/tunedModels/1"))
+        .respond_with(ResponseTemplate::new(200).insert_header("x-test", "deleted"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1beta/tunedModels/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1beta/tunedModels/missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("missing"))
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let tunings = client.tunings();
+
+    let deleted = tunings.delete("1").await.unwrap();
+    let headers = deleted.sdk_http_response.unwrap().headers.unwrap();
+    assert_eq!(headers.get("x-test").map(String::as_str), Some("deleted"));
+
+    let deleted = tunings.delete("tunedModels/2").await.unwrap();
+    assert!(deleted.sdk_http_response.is_some());
+
+    let err = tunings.delete("missing").await.unwrap_err();
+    assert!(matches!(err, rust_genai::Error::ApiError { .. }));
+}
+
+#[tokio::test]
+async fn tunings_wait_polls_until_terminal_state() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/tunedModels/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "tunedModels/1",
+            "state": "CREATING"
+        })))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/tunedModels/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "tunedModels/1",
+            "state": "ACTIVE"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/tunedModels/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "tunedModels/2",
+            "state": "FAILED"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1beta/tunedModels/3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "tunedModels/3",
+            "state": "CREATING"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = build_gemini_client_with_version(&server.uri(), "v1beta");
+    let tunings = client.tunings();
+    let config = PollConfig::fixed(Duration::from_millis(10)).with_timeout(Duration::from_secs(5));
+
+    let job = tunings.wait("1", config.clone()).await.unwrap();
+    assert_eq!(job.state, Some(JobState::JobStateSucceeded));
+    assert_eq!(job.name.as_deref(), Some("tunedModels/1"));
+
+    let err = tunings.wait("2", config).await.unwrap_err();
+    assert!(
+        matches!(err, rust_genai::Error::OperationFailed { ref status } if status.message.contains("JobStateFailed"))
+    );
+
+    let err = tunings
+        .wait(
+            "3",
+            PollConfig::fixed(Duration::from_millis(10)).with_timeout(Duration::from_millis(30)),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, rust_genai::Error::Timeout { .. }));
+}
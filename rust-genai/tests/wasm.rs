@@ -0,0 +1,171 @@
+//! Browser tests for the `wasm` feature.
+//!
+//! Run with `wasm-pack test --headless --firefox rust-genai --features wasm --test wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use std::fmt::Write as _;
+
+use futures_util::future::BoxFuture;
+use futures_util::StreamExt;
+use http::Method;
+use js_sys::Function;
+use reqwest::Url;
+use serde_json::{json, Value};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+use rust_genai::transport::{
+    FetchTransport, Transport, TransportBody, TransportRequest, TransportResponse,
+};
+use rust_genai::types::content::Content;
+use rust_genai::{Client, Result};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Host answered by the stub installed with [`install_echo_fetch`].
+const ECHO_BASE_URL: &str = "https://echo.invalid";
+
+/// Wraps the global `fetch` so requests to [`ECHO_BASE_URL`] are answered in-page with a
+/// `generateContent` response whose text is the method and body the stub received.
+fn install_echo_fetch() {
+    Function::new_no_args(
+        r#"
+        if (globalThis.__rustGenaiEchoFetch) return;
+        globalThis.__rustGenaiEchoFetch = true;
+        const original = globalThis.fetch;
+        globalThis.fetch = async (input, init) => {
+            const request = new Request(input, init);
+            if (new URL(request.url).host !== "echo.invalid") return original(input, init);
+            const text = `${request.method} ${request.headers.get("content-type")} ${await request.text()}`;
+            const payload = { candidates: [{ content: { role: "model", parts: [{ text }] } }] };
+            return new Response(JSON.stringify(payload), {
+                headers: { "content-type": "application/json" },
+            });
+        };
+        "#,
+    )
+    .call0(&js_sys::global())
+    .unwrap();
+}
+
+/// Answers every request through `fetch` with a canned `data:` URL body, so the browser
+/// transport runs end to end without a server.
+struct DataUrlTransport {
+    fetch: FetchTransport,
+    expected_path: &'static str,
+    url: Url,
+}
+
+impl DataUrlTransport {
+    fn new(expected_path: &'static str, mime_type: &str, body: &str) -> Self {
+        let mut url = format!("data:{mime_type},");
+        for byte in body.bytes() {
+            if byte.is_ascii_alphanumeric() {
+                url.push(char::from(byte));
+            } else {
+                let _ = write!(url, "%{byte:02X}");
+            }
+        }
+        Self {
+            fetch: FetchTransport::new(),
+            expected_path,
+            url: Url::parse(&url).unwrap(),
+        }
+    }
+}
+
+impl Transport for DataUrlTransport {
+    fn execute(&self, mut request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse>> {
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url.path(), self.expected_path);
+        assert_eq!(request.headers["x-goog-api-key"], "test-key");
+        request.method = Method::GET;
+        request.url = self.url.clone();
+        request.body = TransportBody::default();
+        self.fetch.execute(request)
+    }
+}
+
+fn client(transport: DataUrlTransport) -> Client {
+    Client::builder()
+        .api_key("test-key")
+        .transport(transport)
+        .build()
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn generate_content_over_fetch() {
+    let body = json!({
+        "candidates": [{"content": {"role": "model", "parts": [{"text": "Hi from the browser"}]}}]
+    });
+    let client = client(DataUrlTransport::new(
+        "/v1beta/models/gemini-2.5-flash:generateContent",
+        "application/json",
+        &body.to_string(),
+    ));
+
+    let response = client
+        .models()
+        .generate_content("gemini-2.5-flash", vec![Content::text("Hello")])
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("Hi from the browser".to_string()));
+}
+
+#[wasm_bindgen_test]
+async fn generate_content_posts_json_body_over_fetch() {
+    install_echo_fetch();
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(ECHO_BASE_URL)
+        .build()
+        .unwrap();
+
+    let response = client
+        .models()
+        .generate_content("gemini-2.5-flash", vec![Content::text("Hello")])
+        .await
+        .unwrap();
+    let echoed = response.text().unwrap();
+    let body = echoed
+        .strip_prefix("POST application/json ")
+        .unwrap_or_else(|| panic!("unexpected request: {echoed}"));
+    let body: Value = serde_json::from_str(body).unwrap();
+    assert_eq!(
+        body["contents"],
+        json!([{"role": "user", "parts": [{"text": "Hello"}]}])
+    );
+}
+
+#[wasm_bindgen_test]
+async fn generate_content_stream_over_fetch() {
+    let events = ["a", "b"]
+        .iter()
+        .map(|text| {
+            let chunk = json!({
+                "candidates": [{"content": {"role": "model", "parts": [{"text": text}]}}]
+            });
+            format!("data: {chunk}\r\n\r\n")
+        })
+        .collect::<String>();
+    let client = client(DataUrlTransport::new(
+        "/v1beta/models/gemini-2.5-flash:streamGenerateContent",
+        "text/event-stream",
+        &events,
+    ));
+
+    let stream = client
+        .models()
+        .generate_content_stream(
+            "gemini-2.5-flash",
+            vec![Content::text("Hello")],
+            Default::default(),
+        )
+        .await
+        .unwrap();
+    let texts: Vec<_> = stream
+        .map(|item| item.unwrap().text().unwrap_or_default())
+        .collect()
+        .await;
+    assert_eq!(texts, vec!["a", "b"]);
+}
@@ -235,7 +235,7 @@ async fn webhooks_error_responses_and_default_rotate_wrapper() {
             status: 400,
             message,
             ..
-        } if message == "create failed"
+        } if message == "/v1beta/webhooks: create failed"
     ));
 
     let err = webhooks
@@ -254,7 +254,7 @@ async fn webhooks_error_responses_and_default_rotate_wrapper() {
             status: 422,
             message,
             ..
-        } if message == "update failed"
+        } if message == "/v1beta/webhooks/hook-1: update failed"
     ));
 
     let err = webhooks.list().await.unwrap_err();
@@ -264,7 +264,7 @@ async fn webhooks_error_responses_and_default_rotate_wrapper() {
             status: 500,
             message,
             ..
-        } if message == "list failed"
+        } if message == "/v1beta/webhooks: list failed"
     ));
 
     let err = webhooks.get("hook-1").await.unwrap_err();
@@ -274,7 +274,7 @@ async fn webhooks_error_responses_and_default_rotate_wrapper() {
             status: 404,
             message,
             ..
-        } if message == "get failed"
+        } if message == "/v1beta/webhooks/hook-1: get failed"
     ));
 
     let err = webhooks.ping("hook-1").await.unwrap_err();
@@ -284,7 +284,7 @@ async fn webhooks_error_responses_and_default_rotate_wrapper() {
             status: 503,
             message,
             ..
-        } if message == "ping failed"
+        } if message == "/v1beta/webhooks/hook-1:ping: ping failed"
     ));
 
     let err = webhooks.rotate_signing_secret("hook-1").await.unwrap_err();
@@ -294,7 +294,7 @@ async fn webhooks_error_responses_and_default_rotate_wrapper() {
             status: 409,
             message,
             ..
-        } if message == "rotate failed"
+        } if message == "/v1beta/webhooks/hook-1:rotateSigningSecret: rotate failed"
     ));
 
     let err = webhooks.delete("hook-1").await.unwrap_err();
@@ -304,6 +304,6 @@ async fn webhooks_error_responses_and_default_rotate_wrapper() {
             status: 410,
             message,
             ..
-        } if message == "delete failed"
+        } if message == "/v1beta/webhooks/hook-1: delete failed"
     ));
 }